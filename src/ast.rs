@@ -5,10 +5,12 @@
 //! actual query building is in the [visitor](../visitor/index.html) module.
 //!
 //! For prelude, all important imports are in `quaint::ast::*`.
+mod coerce;
 mod column;
 mod compare;
 mod conditions;
 mod conjunctive;
+mod db_enum;
 mod delete;
 mod expression;
 mod function;
@@ -20,6 +22,7 @@ mod merge;
 mod ops;
 mod ordering;
 mod over;
+mod qualify;
 mod query;
 mod row;
 mod select;
@@ -28,10 +31,14 @@ mod union;
 mod update;
 mod values;
 
-pub use column::{Column, DefaultValue};
+#[cfg(feature = "json-1")]
+pub use coerce::params_from_json;
+pub use coerce::ValueType;
+pub use column::{Column, DefaultValue, Generated};
 pub use compare::{Comparable, Compare};
 pub use conditions::ConditionTree;
 pub use conjunctive::Conjunctive;
+pub use db_enum::DbEnum;
 pub use delete::Delete;
 pub use expression::*;
 pub use function::*;
@@ -39,17 +46,21 @@ pub use grouping::*;
 pub use index::*;
 pub use insert::*;
 pub use join::{Join, JoinData, Joinable};
-pub(crate) use merge::*;
+pub use merge::{IntoUsing, Merge, Using};
 pub use ops::*;
 pub use ordering::{IntoOrderDefinition, Order, OrderDefinition, Orderable, Ordering};
 pub use over::*;
 pub use query::Query;
 pub use row::Row;
-pub use select::Select;
+pub use select::{LockMode, Select};
 pub use table::*;
 pub use union::Union;
 pub use update::*;
 pub use values::{IntoRaw, Raw, Value, Values};
 
-#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql"))]
+#[cfg(feature = "chrono-0_4")]
+pub(crate) use values::interval_seconds;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql-core"))]
 pub(crate) use values::Params;
+#[cfg(feature = "bit-vec")]
+pub(crate) use values::{bits_from_str, bits_to_string};
@@ -0,0 +1,609 @@
+use crate::{
+    ast::Value,
+    error::{Error, ErrorKind},
+};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use std::{borrow::Cow, fmt, str::FromStr};
+
+#[cfg(feature = "chrono-0_4")]
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+#[cfg(feature = "uuid-0_8")]
+use uuid::Uuid;
+
+#[cfg(feature = "bit-vec")]
+use crate::ast::{bits_from_str, bits_to_string};
+
+/// The type of a [Value](enum.Value.html), without the data. Used as the
+/// target of a [coercion](enum.Value.html#method.coerce).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    Integer,
+    Real,
+    Text,
+    Enum,
+    Bytes,
+    Boolean,
+    Char,
+    #[cfg(all(feature = "array", feature = "postgresql-core"))]
+    Array,
+    #[cfg(feature = "bit-vec")]
+    Bits,
+    #[cfg(feature = "json-1")]
+    Json,
+    #[cfg(feature = "uuid-0_8")]
+    Uuid,
+    #[cfg(feature = "chrono-0_4")]
+    DateTime,
+    #[cfg(feature = "chrono-0_4")]
+    Date,
+    #[cfg(feature = "chrono-0_4")]
+    Time,
+    #[cfg(feature = "chrono-0_4")]
+    Interval,
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl ValueType {
+    /// A null value of this type.
+    fn null<'a>(self) -> Value<'a> {
+        match self {
+            ValueType::Integer => Value::Integer(None),
+            ValueType::Real => Value::Real(None),
+            ValueType::Text => Value::Text(None),
+            ValueType::Enum => Value::Enum(None),
+            ValueType::Bytes => Value::Bytes(None),
+            ValueType::Boolean => Value::Boolean(None),
+            ValueType::Char => Value::Char(None),
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            ValueType::Array => Value::Array(None),
+            #[cfg(feature = "bit-vec")]
+            ValueType::Bits => Value::Bits(None),
+            #[cfg(feature = "json-1")]
+            ValueType::Json => Value::Json(None),
+            #[cfg(feature = "uuid-0_8")]
+            ValueType::Uuid => Value::Uuid(None),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::DateTime => Value::DateTime(None),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::Date => Value::Date(None),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::Time => Value::Time(None),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::Interval => Value::Interval(None),
+        }
+    }
+}
+
+type Coerced<'a> = std::result::Result<Value<'a>, Cow<'static, str>>;
+
+impl<'a> Value<'a> {
+    /// The type of the value.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Value::Integer(_) => ValueType::Integer,
+            Value::Real(_) => ValueType::Real,
+            Value::Text(_) => ValueType::Text,
+            Value::Enum(_) => ValueType::Enum,
+            Value::Bytes(_) => ValueType::Bytes,
+            Value::Boolean(_) => ValueType::Boolean,
+            Value::Char(_) => ValueType::Char,
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(_) => ValueType::Array,
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(_) => ValueType::Bits,
+            #[cfg(feature = "json-1")]
+            Value::Json(_) => ValueType::Json,
+            #[cfg(feature = "uuid-0_8")]
+            Value::Uuid(_) => ValueType::Uuid,
+            #[cfg(feature = "chrono-0_4")]
+            Value::DateTime(_) => ValueType::DateTime,
+            #[cfg(feature = "chrono-0_4")]
+            Value::Date(_) => ValueType::Date,
+            #[cfg(feature = "chrono-0_4")]
+            Value::Time(_) => ValueType::Time,
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(_) => ValueType::Interval,
+        }
+    }
+
+    /// Converts the value to another type, parsing text and checking the
+    /// ranges where needed. A null stays null, typed as the target.
+    ///
+    /// Conversions between numbers, text, booleans, enums and characters
+    /// always work when the data allows it. Text can be parsed as a UUID,
+    /// JSON or a date and time in RFC 3339 format. JSON converts to text as a
+    /// serialized document, except strings that become the string itself.
+    ///
+    /// ```rust
+    /// # use quaint::ast::*;
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// assert_eq!(Value::integer(42), Value::text(" 42 ").coerce(ValueType::Integer)?);
+    /// assert_eq!(Value::boolean(true), Value::integer(1).coerce(ValueType::Boolean)?);
+    /// assert_eq!(Value::Integer(None), Value::Text(None).coerce(ValueType::Integer)?);
+    ///
+    /// let err = Value::text("forty-two").coerce(ValueType::Integer).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     "Conversion failed: Cannot coerce Text value \"forty-two\" to Integer (invalid digit found in string)",
+    ///     err.to_string(),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn coerce(self, target: ValueType) -> crate::Result<Value<'a>> {
+        if self.value_type() == target {
+            return Ok(self);
+        }
+
+        if self.is_null() {
+            return Ok(target.null());
+        }
+
+        let coerced = match target {
+            ValueType::Integer => self.coerce_integer(),
+            ValueType::Real => self.coerce_real(),
+            ValueType::Text => self.coerce_text().map(Value::Text),
+            ValueType::Enum => self.coerce_text().map(Value::Enum),
+            ValueType::Bytes => self.coerce_bytes(),
+            ValueType::Boolean => self.coerce_boolean(),
+            ValueType::Char => self.coerce_char(),
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            ValueType::Array => Err("arrays can only be built from arrays".into()),
+            #[cfg(feature = "bit-vec")]
+            ValueType::Bits => self.coerce_bits(),
+            #[cfg(feature = "json-1")]
+            ValueType::Json => self.coerce_json(),
+            #[cfg(feature = "uuid-0_8")]
+            ValueType::Uuid => self.coerce_uuid(),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::DateTime => self.coerce_datetime(),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::Date => self.coerce_date(),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::Time => self.coerce_time(),
+            #[cfg(feature = "chrono-0_4")]
+            ValueType::Interval => self.coerce_interval(),
+        };
+
+        coerced.map_err(|reason| {
+            let msg = format!(
+                "Cannot coerce {} value {} to {} ({})",
+                self.value_type(),
+                self,
+                target,
+                reason
+            );
+
+            Error::builder(ErrorKind::conversion(msg)).build()
+        })
+    }
+
+    fn coerce_integer(&self) -> Coerced<'a> {
+        let i = match self {
+            Value::Real(Some(d)) if d.trunc() == *d => d.to_i64().ok_or("out of range for a 64-bit integer")?,
+            Value::Real(Some(_)) => return Err("the number has a fractional part".into()),
+            Value::Text(Some(s)) | Value::Enum(Some(s)) => s.trim().parse().map_err(|e| format!("{}", e))?,
+            Value::Boolean(Some(b)) => *b as i64,
+            Value::Char(Some(c)) => c.to_digit(10).ok_or("not a digit")? as i64,
+            #[cfg(feature = "json-1")]
+            Value::Json(Some(j)) => j.as_i64().ok_or("not an integer in JSON")?,
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::integer(i))
+    }
+
+    fn coerce_real(&self) -> Coerced<'a> {
+        let d = match self {
+            Value::Integer(Some(i)) => Decimal::from(*i),
+            Value::Text(Some(s)) | Value::Enum(Some(s)) => Decimal::from_str(s.trim()).map_err(|e| format!("{}", e))?,
+            #[cfg(feature = "json-1")]
+            Value::Json(Some(serde_json::Value::Number(n))) => {
+                Decimal::from_str(&n.to_string()).map_err(|e| format!("{}", e))?
+            }
+            #[cfg(feature = "json-1")]
+            Value::Json(Some(_)) => return Err("not a number in JSON".into()),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::real(d))
+    }
+
+    fn coerce_text(&self) -> std::result::Result<Option<Cow<'a, str>>, Cow<'static, str>> {
+        let s = match self {
+            Value::Text(Some(s)) | Value::Enum(Some(s)) => s.clone(),
+            Value::Integer(Some(i)) => i.to_string().into(),
+            Value::Real(Some(d)) => d.to_string().into(),
+            Value::Boolean(Some(b)) => b.to_string().into(),
+            Value::Char(Some(c)) => c.to_string().into(),
+            Value::Bytes(Some(b)) => String::from_utf8(b.to_vec()).map_err(|_| "not valid UTF-8")?.into(),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(Some(bits)) => bits_to_string(bits).into(),
+            #[cfg(feature = "json-1")]
+            Value::Json(Some(serde_json::Value::String(s))) => s.clone().into(),
+            #[cfg(feature = "json-1")]
+            Value::Json(Some(j)) => j.to_string().into(),
+            #[cfg(feature = "uuid-0_8")]
+            Value::Uuid(Some(u)) => u.to_hyphenated().to_string().into(),
+            #[cfg(feature = "chrono-0_4")]
+            Value::DateTime(Some(dt)) => dt.to_rfc3339().into(),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Date(Some(d)) => d.to_string().into(),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Time(Some(t)) => t.to_string().into(),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Some(s))
+    }
+
+    fn coerce_bytes(&self) -> Coerced<'a> {
+        let bytes: Cow<'a, [u8]> = match self {
+            Value::Text(Some(s)) | Value::Enum(Some(s)) => s.as_bytes().to_vec().into(),
+            #[cfg(feature = "uuid-0_8")]
+            Value::Uuid(Some(u)) => u.as_bytes().to_vec().into(),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::Bytes(Some(bytes)))
+    }
+
+    fn coerce_boolean(&self) -> Coerced<'a> {
+        let b = match self {
+            Value::Integer(Some(0)) => false,
+            Value::Integer(Some(1)) => true,
+            Value::Integer(Some(_)) => return Err("only 0 and 1 are booleans".into()),
+            Value::Text(Some(s)) | Value::Enum(Some(s)) => match s.trim().to_lowercase().as_str() {
+                "true" | "t" | "yes" | "y" | "on" | "1" => true,
+                "false" | "f" | "no" | "n" | "off" | "0" => false,
+                _ => return Err("not a boolean".into()),
+            },
+            #[cfg(feature = "json-1")]
+            Value::Json(Some(j)) => j.as_bool().ok_or("not a boolean in JSON")?,
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::boolean(b))
+    }
+
+    fn coerce_char(&self) -> Coerced<'a> {
+        let c = match self {
+            Value::Text(Some(s)) | Value::Enum(Some(s)) => {
+                let mut chars = s.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => return Err("not a single character".into()),
+                }
+            }
+            Value::Integer(Some(i)) if (0..10).contains(i) => std::char::from_digit(*i as u32, 10).unwrap(),
+            Value::Integer(Some(_)) => return Err("not a single digit".into()),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::character(c))
+    }
+
+    #[cfg(feature = "bit-vec")]
+    fn coerce_bits(&self) -> Coerced<'a> {
+        let bits = match self {
+            Value::Text(Some(s)) => bits_from_str(s.trim()).map_err(|_| "only 0 and 1 are bits")?,
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::bits(bits))
+    }
+
+    #[cfg(feature = "json-1")]
+    fn coerce_json(&self) -> Coerced<'a> {
+        let json = match self {
+            Value::Text(Some(s)) => serde_json::from_str(s).map_err(|e| format!("{}", e))?,
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(Some(_)) => serde_json::Value::from(self.clone()),
+            Value::Bytes(Some(_)) => return Err(unsupported()),
+            _ => serde_json::Value::from(self.clone()),
+        };
+
+        Ok(Value::json(json))
+    }
+
+    #[cfg(feature = "uuid-0_8")]
+    fn coerce_uuid(&self) -> Coerced<'a> {
+        let uuid = match self {
+            Value::Text(Some(s)) => Uuid::parse_str(s.trim()).map_err(|e| format!("{}", e))?,
+            Value::Bytes(Some(b)) => Uuid::from_slice(b).map_err(|e| format!("{}", e))?,
+            #[cfg(feature = "json-1")]
+            Value::Json(Some(serde_json::Value::String(s))) => Uuid::parse_str(s).map_err(|e| format!("{}", e))?,
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::uuid(uuid))
+    }
+
+    #[cfg(feature = "chrono-0_4")]
+    fn coerce_datetime(&self) -> Coerced<'a> {
+        let dt = match self {
+            Value::Text(Some(s)) => DateTime::parse_from_rfc3339(s.trim())
+                .map_err(|e| format!("{}", e))?
+                .with_timezone(&Utc),
+            Value::Integer(Some(secs)) => Utc
+                .timestamp_opt(*secs, 0)
+                .single()
+                .ok_or("out of range for a timestamp")?,
+            Value::Date(Some(d)) => DateTime::from_utc(d.and_hms(0, 0, 0), Utc),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::datetime(dt))
+    }
+
+    #[cfg(feature = "chrono-0_4")]
+    fn coerce_date(&self) -> Coerced<'a> {
+        let date = match self {
+            Value::Text(Some(s)) => NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").map_err(|e| format!("{}", e))?,
+            Value::DateTime(Some(dt)) => dt.naive_utc().date(),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::date(date))
+    }
+
+    #[cfg(feature = "chrono-0_4")]
+    fn coerce_time(&self) -> Coerced<'a> {
+        let time = match self {
+            Value::Text(Some(s)) => NaiveTime::from_str(s.trim()).map_err(|e| format!("{}", e))?,
+            Value::DateTime(Some(dt)) => dt.naive_utc().time(),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::time(time))
+    }
+
+    /// Numbers are read as seconds.
+    #[cfg(feature = "chrono-0_4")]
+    fn coerce_interval(&self) -> Coerced<'a> {
+        let interval = match self {
+            Value::Integer(Some(i)) => Duration::seconds(*i),
+            Value::Real(Some(d)) => {
+                let micros = (d * Decimal::new(1_000_000, 0)).to_i64().ok_or("out of range")?;
+                Duration::microseconds(micros)
+            }
+            _ => return Err(unsupported()),
+        };
+
+        Ok(Value::interval(interval))
+    }
+}
+
+fn unsupported() -> Cow<'static, str> {
+    "no conversion between the types".into()
+}
+
+impl Value<'static> {
+    /// Converts a JSON value to a parameter of the given type. JSON `null` is
+    /// a null of the type, strings are parsed as with
+    /// [coerce](enum.Value.html#method.coerce), and bytes are read from
+    /// base64, as written by the conversion to JSON.
+    #[cfg(feature = "json-1")]
+    pub fn from_json(json: &serde_json::Value, hint: ValueType) -> crate::Result<Self> {
+        let value = match json {
+            serde_json::Value::Null => return Ok(hint.null()),
+            serde_json::Value::String(s) if hint == ValueType::Bytes => {
+                let bytes = base64::decode(s).map_err(|e| {
+                    let msg = format!("Cannot read bytes from JSON string ({})", e);
+                    Error::builder(ErrorKind::conversion(msg)).build()
+                })?;
+
+                Value::Bytes(Some(bytes.into()))
+            }
+            serde_json::Value::String(s) if hint != ValueType::Json => Value::text(s.clone()),
+            serde_json::Value::Bool(b) if hint != ValueType::Json => Value::boolean(*b),
+            json => Value::json(json.clone()),
+        };
+
+        value.coerce(hint)
+    }
+}
+
+/// Converts a JSON array of values to query parameters, the value at each
+/// position converted to the type at the same position in `types`.
+///
+/// ```rust
+/// # use quaint::ast::*;
+/// # use serde_json::json;
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let json = json!(["Musti", "42", null]);
+/// let params = params_from_json(&json, &[ValueType::Text, ValueType::Integer, ValueType::Boolean])?;
+///
+/// assert_eq!(vec![Value::text("Musti"), Value::integer(42), Value::Boolean(None)], params);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "json-1")]
+pub fn params_from_json(values: &serde_json::Value, types: &[ValueType]) -> crate::Result<Vec<Value<'static>>> {
+    let values = match values {
+        serde_json::Value::Array(values) if values.len() == types.len() => values,
+        serde_json::Value::Array(values) => {
+            let msg = format!("Got {} JSON values for {} types", values.len(), types.len());
+            return Err(Error::builder(ErrorKind::conversion(msg)).build());
+        }
+        _ => return Err(Error::builder(ErrorKind::conversion("Parameters must be a JSON array")).build()),
+    };
+
+    values
+        .iter()
+        .zip(types.iter())
+        .map(|(value, hint)| Value::from_json(value, *hint))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_type_is_returned_as_is() {
+        let value = Value::text("foo");
+        assert_eq!(value.clone(), value.coerce(ValueType::Text).unwrap());
+    }
+
+    #[test]
+    fn null_keeps_being_null_with_the_new_type() {
+        assert_eq!(
+            Value::Boolean(None),
+            Value::Integer(None).coerce(ValueType::Boolean).unwrap()
+        );
+    }
+
+    #[test]
+    fn text_to_integer() {
+        assert_eq!(
+            Value::integer(-3),
+            Value::text("-3").coerce(ValueType::Integer).unwrap()
+        );
+        assert!(Value::text("3.5").coerce(ValueType::Integer).is_err());
+    }
+
+    #[test]
+    fn real_to_integer_needs_no_fraction() {
+        let whole = Value::real(Decimal::from_str("4.00").unwrap());
+        let fraction = Value::real(Decimal::from_str("4.20").unwrap());
+
+        assert_eq!(Value::integer(4), whole.coerce(ValueType::Integer).unwrap());
+        assert!(fraction.coerce(ValueType::Integer).is_err());
+    }
+
+    #[test]
+    fn integer_to_boolean() {
+        assert_eq!(
+            Value::boolean(false),
+            Value::integer(0).coerce(ValueType::Boolean).unwrap()
+        );
+        assert!(Value::integer(2).coerce(ValueType::Boolean).is_err());
+    }
+
+    #[test]
+    fn text_to_boolean() {
+        assert_eq!(
+            Value::boolean(true),
+            Value::text("Yes").coerce(ValueType::Boolean).unwrap()
+        );
+        assert_eq!(
+            Value::boolean(false),
+            Value::text("f").coerce(ValueType::Boolean).unwrap()
+        );
+        assert!(Value::text("maybe").coerce(ValueType::Boolean).is_err());
+    }
+
+    #[test]
+    fn numbers_to_text() {
+        assert_eq!(Value::text("12"), Value::integer(12).coerce(ValueType::Text).unwrap());
+        assert_eq!(
+            Value::text("true"),
+            Value::boolean(true).coerce(ValueType::Text).unwrap()
+        );
+    }
+
+    #[test]
+    fn text_to_char() {
+        assert_eq!(Value::character('a'), Value::text("a").coerce(ValueType::Char).unwrap());
+        assert!(Value::text("ab").coerce(ValueType::Char).is_err());
+    }
+
+    #[test]
+    fn error_describes_the_conversion() {
+        let err = Value::integer(7).coerce(ValueType::Boolean).unwrap_err();
+
+        assert_eq!(
+            "Conversion failed: Cannot coerce Integer value 7 to Boolean (only 0 and 1 are booleans)",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "uuid-0_8")]
+    fn text_to_uuid() {
+        let uuid = Uuid::new_v4();
+        let text = Value::text(uuid.to_hyphenated().to_string());
+
+        assert_eq!(Value::uuid(uuid), text.coerce(ValueType::Uuid).unwrap());
+        assert!(Value::text("not-a-uuid").coerce(ValueType::Uuid).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "bit-vec")]
+    fn bits_to_text_and_back() {
+        let bits = Value::bits_from_str("0110").unwrap();
+
+        assert_eq!(Value::text("0110"), bits.clone().coerce(ValueType::Text).unwrap());
+        assert_eq!(bits, Value::text("0110").coerce(ValueType::Bits).unwrap());
+        assert!(Value::text("0120").coerce(ValueType::Bits).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "chrono-0_4")]
+    fn numbers_to_interval() {
+        let real = Value::real(Decimal::from_str("1.5").unwrap());
+
+        assert_eq!(
+            Value::interval(Duration::seconds(90)),
+            Value::integer(90).coerce(ValueType::Interval).unwrap()
+        );
+        assert_eq!(
+            Value::interval(Duration::milliseconds(1500)),
+            real.coerce(ValueType::Interval).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json-1")]
+    fn json_and_text() {
+        let json = Value::text(r#"{"a":1}"#).coerce(ValueType::Json).unwrap();
+        assert_eq!(Value::json(serde_json::json!({ "a": 1 })), json);
+
+        let text = json.coerce(ValueType::Text).unwrap();
+        assert_eq!(Value::text(r#"{"a":1}"#), text);
+
+        let string = Value::json(serde_json::json!("plain"));
+        assert_eq!(Value::text("plain"), string.coerce(ValueType::Text).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json-1")]
+    fn params_from_json_with_bytes_and_nulls() {
+        let json = serde_json::json!(["AQI=", null, 3]);
+        let types = [ValueType::Bytes, ValueType::Text, ValueType::Real];
+        let params = params_from_json(&json, &types).unwrap();
+
+        assert_eq!(
+            vec![
+                Value::bytes(vec![1u8, 2]),
+                Value::Text(None),
+                Value::real(Decimal::from(3))
+            ],
+            params
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json-1")]
+    fn params_from_json_needs_a_type_for_every_value() {
+        let json = serde_json::json!([1, 2]);
+        assert!(params_from_json(&json, &[ValueType::Integer]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "chrono-0_4")]
+    fn text_to_datetime() {
+        let dt = Value::text("2020-06-01T10:00:00+02:00")
+            .coerce(ValueType::DateTime)
+            .unwrap();
+
+        assert_eq!(Value::datetime(Utc.ymd(2020, 6, 1).and_hms(8, 0, 0)), dt);
+    }
+}
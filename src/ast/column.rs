@@ -12,6 +12,17 @@ pub struct Column<'a> {
     pub(crate) table: Option<Table<'a>>,
     pub(crate) alias: Option<Cow<'a, str>>,
     pub(crate) default: Option<DefaultValue<'a>>,
+    pub(crate) generated: Option<Generated>,
+}
+
+/// How the database generates the values of a column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Generated {
+    /// Computed from other columns, such as `GENERATED ALWAYS AS (...)`.
+    /// The column can never be written to.
+    Computed,
+    /// An identity or an auto-incrementing column.
+    Identity,
 }
 
 /// Defines a default value for a `Column`.
@@ -53,6 +64,7 @@ impl<'a> Column<'a> {
             table: None,
             alias: None,
             default: None,
+            generated: None,
         }
     }
 
@@ -73,6 +85,20 @@ impl<'a> Column<'a> {
             .map(|d| d == &DefaultValue::Generated)
             .unwrap_or(false)
     }
+
+    /// Marks the values of the column as generated in the database. See
+    /// [`Table::add_generated_column`].
+    ///
+    /// [`Table::add_generated_column`]: struct.Table.html#method.add_generated_column
+    pub fn generated(mut self, kind: Generated) -> Self {
+        self.generated = Some(kind);
+        self
+    }
+
+    /// True if the column is an identity column.
+    pub fn is_identity(&self) -> bool {
+        self.generated == Some(Generated::Identity)
+    }
 }
 
 impl<'a> From<Column<'a>> for Expression<'a> {
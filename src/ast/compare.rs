@@ -37,10 +37,28 @@ pub enum Compare<'a> {
     Null(Box<Expression<'a>>),
     /// `value IS NOT NULL`
     NotNull(Box<Expression<'a>>),
+    /// `value IS TRUE`
+    IsTrue(Box<Expression<'a>>),
+    /// `value IS FALSE`
+    IsFalse(Box<Expression<'a>>),
     /// `value` BETWEEN `left` AND `right`
     Between(Box<Expression<'a>>, Box<Expression<'a>>, Box<Expression<'a>>),
     /// `value` NOT BETWEEN `left` AND `right`
     NotBetween(Box<Expression<'a>>, Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left @> right` (PostgreSQL)
+    ArrayContains(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left <@ right` (PostgreSQL)
+    ArrayContainedBy(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left && right` (PostgreSQL)
+    ArrayOverlaps(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left = ANY(right)` (PostgreSQL)
+    EqualsAny(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left IS NOT DISTINCT FROM right`
+    NotDistinctFrom(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `left IS DISTINCT FROM right`
+    DistinctFrom(Box<Expression<'a>>, Box<Expression<'a>>),
+    /// `LOWER(left) = LOWER(right)`
+    EqualsIgnoreCase(Box<Expression<'a>>, Box<Expression<'a>>),
 }
 
 impl<'a> From<Compare<'a>> for ConditionTree<'a> {
@@ -198,7 +216,8 @@ pub trait Comparable<'a> {
     where
         T: Into<Expression<'a>>;
 
-    /// Tests if the left side is included in the right side collection.
+    /// Tests if the left side is included in the right side collection. An
+    /// empty collection includes nothing, rendered as `1=0`.
     ///
     /// ```rust
     /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
@@ -219,6 +238,7 @@ pub trait Comparable<'a> {
         T: Into<Expression<'a>>;
 
     /// Tests if the left side is not included in the right side collection.
+    /// Nothing is included in an empty collection, rendered as `1=1`.
     ///
     /// ```rust
     /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
@@ -405,6 +425,44 @@ pub trait Comparable<'a> {
     /// ```
     fn is_not_null(self) -> Compare<'a>;
 
+    /// Tests if the left side is true. A `NULL` is neither true nor false.
+    /// On MySQL any non-zero number is true, and on SQL Server the left side
+    /// is compared to `1`.
+    ///
+    /// A column can also be used as a condition by itself, testing if it is
+    /// true.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, col, visitor::{Visitor, Mssql, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("active".is_true());
+    /// let (sql, _) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `active` IS TRUE", sql);
+    ///
+    /// let query = Select::from_table("users").so_that(col!("active"));
+    /// let (sql, _) = Mssql::build(query)?;
+    ///
+    /// assert_eq!("SELECT [users].* FROM [users] WHERE [active] = 1", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn is_true(self) -> Compare<'a>;
+
+    /// Tests if the left side is false. A `NULL` is neither true nor false.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("active".is_false());
+    /// let (sql, _) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `active` IS FALSE", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn is_false(self) -> Compare<'a>;
+
     /// Tests if the value is between two given values.
     ///
     /// ```rust
@@ -448,6 +506,130 @@ pub trait Comparable<'a> {
     where
         T: Into<Expression<'a>>,
         V: Into<Expression<'a>>;
+
+    /// Tests if the left side array includes all the values of the right side array. Only supported in PostgreSQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("tags".array_contains(Column::from("required_tags")));
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(r#"SELECT "users".* FROM "users" WHERE "tags" @> "required_tags""#, sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn array_contains<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
+    /// Tests if all the values of the left side array are in the right side array. Only supported in PostgreSQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("tags".array_contained_by(Column::from("allowed_tags")));
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(r#"SELECT "users".* FROM "users" WHERE "tags" <@ "allowed_tags""#, sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn array_contained_by<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
+    /// Tests if the arrays on both sides have at least one value in common. Only supported in PostgreSQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("tags".array_overlaps(Column::from("wanted_tags")));
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(r#"SELECT "users".* FROM "users" WHERE "tags" && "wanted_tags""#, sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn array_overlaps<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
+    /// Tests if the left side is equal to any value in the right side array. Only supported in PostgreSQL.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("id".equals_any(Column::from("friend_ids")));
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(r#"SELECT "users".* FROM "users" WHERE "id" = ANY("friend_ids")"#, sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn equals_any<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+    /// Tests if the sides are equal, treating two `NULL` values as equal and
+    /// a `NULL` and a value as different. Unlike `equals`, matches the rows
+    /// where both sides are `NULL`.
+    ///
+    /// Rendered as `<=>` on MySQL, `IS` on SQLite and with `INTERSECT` on SQL
+    /// Server.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("parent_id".is_not_distinct_from(Value::Integer(None)));
+    ///
+    /// let (sql, _) = Postgres::build(query.clone())?;
+    /// assert_eq!(r#"SELECT "users".* FROM "users" WHERE "parent_id" IS NOT DISTINCT FROM $1"#, sql);
+    ///
+    /// let (sql, _) = Mysql::build(query)?;
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `parent_id` <=> ?", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
+    /// Tests if the sides are different, treating two `NULL` values as equal
+    /// and a `NULL` and a value as different. The negation of
+    /// `is_not_distinct_from`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("parent_id".is_distinct_from(1));
+    /// let (sql, _) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `parent_id` IS NOT ?", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
+
+    /// Tests if the sides are equal without regard to case, the way a
+    /// PostgreSQL `citext` column compares. Works on any text column, and
+    /// gives the same result as `equals` on a `citext` column.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").so_that("email".equals_ignore_case("Musti@Example.com"));
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(r#"SELECT "users".* FROM "users" WHERE LOWER("email") = LOWER($1)"#, sql);
+    /// assert_eq!(vec![Value::from("Musti@Example.com")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn equals_ignore_case<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>;
 }
 
 impl<'a, U> Comparable<'a> for U
@@ -593,6 +775,18 @@ where
         val.is_not_null()
     }
 
+    fn is_true(self) -> Compare<'a> {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.is_true()
+    }
+
+    fn is_false(self) -> Compare<'a> {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.is_false()
+    }
+
     fn between<T, V>(self, left: T, right: V) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -612,4 +806,67 @@ where
         let val: Expression<'a> = col.into();
         val.not_between(left, right)
     }
+
+    fn array_contains<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.array_contains(comparison)
+    }
+
+    fn array_contained_by<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.array_contained_by(comparison)
+    }
+
+    fn array_overlaps<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.array_overlaps(comparison)
+    }
+
+    fn equals_any<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.equals_any(comparison)
+    }
+
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.is_not_distinct_from(comparison)
+    }
+
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.is_distinct_from(comparison)
+    }
+
+    fn equals_ignore_case<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let col: Column<'a> = self.into();
+        let val: Expression<'a> = col.into();
+        val.equals_ignore_case(comparison)
+    }
 }
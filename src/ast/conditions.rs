@@ -89,6 +89,24 @@ impl<'a> From<ConditionTree<'a>> for Expression<'a> {
     }
 }
 
+/// A bare column is a condition testing if the column is true, other
+/// expressions are used as they are.
+impl<'a> From<Expression<'a>> for ConditionTree<'a> {
+    fn from(expr: Expression<'a>) -> Self {
+        match expr.kind {
+            ExpressionKind::ConditionTree(tree) => tree,
+            ExpressionKind::Column(_) => ConditionTree::single(expr.is_true()),
+            _ => ConditionTree::single(expr),
+        }
+    }
+}
+
+impl<'a> From<Column<'a>> for ConditionTree<'a> {
+    fn from(column: Column<'a>) -> Self {
+        ConditionTree::single(column.is_true())
+    }
+}
+
 impl<'a> From<Select<'a>> for ConditionTree<'a> {
     fn from(sel: Select<'a>) -> Self {
         let exp = Expression {
@@ -0,0 +1,54 @@
+use crate::{
+    ast::Value,
+    error::{Error, ErrorKind},
+};
+
+/// A Rust enum stored in a database enum or a text column, with an explicit
+/// name for every variant. Usually implemented with the
+/// [db_enum](../macro.db_enum.html) macro, that also makes the enum usable
+/// as a query parameter and as a target of
+/// [ResultSet::column](../connector/struct.ResultSet.html#method.column).
+///
+/// ```rust
+/// # use quaint::{db_enum, ast::*};
+/// # use std::convert::TryFrom;
+/// db_enum! {
+///     #[derive(Debug, Clone, Copy, PartialEq)]
+///     pub enum Mood {
+///         Happy => "happy",
+///         Grumpy => "grumpy",
+///     }
+/// }
+///
+/// assert_eq!(Value::enum_variant("grumpy"), Value::from(Mood::Grumpy));
+/// assert_eq!(Mood::Happy, Mood::try_from(Value::text("happy")).unwrap());
+/// assert!(Mood::try_from(Value::text("hungry")).is_err());
+/// ```
+pub trait DbEnum: Sized {
+    /// The name of the variant in the database.
+    fn db_name(&self) -> &'static str;
+
+    /// The variant with the given name in the database, if any.
+    fn from_db_name(name: &str) -> Option<Self>;
+
+    /// The variant as an enum value.
+    fn to_value<'a>(&self) -> Value<'a> {
+        Value::enum_variant(self.db_name())
+    }
+
+    /// Reads the variant from an enum or a text value.
+    fn from_value(value: &Value<'_>) -> crate::Result<Self> {
+        let name = match value {
+            Value::Enum(Some(name)) | Value::Text(Some(name)) => name,
+            _ => {
+                let msg = "Not an enum or a text value";
+                return Err(Error::builder(ErrorKind::conversion(msg)).build());
+            }
+        };
+
+        Self::from_db_name(name).ok_or_else(|| {
+            let msg = format!("Unknown enum variant `{}`", name);
+            Error::builder(ErrorKind::conversion(msg)).build()
+        })
+    }
+}
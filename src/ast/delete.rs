@@ -5,6 +5,8 @@ use crate::ast::*;
 pub struct Delete<'a> {
     pub(crate) table: Table<'a>,
     pub(crate) conditions: Option<ConditionTree<'a>>,
+    pub(crate) ordering: Ordering<'a>,
+    pub(crate) limit: Option<Value<'a>>,
 }
 
 impl<'a> From<Delete<'a>> for Query<'a> {
@@ -33,6 +35,8 @@ impl<'a> Delete<'a> {
         Self {
             table: table.into(),
             conditions: None,
+            ordering: Ordering::default(),
+            limit: None,
         }
     }
 
@@ -57,4 +61,53 @@ impl<'a> Delete<'a> {
         self.conditions = Some(conditions.into());
         self
     }
+
+    /// Adds an ordering to the `ORDER BY` section, deciding which rows are
+    /// deleted first. Meaningful together with a `limit`.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Delete::from_table("jobs").so_that("done".is_true()).order_by("id").limit(10);
+    /// let (sql, params) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("DELETE FROM `jobs` WHERE `done` IS TRUE ORDER BY `id` LIMIT ?", sql);
+    /// assert_eq!(vec![Value::from(10)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        self.ordering = self.ordering.append(value.into_order_definition());
+        self
+    }
+
+    /// Deletes at most `limit` rows.
+    ///
+    /// MySQL supports the `ORDER BY` and `LIMIT` clauses in a `DELETE`. On
+    /// PostgreSQL and SQLite the rows are selected in a subquery by their
+    /// `ctid` or `rowid`, which tables created `WITHOUT ROWID` in SQLite
+    /// lack. On SQL Server the rows are deleted through a common table
+    /// expression selecting the `TOP` rows.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Delete::from_table("jobs").order_by("id").limit(10);
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     r#"DELETE FROM "jobs" WHERE "ctid" IN (SELECT "ctid" FROM "jobs" ORDER BY "id" LIMIT $1)"#,
+    ///     sql
+    /// );
+    /// assert_eq!(vec![Value::from(10)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(Value::from(limit));
+        self
+    }
 }
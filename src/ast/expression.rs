@@ -45,6 +45,8 @@ pub enum ExpressionKind<'a> {
     Compare(Compare<'a>),
     /// A single value, column, row or a nested select
     Value(Box<Expression<'a>>),
+    /// The `DEFAULT` keyword, using the column default in the database
+    Default,
 }
 
 /// A quick alias to create an asterisk to a table.
@@ -55,6 +57,33 @@ pub fn asterisk() -> Expression<'static> {
     }
 }
 
+/// The `DEFAULT` keyword, for explicitly requesting the database default of a
+/// column in `INSERT` values or `UPDATE` assignments.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Insert::multi_into("users", vec!["id", "name"])
+///     .values(vec![default_value(), "Musti".into()])
+///     .values(vec![Expression::from(2), default_value()]);
+///
+/// let (sql, params) = Postgres::build(query)?;
+///
+/// assert_eq!(
+///     "INSERT INTO \"users\" (\"id\",\"name\") VALUES (DEFAULT,$1), ($2,DEFAULT)",
+///     sql
+/// );
+/// assert_eq!(vec![Value::from("Musti"), Value::from(2)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn default_value() -> Expression<'static> {
+    Expression {
+        kind: ExpressionKind::Default,
+        alias: None,
+    }
+}
+
 expression!(Row, Row);
 expression!(Function, Function);
 
@@ -226,6 +255,14 @@ impl<'a> Comparable<'a> for Expression<'a> {
         Compare::NotNull(Box::new(self))
     }
 
+    fn is_true(self) -> Compare<'a> {
+        Compare::IsTrue(Box::new(self))
+    }
+
+    fn is_false(self) -> Compare<'a> {
+        Compare::IsFalse(Box::new(self))
+    }
+
     fn between<T, V>(self, left: T, right: V) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -241,4 +278,53 @@ impl<'a> Comparable<'a> for Expression<'a> {
     {
         Compare::NotBetween(Box::new(self), Box::new(left.into()), Box::new(right.into()))
     }
+
+    fn array_contains<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::ArrayContains(Box::new(self), Box::new(comparison.into()))
+    }
+
+    fn array_contained_by<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::ArrayContainedBy(Box::new(self), Box::new(comparison.into()))
+    }
+
+    fn array_overlaps<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::ArrayOverlaps(Box::new(self), Box::new(comparison.into()))
+    }
+
+    fn equals_any<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::EqualsAny(Box::new(self), Box::new(comparison.into()))
+    }
+
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::NotDistinctFrom(Box::new(self), Box::new(comparison.into()))
+    }
+
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::DistinctFrom(Box::new(self), Box::new(comparison.into()))
+    }
+
+    fn equals_ignore_case<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        Compare::EqualsIgnoreCase(Box::new(self), Box::new(comparison.into()))
+    }
 }
@@ -1,21 +1,31 @@
 mod aggregate_to_string;
 mod average;
+mod cast;
 mod count;
+mod json_extract;
+mod json_set;
+mod json_unquote;
 mod lower;
 mod maximum;
 mod minimum;
 mod row_number;
 mod sum;
+mod unnest;
 mod upper;
 
 pub use aggregate_to_string::*;
 pub use average::*;
+pub use cast::*;
 pub use count::*;
+pub use json_extract::*;
+pub use json_set::*;
+pub use json_unquote::*;
 pub use lower::*;
 pub use maximum::*;
 pub use minimum::*;
 pub use row_number::*;
 pub use sum::*;
+pub use unnest::*;
 pub use upper::*;
 
 use super::{Aliasable, Expression};
@@ -40,6 +50,10 @@ pub(crate) enum FunctionType<'a> {
     Upper(Upper<'a>),
     Minimum(Minimum<'a>),
     Maximum(Maximum<'a>),
+    Cast(Cast<'a>),
+    JsonExtract(JsonExtract<'a>),
+    JsonSet(JsonSet<'a>),
+    JsonUnquote(JsonUnquote<'a>),
 }
 
 impl<'a> Aliasable<'a> for Function<'a> {
@@ -63,5 +77,9 @@ function!(
     Lower,
     Upper,
     Minimum,
-    Maximum
+    Maximum,
+    Cast,
+    JsonExtract,
+    JsonSet,
+    JsonUnquote
 );
@@ -0,0 +1,38 @@
+use super::Function;
+use crate::ast::Expression;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Converts a value into another type.
+pub struct Cast<'a> {
+    pub(crate) expression: Box<Expression<'a>>,
+    pub(crate) target: Cow<'a, str>,
+}
+
+/// Converts the result of the expression into the given type, written to the
+/// query as is. Useful to compare a value read from a JSON document, or to
+/// match the expression of an index on a generated column.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let age: Expression = cast(json_extract(Column::from("data"), "$.age"), "UNSIGNED").into();
+/// let query = Select::from_table("users").so_that(age.greater_than(18));
+/// let (sql, _) = Mysql::build(query)?;
+///
+/// assert_eq!("SELECT `users`.* FROM `users` WHERE CAST(JSON_EXTRACT(`data`, ?) AS UNSIGNED) > ?", sql);
+/// # Ok(())
+/// # }
+/// ```
+pub fn cast<'a, E, T>(expression: E, target: T) -> Function<'a>
+where
+    E: Into<Expression<'a>>,
+    T: Into<Cow<'a, str>>,
+{
+    let fun = Cast {
+        expression: Box::new(expression.into()),
+        target: target.into(),
+    };
+
+    fun.into()
+}
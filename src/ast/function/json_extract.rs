@@ -0,0 +1,87 @@
+use super::Function;
+use crate::ast::Expression;
+use std::borrow::Cow;
+
+/// A path to a value inside a JSON document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonPath<'a> {
+    /// A MySQL path expression, such as `$.address.city`.
+    String(Cow<'a, str>),
+    /// The keys and array indices from the root, such as `["address", "city"]`.
+    /// Used by PostgreSQL.
+    Array(Vec<Cow<'a, str>>),
+}
+
+impl<'a> JsonPath<'a> {
+    /// A MySQL path expression.
+    pub fn string<S>(path: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Self::String(path.into())
+    }
+
+    /// A PostgreSQL path of keys and array indices.
+    pub fn array<I, S>(path: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Cow<'a, str>>,
+    {
+        Self::Array(path.into_iter().map(|s| s.into()).collect())
+    }
+}
+
+impl<'a> From<&'a str> for JsonPath<'a> {
+    fn from(path: &'a str) -> Self {
+        Self::string(path)
+    }
+}
+
+impl<'a> From<Vec<&'a str>> for JsonPath<'a> {
+    fn from(path: Vec<&'a str>) -> Self {
+        Self::array(path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Reads a value from a JSON document.
+pub struct JsonExtract<'a> {
+    pub(crate) expression: Box<Expression<'a>>,
+    pub(crate) path: JsonPath<'a>,
+}
+
+/// Reads the value in the given path of a JSON document, as JSON. Wrap the
+/// result in [`json_unquote`] to read a string value as text.
+///
+/// MySQL takes the path as a string, PostgreSQL as an array of keys:
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Mysql, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Select::from_table("users").value(json_extract(Column::from("data"), "$.address.city"));
+/// let (sql, params) = Mysql::build(query)?;
+///
+/// assert_eq!("SELECT JSON_EXTRACT(`data`, ?) FROM `users`", sql);
+/// assert_eq!(vec![Value::from("$.address.city")], params);
+///
+/// let query = Select::from_table("users").value(json_extract(Column::from("data"), vec!["address", "city"]));
+/// let (sql, _) = Postgres::build(query)?;
+///
+/// assert_eq!("SELECT (\"data\" #> ARRAY[$1, $2]::text[]) FROM \"users\"", sql);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`json_unquote`]: fn.json_unquote.html
+pub fn json_extract<'a, E, P>(expression: E, path: P) -> Function<'a>
+where
+    E: Into<Expression<'a>>,
+    P: Into<JsonPath<'a>>,
+{
+    let fun = JsonExtract {
+        expression: Box::new(expression.into()),
+        path: path.into(),
+    };
+
+    fun.into()
+}
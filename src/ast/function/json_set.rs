@@ -0,0 +1,43 @@
+use super::{Function, JsonPath};
+use crate::ast::Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Replaces a value in a JSON document.
+pub struct JsonSet<'a> {
+    pub(crate) expression: Box<Expression<'a>>,
+    pub(crate) path: JsonPath<'a>,
+    pub(crate) value: Box<Expression<'a>>,
+}
+
+/// Returns the JSON document with the value in the given path replaced or
+/// added. Rendered as `JSON_SET` in MySQL and `jsonb_set` in PostgreSQL,
+/// where the value must be JSON and the column a `jsonb`.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = Update::table("users")
+///     .set("data", json_set(Column::from("data"), "$.address.city", "Helsinki"))
+///     .so_that("id".equals(1));
+///
+/// let (sql, params) = Mysql::build(query)?;
+///
+/// assert_eq!("UPDATE `users` SET `data` = JSON_SET(`data`, ?, ?) WHERE `id` = ?", sql);
+/// assert_eq!(vec![Value::from("$.address.city"), Value::from("Helsinki"), Value::from(1)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn json_set<'a, E, P, V>(expression: E, path: P, value: V) -> Function<'a>
+where
+    E: Into<Expression<'a>>,
+    P: Into<JsonPath<'a>>,
+    V: Into<Expression<'a>>,
+{
+    let fun = JsonSet {
+        expression: Box::new(expression.into()),
+        path: path.into(),
+        value: Box::new(value.into()),
+    };
+
+    fun.into()
+}
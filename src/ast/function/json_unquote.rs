@@ -0,0 +1,32 @@
+use super::Function;
+use crate::ast::Expression;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Converts a JSON value into text.
+pub struct JsonUnquote<'a> {
+    pub(crate) expression: Box<Expression<'a>>,
+}
+
+/// Converts a JSON value into text, a JSON string without the quotes.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let city: Expression = json_unquote(json_extract(Column::from("data"), "$.address.city")).into();
+/// let query = Select::from_table("users").so_that(city.equals("Helsinki"));
+/// let (sql, _) = Mysql::build(query)?;
+///
+/// assert_eq!("SELECT `users`.* FROM `users` WHERE JSON_UNQUOTE(JSON_EXTRACT(`data`, ?)) = ?", sql);
+/// # Ok(())
+/// # }
+/// ```
+pub fn json_unquote<'a, E>(expression: E) -> Function<'a>
+where
+    E: Into<Expression<'a>>,
+{
+    let fun = JsonUnquote {
+        expression: Box::new(expression.into()),
+    };
+
+    fun.into()
+}
@@ -0,0 +1,72 @@
+use crate::ast::{Expression, Table, TableType};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A table with a row for every value in an array.
+pub struct Unnest<'a> {
+    pub(crate) expression: Box<Expression<'a>>,
+    pub(crate) value_type: Cow<'a, str>,
+}
+
+/// Turns an array into a table of one `value` column, to join against a list
+/// of values passed as a single parameter instead of a long `IN` list. The
+/// table must be given an alias.
+///
+/// PostgreSQL takes an array value. MySQL 8 takes a JSON array, read with
+/// `JSON_TABLE` into a column of the [`value_type`], `JSON` by default.
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let ids = Table::from(unnest(Value::text("[1, 2, 3]")).value_type("BIGINT")).alias("ids");
+///
+/// let query = Select::from_table("users")
+///     .inner_join(ids.on(("users", "id").equals(Column::from(("ids", "value")))));
+///
+/// let (sql, _) = Mysql::build(query)?;
+///
+/// assert_eq!(
+///     "SELECT `users`.* FROM `users` INNER JOIN \
+///      JSON_TABLE(?, '$[*]' COLUMNS (`value` BIGINT PATH '$')) AS `ids` \
+///      ON `users`.`id` = `ids`.`value`",
+///     sql
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`value_type`]: struct.Unnest.html#method.value_type
+pub fn unnest<'a, E>(expression: E) -> Unnest<'a>
+where
+    E: Into<Expression<'a>>,
+{
+    Unnest {
+        expression: Box::new(expression.into()),
+        value_type: Cow::Borrowed("JSON"),
+    }
+}
+
+impl<'a> Unnest<'a> {
+    /// The type of the `value` column in MySQL, written to the query as is.
+    /// PostgreSQL uses the type of the array.
+    pub fn value_type<T>(mut self, value_type: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.value_type = value_type.into();
+        self
+    }
+}
+
+impl<'a> From<Unnest<'a>> for Table<'a> {
+    fn from(unnest: Unnest<'a>) -> Self {
+        Table {
+            typ: TableType::Unnest(unnest),
+            alias: None,
+            database: None,
+            schema: None,
+            index_definitions: Vec::new(),
+            generated_columns: Vec::new(),
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::ast::*;
+use crate::{
+    ast::*,
+    error::{Error, ErrorKind},
+};
 
 /// A builder for an `INSERT` statement.
 #[derive(Clone, Debug, PartialEq)]
@@ -6,8 +9,9 @@ pub struct Insert<'a> {
     pub(crate) table: Option<Table<'a>>,
     pub(crate) columns: Vec<Column<'a>>,
     pub(crate) values: Expression<'a>,
-    pub(crate) on_conflict: Option<OnConflict>,
+    pub(crate) on_conflict: Option<OnConflict<'a>>,
     pub(crate) returning: Option<Vec<Column<'a>>>,
+    pub(crate) overriding_system_value: bool,
 }
 
 /// A builder for an `INSERT` statement for a single row.
@@ -15,6 +19,8 @@ pub struct SingleRowInsert<'a> {
     pub(crate) table: Option<Table<'a>>,
     pub(crate) columns: Vec<Column<'a>>,
     pub(crate) values: Row<'a>,
+    pub(crate) declared_columns: Option<Vec<Column<'a>>>,
+    pub(crate) overriding_system_value: bool,
 }
 
 /// A builder for an `INSERT` statement for multiple rows.
@@ -22,11 +28,12 @@ pub struct MultiRowInsert<'a> {
     pub(crate) table: Option<Table<'a>>,
     pub(crate) columns: Vec<Column<'a>>,
     pub(crate) values: Vec<Row<'a>>,
+    pub(crate) overriding_system_value: bool,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 /// `INSERT` conflict resolution strategies.
-pub enum OnConflict {
+pub enum OnConflict<'a> {
     /// When a row already exists, do nothing. Works with PostgreSQL, MySQL or
     /// SQLite without schema information.
     ///
@@ -71,7 +78,7 @@ pub enum OnConflict {
     /// ```
     ///
     /// If the `INSERT` statement misses a value for a unique column that does
-    /// not have default value set, the visitor will raise a panic. For compound
+    /// not have default value set, the visitor returns an error. For compound
     /// unique indices, the `add_unique_index` takes a vector as a parameter.
     ///
     /// If the [column has a default value], it should be added to the `Column`
@@ -82,6 +89,39 @@ pub enum OnConflict {
     /// [`DefaultValue::Generated`]: enum.DefaultValue.html#variant.Generated
     /// [column has a default value]: struct.Column.html#method.default
     DoNothing,
+    /// When a row with the same values in the `target` columns already
+    /// exists, set its `columns` to the inserted values instead, also known
+    /// as an upsert. The `columns` must not be empty.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query: Insert = Insert::single_into("users").value("id", 1).value("name", "Musti").into();
+    ///
+    /// let (sql, _) = Postgres::build(query.on_conflict(OnConflict::Update {
+    ///     target: vec!["id".into()],
+    ///     columns: vec!["name".into()],
+    /// }))?;
+    ///
+    /// assert_eq!(
+    ///     "INSERT INTO \"users\" (\"id\",\"name\") VALUES ($1,$2) \
+    ///      ON CONFLICT (\"id\") DO UPDATE SET \"name\" = \"excluded\".\"name\"",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// If the `target` is empty, the columns of the first unique index of the
+    /// table are used, added with `Table::add_unique_index`. MySQL ignores the
+    /// target and updates the row on a conflict in any unique index, and SQL
+    /// Server converts the `INSERT` to a `MERGE` joining on the target.
+    Update {
+        /// The columns of the unique index to check for conflicts.
+        target: Vec<Column<'a>>,
+        /// The columns to update in the conflicting row.
+        columns: Vec<Column<'a>>,
+    },
 }
 
 impl<'a> From<Insert<'a>> for Query<'a> {
@@ -92,36 +132,93 @@ impl<'a> From<Insert<'a>> for Query<'a> {
 
 impl<'a> From<SingleRowInsert<'a>> for Insert<'a> {
     fn from(insert: SingleRowInsert<'a>) -> Self {
-        let values = if insert.values.is_empty() {
+        let (columns, mut rows) = omit_generated(
+            insert.table.as_ref(),
+            insert.overriding_system_value,
+            insert.columns,
+            vec![insert.values],
+        );
+        let row = rows.pop().unwrap_or_default();
+
+        let values = if row.is_empty() {
             Expression::from(Row::new())
         } else {
-            Expression::from(insert.values)
+            Expression::from(row)
         };
 
         Insert {
             table: insert.table,
-            columns: insert.columns,
+            columns,
             values,
             on_conflict: None,
             returning: None,
+            overriding_system_value: insert.overriding_system_value,
         }
     }
 }
 
 impl<'a> From<MultiRowInsert<'a>> for Insert<'a> {
     fn from(insert: MultiRowInsert<'a>) -> Self {
-        let values = Expression::from(Values::new(insert.values));
+        let (columns, rows) = omit_generated(
+            insert.table.as_ref(),
+            insert.overriding_system_value,
+            insert.columns,
+            insert.values,
+        );
+        let values = Expression::from(Values::new(rows));
 
         Insert {
             table: insert.table,
-            columns: insert.columns,
+            columns,
             values,
             on_conflict: None,
             returning: None,
+            overriding_system_value: insert.overriding_system_value,
         }
     }
 }
 
+/// Leaves out the columns generated in the database, with their values,
+/// keeping the identity columns if overriding the system value.
+fn omit_generated<'a>(
+    table: Option<&Table<'a>>,
+    overriding_system_value: bool,
+    columns: Vec<Column<'a>>,
+    rows: Vec<Row<'a>>,
+) -> (Vec<Column<'a>>, Vec<Row<'a>>) {
+    let is_omitted = |column: &Column<'a>| match table {
+        Some(table) if overriding_system_value => table.is_computed(column),
+        Some(table) => table.is_generated(column),
+        None => false,
+    };
+
+    let keep: Vec<bool> = columns.iter().map(|c| !is_omitted(c)).collect();
+
+    if keep.iter().all(|k| *k) {
+        return (columns, rows);
+    }
+
+    let columns = columns
+        .into_iter()
+        .zip(keep.iter())
+        .filter(|(_, k)| **k)
+        .map(|(c, _)| c);
+
+    let rows = rows.into_iter().map(|row| {
+        let values = row
+            .values
+            .into_iter()
+            .zip(keep.iter())
+            .filter(|(_, k)| **k)
+            .map(|(v, _)| v);
+        Row {
+            values: values.collect(),
+        }
+    });
+
+    (columns.collect(), rows.collect())
+}
+
 impl<'a> From<SingleRowInsert<'a>> for Query<'a> {
     fn from(insert: SingleRowInsert<'a>) -> Query<'a> {
         Query::from(Insert::from(insert))
@@ -155,6 +252,8 @@ impl<'a> Insert<'a> {
             table: Some(table.into()),
             columns: Vec::new(),
             values: Row::new(),
+            declared_columns: None,
+            overriding_system_value: false,
         }
     }
 
@@ -163,6 +262,8 @@ impl<'a> Insert<'a> {
             table: None,
             columns: Vec::new(),
             values: Row::new(),
+            declared_columns: None,
+            overriding_system_value: false,
         }
     }
 
@@ -177,6 +278,7 @@ impl<'a> Insert<'a> {
             table: Some(table.into()),
             columns: columns.into_iter().map(|c| c.into()).collect(),
             values: Vec::new(),
+            overriding_system_value: false,
         }
     }
 
@@ -189,6 +291,7 @@ impl<'a> Insert<'a> {
             table: None,
             columns: columns.into_iter().map(|c| c.into()).collect(),
             values: Vec::new(),
+            overriding_system_value: false,
         }
     }
 
@@ -205,15 +308,97 @@ impl<'a> Insert<'a> {
             values: expression.into(),
             on_conflict: None,
             returning: None,
+            overriding_system_value: false,
         }
     }
 
     /// Sets the conflict resolution strategy.
-    pub fn on_conflict(mut self, on_conflict: OnConflict) -> Self {
+    pub fn on_conflict(mut self, on_conflict: OnConflict<'a>) -> Self {
         self.on_conflict = Some(on_conflict);
         self
     }
 
+    /// The conflict resolution strategy, erroring if an update has no
+    /// columns to update. The target is left as given, for databases finding
+    /// the conflicting row from all unique indices.
+    pub(crate) fn checked_on_conflict(&self) -> crate::Result<Option<OnConflict<'a>>> {
+        match &self.on_conflict {
+            Some(OnConflict::Update { columns, .. }) if columns.is_empty() => {
+                let kind = ErrorKind::conversion("An upsert needs at least one column to update.");
+                Err(Error::builder(kind).build())
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// The conflict resolution strategy, with the target of an update
+    /// inferred from the unique indices of the table if not given.
+    pub(crate) fn resolved_on_conflict(&self) -> crate::Result<Option<OnConflict<'a>>> {
+        let (target, columns) = match self.checked_on_conflict()? {
+            Some(OnConflict::Update { target, columns }) => (target, columns),
+            other => return Ok(other),
+        };
+
+        let target = if target.is_empty() {
+            match self.table.as_ref().and_then(|t| t.index_definitions.first()) {
+                Some(IndexDefinition::Single(column)) => vec![column.clone()],
+                Some(IndexDefinition::Compound(columns)) => columns.clone(),
+                None => {
+                    let kind = ErrorKind::conversion("An upsert needs a target or a table with a unique index.");
+                    return Err(Error::builder(kind).build());
+                }
+            }
+        } else {
+            target
+        };
+
+        Ok(Some(OnConflict::Update {
+            target: target.into_iter().map(|c| c.into_bare()).collect(),
+            columns: columns.into_iter().map(|c| c.into_bare()).collect(),
+        }))
+    }
+
+    /// Errors if the insert is not an upsert with `OnConflict::Update`.
+    pub(crate) fn ensure_upsert(&self) -> crate::Result<()> {
+        match self.on_conflict {
+            Some(OnConflict::Update { .. }) => Ok(()),
+            _ => {
+                let kind = ErrorKind::conversion("Reading the outcome of the rows needs an `OnConflict::Update`.");
+                Err(Error::builder(kind).build())
+            }
+        }
+    }
+
+    /// Splits an insert of many rows into an insert for each row.
+    pub(crate) fn into_rows(self) -> Vec<Insert<'a>> {
+        let values = match self.values.kind {
+            ExpressionKind::Values(values) => values,
+            _ => return vec![self],
+        };
+
+        let Insert {
+            table,
+            columns,
+            on_conflict,
+            returning,
+            overriding_system_value,
+            ..
+        } = self;
+
+        values
+            .rows
+            .into_iter()
+            .map(|row| Insert {
+                table: table.clone(),
+                columns: columns.clone(),
+                values: Expression::from(row),
+                on_conflict: on_conflict.clone(),
+                returning: returning.clone(),
+                overriding_system_value,
+            })
+            .collect()
+    }
+
     /// Sets the returned columns.
     ///
     /// ```rust
@@ -227,7 +412,7 @@ impl<'a> Insert<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(any(feature = "postgresql", feature = "mssql"))]
+    #[cfg(any(feature = "postgresql-core", feature = "mssql"))]
     pub fn returning<K, I>(mut self, columns: I) -> Self
     where
         K: Into<Column<'a>>,
@@ -236,6 +421,71 @@ impl<'a> Insert<'a> {
         self.returning = Some(columns.into_iter().map(|k| k.into()).collect());
         self
     }
+
+    /// Adds the identity columns of the table to the returned columns, to
+    /// read the values generated for the new row.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let table = Table::from("users").add_generated_column(Column::from("id").generated(Generated::Identity));
+    /// let query = Insert::single_into(table).value("id", 1).value("name", "Musti");
+    /// let (sql, _) = Postgres::build(Insert::from(query).returning_generated())?;
+    ///
+    /// assert_eq!("INSERT INTO \"users\" (\"name\") VALUES ($1) RETURNING \"id\"", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(feature = "postgresql-core", feature = "mssql"))]
+    pub fn returning_generated(mut self) -> Self {
+        let identity = self
+            .table
+            .iter()
+            .flat_map(|t| t.generated_columns.iter())
+            .filter(|c| c.is_identity());
+
+        let mut returning = self.returning.take().unwrap_or_default();
+
+        for column in identity {
+            if !returning.contains(column) {
+                returning.push(column.clone());
+            }
+        }
+
+        if !returning.is_empty() {
+            self.returning = Some(returning);
+        }
+
+        self
+    }
+
+    /// Writes the given values to the identity columns, instead of the ones
+    /// generated in the database. PostgreSQL needs `OVERRIDING SYSTEM VALUE`
+    /// for columns declared `GENERATED ALWAYS AS IDENTITY`, other databases
+    /// take explicit values as they are, apart from SQL Server needing
+    /// `IDENTITY_INSERT` set for the table.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Insert::single_into("users").value("id", 1).value("name", "Musti");
+    /// let (sql, _) = Postgres::build(Insert::from(query).overriding_system_value())?;
+    ///
+    /// assert_eq!(
+    ///     "INSERT INTO \"users\" (\"id\",\"name\") OVERRIDING SYSTEM VALUE VALUES ($1,$2)",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The identity columns of the table are left out from the inserted
+    /// values, unless overriding the system value already in the single or
+    /// multi row builder.
+    pub fn overriding_system_value(mut self) -> Self {
+        self.overriding_system_value = true;
+        self
+    }
 }
 
 impl<'a> SingleRowInsert<'a> {
@@ -263,10 +513,142 @@ impl<'a> SingleRowInsert<'a> {
         self
     }
 
+    /// Adds a column to the `INSERT` statement, using the database default as
+    /// its value.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Insert::single_into("users").value("foo", 10).value_default("created_at");
+    /// let (sql, params) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("INSERT INTO `users` (`foo`,`created_at`) VALUES (?,DEFAULT)", sql);
+    /// assert_eq!(vec![Value::from(10)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// SQLite does not know the `DEFAULT` keyword, so the column is left out
+    /// from the statement instead.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Insert::single_into("users").value("foo", 10).value_default("created_at");
+    /// let (sql, _) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("INSERT INTO `users` (`foo`) VALUES (?)", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn value_default<K>(self, key: K) -> SingleRowInsert<'a>
+    where
+        K: Into<Column<'a>>,
+    {
+        self.value(key, default_value())
+    }
+
+    /// Declares the columns of the row, to check the values against them in
+    /// [`try_build`]. Columns with a default value or generated in the
+    /// database can be left without a value.
+    ///
+    /// ```rust
+    /// # use quaint::ast::*;
+    /// let columns = vec![
+    ///     Column::from("id"),
+    ///     Column::from("name"),
+    ///     Column::from("created_at").default(DefaultValue::Generated),
+    /// ];
+    ///
+    /// let insert = Insert::single_into("users").declare_columns(columns.clone());
+    /// assert!(insert.value("id", 1).value("name", "Musti").try_build().is_ok());
+    ///
+    /// let insert = Insert::single_into("users").declare_columns(columns.clone());
+    /// assert!(insert.value("id", 1).value("nmae", "Musti").try_build().is_err());
+    ///
+    /// let insert = Insert::single_into("users").declare_columns(columns);
+    /// assert!(insert.value("id", 1).try_build().is_err());
+    /// ```
+    ///
+    /// [`try_build`]: #method.try_build
+    pub fn declare_columns<K, I>(mut self, columns: I) -> Self
+    where
+        K: Into<Column<'a>>,
+        I: IntoIterator<Item = K>,
+    {
+        self.declared_columns = Some(columns.into_iter().map(|c| c.into()).collect());
+        self
+    }
+
+    /// Keeps the values of the identity columns of the table, and writes
+    /// them instead of the generated ones. See
+    /// [`Insert::overriding_system_value`].
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let table = Table::from("users")
+    ///     .add_generated_column(Column::from("id").generated(Generated::Identity))
+    ///     .add_generated_column("full_name");
+    ///
+    /// let query = Insert::single_into(table)
+    ///     .value("id", 1)
+    ///     .value("first_name", "Musti")
+    ///     .value("full_name", "Musti Cat")
+    ///     .overriding_system_value();
+    ///
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "INSERT INTO \"users\" (\"id\",\"first_name\") OVERRIDING SYSTEM VALUE VALUES ($1,$2)",
+    ///     sql
+    /// );
+    /// assert_eq!(vec![Value::from(1), Value::from("Musti")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Insert::overriding_system_value`]: struct.Insert.html#method.overriding_system_value
+    pub fn overriding_system_value(mut self) -> Self {
+        self.overriding_system_value = true;
+        self
+    }
+
     /// Convert into a common `Insert` statement.
     pub fn build(self) -> Insert<'a> {
         Insert::from(self)
     }
+
+    /// Convert into a common `Insert` statement, returning an error if a
+    /// value is given to a column not in the [declared columns], or if a
+    /// declared column without a default value has no value.
+    ///
+    /// [declared columns]: #method.declare_columns
+    pub fn try_build(self) -> crate::Result<Insert<'a>> {
+        if let Some(declared) = self.declared_columns.as_ref() {
+            let is_declared = |column: &Column<'a>| declared.iter().any(|d| d.name == column.name);
+            let has_value = |column: &Column<'a>| self.columns.iter().any(|c| c.name == column.name);
+
+            if let Some(unknown) = self.columns.iter().find(|c| !is_declared(c)) {
+                let msg = format!("Column `{}` is not declared for the insert.", unknown.name);
+                return Err(Error::builder(ErrorKind::conversion(msg)).build());
+            }
+
+            let is_generated =
+                |column: &Column<'a>| self.table.as_ref().map(|t| t.is_generated(column)).unwrap_or(false);
+
+            let missing = declared
+                .iter()
+                .find(|d| d.default.is_none() && !is_generated(d) && !has_value(d));
+
+            if let Some(missing) = missing {
+                let msg = format!("Column `{}` has no value and no default.", missing.name);
+                return Err(Error::builder(ErrorKind::conversion(msg)).build());
+            }
+        }
+
+        Ok(Insert::from(self))
+    }
 }
 
 impl<'a> MultiRowInsert<'a> {
@@ -299,6 +681,16 @@ impl<'a> MultiRowInsert<'a> {
         self
     }
 
+    /// Keeps the values of the identity columns of the table, and writes
+    /// them instead of the generated ones. See
+    /// [`Insert::overriding_system_value`].
+    ///
+    /// [`Insert::overriding_system_value`]: struct.Insert.html#method.overriding_system_value
+    pub fn overriding_system_value(mut self) -> Self {
+        self.overriding_system_value = true;
+        self
+    }
+
     /// Convert into a common `Insert` statement.
     pub fn build(self) -> Insert<'a> {
         Insert::from(self)
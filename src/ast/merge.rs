@@ -2,15 +2,60 @@ use super::*;
 use crate::error::*;
 use std::convert::TryFrom;
 
+/// A builder for a `MERGE` statement, inserting, updating or deleting rows in
+/// the target table depending on whether they match the rows of the source.
+///
+/// Supported by PostgreSQL 15 or later and Microsoft SQL Server. MySQL and
+/// MariaDB have no `MERGE`, and an `ON DUPLICATE KEY UPDATE` matches the rows
+/// on the unique keys of the table instead of the given conditions, so the
+/// statement is not emulated: rendering it for MySQL or SQLite returns an
+/// error. An upsert for all of them is an `Insert` with
+/// [OnConflict::Update](enum.OnConflict.html).
+///
+/// ```rust
+/// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let using = Select::from_table("staging")
+///     .into_using("src", vec!["id", "name"])
+///     .on(("src", "id").equals(Column::from(("users", "id"))));
+///
+/// let insert = Insert::multi(vec!["id", "name"])
+///     .values(vec![Column::from(("src", "id")), Column::from(("src", "name"))]);
+///
+/// let query = Merge::new("users", using)
+///     .when_matched_update(Update::table("users").set("name", Column::from(("src", "name"))))
+///     .when_not_matched(insert);
+///
+/// let (sql, _) = Postgres::build(query)?;
+///
+/// assert_eq!(
+///     "MERGE INTO \"users\" USING (SELECT \"staging\".* FROM \"staging\") AS \"src\" (\"id\",\"name\") \
+///      ON \"src\".\"id\" = \"users\".\"id\" \
+///      WHEN MATCHED THEN UPDATE SET \"name\" = \"src\".\"name\" \
+///      WHEN NOT MATCHED THEN INSERT (\"id\",\"name\") VALUES (\"src\".\"id\",\"src\".\"name\")",
+///     sql
+/// );
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) struct Merge<'a> {
+pub struct Merge<'a> {
     pub(crate) table: Table<'a>,
     pub(crate) using: Using<'a>,
+    pub(crate) when_matched: Vec<Query<'a>>,
     pub(crate) when_not_matched: Option<Query<'a>>,
     pub(crate) returning: Option<Vec<Column<'a>>>,
 }
 
+impl<'a> From<Merge<'a>> for Query<'a> {
+    fn from(merge: Merge<'a>) -> Self {
+        Query::Merge(Box::new(merge))
+    }
+}
+
 impl<'a> Merge<'a> {
+    /// Creates the basis for a `MERGE` statement into the given table, using
+    /// the rows of the source for matching.
     pub fn new<T, U>(table: T, using: U) -> Self
     where
         T: Into<Table<'a>>,
@@ -19,11 +64,85 @@ impl<'a> Merge<'a> {
         Self {
             table: table.into(),
             using: using.into(),
+            when_matched: Vec::new(),
             when_not_matched: None,
             returning: None,
         }
     }
 
+    /// Updates the matched rows with the assignments of the given `UPDATE`.
+    /// The table of the update is ignored, and its conditions are added to
+    /// the `WHEN MATCHED` branch.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mssql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let using = Select::from_table("staging")
+    ///     .into_using("src", vec!["id", "name"])
+    ///     .on(("src", "id").equals(Column::from(("users", "id"))));
+    ///
+    /// let update = Update::table("users")
+    ///     .set("name", Column::from(("src", "name")))
+    ///     .so_that(("users", "locked").equals(false));
+    ///
+    /// let query = Merge::new("users", using).when_matched_update(update);
+    /// let (sql, _) = Mssql::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "MERGE INTO [users] USING (SELECT [staging].* FROM [staging]) AS [src] ([id],[name]) \
+    ///      ON [src].[id] = [users].[id] \
+    ///      WHEN MATCHED AND [users].[locked] = @P1 THEN UPDATE SET [name] = [src].[name];",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn when_matched_update(mut self, update: Update<'a>) -> Self {
+        self.when_matched.push(Query::from(update));
+        self
+    }
+
+    /// Deletes the matched rows.
+    pub fn when_matched_delete(mut self) -> Self {
+        let delete = Delete::from_table(self.table.clone());
+        self.when_matched.push(Query::from(delete));
+        self
+    }
+
+    /// Deletes the matched rows fulfilling the given conditions.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let using = Select::from_table("staging")
+    ///     .into_using("src", vec!["id", "deleted"])
+    ///     .on(("src", "id").equals(Column::from(("users", "id"))));
+    ///
+    /// let query = Merge::new("users", using).when_matched_delete_if(("src", "deleted").equals(true));
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "MERGE INTO \"users\" USING (SELECT \"staging\".* FROM \"staging\") AS \"src\" (\"id\",\"deleted\") \
+    ///      ON \"src\".\"id\" = \"users\".\"id\" WHEN MATCHED AND \"src\".\"deleted\" = $1 THEN DELETE",
+    ///     sql
+    /// );
+    /// assert_eq!(vec![Value::from(true)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn when_matched_delete_if<T>(mut self, conditions: T) -> Self
+    where
+        T: Into<ConditionTree<'a>>,
+    {
+        let delete = Delete::from_table(self.table.clone()).so_that(conditions);
+        self.when_matched.push(Query::from(delete));
+        self
+    }
+
+    /// Inserts the rows not matching the target table. The insert should not
+    /// point to a table, e.g. created with
+    /// [Insert::single](struct.Insert.html#method.single) or
+    /// [Insert::multi](struct.Insert.html#method.multi).
     pub fn when_not_matched<Q>(mut self, query: Q) -> Self
     where
         Q: Into<Query<'a>>,
@@ -32,7 +151,7 @@ impl<'a> Merge<'a> {
         self
     }
 
-    pub fn returning<K, I>(mut self, columns: I) -> Self
+    pub(crate) fn returning<K, I>(mut self, columns: I) -> Self
     where
         K: Into<Column<'a>>,
         I: IntoIterator<Item = K>,
@@ -42,8 +161,10 @@ impl<'a> Merge<'a> {
     }
 }
 
+/// The source rows of a `MERGE` statement, created with
+/// [IntoUsing](trait.IntoUsing.html).
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) struct Using<'a> {
+pub struct Using<'a> {
     pub(crate) base_query: Query<'a>,
     pub(crate) columns: Vec<Column<'a>>,
     pub(crate) as_table: Table<'a>,
@@ -51,6 +172,7 @@ pub(crate) struct Using<'a> {
 }
 
 impl<'a> Using<'a> {
+    /// The conditions for matching the source rows to the target table.
     pub fn on<T>(mut self, conditions: T) -> Self
     where
         T: Into<ConditionTree<'a>>,
@@ -60,19 +182,28 @@ impl<'a> Using<'a> {
     }
 }
 
-pub(crate) trait IntoUsing<'a> {
-    fn into_using(self, alias: &'a str, columns: Vec<Column<'a>>) -> Using<'a>;
+/// Converts a query into the source of a `MERGE` statement.
+pub trait IntoUsing<'a> {
+    /// Names the source `alias` with the given `columns`.
+    fn into_using<K, I>(self, alias: &'a str, columns: I) -> Using<'a>
+    where
+        K: Into<Column<'a>>,
+        I: IntoIterator<Item = K>;
 }
 
-impl<'a, I> IntoUsing<'a> for I
+impl<'a, Q> IntoUsing<'a> for Q
 where
-    I: Into<Query<'a>>,
+    Q: Into<Query<'a>>,
 {
-    fn into_using(self, alias: &'a str, columns: Vec<Column<'a>>) -> Using<'a> {
+    fn into_using<K, I>(self, alias: &'a str, columns: I) -> Using<'a>
+    where
+        K: Into<Column<'a>>,
+        I: IntoIterator<Item = K>,
+    {
         Using {
             base_query: self.into(),
             as_table: Table::from(alias),
-            columns,
+            columns: columns.into_iter().map(|c| c.into()).collect(),
             on_conditions: ConditionTree::NoCondition,
         }
     }
@@ -82,16 +213,38 @@ impl<'a> TryFrom<Insert<'a>> for Merge<'a> {
     type Error = Error;
 
     fn try_from(insert: Insert<'a>) -> crate::Result<Self> {
+        let on_conflict = insert.resolved_on_conflict()?;
+
         let table = insert.table.ok_or_else(|| {
             let kind = ErrorKind::conversion("Insert needs to point to a table for conversion to Merge.");
             Error::builder(kind).build()
         })?;
 
-        if table.index_definitions.is_empty() {
+        // An upsert joins on its target, otherwise all unique indices of the
+        // table are needed to find the existing rows.
+        let (target, update_columns) = match on_conflict {
+            Some(OnConflict::Update { target, columns }) => (Some(target), columns),
+            _ => (None, Vec::new()),
+        };
+
+        if target.is_none() && table.index_definitions.is_empty() {
             let kind = ErrorKind::conversion("Insert table needs schema metadata for conversion to Merge.");
             return Err(Error::builder(kind).build());
         }
 
+        let is_default = |expr: &Expression<'a>| expr.kind == ExpressionKind::Default;
+
+        let has_defaults = match &insert.values.kind {
+            ExpressionKind::Row(row) => row.values.iter().any(is_default),
+            ExpressionKind::Values(values) => values.rows.iter().any(|row| row.values.iter().any(is_default)),
+            _ => false,
+        };
+
+        if has_defaults {
+            let kind = ErrorKind::conversion("DEFAULT values are not supported in an Insert converted to Merge.");
+            return Err(Error::builder(kind).build());
+        }
+
         let columns = insert.columns;
 
         let query = match insert.values.kind {
@@ -134,13 +287,34 @@ impl<'a> TryFrom<Insert<'a>> for Merge<'a> {
 
         let bare_columns: Vec<_> = columns.clone().into_iter().map(|c| c.into_bare()).collect();
 
-        let using = query
-            .into_using("dual", bare_columns.clone())
-            .on(table.join_conditions(&columns).unwrap());
+        let join_conditions = match target {
+            Some(target) => target
+                .into_iter()
+                .fold(ConditionTree::NoCondition, |conditions, column| {
+                    let condition = column.clone().table("dual").equals(column.table(table.clone()));
+
+                    match conditions {
+                        ConditionTree::NoCondition => condition.into(),
+                        conditions => conditions.and(condition),
+                    }
+                }),
+            None => table.join_conditions(&columns)?,
+        };
+
+        let using = query.into_using("dual", bare_columns.clone()).on(join_conditions);
 
         let dual_columns: Vec<_> = columns.into_iter().map(|c| c.table("dual")).collect();
         let not_matched = Insert::multi(bare_columns).values(dual_columns);
-        let mut merge = Merge::new(table, using).when_not_matched(not_matched);
+        let mut merge = Merge::new(table.clone(), using).when_not_matched(not_matched);
+
+        if !update_columns.is_empty() {
+            let update = update_columns.into_iter().fold(Update::table(table), |update, column| {
+                let value = column.clone().table("dual");
+                update.set(column, value)
+            });
+
+            merge = merge.when_matched_update(update);
+        }
 
         if let Some(columns) = insert.returning {
             merge = merge.returning(columns);
@@ -11,42 +11,57 @@ pub enum SqlOp<'a> {
     Rem(Expression<'a>, Expression<'a>),
 }
 
-impl<'a> Add for Expression<'a> {
+impl<'a, T> Add<T> for Expression<'a>
+where
+    T: Into<Expression<'a>>,
+{
     type Output = Expression<'a>;
 
-    fn add(self, other: Self) -> Self {
-        SqlOp::Add(self, other).into()
+    fn add(self, other: T) -> Self {
+        SqlOp::Add(self, other.into()).into()
     }
 }
 
-impl<'a> Sub for Expression<'a> {
+impl<'a, T> Sub<T> for Expression<'a>
+where
+    T: Into<Expression<'a>>,
+{
     type Output = Expression<'a>;
 
-    fn sub(self, other: Self) -> Self {
-        SqlOp::Sub(self, other).into()
+    fn sub(self, other: T) -> Self {
+        SqlOp::Sub(self, other.into()).into()
     }
 }
 
-impl<'a> Mul for Expression<'a> {
+impl<'a, T> Mul<T> for Expression<'a>
+where
+    T: Into<Expression<'a>>,
+{
     type Output = Expression<'a>;
 
-    fn mul(self, other: Self) -> Self {
-        SqlOp::Mul(self, other).into()
+    fn mul(self, other: T) -> Self {
+        SqlOp::Mul(self, other.into()).into()
     }
 }
 
-impl<'a> Div for Expression<'a> {
+impl<'a, T> Div<T> for Expression<'a>
+where
+    T: Into<Expression<'a>>,
+{
     type Output = Expression<'a>;
 
-    fn div(self, other: Self) -> Self {
-        SqlOp::Div(self, other).into()
+    fn div(self, other: T) -> Self {
+        SqlOp::Div(self, other.into()).into()
     }
 }
 
-impl<'a> Rem for Expression<'a> {
+impl<'a, T> Rem<T> for Expression<'a>
+where
+    T: Into<Expression<'a>>,
+{
     type Output = Expression<'a>;
 
-    fn rem(self, other: Self) -> Self {
-        SqlOp::Rem(self, other).into()
+    fn rem(self, other: T) -> Self {
+        SqlOp::Rem(self, other.into()).into()
     }
 }
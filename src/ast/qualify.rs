@@ -0,0 +1,162 @@
+use crate::ast::*;
+use std::borrow::Cow;
+
+impl<'a> Query<'a> {
+    /// Prefixes every table without a database or a schema with the given
+    /// one, so the SQL names the tables explicitly instead of relying on the
+    /// search path of the connection. Covers the joins, unions and nested
+    /// selects of the query. Tables that already have a database or a schema
+    /// are kept as they are.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users")
+    ///     .inner_join("posts".on(("posts", "user_id").equals(Column::from(("users", "id")))))
+    ///     .and_from(Table::from("audit").database("logs"));
+    ///
+    /// let (sql, _) = Postgres::build(Query::from(query).qualify_tables("app"))?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT \"app\".\"users\".*, \"logs\".\"audit\".* FROM \"app\".\"users\", \"logs\".\"audit\" \
+    ///      INNER JOIN \"app\".\"posts\" ON \"posts\".\"user_id\" = \"users\".\"id\"",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn qualify_tables<T: Into<Cow<'a, str>>>(mut self, database: T) -> Self {
+        let database = database.into();
+
+        match &mut self {
+            Query::Select(select) => qualify_select(select, &database),
+            Query::Union(union) => {
+                for select in union.selects.iter_mut() {
+                    qualify_select(select, &database);
+                }
+            }
+            Query::Insert(insert) => {
+                if let Some(ref mut table) = insert.table {
+                    qualify_table(table, &database);
+                }
+
+                qualify_expression(&mut insert.values, &database);
+            }
+            Query::Update(update) => {
+                qualify_table(&mut update.table, &database);
+
+                for join in update.joins.iter_mut() {
+                    qualify_join(join, &database);
+                }
+
+                if let Some(ref mut conditions) = update.conditions {
+                    qualify_conditions(conditions, &database);
+                }
+            }
+            Query::Delete(delete) => {
+                qualify_table(&mut delete.table, &database);
+
+                if let Some(ref mut conditions) = delete.conditions {
+                    qualify_conditions(conditions, &database);
+                }
+            }
+            Query::Merge(merge) => {
+                qualify_table(&mut merge.table, &database);
+
+                let base_query = std::mem::replace(&mut merge.using.base_query, Query::Raw(Cow::Borrowed("")));
+                merge.using.base_query = base_query.qualify_tables(database);
+            }
+            Query::Raw(_) => (),
+        }
+
+        self
+    }
+}
+
+fn qualify_table<'a>(table: &mut Table<'a>, database: &Cow<'a, str>) {
+    match table.typ {
+        TableType::Table(_) if table.database.is_none() && table.schema.is_none() => {
+            table.database = Some(database.clone());
+        }
+        TableType::Query(ref mut select) => qualify_select(select, database),
+        _ => (),
+    }
+}
+
+fn qualify_select<'a>(select: &mut Select<'a>, database: &Cow<'a, str>) {
+    for table in select.tables.iter_mut() {
+        qualify_table(table, database);
+    }
+
+    for join in select.joins.iter_mut() {
+        let data = match join {
+            Join::Inner(data) | Join::Left(data) | Join::Right(data) | Join::Full(data) => data,
+        };
+
+        qualify_join(data, database);
+    }
+
+    for column in select.columns.iter_mut() {
+        qualify_expression(column, database);
+    }
+
+    if let Some(ref mut conditions) = select.conditions {
+        qualify_conditions(conditions, database);
+    }
+
+    if let Some(ref mut having) = select.having {
+        qualify_conditions(having, database);
+    }
+}
+
+fn qualify_join<'a>(join: &mut JoinData<'a>, database: &Cow<'a, str>) {
+    qualify_table(&mut join.table, database);
+    qualify_conditions(&mut join.conditions, database);
+}
+
+fn qualify_conditions<'a>(tree: &mut ConditionTree<'a>, database: &Cow<'a, str>) {
+    match tree {
+        ConditionTree::And(exprs) | ConditionTree::Or(exprs) => {
+            for expr in exprs.iter_mut() {
+                qualify_expression(expr, database);
+            }
+        }
+        ConditionTree::Not(expr) | ConditionTree::Single(expr) => qualify_expression(expr, database),
+        ConditionTree::NoCondition | ConditionTree::NegativeCondition => (),
+    }
+}
+
+fn qualify_expression<'a>(expr: &mut Expression<'a>, database: &Cow<'a, str>) {
+    match &mut expr.kind {
+        ExpressionKind::Select(select) => qualify_select(select, database),
+        ExpressionKind::ConditionTree(tree) => qualify_conditions(tree, database),
+        ExpressionKind::Value(expr) => qualify_expression(expr, database),
+        ExpressionKind::Row(row) => {
+            for expr in row.values.iter_mut() {
+                qualify_expression(expr, database);
+            }
+        }
+        ExpressionKind::Compare(compare) => match compare {
+            Compare::Equals(left, right)
+            | Compare::NotEquals(left, right)
+            | Compare::LessThan(left, right)
+            | Compare::LessThanOrEquals(left, right)
+            | Compare::GreaterThan(left, right)
+            | Compare::GreaterThanOrEquals(left, right)
+            | Compare::In(left, right)
+            | Compare::NotIn(left, right)
+            | Compare::ArrayContains(left, right)
+            | Compare::ArrayContainedBy(left, right)
+            | Compare::ArrayOverlaps(left, right)
+            | Compare::EqualsAny(left, right)
+            | Compare::NotDistinctFrom(left, right)
+            | Compare::DistinctFrom(left, right)
+            | Compare::EqualsIgnoreCase(left, right) => {
+                qualify_expression(left, database);
+                qualify_expression(right, database);
+            }
+            _ => (),
+        },
+        _ => (),
+    }
+}
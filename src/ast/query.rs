@@ -1,4 +1,4 @@
-use crate::ast::{Delete, Insert, Select, Union, Update};
+use crate::ast::{Delete, Insert, Merge, Select, Union, Update, Value};
 use std::borrow::Cow;
 
 /// A database query
@@ -9,6 +9,7 @@ pub enum Query<'a> {
     Insert(Box<Insert<'a>>),
     Update(Box<Update<'a>>),
     Delete(Box<Delete<'a>>),
+    Merge(Box<Merge<'a>>),
     Union(Union<'a>),
     Raw(Cow<'a, str>),
 }
@@ -23,6 +24,32 @@ where
 }
 
 impl<'a> Query<'a> {
+    /// Limits a `SELECT` without a `LIMIT` to `max` rows, unless it is marked
+    /// [unlimited](struct.Select.html#method.unlimited). Used by connections
+    /// with the `max_interactive_rows` option, to not stream a whole table to
+    /// an interactive tool by accident. Other queries are kept as they are.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Query::from(Select::from_table("users")).limit_rows(1000);
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" LIMIT $1", sql);
+    /// assert_eq!(vec![Value::from(1000)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit_rows(self, max: usize) -> Self {
+        match self {
+            Query::Select(mut select) if select.limit.is_none() && !select.unlimited => {
+                select.limit = Some(Value::from(max));
+                Query::Select(select)
+            }
+            q => q,
+        }
+    }
+
     pub fn is_select(&self) -> bool {
         if let Query::Select(_) = self {
             true
@@ -55,6 +82,14 @@ impl<'a> Query<'a> {
         }
     }
 
+    pub fn is_merge(&self) -> bool {
+        if let Query::Merge(_) = self {
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn is_union(&self) -> bool {
         if let Query::Union(_) = self {
             true
@@ -268,6 +268,16 @@ impl<'a> Comparable<'a> for Row<'a> {
         value.is_not_null()
     }
 
+    fn is_true(self) -> Compare<'a> {
+        let value: Expression<'a> = self.into();
+        value.is_true()
+    }
+
+    fn is_false(self) -> Compare<'a> {
+        let value: Expression<'a> = self.into();
+        value.is_false()
+    }
+
     fn between<T, V>(self, left: T, right: V) -> Compare<'a>
     where
         T: Into<Expression<'a>>,
@@ -285,4 +295,60 @@ impl<'a> Comparable<'a> for Row<'a> {
         let value: Expression<'a> = self.into();
         value.not_between(left, right)
     }
+
+    fn array_contains<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.array_contains(comparison)
+    }
+
+    fn array_contained_by<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.array_contained_by(comparison)
+    }
+
+    fn array_overlaps<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.array_overlaps(comparison)
+    }
+
+    fn equals_any<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.equals_any(comparison)
+    }
+
+    fn is_not_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.is_not_distinct_from(comparison)
+    }
+
+    fn is_distinct_from<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.is_distinct_from(comparison)
+    }
+
+    fn equals_ignore_case<T>(self, comparison: T) -> Compare<'a>
+    where
+        T: Into<Expression<'a>>,
+    {
+        let value: Expression<'a> = self.into();
+        value.equals_ignore_case(comparison)
+    }
 }
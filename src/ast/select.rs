@@ -13,6 +13,21 @@ pub struct Select<'a> {
     pub(crate) limit: Option<Value<'a>>,
     pub(crate) offset: Option<Value<'a>>,
     pub(crate) joins: Vec<Join<'a>>,
+    pub(crate) as_of: Option<Box<Expression<'a>>>,
+    pub(crate) lock: Option<LockMode>,
+    pub(crate) unlimited: bool,
+}
+
+/// How a `SELECT ... FOR UPDATE` waits for the rows locked by other
+/// transactions.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LockMode {
+    /// Wait until the locks are released.
+    Wait,
+    /// Fail right away if a row is already locked.
+    NoWait,
+    /// Leave out the rows that are already locked.
+    SkipLocked,
 }
 
 impl<'a> From<Select<'a>> for Expression<'a> {
@@ -554,4 +569,79 @@ impl<'a> Select<'a> {
         self.offset = Some(Value::from(offset));
         self
     }
+
+    /// Returns all the rows even if the connection limits the rows of a
+    /// `SELECT` without a `LIMIT` with `max_interactive_rows`. See
+    /// [Query::limit_rows](enum.Query.html#method.limit_rows). The selects
+    /// quaint runs on its own, such as the one of `delete_returning`, are
+    /// always unlimited.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Query::from(Select::from_table("users").unlimited()).limit_rows(1000);
+    /// let (sql, _) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("SELECT `users`.* FROM `users`", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unlimited(mut self) -> Self {
+        self.unlimited = true;
+        self
+    }
+
+    /// Reads the data as it was at the given point in time.
+    ///
+    /// Rendered as `AS OF SYSTEM TIME` for CockroachDB using the PostgreSQL
+    /// visitor, and as `FOR SYSTEM_TIME AS OF` after every table in the `FROM`
+    /// clause for system-versioned tables in MariaDB and SQL Server. Building
+    /// the query for SQLite returns an error.
+    ///
+    /// `AS OF SYSTEM TIME` is CockroachDB only: PostgreSQL itself rejects the
+    /// query. The time is a parameter on every database.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres, Mysql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").as_of("-10s");
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" AS OF SYSTEM TIME $1", sql);
+    /// assert_eq!(vec![Value::from("-10s")], params);
+    ///
+    /// let query = Select::from_table(Table::from("users").alias("u")).as_of("2020-06-01 00:00:00");
+    /// let (sql, params) = Mysql::build(query)?;
+    ///
+    /// assert_eq!("SELECT `u`.* FROM `users` FOR SYSTEM_TIME AS OF TIMESTAMP ? AS `u`", sql);
+    /// assert_eq!(vec![Value::from("2020-06-01 00:00:00")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_of<T>(mut self, time: T) -> Self
+    where
+        T: Into<Expression<'a>>,
+    {
+        self.as_of = Some(Box::new(time.into()));
+        self
+    }
+
+    /// Locks the selected rows for updating until the end of the transaction.
+    /// Supported by PostgreSQL and MySQL 8, building the query for SQLite or
+    /// SQL Server returns an error.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("jobs").so_that("id".equals(1)).for_update(LockMode::NoWait);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"jobs\".* FROM \"jobs\" WHERE \"id\" = $1 FOR UPDATE NOWAIT", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_update(mut self, mode: LockMode) -> Self {
+        self.lock = Some(mode);
+        self
+    }
 }
@@ -1,4 +1,4 @@
-use super::{Column, Comparable, ConditionTree, DefaultValue, ExpressionKind, IndexDefinition};
+use super::{Column, Comparable, ConditionTree, DefaultValue, ExpressionKind, Generated, IndexDefinition, Unnest};
 use crate::{
     ast::{Expression, Row, Select, Values},
     error::{Error, ErrorKind},
@@ -21,6 +21,7 @@ pub enum TableType<'a> {
     Table(Cow<'a, str>),
     Query(Select<'a>),
     Values(Values<'a>),
+    Unnest(Unnest<'a>),
 }
 
 /// A table definition
@@ -29,12 +30,14 @@ pub struct Table<'a> {
     pub typ: TableType<'a>,
     pub alias: Option<Cow<'a, str>>,
     pub database: Option<Cow<'a, str>>,
+    pub schema: Option<Cow<'a, str>>,
     pub(crate) index_definitions: Vec<IndexDefinition<'a>>,
+    pub(crate) generated_columns: Vec<Column<'a>>,
 }
 
 impl<'a> PartialEq for Table<'a> {
     fn eq(&self, other: &Table) -> bool {
-        self.typ == other.typ && self.database == other.database
+        self.typ == other.typ && self.database == other.database && self.schema == other.schema
     }
 }
 
@@ -48,6 +51,40 @@ impl<'a> Table<'a> {
         self
     }
 
+    /// Define in which schema of the database the table is located, for a
+    /// three-part `database.schema.table` name. Without a database, the name
+    /// is `schema.table`. On MySQL a schema is the same as a database, so set
+    /// only one of the two.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mssql}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let orders = Table::from("orders").database("sales").schema("dbo");
+    /// let customers = Table::from(("crm", "dbo", "customers"));
+    /// let customer_id = Column::from((("crm", "dbo", "customers"), "id"));
+    ///
+    /// let query = Select::from_table(orders)
+    ///     .column("id")
+    ///     .inner_join(customers.on(customer_id.equals(Column::from("customer_id"))));
+    ///
+    /// let (sql, _) = Mssql::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "SELECT [id] FROM [sales].[dbo].[orders] INNER JOIN [crm].[dbo].[customers] \
+    ///      ON [crm].[dbo].[customers].[id] = [customer_id]",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn schema<T>(mut self, schema: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.schema = Some(schema.into());
+        self
+    }
+
     /// A qualified asterisk to this table
     pub fn asterisk(self) -> Expression<'a> {
         Expression {
@@ -63,6 +100,53 @@ impl<'a> Table<'a> {
         self
     }
 
+    /// Add a column with values generated in the database, a computed column
+    /// if not marked otherwise with [`Column::generated`]. The column is left
+    /// out from the inserts into the table, apart from the identity columns
+    /// when overriding the system value.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let table = Table::from("users")
+    ///     .add_generated_column(Column::from("id").generated(Generated::Identity))
+    ///     .add_generated_column("full_name");
+    ///
+    /// let query = Insert::single_into(table)
+    ///     .value("id", 1)
+    ///     .value("first_name", "Musti")
+    ///     .value("full_name", "Musti Cat");
+    ///
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("INSERT INTO `users` (`first_name`) VALUES (?)", sql);
+    /// assert_eq!(vec![Value::from("Musti")], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Column::generated`]: struct.Column.html#method.generated
+    pub fn add_generated_column(mut self, column: impl Into<Column<'a>>) -> Self {
+        let column = column.into();
+        let kind = column.generated.unwrap_or(Generated::Computed);
+
+        self.generated_columns.push(column.into_bare().generated(kind));
+        self
+    }
+
+    /// True if the values of the column are generated in the database.
+    pub(crate) fn is_generated(&self, column: &Column<'a>) -> bool {
+        self.generated_columns.iter().any(|c| c.name == column.name)
+    }
+
+    /// True if the values of the column are computed in the database, and
+    /// can never be written to.
+    pub(crate) fn is_computed(&self, column: &Column<'a>) -> bool {
+        self.generated_columns
+            .iter()
+            .any(|c| c.name == column.name && !c.is_identity())
+    }
+
     /// Conditions for Microsoft T-SQL MERGE using the table metadata.
     ///
     /// - Find the unique indices from the table that matches the inserted columns
@@ -134,7 +218,9 @@ impl<'a> From<&'a str> for Table<'a> {
             typ: TableType::Table(s.into()),
             alias: None,
             database: None,
+            schema: None,
             index_definitions: Vec::new(),
+            generated_columns: Vec::new(),
         }
     }
 }
@@ -145,7 +231,9 @@ impl<'a> From<&'a String> for Table<'a> {
             typ: TableType::Table(s.into()),
             alias: None,
             database: None,
+            schema: None,
             index_definitions: Vec::new(),
+            generated_columns: Vec::new(),
         }
     }
 }
@@ -178,13 +266,22 @@ impl<'a> From<(&'a String, &'a String)> for Table<'a> {
     }
 }
 
+impl<'a> From<(&'a str, &'a str, &'a str)> for Table<'a> {
+    fn from(s: (&'a str, &'a str, &'a str)) -> Table<'a> {
+        let table: Table<'a> = s.2.into();
+        table.database(s.0).schema(s.1)
+    }
+}
+
 impl<'a> From<String> for Table<'a> {
     fn from(s: String) -> Self {
         Table {
             typ: TableType::Table(s.into()),
             alias: None,
             database: None,
+            schema: None,
             index_definitions: Vec::new(),
+            generated_columns: Vec::new(),
         }
     }
 }
@@ -201,7 +298,9 @@ impl<'a> From<Values<'a>> for Table<'a> {
             typ: TableType::Values(values),
             alias: None,
             database: None,
+            schema: None,
             index_definitions: Vec::new(),
+            generated_columns: Vec::new(),
         }
     }
 }
@@ -213,13 +312,22 @@ impl<'a> From<(String, String)> for Table<'a> {
     }
 }
 
+impl<'a> From<(String, String, String)> for Table<'a> {
+    fn from(s: (String, String, String)) -> Table<'a> {
+        let table: Table<'a> = s.2.into();
+        table.database(s.0).schema(s.1)
+    }
+}
+
 impl<'a> From<Select<'a>> for Table<'a> {
     fn from(select: Select<'a>) -> Self {
         Table {
             typ: TableType::Query(select),
             alias: None,
             database: None,
+            schema: None,
             index_definitions: Vec::new(),
+            generated_columns: Vec::new(),
         }
     }
 }
@@ -236,5 +344,5 @@ impl<'a> Aliasable<'a> for Table<'a> {
     }
 }
 
-aliasable!(String, (String, String));
-aliasable!(&'a str, (&'a str, &'a str));
+aliasable!(String, (String, String), (String, String, String));
+aliasable!(&'a str, (&'a str, &'a str), (&'a str, &'a str, &'a str));
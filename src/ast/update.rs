@@ -6,7 +6,10 @@ pub struct Update<'a> {
     pub(crate) table: Table<'a>,
     pub(crate) columns: Vec<Column<'a>>,
     pub(crate) values: Vec<Expression<'a>>,
+    pub(crate) joins: Vec<JoinData<'a>>,
     pub(crate) conditions: Option<ConditionTree<'a>>,
+    pub(crate) ordering: Ordering<'a>,
+    pub(crate) limit: Option<Value<'a>>,
 }
 
 impl<'a> From<Update<'a>> for Query<'a> {
@@ -25,7 +28,10 @@ impl<'a> Update<'a> {
             table: table.into(),
             columns: Vec::new(),
             values: Vec::new(),
+            joins: Vec::new(),
             conditions: None,
+            ordering: Ordering::default(),
+            limit: None,
         }
     }
 
@@ -49,6 +55,27 @@ impl<'a> Update<'a> {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// The value can be any expression, such as a calculation on the current
+    /// value of the column or a column from another table.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, col, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Update::table("users")
+    ///     .set("counter", col!("counter") + 1)
+    ///     .set("total", col!("counter") * col!("price"));
+    ///
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     r#"UPDATE "users" SET "counter" = ("counter" + $1), "total" = ("counter" * "price")"#,
+    ///     sql
+    /// );
+    /// assert_eq!(vec![Value::from(1)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn set<K, V>(mut self, column: K, value: V) -> Update<'a>
     where
         K: Into<Column<'a>>,
@@ -60,6 +87,154 @@ impl<'a> Update<'a> {
         self
     }
 
+    /// Sets the column back to its default value in the database.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Update::table("users").set("foo", 10).set_default("bar");
+    /// let (sql, params) = Postgres::build(query)?;
+    ///
+    /// assert_eq!(r#"UPDATE "users" SET "foo" = $1, "bar" = DEFAULT"#, sql);
+    /// assert_eq!(vec![Value::from(10)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_default<K>(self, column: K) -> Update<'a>
+    where
+        K: Into<Column<'a>>,
+    {
+        self.set(column, default_value())
+    }
+
+    /// Increments the value of the column by the given amount in the database,
+    /// without reading it first.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Update::table("posts").increment("views", 1).so_that("id".equals(3));
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("UPDATE `posts` SET `views` = (`views` + ?) WHERE `id` = ?", sql);
+    /// assert_eq!(vec![Value::from(1), Value::from(3)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn increment<K, V>(self, column: K, amount: V) -> Update<'a>
+    where
+        K: Into<Column<'a>>,
+        V: Into<Expression<'a>>,
+    {
+        let column = column.into();
+        let value = Expression::from(column.clone()) + amount;
+
+        self.set(column, value)
+    }
+
+    /// Decrements the value of the column by the given amount in the database,
+    /// without reading it first.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Update::table("products").decrement("stock", 2);
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("UPDATE `products` SET `stock` = (`stock` - ?)", sql);
+    /// assert_eq!(vec![Value::from(2)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decrement<K, V>(self, column: K, amount: V) -> Update<'a>
+    where
+        K: Into<Column<'a>>,
+        V: Into<Expression<'a>>,
+    {
+        let column = column.into();
+        let value = Expression::from(column.clone()) - amount;
+
+        self.set(column, value)
+    }
+
+    /// Replaces one value in a JSON document in the database, without
+    /// reading the document first. The path is a string such as
+    /// `$.address.city` on MySQL and SQLite, and an array of keys on
+    /// PostgreSQL, where the value must be JSON. See [`json_set`].
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Update::table("users")
+    ///     .set_json_path("data", "$.address.city", "Helsinki")
+    ///     .so_that("id".equals(1));
+    ///
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!("UPDATE `users` SET `data` = json_set(`data`, ?, ?) WHERE `id` = ?", sql);
+    /// assert_eq!(vec![Value::from("$.address.city"), Value::from("Helsinki"), Value::from(1)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`json_set`]: fn.json_set.html
+    pub fn set_json_path<K, P, V>(self, column: K, path: P, value: V) -> Update<'a>
+    where
+        K: Into<Column<'a>>,
+        P: Into<JsonPath<'a>>,
+        V: Into<Expression<'a>>,
+    {
+        let column = column.into();
+        let value = json_set(column.clone(), path, value);
+
+        self.set(column, value)
+    }
+
+    /// Adds an `INNER JOIN` to the query, allowing the values and the
+    /// conditions to refer to the columns of the joined table.
+    ///
+    /// PostgreSQL renders the join as an `UPDATE ... FROM` with the join
+    /// conditions moved to the `WHERE` clause, MySQL as an
+    /// `UPDATE ... INNER JOIN` and SQL Server as an `UPDATE ... FROM ... INNER
+    /// JOIN`. The SQLite bundled with the crate predates `UPDATE ... FROM`,
+    /// so building the query for SQLite returns an error.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Mysql, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let join = "accounts".on(("accounts", "user_id").equals(Column::from(("users", "id"))));
+    ///
+    /// let query = Update::table("users")
+    ///     .set("balance", Column::from(("accounts", "balance")))
+    ///     .inner_join(join)
+    ///     .so_that(("users", "id").equals(1));
+    ///
+    /// let (sql, _) = Postgres::build(query.clone())?;
+    ///
+    /// assert_eq!(
+    ///     "UPDATE \"users\" SET \"balance\" = \"accounts\".\"balance\" FROM \"accounts\" \
+    ///      WHERE (\"accounts\".\"user_id\" = \"users\".\"id\" AND \"users\".\"id\" = $1)",
+    ///     sql
+    /// );
+    ///
+    /// let (sql, _) = Mysql::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "UPDATE `users` INNER JOIN `accounts` ON `accounts`.`user_id` = `users`.`id` \
+    ///      SET `balance` = `accounts`.`balance` WHERE `users`.`id` = ?",
+    ///     sql
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inner_join<J>(mut self, join: J) -> Self
+    where
+        J: Into<JoinData<'a>>,
+    {
+        self.joins.push(join.into());
+        self
+    }
+
     /// Adds `WHERE` conditions to the query. See
     /// [Comparable](trait.Comparable.html#required-methods) for more examples.
     ///
@@ -113,4 +288,45 @@ impl<'a> Update<'a> {
         self.conditions = Some(conditions.into());
         self
     }
+
+    /// Adds an ordering to the `ORDER BY` section, deciding which rows are
+    /// updated first. Meaningful together with a `limit`.
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        self.ordering = self.ordering.append(value.into_order_definition());
+        self
+    }
+
+    /// Updates at most `limit` rows, selected like in
+    /// [Delete::limit](struct.Delete.html#method.limit). Not supported in
+    /// updates with joins.
+    ///
+    /// On SQL Server the update goes through a common table expression, so
+    /// the new values cannot refer to the columns by the table name.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Sqlite}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Update::table("jobs")
+    ///     .set("state", "running")
+    ///     .so_that("state".equals("queued"))
+    ///     .order_by("id")
+    ///     .limit(10);
+    ///
+    /// let (sql, params) = Sqlite::build(query)?;
+    ///
+    /// assert_eq!(
+    ///     "UPDATE `jobs` SET `state` = ? WHERE `rowid` IN (SELECT `rowid` FROM `jobs` WHERE `state` = ? ORDER BY `id` LIMIT ?)",
+    ///     sql
+    /// );
+    /// assert_eq!(vec![Value::from("running"), Value::from("queued"), Value::from(10)], params);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(Value::from(limit));
+        self
+    }
 }
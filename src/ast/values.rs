@@ -17,8 +17,11 @@ use serde_json::{Number, Value as JsonValue};
 #[cfg(feature = "uuid-0_8")]
 use uuid::Uuid;
 
+#[cfg(feature = "bit-vec")]
+use bit_vec::BitVec;
+
 #[cfg(feature = "chrono-0_4")]
-use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Timelike, Utc};
 
 /// A value written to the query as-is without parameterization.
 #[derive(Debug, Clone, PartialEq)]
@@ -56,9 +59,12 @@ pub enum Value<'a> {
     Boolean(Option<bool>),
     /// A single character.
     Char(Option<char>),
-    #[cfg(all(feature = "array", feature = "postgresql"))]
+    #[cfg(all(feature = "array", feature = "postgresql-core"))]
     /// An array value (PostgreSQL).
     Array(Option<Vec<Value<'a>>>),
+    #[cfg(feature = "bit-vec")]
+    /// A bit string (PostgreSQL `BIT` and `VARBIT`).
+    Bits(Option<BitVec>),
     #[cfg(feature = "json-1")]
     /// A JSON value.
     Json(Option<serde_json::Value>),
@@ -74,6 +80,9 @@ pub enum Value<'a> {
     #[cfg(feature = "chrono-0_4")]
     /// A time value.
     Time(Option<NaiveTime>),
+    #[cfg(feature = "chrono-0_4")]
+    /// A time interval, such as an `INTERVAL` in PostgreSQL.
+    Interval(Option<Duration>),
 }
 
 pub(crate) struct Params<'a>(pub(crate) &'a [Value<'a>]);
@@ -118,6 +127,8 @@ impl<'a> fmt::Display for Value<'a> {
                 }
                 write!(f, "]")
             }),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(val) => val.as_ref().map(|v| write!(f, "{}", bits_to_string(v))),
             #[cfg(feature = "json-1")]
             Value::Json(val) => val.as_ref().map(|v| write!(f, "{}", v)),
             #[cfg(feature = "uuid-0_8")]
@@ -128,6 +139,8 @@ impl<'a> fmt::Display for Value<'a> {
             Value::Date(val) => val.map(|v| write!(f, "{}", v)),
             #[cfg(feature = "chrono-0_4")]
             Value::Time(val) => val.map(|v| write!(f, "{}", v)),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(val) => val.map(|v| write!(f, "{}", v)),
         };
 
         match res {
@@ -160,6 +173,8 @@ impl<'a> From<Value<'a>> for serde_json::Value {
             Value::Array(v) => {
                 v.map(|v| serde_json::Value::Array(v.into_iter().map(serde_json::Value::from).collect()))
             }
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(bits) => bits.map(|bits| serde_json::Value::String(bits_to_string(&bits))),
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(u) => u.map(|u| serde_json::Value::String(u.to_hyphenated().to_string())),
             #[cfg(feature = "chrono-0_4")]
@@ -168,6 +183,8 @@ impl<'a> From<Value<'a>> for serde_json::Value {
             Value::Date(date) => date.map(|date| serde_json::Value::String(format!("{}", date))),
             #[cfg(feature = "chrono-0_4")]
             Value::Time(time) => time.map(|time| serde_json::Value::String(format!("{}", time))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(interval) => interval.map(|interval| serde_json::Value::String(format!("{}", interval))),
         };
 
         match res {
@@ -207,7 +224,10 @@ impl<'a> Value<'a> {
         Value::Enum(Some(value.into()))
     }
 
-    /// Creates a new bytes value.
+    /// Creates a new bytes value. A borrowed slice is bound to the query
+    /// without copying in PostgreSQL and SQLite, and copied once in MySQL and
+    /// in SQL Server, where the parameters are handed over to the connection
+    /// task.
     pub fn bytes<B>(value: B) -> Self
     where
         B: Into<Cow<'a, [u8]>>,
@@ -265,12 +285,41 @@ impl<'a> Value<'a> {
         Value::Time(Some(value))
     }
 
+    /// Creates a new interval value.
+    #[cfg(feature = "chrono-0_4")]
+    pub fn interval(value: Duration) -> Self {
+        Value::Interval(Some(value))
+    }
+
     /// Creates a new JSON value.
     #[cfg(feature = "json-1")]
     pub fn json(value: serde_json::Value) -> Self {
         Value::Json(Some(value))
     }
 
+    /// Creates a new bit string value.
+    #[cfg(feature = "bit-vec")]
+    pub fn bits(value: BitVec) -> Self {
+        Value::Bits(Some(value))
+    }
+
+    /// Creates a new bit string value from its text form, such as `"1001"`.
+    ///
+    /// ```rust
+    /// # use quaint::ast::Value;
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let bits = Value::bits_from_str("1001")?;
+    ///
+    /// assert_eq!(Some("1001".to_string()), bits.to_bit_string());
+    /// assert!(Value::bits_from_str("1002").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "bit-vec")]
+    pub fn bits_from_str(value: &str) -> crate::Result<Self> {
+        Ok(Value::Bits(Some(bits_from_str(value)?)))
+    }
+
     /// `true` if the `Value` is null.
     pub fn is_null(&self) -> bool {
         match self {
@@ -283,6 +332,8 @@ impl<'a> Value<'a> {
             Value::Char(c) => c.is_none(),
             #[cfg(feature = "array")]
             Value::Array(v) => v.is_none(),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(b) => b.is_none(),
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(u) => u.is_none(),
             #[cfg(feature = "chrono-0_4")]
@@ -291,6 +342,8 @@ impl<'a> Value<'a> {
             Value::Date(d) => d.is_none(),
             #[cfg(feature = "chrono-0_4")]
             Value::Time(t) => t.is_none(),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(i) => i.is_none(),
             #[cfg(feature = "json-1")]
             Value::Json(json) => json.is_none(),
         }
@@ -507,6 +560,54 @@ impl<'a> Value<'a> {
         }
     }
 
+    /// `true` if the `Value` is an `Interval`.
+    #[cfg(feature = "chrono-0_4")]
+    pub fn is_interval(&self) -> bool {
+        match self {
+            Value::Interval(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns a `Duration` if the value is an `Interval`, otherwise `None`.
+    #[cfg(feature = "chrono-0_4")]
+    pub fn as_interval(&self) -> Option<Duration> {
+        match self {
+            Value::Interval(interval) => *interval,
+            _ => None,
+        }
+    }
+
+    /// `true` if the `Value` is a bit string.
+    #[cfg(feature = "bit-vec")]
+    pub fn is_bits(&self) -> bool {
+        match self {
+            Value::Bits(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns a reference to a `BitVec` if the value is a bit string,
+    /// otherwise `None`.
+    #[cfg(feature = "bit-vec")]
+    pub fn as_bits(&self) -> Option<&BitVec> {
+        match self {
+            Value::Bits(Some(bits)) => Some(bits),
+            _ => None,
+        }
+    }
+
+    /// Returns the bits as a string of `0` and `1` characters if the value is
+    /// a bit string, or text made of such characters, otherwise `None`.
+    #[cfg(feature = "bit-vec")]
+    pub fn to_bit_string(&self) -> Option<String> {
+        match self {
+            Value::Bits(Some(bits)) => Some(bits_to_string(bits)),
+            Value::Text(Some(s)) if s.chars().all(|c| c == '0' || c == '1') => Some(s.to_string()),
+            _ => None,
+        }
+    }
+
     /// `true` if the `Value` is a JSON value.
     #[cfg(feature = "json-1")]
     pub fn is_json(&self) -> bool {
@@ -535,7 +636,7 @@ impl<'a> Value<'a> {
     }
 
     /// Returns a Vec<T> if the value is an array of T, otherwise `None`.
-    #[cfg(all(feature = "array", feature = "postgresql"))]
+    #[cfg(all(feature = "array", feature = "postgresql-core"))]
     pub fn into_vec<T>(self) -> Option<Vec<T>>
     where
         // Implement From<Value>
@@ -552,6 +653,117 @@ impl<'a> Value<'a> {
             _ => None,
         }
     }
+
+    /// Converts the value into one owning its data, copying the borrowed text
+    /// and bytes.
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Value::Integer(i) => Value::Integer(i),
+            Value::Real(r) => Value::Real(r),
+            Value::Text(t) => Value::Text(t.map(|t| Cow::Owned(t.into_owned()))),
+            Value::Enum(e) => Value::Enum(e.map(|e| Cow::Owned(e.into_owned()))),
+            Value::Bytes(b) => Value::Bytes(b.map(|b| Cow::Owned(b.into_owned()))),
+            Value::Boolean(b) => Value::Boolean(b),
+            Value::Char(c) => Value::Char(c),
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(v) => Value::Array(v.map(|v| v.into_iter().map(Value::into_owned).collect())),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(b) => Value::Bits(b),
+            #[cfg(feature = "json-1")]
+            Value::Json(json) => Value::Json(json),
+            #[cfg(feature = "uuid-0_8")]
+            Value::Uuid(u) => Value::Uuid(u),
+            #[cfg(feature = "chrono-0_4")]
+            Value::DateTime(dt) => Value::DateTime(dt),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Date(d) => Value::Date(d),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Time(t) => Value::Time(t),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(i) => Value::Interval(i),
+        }
+    }
+
+    /// Converts the value into a canonical form, for comparing values read
+    /// from different databases:
+    ///
+    /// - Every null is `Value::Integer(None)`.
+    /// - Booleans are integers, `1` for true and `0` for false.
+    /// - Enums, characters and UUIDs are text, UUIDs in the hyphenated form.
+    /// - Decimals have no trailing zeros.
+    /// - Datetimes and times are truncated to microseconds.
+    ///
+    /// ```rust
+    /// # use quaint::ast::Value;
+    /// # use std::str::FromStr;
+    /// # use rust_decimal::Decimal;
+    /// assert_eq!(Value::integer(1), Value::boolean(true).normalized());
+    /// assert_eq!(Value::Integer(None), Value::Text(None).normalized());
+    ///
+    /// let real = Value::real(Decimal::from_str("1.50").unwrap());
+    /// assert_eq!(Value::real(Decimal::from_str("1.5").unwrap()), real.normalized());
+    /// ```
+    pub fn normalized(self) -> Self {
+        if self.is_null() {
+            return Value::Integer(None);
+        }
+
+        match self {
+            Value::Boolean(b) => Value::Integer(b.map(i64::from)),
+            Value::Enum(e) => Value::Text(e),
+            Value::Char(c) => Value::Text(c.map(|c| Cow::Owned(c.to_string()))),
+            Value::Real(r) => Value::Real(r.map(|r| r.normalize())),
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(v) => Value::Array(v.map(|v| v.into_iter().map(Value::normalized).collect())),
+            #[cfg(feature = "uuid-0_8")]
+            Value::Uuid(u) => Value::Text(u.map(|u| Cow::Owned(u.to_hyphenated().to_string()))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::DateTime(dt) => Value::DateTime(dt.map(|dt| {
+                let nanos = dt.timestamp_subsec_nanos();
+                dt - Duration::nanoseconds(i64::from(nanos % 1_000))
+            })),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Time(t) => Value::Time(t.map(|t| {
+                let nanos = t.nanosecond();
+                t - Duration::nanoseconds(i64::from(nanos % 1_000))
+            })),
+            value => value,
+        }
+    }
+}
+
+/// Parses a string of `0` and `1` characters into bits.
+#[cfg(feature = "bit-vec")]
+pub(crate) fn bits_from_str(s: &str) -> crate::Result<BitVec> {
+    let mut bits = BitVec::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '0' => bits.push(false),
+            '1' => bits.push(true),
+            _ => {
+                let msg = "Unexpected character for bits input. Expected only 1 and 0.";
+                return Err(Error::builder(ErrorKind::conversion(msg)).build());
+            }
+        }
+    }
+
+    Ok(bits)
+}
+
+/// The bits as a string of `0` and `1` characters.
+#[cfg(feature = "bit-vec")]
+pub(crate) fn bits_to_string(bits: &BitVec) -> String {
+    bits.iter().map(|bit| if bit { '1' } else { '0' }).collect()
+}
+
+/// The length of an interval in seconds, with a microsecond precision.
+#[cfg(feature = "chrono-0_4")]
+pub(crate) fn interval_seconds(interval: Duration) -> Decimal {
+    match interval.num_microseconds() {
+        Some(micros) => Decimal::new(micros, 6),
+        None => Decimal::new(interval.num_milliseconds(), 3),
+    }
 }
 
 value!(val: i64, Integer, val);
@@ -561,6 +773,8 @@ value!(val: Decimal, Real, val);
 value!(val: JsonValue, Json, val);
 #[cfg(feature = "uuid-0_8")]
 value!(val: Uuid, Uuid, val);
+#[cfg(feature = "bit-vec")]
+value!(val: BitVec, Bits, val);
 value!(val: &'a str, Text, val.into());
 value!(val: String, Text, val.into());
 value!(val: usize, Integer, i64::try_from(val).unwrap());
@@ -572,6 +786,8 @@ value!(val: DateTime<Utc>, DateTime, val);
 value!(val: chrono::NaiveTime, Time, val);
 #[cfg(feature = "chrono-0_4")]
 value!(val: chrono::NaiveDate, Date, val);
+#[cfg(feature = "chrono-0_4")]
+value!(val: chrono::Duration, Interval, val);
 
 value!(
     val: f64,
@@ -632,6 +848,19 @@ impl<'a> TryFrom<Value<'a>> for bool {
     }
 }
 
+#[cfg(feature = "bit-vec")]
+impl<'a> TryFrom<Value<'a>> for BitVec {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<BitVec, Self::Error> {
+        match value {
+            Value::Bits(Some(bits)) => Ok(bits),
+            Value::Text(Some(s)) => bits_from_str(&s),
+            _ => Err(Error::builder(ErrorKind::conversion("Not a bit string")).build()),
+        }
+    }
+}
+
 #[cfg(feature = "chrono-0_4")]
 impl<'a> TryFrom<Value<'a>> for DateTime<Utc> {
     type Error = Error;
@@ -728,12 +957,22 @@ impl<'a> IntoIterator for Values<'a> {
     }
 }
 
-#[cfg(all(test, feature = "array", feature = "postgresql"))]
+#[cfg(all(test, feature = "array", feature = "postgresql-core"))]
 mod tests {
     use super::*;
     #[cfg(feature = "chrono-0_4")]
     use std::str::FromStr;
 
+    #[test]
+    fn a_borrowed_value_can_be_converted_into_an_owned_value() {
+        let owned = {
+            let text = String::from("Musti");
+            Value::from(text.as_str()).into_owned()
+        };
+
+        assert_eq!(Value::text("Musti"), owned);
+    }
+
     #[test]
     fn a_parameterized_value_of_ints_can_be_converted_into_a_vec() {
         let pv = Value::array(vec![1]);
@@ -0,0 +1,460 @@
+//! Audit records of the writes to chosen tables.
+//!
+//! An [`AuditLog`] names the audited tables and the table the records are
+//! written to. Wrapping a transaction with [`AuditLog::on`] gives a connection
+//! that writes an audit record for every row inserted, updated or deleted in
+//! an audited table through it, in the same transaction as the change.
+//!
+//! The audit table is created by the application, with the columns
+//!
+//! - `table_name` and `operation`, one of `INSERT`, `UPDATE` or `DELETE`, as
+//!   text
+//! - `old_values` and `new_values` as JSON, or as text on SQLite
+//! - `actor` as text, set with [`Audited::actor`]
+//! - `created_at` as a timestamp
+//!
+//! The old values are the whole row before an update or a delete, read with
+//! `SELECT ... FOR UPDATE` on PostgreSQL and MySQL. The new values are the
+//! columns given to an insert or an update, with `null` for the values
+//! computed in the database, such as defaults and increments.
+//!
+//! Tables are named `database.schema.table` with the parts given in the
+//! query. Raw queries are not audited. Upserts, merges and inserts from a
+//! query or with a conflict resolution into an audited table return an
+//! error.
+//!
+//! ```no_run
+//! # use quaint::{prelude::*, single::Quaint, audit::AuditLog};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let log = AuditLog::new("audit_log").table("users");
+//!
+//! let conn = Quaint::new("postgres://localhost/db").await?;
+//! let tx = conn.start_transaction().await?;
+//!
+//! let audited = log.on(&tx).actor("admin");
+//! audited.update(Update::table("users").set("name", "Naukio").so_that("id".equals(1))).await?;
+//!
+//! tx.commit().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`AuditLog`]: struct.AuditLog.html
+//! [`AuditLog::on`]: struct.AuditLog.html#method.on
+//! [`Audited::actor`]: struct.Audited.html#method.actor
+
+use crate::{
+    ast::*,
+    connector::{
+        CommandHandle, ConnectionInfo, ExecuteResult, Queryable, ResultSet, SqlFamily, Transaction, UpsertOutcome,
+    },
+    error::{Error, ErrorKind},
+    tables::table_names,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::{Map, Value as JsonValue};
+
+/// The audited tables, and the table the audit records are written to.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    table: String,
+    tables: Vec<String>,
+}
+
+impl AuditLog {
+    /// A log writing its records to the given table.
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            tables: Vec::new(),
+        }
+    }
+
+    /// Audits the writes to the table.
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.tables.push(table.into());
+        self
+    }
+
+    /// A connection auditing the writes made through it in the transaction.
+    pub fn on<'a>(&'a self, tx: &'a Transaction<'a>) -> Audited<'a> {
+        Audited {
+            log: self,
+            tx,
+            actor: None,
+        }
+    }
+
+    /// The name of the table, if audited.
+    fn audited(&self, table: &Table<'_>) -> Option<String> {
+        let mut names = Vec::new();
+        table_names(table, &mut names);
+
+        names.into_iter().next().filter(|name| self.tables.contains(name))
+    }
+}
+
+/// A transaction writing audit records for the changes made through it. See
+/// the [module documentation](index.html) for what is recorded.
+pub struct Audited<'a> {
+    log: &'a AuditLog,
+    tx: &'a Transaction<'a>,
+    actor: Option<String>,
+}
+
+/// The records of a write, collected before running it.
+struct Pending {
+    table: String,
+    operation: &'static str,
+    records: Vec<(Option<JsonValue>, Option<JsonValue>)>,
+}
+
+impl<'a> Audited<'a> {
+    /// Sets the actor written to the audit records, e.g. the user making the
+    /// changes.
+    pub fn actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    async fn prepare(&self, q: &Query<'_>) -> crate::Result<Option<Pending>> {
+        match q {
+            Query::Insert(insert) => {
+                let table = match insert.table.as_ref().and_then(|t| self.log.audited(t)) {
+                    Some(table) => table,
+                    None => return Ok(None),
+                };
+
+                if insert.on_conflict.is_some() {
+                    return Err(not_audited("An insert with a conflict resolution", &table));
+                }
+
+                let rows = match &insert.values.kind {
+                    ExpressionKind::Row(row) => vec![row],
+                    ExpressionKind::Values(values) => values.rows.iter().collect(),
+                    _ => return Err(not_audited("An insert from a query", &table)),
+                };
+
+                let records = rows
+                    .into_iter()
+                    .map(|row| (None, Some(written_values(&insert.columns, &row.values))))
+                    .collect();
+
+                Ok(Some(Pending {
+                    table,
+                    operation: "INSERT",
+                    records,
+                }))
+            }
+            Query::Update(update) => {
+                let table = match self.log.audited(&update.table) {
+                    Some(table) => table,
+                    None => return Ok(None),
+                };
+
+                let mut select = Select::from_table(update.table.clone())
+                    .value(update.table.clone().asterisk())
+                    .unlimited();
+                select.joins = update.joins.iter().cloned().map(Join::Inner).collect();
+                select.conditions = update.conditions.clone();
+                select.ordering = update.ordering.clone();
+                select.limit = update.limit.clone();
+
+                let new = written_values(&update.columns, &update.values);
+
+                let records = self
+                    .old_rows(select)
+                    .await?
+                    .into_iter()
+                    .map(|old| (Some(old), Some(new.clone())))
+                    .collect();
+
+                Ok(Some(Pending {
+                    table,
+                    operation: "UPDATE",
+                    records,
+                }))
+            }
+            Query::Delete(delete) => {
+                let table = match self.log.audited(&delete.table) {
+                    Some(table) => table,
+                    None => return Ok(None),
+                };
+
+                let mut select = Select::from_table(delete.table.clone()).unlimited();
+                select.conditions = delete.conditions.clone();
+                select.ordering = delete.ordering.clone();
+                select.limit = delete.limit.clone();
+
+                let records = self
+                    .old_rows(select)
+                    .await?
+                    .into_iter()
+                    .map(|old| (Some(old), None))
+                    .collect();
+
+                Ok(Some(Pending {
+                    table,
+                    operation: "DELETE",
+                    records,
+                }))
+            }
+            Query::Merge(merge) => match self.log.audited(&merge.table) {
+                Some(table) => Err(not_audited("A merge", &table)),
+                None => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads the rows about to change, locking them where possible.
+    async fn old_rows(&self, select: Select<'_>) -> crate::Result<Vec<JsonValue>> {
+        let select = match self.tx.connection_info().sql_family() {
+            #[cfg(feature = "postgresql-core")]
+            SqlFamily::Postgres => select.for_update(LockMode::Wait),
+            #[cfg(feature = "mysql")]
+            SqlFamily::Mysql => select.for_update(LockMode::Wait),
+            #[cfg(feature = "sqlite")]
+            SqlFamily::Sqlite => select,
+            #[cfg(feature = "mssql")]
+            SqlFamily::Mssql => select,
+        };
+
+        Ok(rows_json(self.tx.select(select).await?))
+    }
+
+    async fn write(&self, pending: Option<Pending>) -> crate::Result<()> {
+        let pending = match pending {
+            Some(pending) => pending,
+            None => return Ok(()),
+        };
+
+        let now = Utc::now();
+
+        for (old, new) in pending.records {
+            let insert = Insert::single_into(self.log.table.as_str())
+                .value("table_name", pending.table.as_str())
+                .value("operation", pending.operation)
+                .value("old_values", Value::Json(old))
+                .value("new_values", Value::Json(new))
+                .value("actor", Value::Text(self.actor.as_deref().map(Into::into)))
+                .value("created_at", Value::datetime(now));
+
+            self.tx.execute(insert.into()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<'a> Queryable for Audited<'a> {
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let pending = self.prepare(&q).await?;
+        let result = self.tx.query(q).await?;
+        self.write(pending).await?;
+
+        Ok(result)
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.tx.query_raw(sql, params).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        let pending = self.prepare(&q).await?;
+        let result = self.tx.execute(q).await?;
+        self.write(pending).await?;
+
+        Ok(result)
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.tx.execute_raw(sql, params).await
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        let pending = self.prepare(&q).await?;
+        let result = self.tx.execute_result(q).await?;
+        self.write(pending).await?;
+
+        Ok(result)
+    }
+
+    async fn upsert(&self, q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        if let Some(table) = q.table.as_ref().and_then(|t| self.log.audited(t)) {
+            return Err(not_audited("An upsert", &table));
+        }
+
+        self.tx.upsert(q).await
+    }
+
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        let table = self.log.audited(&q.table);
+        let rows = self.tx.delete_returning(q).await?;
+
+        if let Some(table) = table {
+            let records = rows_json(rows.clone())
+                .into_iter()
+                .map(|old| (Some(old), None))
+                .collect();
+
+            let pending = Pending {
+                table,
+                operation: "DELETE",
+                records,
+            };
+
+            self.write(Some(pending)).await?;
+        }
+
+        Ok(rows)
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        let update = Update::table(table.clone())
+            .increment(column.clone(), amount)
+            .so_that(conditions.clone());
+
+        let pending = self.prepare(&update.into()).await?;
+        let result = self.tx.increment(table, conditions, column, amount).await?;
+        self.write(pending).await?;
+
+        Ok(result)
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        self.tx.execute_raw_result(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.tx.raw_cmd(cmd).await
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.tx.version().await
+    }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        self.tx.connection_info()
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        self.tx.command_handle()
+    }
+}
+
+/// The given columns with their values, `null` for the ones not given as
+/// parameters.
+fn written_values(columns: &[Column<'_>], values: &[Expression<'_>]) -> JsonValue {
+    let object = columns
+        .iter()
+        .zip(values.iter())
+        .map(|(column, value)| {
+            let value = match &value.kind {
+                ExpressionKind::Parameterized(value) => JsonValue::from(value.clone()),
+                _ => JsonValue::Null,
+            };
+
+            (column.name.to_string(), value)
+        })
+        .collect::<Map<_, _>>();
+
+    JsonValue::Object(object)
+}
+
+fn rows_json(rows: ResultSet) -> Vec<JsonValue> {
+    let columns = rows.columns().clone();
+
+    rows.into_iter()
+        .map(|row| {
+            let object = columns
+                .iter()
+                .cloned()
+                .zip(row.into_iter().map(JsonValue::from))
+                .collect::<Map<_, _>>();
+
+            JsonValue::Object(object)
+        })
+        .collect()
+}
+
+fn not_audited(what: &str, table: &str) -> Error {
+    let msg = format!("{} into the audited table `{}` is not supported.", what, table);
+    Error::builder(ErrorKind::conversion(msg)).build()
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::connector::{Sqlite, TransactionCapable};
+
+    #[tokio::test]
+    async fn writes_to_audited_tables_are_recorded() {
+        let conn = Sqlite::new("db/test.db").unwrap();
+
+        conn.raw_cmd("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+            .await
+            .unwrap();
+        conn.raw_cmd("CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT)")
+            .await
+            .unwrap();
+        conn.raw_cmd(
+            "CREATE TABLE audit_log (table_name TEXT, operation TEXT, old_values TEXT, new_values TEXT, actor TEXT, created_at DATETIME)",
+        )
+        .await
+        .unwrap();
+
+        let log = AuditLog::new("audit_log").table("users");
+        let tx = conn.start_transaction().await.unwrap();
+        let audited = log.on(&tx).actor("admin");
+
+        let insert = Insert::single_into("users").value("id", 1).value("name", "Musti");
+        audited.insert(insert.into()).await.unwrap();
+
+        let update = Update::table("users").set("name", "Naukio").so_that("id".equals(1));
+        audited.update(update).await.unwrap();
+
+        let insert = Insert::single_into("posts").value("title", "Meow");
+        audited.insert(insert.into()).await.unwrap();
+
+        tx.commit().await.unwrap();
+
+        let select = Select::from_table("audit_log").order_by("rowid".ascend());
+        let rows = conn.select(select).await.unwrap();
+
+        assert_eq!(2, rows.len());
+
+        let json = |value: Option<&Value<'_>>| -> JsonValue {
+            value
+                .and_then(|v| v.as_str())
+                .map(|v| serde_json::from_str(v).unwrap())
+                .unwrap_or(JsonValue::Null)
+        };
+
+        let insert = rows.get(0).unwrap();
+        assert_eq!(Some("INSERT"), insert["operation"].as_str());
+        assert_eq!(Some("admin"), insert["actor"].as_str());
+        assert_eq!(JsonValue::Null, json(insert.get("old_values")));
+        assert_eq!(
+            serde_json::json!({ "id": 1, "name": "Musti" }),
+            json(insert.get("new_values"))
+        );
+
+        let update = rows.get(1).unwrap();
+        assert_eq!(Some("UPDATE"), update["operation"].as_str());
+        assert_eq!(
+            serde_json::json!({ "id": 1, "name": "Musti" }),
+            json(update.get("old_values"))
+        );
+        assert_eq!(serde_json::json!({ "name": "Naukio" }), json(update.get("new_values")));
+    }
+}
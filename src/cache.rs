@@ -0,0 +1,484 @@
+//! Caching of query results.
+//!
+//! A [`CachedQueryable`] wraps a connection, answering the `SELECT` and
+//! `UNION` queries built with the AST from a [`CacheBackend`]. The queries are
+//! keyed by their SQL and parameters, and every result is tagged with the
+//! tables the query reads. Inserts, updates, deletes and merges run through
+//! the wrapper invalidate the results tagged with their table. A table
+//! qualified with its database or schema is tagged with its bare name too,
+//! so writes find the results whether or not the two queries qualify it.
+//!
+//! Raw queries and selects locking their rows with `FOR UPDATE` are never
+//! cached, and raw writes or writes from other
+//! connections need an explicit [`invalidate`]. Transactions should be
+//! started on the wrapped connection, keeping uncommitted data out of the
+//! cache.
+//!
+//! ```no_run
+//! # use quaint::{prelude::*, single::Quaint, cache::{CachedQueryable, MemoryCache}};
+//! # use std::time::Duration;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let conn = Quaint::new("postgres://localhost/db").await?;
+//! let cached = CachedQueryable::new(conn, MemoryCache::new(1000, Duration::from_secs(60)));
+//!
+//! // Read from the database, then from the cache.
+//! cached.select(Select::from_table("users")).await?;
+//! cached.select(Select::from_table("users")).await?;
+//!
+//! // Invalidates the results read from `users`.
+//! cached.insert(Insert::single_into("users").value("name", "Musti").into()).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`CachedQueryable`]: struct.CachedQueryable.html
+//! [`CacheBackend`]: trait.CacheBackend.html
+//! [`invalidate`]: struct.CachedQueryable.html#method.invalidate
+
+use crate::{
+    ast::*,
+    connector::{CommandHandle, ConnectionInfo, ExecuteResult, Queryable, ResultSet, Transaction, UpsertOutcome},
+    tables::{query_tables, table_names, written_tables},
+};
+use async_trait::async_trait;
+use lru_cache::LruCache;
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Identifies a cached result: the SQL of the query together with its
+/// parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    sql: String,
+    params: String,
+}
+
+impl CacheKey {
+    /// The SQL of the query.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The parameters of the query, in their debug format.
+    pub fn params(&self) -> &str {
+        &self.params
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.sql, self.params)
+    }
+}
+
+/// Storage for the cached results, e.g. in memory or in Redis.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// The result stored for the key, if any.
+    async fn get(&self, key: &CacheKey) -> crate::Result<Option<ResultSet>>;
+
+    /// Stores a result, tagged with the tables the query reads.
+    async fn put(&self, key: CacheKey, tags: Vec<String>, result: &ResultSet) -> crate::Result<()>;
+
+    /// Removes the results tagged with the table.
+    async fn invalidate(&self, tag: &str) -> crate::Result<()>;
+}
+
+struct CacheEntry {
+    result: ResultSet,
+    tags: Vec<String>,
+    stored_at: Instant,
+}
+
+/// An in-memory cache backend, keeping up to `capacity` results for at most
+/// `ttl`. The least recently used results are evicted first.
+pub struct MemoryCache {
+    entries: Mutex<LruCache<CacheKey, CacheEntry>>,
+    ttl: Duration,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryCache {
+    async fn get(&self, key: &CacheKey) -> crate::Result<Option<ResultSet>> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let expired = match entries.get_mut(key) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => return Ok(Some(entry.result.clone())),
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            entries.remove(key);
+        }
+
+        Ok(None)
+    }
+
+    async fn put(&self, key: CacheKey, tags: Vec<String>, result: &ResultSet) -> crate::Result<()> {
+        let entry = CacheEntry {
+            result: result.clone(),
+            tags,
+            stored_at: Instant::now(),
+        };
+
+        self.entries.lock().unwrap().insert(key, entry);
+
+        Ok(())
+    }
+
+    async fn invalidate(&self, tag: &str) -> crate::Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let keys: Vec<CacheKey> = entries
+            .iter()
+            .filter(|(_, entry)| entry.tags.iter().any(|t| t == tag))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in keys {
+            entries.remove(&key);
+        }
+
+        Ok(())
+    }
+}
+
+/// A connection answering the queries it has already seen from a cache. See
+/// the [module documentation](index.html) for what is cached.
+pub struct CachedQueryable<Q, B> {
+    inner: Q,
+    backend: B,
+}
+
+impl<Q, B> CachedQueryable<Q, B>
+where
+    Q: Queryable,
+    B: CacheBackend,
+{
+    pub fn new(inner: Q, backend: B) -> Self {
+        Self { inner, backend }
+    }
+
+    /// The wrapped connection.
+    pub fn inner(&self) -> &Q {
+        &self.inner
+    }
+
+    /// Removes the cached results that read the given table.
+    pub async fn invalidate(&self, table: &str) -> crate::Result<()> {
+        self.invalidate_tags(vec![table.to_string()]).await
+    }
+
+    fn cache_key(&self, q: &Query<'_>) -> crate::Result<CacheKey> {
+        let (sql, params) = self.inner.connection_info().sql_family().build(q.clone())?;
+
+        Ok(CacheKey {
+            sql,
+            params: format!("{:?}", params),
+        })
+    }
+
+    async fn invalidate_tags(&self, tags: Vec<String>) -> crate::Result<()> {
+        for tag in with_bare_names(tags) {
+            self.backend.invalidate(&tag).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds the names of the tables without their database and schema.
+fn with_bare_names(mut tags: Vec<String>) -> Vec<String> {
+    for i in 0..tags.len() {
+        let bare = match tags[i].rsplit('.').next() {
+            Some(bare) if bare != tags[i] => bare.to_string(),
+            _ => continue,
+        };
+
+        if !tags.contains(&bare) {
+            tags.push(bare);
+        }
+    }
+
+    tags
+}
+
+#[async_trait]
+impl<Q, B> Queryable for CachedQueryable<Q, B>
+where
+    Q: Queryable,
+    B: CacheBackend,
+{
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        match q {
+            Query::Select(ref select) if select.lock.is_some() => self.inner.query(q).await,
+            Query::Select(_) | Query::Union(_) => {
+                let key = self.cache_key(&q)?;
+
+                if let Some(result) = self.backend.get(&key).await? {
+                    return Ok(result);
+                }
+
+                let mut tags = Vec::new();
+                query_tables(&q, &mut tags);
+
+                let result = self.inner.query(q).await?;
+                self.backend.put(key, with_bare_names(tags), &result).await?;
+
+                Ok(result)
+            }
+            q => {
+                let tags = written_tables(&q);
+                let result = self.inner.query(q).await?;
+                self.invalidate_tags(tags).await?;
+
+                Ok(result)
+            }
+        }
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.inner.query_raw(sql, params).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        let tags = written_tables(&q);
+        let result = self.inner.execute(q).await?;
+        self.invalidate_tags(tags).await?;
+
+        Ok(result)
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.inner.execute_raw(sql, params).await
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        let tags = written_tables(&q);
+        let result = self.inner.execute_result(q).await?;
+        self.invalidate_tags(tags).await?;
+
+        Ok(result)
+    }
+
+    async fn upsert(&self, q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        let mut tags = Vec::new();
+
+        if let Some(ref table) = q.table {
+            table_names(table, &mut tags);
+        }
+
+        let result = self.inner.upsert(q).await?;
+        self.invalidate_tags(tags).await?;
+
+        Ok(result)
+    }
+
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        let mut tags = Vec::new();
+        table_names(&q.table, &mut tags);
+
+        let result = self.inner.delete_returning(q).await?;
+        self.invalidate_tags(tags).await?;
+
+        Ok(result)
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        let mut tags = Vec::new();
+        table_names(&table, &mut tags);
+
+        let result = self.inner.increment(table, conditions, column, amount).await?;
+        self.invalidate_tags(tags).await?;
+
+        Ok(result)
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        self.inner.execute_raw_result(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.inner.raw_cmd(cmd).await
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.inner.version().await
+    }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        self.inner.connection_info()
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        self.inner.command_handle()
+    }
+
+    async fn server_reset_query(&self, tx: &Transaction<'_>) -> crate::Result<()> {
+        self.inner.server_reset_query(tx).await
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        self.inner.create_savepoint_statement(name)
+    }
+
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        self.inner.release_savepoint_statement(name)
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        self.inner.rollback_to_savepoint_statement(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn memory_cache_invalidates_by_tag() {
+        let cache = MemoryCache::new(10, Duration::from_secs(60));
+        let key = CacheKey {
+            sql: String::from("SELECT 1"),
+            params: String::from("[]"),
+        };
+
+        let result = ResultSet::new(vec![String::from("a")], vec![vec![Value::from(1)]]);
+        cache
+            .put(key.clone(), vec![String::from("users")], &result)
+            .await
+            .unwrap();
+        assert!(cache.get(&key).await.unwrap().is_some());
+
+        cache.invalidate("posts").await.unwrap();
+        assert!(cache.get(&key).await.unwrap().is_some());
+
+        cache.invalidate("users").await.unwrap();
+        assert!(cache.get(&key).await.unwrap().is_none());
+    }
+
+    #[cfg(feature = "sqlite")]
+    mod locking {
+        use super::*;
+        use crate::connector::Sqlite;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Counts the queries reaching the connection, answering them with
+        /// an empty result.
+        struct Counting {
+            inner: Sqlite,
+            queries: AtomicUsize,
+        }
+
+        #[async_trait]
+        impl Queryable for Counting {
+            async fn query(&self, _: Query<'_>) -> crate::Result<ResultSet> {
+                self.queries.fetch_add(1, Ordering::SeqCst);
+                Ok(ResultSet::new(Vec::new(), Vec::new()))
+            }
+
+            async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+                self.inner.query_raw(sql, params).await
+            }
+
+            async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+                self.inner.execute(q).await
+            }
+
+            async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+                self.inner.execute_raw(sql, params).await
+            }
+
+            async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+                self.inner.raw_cmd(cmd).await
+            }
+
+            async fn version(&self) -> crate::Result<Option<String>> {
+                self.inner.version().await
+            }
+
+            fn connection_info(&self) -> &ConnectionInfo {
+                self.inner.connection_info()
+            }
+        }
+
+        #[tokio::test]
+        async fn locking_selects_always_reach_the_connection() {
+            let conn = Counting {
+                inner: Sqlite::new("db/test.db").unwrap(),
+                queries: AtomicUsize::new(0),
+            };
+
+            let cached = CachedQueryable::new(conn, MemoryCache::new(10, Duration::from_secs(60)));
+            let select = || Select::from_table("jobs").for_update(LockMode::Wait);
+
+            cached.select(select()).await.unwrap();
+            cached.select(select()).await.unwrap();
+            assert_eq!(2, cached.inner().queries.load(Ordering::SeqCst));
+
+            cached.select(Select::from_table("jobs")).await.unwrap();
+            cached.select(Select::from_table("jobs")).await.unwrap();
+            assert_eq!(3, cached.inner().queries.load(Ordering::SeqCst));
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    mod tags {
+        use super::*;
+        use crate::connector::Sqlite;
+
+        #[tokio::test]
+        async fn writes_invalidate_qualified_and_unqualified_reads() {
+            let conn = Sqlite::new("db/test.db").unwrap();
+            conn.raw_cmd("CREATE TABLE test_cache_qualified (id INTEGER PRIMARY KEY)")
+                .await
+                .unwrap();
+
+            let cached = CachedQueryable::new(conn, MemoryCache::new(10, Duration::from_secs(60)));
+            let qualified = || Table::from("test_cache_qualified").database("main");
+            let count = |table: Table<'static>| {
+                let select = Select::from_table(table).column("id");
+                async { cached.select(select).await.unwrap().len() }
+            };
+
+            assert_eq!(0, count(qualified()).await);
+            assert_eq!(0, count("test_cache_qualified".into()).await);
+
+            let insert = Insert::single_into("test_cache_qualified").value("id", 1);
+            cached.insert(insert.into()).await.unwrap();
+
+            assert_eq!(1, count(qualified()).await);
+            assert_eq!(1, count("test_cache_qualified".into()).await);
+
+            let insert = Insert::single_into(qualified()).value("id", 2);
+            cached.insert(insert.into()).await.unwrap();
+
+            assert_eq!(2, count(qualified()).await);
+            assert_eq!(2, count("test_cache_qualified".into()).await);
+        }
+    }
+}
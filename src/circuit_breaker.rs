@@ -0,0 +1,459 @@
+//! Failing fast when the database is degraded.
+//!
+//! A [`CircuitBreaker`] wraps a connection and keeps track of how its latest
+//! queries went. When too many of them fail with a connection error or a
+//! timeout, or take longer than the slow query threshold, the circuit
+//! opens: the queries fail right away with a `CircuitOpen` error, without
+//! reaching the database, giving it time to recover.
+//!
+//! After a while the circuit is half-open, and lets probe queries through
+//! one at a time. The circuit closes after enough successful probes, and
+//! opens again on the first failed one. Only interactive queries are used as
+//! probes, the batch and migration queries fail right away until the circuit
+//! closes.
+//!
+//! Raw commands go through the circuit like the queries, except the ones
+//! ending a transaction or a savepoint, such as `COMMIT` and `ROLLBACK`. They
+//! always reach the database, so a transaction started on the breaker can be
+//! finished while the circuit is open.
+//!
+//! ```no_run
+//! # use quaint::{prelude::*, single::Quaint, circuit_breaker::{CircuitBreaker, CircuitBreakerConfig}};
+//! # use std::time::Duration;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let config = CircuitBreakerConfig {
+//!     slow_query: Some(Duration::from_secs(2)),
+//!     ..Default::default()
+//! };
+//!
+//! let conn = CircuitBreaker::new(Quaint::new("postgres://localhost/db").await?, config);
+//! conn.select(Select::from_table("users")).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`CircuitBreaker`]: struct.CircuitBreaker.html
+
+use crate::{
+    ast::*,
+    connector::{
+        CommandHandle, ConnectionInfo, ExecuteResult, QueryClass, Queryable, ResultSet, Transaction,
+        TransactionCapable, UpsertOutcome,
+    },
+    error::{Error, ErrorKind},
+};
+use async_trait::async_trait;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// When the circuit of a [`CircuitBreaker`] opens and closes.
+///
+/// ```
+/// # use quaint::circuit_breaker::CircuitBreakerConfig;
+/// # use std::time::Duration;
+/// let config = CircuitBreakerConfig {
+///     failure_rate: 0.25,
+///     open_for: Duration::from_secs(10),
+///     ..Default::default()
+/// };
+/// ```
+///
+/// [`CircuitBreaker`]: struct.CircuitBreaker.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// How many of the latest queries the failure rate is counted from.
+    /// Defaults to 20.
+    pub window: usize,
+    /// How many queries the window must hold before the circuit can open.
+    /// Defaults to 10.
+    pub min_queries: usize,
+    /// The share of failed queries in the window opening the circuit, from
+    /// 0 to 1. Defaults to 0.5.
+    pub failure_rate: f64,
+    /// Queries taking longer than this are counted as failed, even if they
+    /// succeed. Defaults to none.
+    pub slow_query: Option<Duration>,
+    /// How long the circuit stays open before letting probes through.
+    /// Defaults to 30 seconds.
+    pub open_for: Duration,
+    /// How many probes must succeed to close the circuit. Defaults to 3.
+    pub probes: usize,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            window: 20,
+            min_queries: 10,
+            failure_rate: 0.5,
+            slow_query: None,
+            open_for: Duration::from_secs(30),
+            probes: 3,
+        }
+    }
+}
+
+/// The state of the circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// The queries run normally.
+    Closed,
+    /// The queries fail without reaching the database.
+    Open,
+    /// Probe queries are let through one at a time.
+    HalfOpen,
+}
+
+enum Circuit {
+    Closed,
+    Open {
+        until: Instant,
+    },
+    /// A probe cancelled before finishing is replaced after `open_for`.
+    HalfOpen {
+        probe: Option<Instant>,
+        successes: usize,
+    },
+}
+
+struct Breaker {
+    /// The latest outcomes, `true` for a failure.
+    window: VecDeque<bool>,
+    circuit: Circuit,
+}
+
+/// A connection failing fast while the database is degraded. See the
+/// [module documentation](index.html) for when the circuit opens.
+pub struct CircuitBreaker<Q> {
+    inner: Q,
+    config: CircuitBreakerConfig,
+    breaker: Mutex<Breaker>,
+}
+
+impl<Q> CircuitBreaker<Q>
+where
+    Q: Queryable,
+{
+    pub fn new(inner: Q, config: CircuitBreakerConfig) -> Self {
+        let breaker = Breaker {
+            window: VecDeque::with_capacity(config.window),
+            circuit: Circuit::Closed,
+        };
+
+        Self {
+            inner,
+            config,
+            breaker: Mutex::new(breaker),
+        }
+    }
+
+    /// The wrapped connection.
+    pub fn inner(&self) -> &Q {
+        &self.inner
+    }
+
+    /// The current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        let mut breaker = self.breaker.lock().unwrap();
+
+        match breaker.circuit {
+            Circuit::Closed => CircuitState::Closed,
+            Circuit::Open { until } if Instant::now() < until => CircuitState::Open,
+            Circuit::Open { .. } => {
+                breaker.circuit = Circuit::HalfOpen {
+                    probe: None,
+                    successes: 0,
+                };
+
+                CircuitState::HalfOpen
+            }
+            Circuit::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Runs the statement if the circuit lets it through, and counts its
+    /// outcome.
+    async fn guard<T, F>(&self, statement: F) -> crate::Result<T>
+    where
+        F: Future<Output = crate::Result<T>>,
+    {
+        let probe = self.admit()?;
+
+        let start = Instant::now();
+        let result = statement.await;
+
+        let slow = match self.config.slow_query {
+            Some(threshold) => start.elapsed() > threshold,
+            None => false,
+        };
+
+        let failed = match result {
+            Ok(_) => slow,
+            Err(ref err) => slow || is_degraded(err.kind()),
+        };
+
+        self.record(probe, failed);
+
+        result
+    }
+
+    /// Whether the query can run, and if it runs as a probe.
+    fn admit(&self) -> crate::Result<bool> {
+        let state = self.state();
+        let mut breaker = self.breaker.lock().unwrap();
+
+        match (state, &mut breaker.circuit) {
+            (CircuitState::Closed, _) => Ok(false),
+            (CircuitState::HalfOpen, Circuit::HalfOpen { probe, .. })
+                if probe.map_or(true, |started| started.elapsed() > self.config.open_for)
+                    && !QueryClass::current().is_background() =>
+            {
+                *probe = Some(Instant::now());
+                Ok(true)
+            }
+            _ => Err(Error::builder(ErrorKind::CircuitOpen).build()),
+        }
+    }
+
+    fn record(&self, probe: bool, failed: bool) {
+        let mut breaker = self.breaker.lock().unwrap();
+
+        if probe {
+            let successes = match breaker.circuit {
+                Circuit::HalfOpen { successes, .. } => successes + 1,
+                _ => return,
+            };
+
+            if failed {
+                self.open(&mut breaker);
+            } else if successes >= self.config.probes {
+                breaker.circuit = Circuit::Closed;
+                breaker.window.clear();
+            } else {
+                breaker.circuit = Circuit::HalfOpen { probe: None, successes };
+            }
+
+            return;
+        }
+
+        if !matches!(breaker.circuit, Circuit::Closed) {
+            return;
+        }
+
+        if breaker.window.len() >= self.config.window {
+            breaker.window.pop_front();
+        }
+
+        breaker.window.push_back(failed);
+
+        let queries = breaker.window.len();
+        let failures = breaker.window.iter().filter(|failed| **failed).count();
+
+        if queries >= self.config.min_queries && failures as f64 >= queries as f64 * self.config.failure_rate {
+            self.open(&mut breaker);
+        }
+    }
+
+    fn open(&self, breaker: &mut Breaker) {
+        breaker.circuit = Circuit::Open {
+            until: Instant::now() + self.config.open_for,
+        };
+    }
+}
+
+/// The errors telling the database is unavailable or overloaded, as opposed
+/// to the ones caused by the query.
+fn is_degraded(kind: &ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::ConnectionError(_)
+            | ErrorKind::ConnectTimeout(_)
+            | ErrorKind::Timeout(_)
+            | ErrorKind::IoError(_)
+            | ErrorKind::TlsError { .. }
+    )
+}
+
+/// `COMMIT`, `ROLLBACK` and `RELEASE`, with their savepoint and SQL Server
+/// variants.
+fn ends_transaction(cmd: &str) -> bool {
+    let keyword = cmd.split_whitespace().next().unwrap_or("");
+
+    ["COMMIT", "ROLLBACK", "RELEASE"]
+        .iter()
+        .any(|stmt| keyword.eq_ignore_ascii_case(stmt))
+}
+
+impl<Q> TransactionCapable for CircuitBreaker<Q> where Q: TransactionCapable {}
+
+#[async_trait]
+impl<Q> Queryable for CircuitBreaker<Q>
+where
+    Q: Queryable,
+{
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        self.guard(self.inner.query(q)).await
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.guard(self.inner.query_raw(sql, params)).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        self.guard(self.inner.execute(q)).await
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.guard(self.inner.execute_raw(sql, params)).await
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        self.guard(self.inner.execute_result(q)).await
+    }
+
+    async fn upsert(&self, q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        self.guard(self.inner.upsert(q)).await
+    }
+
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        self.guard(self.inner.delete_returning(q)).await
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        self.guard(self.inner.increment(table, conditions, column, amount))
+            .await
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        self.guard(self.inner.execute_raw_result(sql, params)).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        if ends_transaction(cmd) {
+            return self.inner.raw_cmd(cmd).await;
+        }
+
+        self.guard(self.inner.raw_cmd(cmd)).await
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.inner.version().await
+    }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        self.inner.connection_info()
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        self.inner.command_handle()
+    }
+
+    async fn server_reset_query(&self, tx: &Transaction<'_>) -> crate::Result<()> {
+        self.inner.server_reset_query(tx).await
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        self.inner.create_savepoint_statement(name)
+    }
+
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        self.inner.release_savepoint_statement(name)
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        self.inner.rollback_to_savepoint_statement(name)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::connector::Sqlite;
+
+    #[tokio::test]
+    async fn slow_queries_open_the_circuit_until_the_probes_pass() {
+        let config = CircuitBreakerConfig {
+            window: 2,
+            min_queries: 2,
+            slow_query: Some(Duration::from_nanos(0)),
+            open_for: Duration::from_millis(20),
+            probes: 1,
+            ..Default::default()
+        };
+
+        let conn = CircuitBreaker::new(Sqlite::new("db/test.db").unwrap(), config);
+
+        conn.query_raw("SELECT 1", &[]).await.unwrap();
+        assert_eq!(CircuitState::Closed, conn.state());
+
+        conn.query_raw("SELECT 1", &[]).await.unwrap();
+        assert_eq!(CircuitState::Open, conn.state());
+
+        let err = conn.query_raw("SELECT 1", &[]).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::CircuitOpen));
+
+        tokio::time::delay_for(Duration::from_millis(30)).await;
+        assert_eq!(CircuitState::HalfOpen, conn.state());
+
+        // The probe is slow too, opening the circuit again.
+        conn.query_raw("SELECT 1", &[]).await.unwrap();
+        assert_eq!(CircuitState::Open, conn.state());
+    }
+
+    #[tokio::test]
+    async fn raw_commands_are_rejected_while_the_circuit_is_open() {
+        let config = CircuitBreakerConfig {
+            window: 1,
+            min_queries: 1,
+            slow_query: Some(Duration::from_nanos(0)),
+            ..Default::default()
+        };
+
+        let conn = CircuitBreaker::new(Sqlite::new("db/test.db").unwrap(), config);
+
+        conn.raw_cmd("SELECT 1").await.unwrap();
+        assert_eq!(CircuitState::Open, conn.state());
+
+        let err = conn.raw_cmd("SELECT 1").await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::CircuitOpen));
+    }
+
+    #[tokio::test]
+    async fn transactions_can_be_finished_while_the_circuit_is_open() {
+        let config = CircuitBreakerConfig {
+            window: 1,
+            min_queries: 1,
+            slow_query: Some(Duration::from_nanos(0)),
+            ..Default::default()
+        };
+
+        let conn = CircuitBreaker::new(Sqlite::new("db/test.db").unwrap(), config);
+
+        // The slow `BEGIN` opens the circuit.
+        let tx = conn.start_transaction().await.unwrap();
+        assert_eq!(CircuitState::Open, conn.state());
+
+        let err = tx.query_raw("SELECT 1", &[]).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::CircuitOpen));
+
+        tx.rollback().await.unwrap();
+
+        let err = conn.start_transaction().await.err().unwrap();
+        assert!(matches!(err.kind(), ErrorKind::CircuitOpen));
+    }
+}
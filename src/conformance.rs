@@ -0,0 +1,437 @@
+//! A test suite for connectors.
+//!
+//! The checks run the same queries on any [`Queryable`], and assert the
+//! behavior the built-in connectors agree on: how values of the common types
+//! are written and read back, how constraint violations are reported, tuple
+//! comparisons and transactions. The built-in connectors run it in their
+//! tests, and third-party connectors can run it to verify they can be used in
+//! place of the built-in ones.
+//!
+//! The connection and the tables are given by a [`Harness`], as the SQL
+//! creating a table differs between databases. A [`TableHarness`] covers
+//! the databases creating temporary tables in SQL, given the column types
+//! and how names are quoted. A check fails by returning the error of a
+//! query, or by panicking on an unexpected result, so they can be called
+//! from the tests of the connector.
+//!
+//! ```no_run
+//! # use quaint::{single::Quaint, conformance::{self, ColumnType, QuoteStyle, TableHarness}};
+//! fn column_type(r#type: ColumnType) -> &'static str {
+//!     match r#type {
+//!         ColumnType::Id => "INTEGER PRIMARY KEY",
+//!         ColumnType::Integer => "BIGINT",
+//!         ColumnType::Text => "TEXT",
+//!         ColumnType::Decimal => "DECIMAL(12, 4)",
+//!         ColumnType::Boolean => "BOOLEAN",
+//!         ColumnType::Bytes => "BYTEA",
+//!         ColumnType::DateTime => "TIMESTAMPTZ",
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let conn = Quaint::new("postgres://localhost/db").await?;
+//! let harness = TableHarness::new(conn, column_type, QuoteStyle::DoubleQuotes);
+//!
+//! conformance::run_all(&harness).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Queryable`]: ../connector/trait.Queryable.html
+//! [`Harness`]: trait.Harness.html
+//! [`TableHarness`]: struct.TableHarness.html
+
+use crate::{
+    ast::*,
+    connector::{Queryable, Transaction, TransactionCapable},
+    error::ErrorKind,
+};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The types of the columns in the tables of the checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// An integer primary key. The checks always give the value.
+    Id,
+    /// A 64-bit integer.
+    Integer,
+    /// A string of any length.
+    Text,
+    /// A decimal number with at least four digits on both sides of the
+    /// point.
+    Decimal,
+    Boolean,
+    /// A byte string of any length.
+    Bytes,
+    /// A timestamp with at least a millisecond precision.
+    DateTime,
+}
+
+/// A column in a table created for a check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnDef {
+    pub name: &'static str,
+    pub r#type: ColumnType,
+    pub nullable: bool,
+}
+
+impl ColumnDef {
+    fn new(name: &'static str, r#type: ColumnType, nullable: bool) -> Self {
+        Self { name, r#type, nullable }
+    }
+}
+
+/// The connection under test, and how to create tables in its database.
+#[async_trait]
+pub trait Harness: Send + Sync {
+    /// The connection the checks run their queries on.
+    fn conn(&self) -> &dyn Queryable;
+
+    /// Creates an empty table with the columns, returning its name. Every
+    /// call must create a new table, and the table should be visible only
+    /// to the connection of the harness, such as a temporary table.
+    async fn create_table(&self, columns: &[ColumnDef]) -> crate::Result<String>;
+}
+
+/// How a [`TableHarness`] quotes the names of tables and columns.
+///
+/// [`TableHarness`]: struct.TableHarness.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// `"name"`, as in PostgreSQL.
+    DoubleQuotes,
+    /// `` `name` ``, as in MySQL and SQLite.
+    Backticks,
+    /// `[name]`, as in SQL Server. The tables are named with a `#` prefix to
+    /// make them temporary.
+    Brackets,
+}
+
+impl QuoteStyle {
+    fn quote(self, name: &str) -> String {
+        match self {
+            Self::DoubleQuotes => format!("\"{}\"", name),
+            Self::Backticks => format!("`{}`", name),
+            Self::Brackets => format!("[{}]", name),
+        }
+    }
+
+    fn table_name(self, number: usize) -> String {
+        match self {
+            Self::Brackets => format!("#conformance_{}", number),
+            _ => format!("conformance_{}", number),
+        }
+    }
+
+    fn create_table(self, name: &str, columns: &[String]) -> String {
+        let create = match self {
+            Self::Brackets => "CREATE TABLE",
+            _ => "CREATE TEMPORARY TABLE",
+        };
+
+        format!("{} {} ({})", create, self.quote(name), columns.join(", "))
+    }
+}
+
+/// A [`Harness`] creating the tables of the checks as temporary tables,
+/// with the column types given by a function.
+///
+/// [`Harness`]: trait.Harness.html
+pub struct TableHarness<Q> {
+    conn: Q,
+    column_type: fn(ColumnType) -> &'static str,
+    quotes: QuoteStyle,
+    tables: AtomicUsize,
+}
+
+impl<Q: Queryable> TableHarness<Q> {
+    /// A harness for the connection, creating columns of the SQL type
+    /// `column_type` returns for the [`ColumnType`].
+    ///
+    /// [`ColumnType`]: enum.ColumnType.html
+    pub fn new(conn: Q, column_type: fn(ColumnType) -> &'static str, quotes: QuoteStyle) -> Self {
+        Self {
+            conn,
+            column_type,
+            quotes,
+            tables: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl<Q: Queryable> Harness for TableHarness<Q> {
+    fn conn(&self) -> &dyn Queryable {
+        &self.conn
+    }
+
+    async fn create_table(&self, columns: &[ColumnDef]) -> crate::Result<String> {
+        let name = self.quotes.table_name(self.tables.fetch_add(1, Ordering::SeqCst));
+
+        let columns: Vec<_> = columns
+            .iter()
+            .map(|column| {
+                let null = if column.nullable { "NULL" } else { "NOT NULL" };
+                let r#type = (self.column_type)(column.r#type);
+
+                format!("{} {} {}", self.quotes.quote(column.name), r#type, null)
+            })
+            .collect();
+
+        self.conn.raw_cmd(&self.quotes.create_table(&name, &columns)).await?;
+
+        Ok(name)
+    }
+}
+
+/// Runs every check in the suite.
+pub async fn run_all(harness: &dyn Harness) -> crate::Result<()> {
+    select_value(harness).await?;
+    type_round_trips(harness).await?;
+    tuple_in(harness).await?;
+    unique_constraint_violation(harness).await?;
+    null_constraint_violation(harness).await?;
+    transaction_rollback(harness).await?;
+
+    Ok(())
+}
+
+/// A parameter is selected back as it was given.
+pub async fn select_value(harness: &dyn Harness) -> crate::Result<()> {
+    let select = Select::default().value(Expression::from(Value::integer(1)).alias("value"));
+    let rows = harness.conn().select(select).await?;
+
+    assert_eq!(1, rows.len(), "selecting a value returns one row");
+    assert_eq!(Some(1), rows.get(0).unwrap()["value"].as_i64());
+
+    Ok(())
+}
+
+/// The values of every column type, and nulls, are read back as they were
+/// written, compared in their [normalized] form.
+///
+/// [normalized]: ../ast/enum.Value.html#method.normalized
+pub async fn type_round_trips(harness: &dyn Harness) -> crate::Result<()> {
+    // The name and type of the column, a value and a null of the type.
+    #[cfg_attr(not(feature = "chrono-0_4"), allow(unused_mut))]
+    let mut values = vec![
+        (
+            "int_value",
+            ColumnType::Integer,
+            Value::integer(i64::MAX),
+            Value::Integer(None),
+        ),
+        (
+            "text_value",
+            ColumnType::Text,
+            Value::text("Musti ja Naukio"),
+            Value::Text(None),
+        ),
+        (
+            "decimal_value",
+            ColumnType::Decimal,
+            Value::real(Decimal::new(123_450, 4)),
+            Value::Real(None),
+        ),
+        (
+            "bool_value",
+            ColumnType::Boolean,
+            Value::boolean(true),
+            Value::Boolean(None),
+        ),
+        (
+            "bytes_value",
+            ColumnType::Bytes,
+            Value::bytes(vec![0u8, 1, 255]),
+            Value::Bytes(None),
+        ),
+    ];
+
+    #[cfg(feature = "chrono-0_4")]
+    {
+        let datetime = chrono::DateTime::parse_from_rfc3339("2020-04-01T12:30:15.123Z").unwrap();
+        let value = Value::datetime(datetime.into());
+
+        values.push(("datetime_value", ColumnType::DateTime, value, Value::DateTime(None)));
+    }
+
+    let mut columns = vec![ColumnDef::new("id", ColumnType::Id, false)];
+    columns.extend(
+        values
+            .iter()
+            .map(|(name, r#type, _, _)| ColumnDef::new(name, *r#type, true)),
+    );
+
+    let table = harness.create_table(&columns).await?;
+
+    let names: Vec<_> = values.iter().map(|(name, _, _, _)| *name).collect();
+    let written: Vec<_> = values.iter().map(|(_, _, value, _)| value.clone()).collect();
+
+    let mut row = vec![Value::integer(1)];
+    row.extend(written.clone());
+
+    let mut nulls = vec![Value::integer(2)];
+    nulls.extend(values.iter().map(|(_, _, _, null)| null.clone()));
+
+    let mut insert_columns = vec!["id"];
+    insert_columns.extend(names.iter());
+
+    let insert = Insert::multi_into(table.as_str(), insert_columns)
+        .values(row)
+        .values(nulls);
+
+    harness.conn().insert(insert.into()).await?;
+
+    let select = Select::from_table(table.as_str())
+        .columns(names.clone())
+        .order_by("id".ascend());
+
+    let rows = harness.conn().select(select).await?;
+    assert_eq!(2, rows.len(), "both rows are read back");
+
+    for (i, (name, value)) in names.iter().zip(written.into_iter()).enumerate() {
+        let read = rows.get(0).unwrap()[i].clone();
+        assert_eq!(value.normalized(), read.normalized(), "the value of `{}`", name);
+
+        let null = rows.get(1).unwrap()[i].clone();
+        assert!(null.is_null(), "a null `{}` is read back as null, not {:?}", name, null);
+    }
+
+    Ok(())
+}
+
+/// A row of columns compared to a list of rows with `IN`, and a row of one
+/// column to a list of values.
+pub async fn tuple_in(harness: &dyn Harness) -> crate::Result<()> {
+    let columns = [
+        ColumnDef::new("id", ColumnType::Id, false),
+        ColumnDef::new("id2", ColumnType::Integer, false),
+    ];
+
+    let table = harness.create_table(&columns).await?;
+
+    let insert = Insert::multi_into(table.as_str(), vec!["id", "id2"])
+        .values(vec![1, 2])
+        .values(vec![3, 4])
+        .values(vec![5, 6]);
+
+    harness.conn().insert(insert.into()).await?;
+
+    let pairs = Values::from(vec![vec![1, 2], vec![3, 4], vec![5, 5]].into_iter());
+    let select = Select::from_table(table.as_str())
+        .column("id")
+        .so_that(Row::from(vec![Column::from("id"), Column::from("id2")]).in_selection(pairs))
+        .order_by("id".ascend());
+
+    let ids: Vec<_> = harness
+        .conn()
+        .select(select)
+        .await?
+        .into_iter()
+        .map(|row| row[0].as_i64())
+        .collect();
+
+    assert_eq!(vec![Some(1), Some(3)], ids, "the rows matching a pair in the list");
+
+    let select = Select::from_table(table.as_str())
+        .column("id")
+        .so_that(Row::from(vec![Column::from("id2")]).in_selection(Row::from(vec![4])));
+
+    let ids: Vec<_> = harness
+        .conn()
+        .select(select)
+        .await?
+        .into_iter()
+        .map(|row| row[0].as_i64())
+        .collect();
+
+    assert_eq!(vec![Some(3)], ids, "the rows matching a single column row");
+
+    Ok(())
+}
+
+/// A duplicate primary key is a `UniqueConstraintViolation` error.
+pub async fn unique_constraint_violation(harness: &dyn Harness) -> crate::Result<()> {
+    let table = harness
+        .create_table(&[ColumnDef::new("id", ColumnType::Id, false)])
+        .await?;
+
+    let insert = || Insert::single_into(table.as_str()).value("id", 1);
+    harness.conn().insert(insert().into()).await?;
+
+    let err = harness.conn().insert(insert().into()).await.unwrap_err();
+
+    assert!(
+        matches!(err.kind(), ErrorKind::UniqueConstraintViolation { .. }),
+        "expected a unique constraint violation, got {:?}",
+        err.kind()
+    );
+
+    Ok(())
+}
+
+/// A null in a column not allowing them is a `NullConstraintViolation`
+/// error.
+pub async fn null_constraint_violation(harness: &dyn Harness) -> crate::Result<()> {
+    let columns = [
+        ColumnDef::new("id", ColumnType::Id, false),
+        ColumnDef::new("name", ColumnType::Text, false),
+    ];
+
+    let table = harness.create_table(&columns).await?;
+
+    let insert = Insert::single_into(table.as_str())
+        .value("id", 1)
+        .value("name", Value::Text(None));
+
+    let err = harness.conn().insert(insert.into()).await.unwrap_err();
+
+    assert!(
+        matches!(err.kind(), ErrorKind::NullConstraintViolation { .. }),
+        "expected a null constraint violation, got {:?}",
+        err.kind()
+    );
+
+    Ok(())
+}
+
+/// The changes of a rolled back transaction are gone, and the ones of a
+/// rolled back savepoint are gone without affecting the transaction.
+pub async fn transaction_rollback(harness: &dyn Harness) -> crate::Result<()> {
+    let table = harness
+        .create_table(&[ColumnDef::new("id", ColumnType::Id, false)])
+        .await?;
+    let conn = harness.conn();
+
+    let tx = Transaction::new(conn, conn.begin_statement()).await?;
+    tx.insert(Insert::single_into(table.as_str()).value("id", 1).into())
+        .await?;
+    tx.rollback().await?;
+
+    let tx = Transaction::new(conn, conn.begin_statement()).await?;
+    tx.insert(Insert::single_into(table.as_str()).value("id", 2).into())
+        .await?;
+
+    let nested = tx.start_transaction().await?;
+    nested
+        .insert(Insert::single_into(table.as_str()).value("id", 3).into())
+        .await?;
+    nested.rollback().await?;
+
+    tx.commit().await?;
+
+    let select = Select::from_table(table.as_str()).column("id");
+    let ids: Vec<_> = conn
+        .select(select)
+        .await?
+        .into_iter()
+        .map(|row| row[0].as_i64())
+        .collect();
+
+    assert_eq!(vec![Some(2)], ids, "only the committed row is kept");
+
+    Ok(())
+}
@@ -10,7 +10,13 @@
 //! querying interface.
 
 mod connection_info;
+mod credentials;
+pub(crate) mod deadline;
+mod decoding;
+mod execute_result;
 pub(crate) mod metrics;
+mod query_class;
+mod query_options;
 mod queryable;
 mod result_set;
 mod transaction;
@@ -20,21 +26,43 @@ mod type_identifier;
 pub(crate) mod mssql;
 #[cfg(feature = "mysql")]
 pub(crate) mod mysql;
-#[cfg(feature = "postgresql")]
+#[cfg(feature = "postgresql-core")]
 pub(crate) mod postgres;
+#[cfg(all(
+    feature = "proxy",
+    any(feature = "postgresql-core", feature = "mysql", feature = "mssql")
+))]
+pub(crate) mod proxy;
 #[cfg(feature = "sqlite")]
 pub(crate) mod sqlite;
+#[cfg(feature = "ssh")]
+pub(crate) mod ssh;
 
 #[cfg(feature = "mysql")]
 pub use self::mysql::*;
-#[cfg(feature = "postgresql")]
+#[cfg(feature = "postgresql-core")]
 pub use self::postgres::*;
+#[cfg(all(
+    feature = "proxy",
+    any(feature = "postgresql-core", feature = "mysql", feature = "mssql")
+))]
+pub use self::proxy::{ProxyKind, ProxyParams};
 pub use self::result_set::*;
 pub use connection_info::*;
+pub use credentials::*;
+pub use deadline::{current_deadline, with_deadline};
+#[cfg(any(feature = "mysql", feature = "postgresql-core"))]
+pub(crate) use decoding::DecodeOptions;
+pub use decoding::{InvalidDates, NonFiniteFloats};
+pub use execute_result::*;
 #[cfg(feature = "mssql")]
 pub use mssql::*;
+pub use query_class::*;
+pub use query_options::QueryOptions;
 pub use queryable::*;
 #[cfg(feature = "sqlite")]
 pub use sqlite::*;
+#[cfg(feature = "ssh")]
+pub use ssh::SshParams;
 pub use transaction::*;
 pub(crate) use type_identifier::*;
@@ -0,0 +1,71 @@
+use super::ConnectionInfo;
+#[cfg(any(feature = "mysql", feature = "postgresql-core", feature = "mssql"))]
+use crate::error::{Error, ErrorKind};
+use async_trait::async_trait;
+#[cfg(any(feature = "mysql", feature = "postgresql-core", feature = "mssql"))]
+use std::{env, fs};
+
+/// Generates the password for a new database connection just before
+/// connecting, instead of using the one from the connection string.
+///
+/// Meant for short-lived authentication tokens, such as AWS RDS IAM
+/// authentication or Cloud SQL access tokens. When set to a
+/// [pool](../pooled/struct.Builder.html#method.credentials_provider), the
+/// provider is called again on every reconnect, so a token that expired in
+/// the meantime is never reused.
+///
+/// ```no_run
+/// # use quaint::{connector::{ConnectionInfo, CredentialsProvider}, pooled::Quaint};
+/// # use async_trait::async_trait;
+/// struct IamToken;
+///
+/// #[async_trait]
+/// impl CredentialsProvider for IamToken {
+///     async fn password(&self, info: &ConnectionInfo) -> quaint::Result<String> {
+///         // Generate a token for `info.host()` and `info.username()`...
+///         # unimplemented!()
+///     }
+/// }
+///
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let mut builder = Quaint::builder("postgresql://iam_user@localhost:5432/postgres")?;
+/// builder.credentials_provider(IamToken);
+///
+/// let pool = builder.build();
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait CredentialsProvider: Send + Sync {
+    /// The password to use when opening a connection described by `info`.
+    async fn password(&self, info: &ConnectionInfo) -> crate::Result<String>;
+}
+
+/// Reads the password from the environment, if given as `${VAR}` in the
+/// connection string.
+#[cfg(any(feature = "mysql", feature = "postgresql-core", feature = "mssql"))]
+pub(crate) fn password_from_env(password: &str) -> crate::Result<Option<String>> {
+    let name = match password.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    env::var(name).map(Some).map_err(|_| {
+        let kind =
+            ErrorKind::DatabaseUrlIsInvalid(format!("Environment variable `{}` for the password is not set.", name));
+
+        Error::builder(kind).build()
+    })
+}
+
+/// Reads the password from a file, e.g. a mounted secret. A trailing newline
+/// is not considered to be a part of the password.
+#[cfg(any(feature = "mysql", feature = "postgresql-core", feature = "mssql"))]
+pub(crate) fn password_from_file(path: &str) -> crate::Result<String> {
+    let password = fs::read_to_string(path).map_err(|err| {
+        let kind = ErrorKind::DatabaseUrlIsInvalid(format!("Could not read the password file `{}` ({}).", path, err));
+        Error::builder(kind).build()
+    })?;
+
+    Ok(password.trim_end_matches(|c| c == '\n' || c == '\r').to_string())
+}
@@ -0,0 +1,97 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+/// Runs the future with every query in it finishing before the deadline,
+/// including the transaction commands and the connections it checks out
+/// from a pool. A query still running at the deadline fails with a timeout
+/// error, the same way as when reaching the `socket_timeout` of the
+/// connection.
+///
+/// Deadlines nest: inside of another `with_deadline`, the earlier of the two
+/// is used, so a function can't extend the budget of its caller.
+///
+/// The deadline bounds the queries of PostgreSQL, MySQL and SQL Server.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, pooled::Quaint, connector::with_deadline};
+/// # use std::time::{Duration, Instant};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let pool = Quaint::builder("postgres://localhost/db")?.build();
+///
+/// let users = with_deadline(Instant::now() + Duration::from_secs(1), async {
+///     let conn = pool.check_out().await?;
+///     let tx = conn.start_transaction().await?;
+///     let users = tx.select(Select::from_table("users")).await?;
+///     tx.commit().await?;
+///
+///     Ok::<_, quaint::error::Error>(users)
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_deadline<F>(deadline: Instant, f: F) -> F::Output
+where
+    F: Future,
+{
+    let deadline = match current_deadline() {
+        Some(outer) => outer.min(deadline),
+        None => deadline,
+    };
+
+    DEADLINE.scope(deadline, f).await
+}
+
+/// The deadline of the queries run in the current task, if any.
+pub fn current_deadline() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+/// The given timeout, shortened to the time left until the deadline.
+pub(crate) fn bounded(timeout: Option<Duration>) -> Option<Duration> {
+    let remaining = current_deadline().map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+    match (timeout, remaining) {
+        (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+        (timeout, remaining) => timeout.or(remaining),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn the_earlier_deadline_is_kept_when_nesting() {
+        let now = Instant::now();
+        let outer = now + Duration::from_secs(1);
+
+        let inner = with_deadline(outer, async {
+            with_deadline(now + Duration::from_secs(5), async { current_deadline() }).await
+        })
+        .await;
+
+        assert_eq!(Some(outer), inner);
+    }
+
+    #[tokio::test]
+    async fn timeouts_are_bounded_by_the_deadline() {
+        assert_eq!(Some(Duration::from_secs(5)), bounded(Some(Duration::from_secs(5))));
+        assert_eq!(None, bounded(None));
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+
+        with_deadline(deadline, async {
+            assert!(bounded(Some(Duration::from_secs(5))).unwrap() <= Duration::from_secs(1));
+            assert!(bounded(None).unwrap() <= Duration::from_secs(1));
+        })
+        .await;
+    }
+}
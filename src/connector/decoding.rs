@@ -0,0 +1,226 @@
+#[cfg(any(feature = "postgresql-core", feature = "mysql"))]
+use crate::ast::Value;
+use crate::error::{Error, ErrorKind};
+#[cfg(feature = "chrono-0_4")]
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use std::str::FromStr;
+
+/// What to do with the `NaN` and infinite floats read from the database,
+/// which a `Decimal` cannot hold. Set with the `non_finite_floats` connection
+/// parameter, either `error` or `null`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonFiniteFloats {
+    /// Fail the query with an error naming the column.
+    Error,
+    /// Read the value as `NULL`.
+    Null,
+}
+
+impl Default for NonFiniteFloats {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+impl FromStr for NonFiniteFloats {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s {
+            "error" => Ok(Self::Error),
+            "null" => Ok(Self::Null),
+            _ => Err(Error::builder(ErrorKind::InvalidConnectionArguments).build()),
+        }
+    }
+}
+
+/// What to do with the dates that do not exist, such as the
+/// `0000-00-00 00:00:00` MySQL stores for a zero date. Set with the
+/// `invalid_dates` connection parameter, either `error`, `null` or `min_date`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidDates {
+    /// Fail the query with an error naming the column.
+    Error,
+    /// Read the value as `NULL`.
+    Null,
+    /// Read the value as `0001-01-01 00:00:00 UTC`.
+    MinDate,
+}
+
+impl Default for InvalidDates {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+impl FromStr for InvalidDates {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s {
+            "error" => Ok(Self::Error),
+            "null" => Ok(Self::Null),
+            "min_date" => Ok(Self::MinDate),
+            _ => Err(Error::builder(ErrorKind::InvalidConnectionArguments).build()),
+        }
+    }
+}
+
+/// How the values of a row are read.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DecodeOptions {
+    pub(crate) numeric_as_text: bool,
+    pub(crate) non_finite_floats: NonFiniteFloats,
+    pub(crate) invalid_dates: InvalidDates,
+    pub(crate) decode_threads: usize,
+}
+
+/// Results with fewer rows are always decoded on the calling task.
+#[cfg(any(feature = "postgresql-core", feature = "mysql"))]
+const PARALLEL_DECODE_MIN_ROWS: usize = 1024;
+
+impl DecodeOptions {
+    /// Decodes the rows with the given function. With more than one
+    /// `decode_threads` and a large enough result, the rows are split into
+    /// chunks decoded on the blocking thread pool, keeping their order.
+    #[cfg(any(feature = "postgresql-core", feature = "mysql"))]
+    pub(crate) async fn decode_rows<R, F>(self, rows: Vec<R>, decode: F) -> crate::Result<Vec<Vec<Value<'static>>>>
+    where
+        R: Send + 'static,
+        F: Fn(R, DecodeOptions) -> crate::Result<Vec<Value<'static>>> + Copy + Send + 'static,
+    {
+        let len = rows.len();
+
+        if self.decode_threads <= 1 || len < PARALLEL_DECODE_MIN_ROWS {
+            return rows.into_iter().map(|row| decode(row, self)).collect();
+        }
+
+        // At least a few hundred rows per thread to make up for the handoff.
+        let threads = self.decode_threads.min(len / 256);
+        let chunk_size = (len + threads - 1) / threads;
+        let mut rows = rows.into_iter();
+
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                let chunk: Vec<R> = rows.by_ref().take(chunk_size).collect();
+
+                tokio::task::spawn_blocking(move || {
+                    chunk
+                        .into_iter()
+                        .map(|row| decode(row, self))
+                        .collect::<crate::Result<Vec<_>>>()
+                })
+            })
+            .collect();
+
+        let mut values = Vec::with_capacity(len);
+
+        for handle in handles {
+            let chunk = handle.await.map_err(|e| {
+                let msg = format!("Decoding the rows failed: {}", e);
+                Error::builder(ErrorKind::conversion(msg)).build()
+            })?;
+
+            values.extend(chunk?);
+        }
+
+        Ok(values)
+    }
+
+    /// Reads a `f32` from the given column as a `Decimal`.
+    pub(crate) fn f32(&self, column: &str, float: f32) -> crate::Result<Option<Decimal>> {
+        match Decimal::from_f32(float) {
+            Some(decimal) => Ok(Some(decimal)),
+            None => self.not_a_decimal(column, f64::from(float)),
+        }
+    }
+
+    /// Reads a `f64` from the given column as a `Decimal`.
+    pub(crate) fn f64(&self, column: &str, float: f64) -> crate::Result<Option<Decimal>> {
+        // Decimal::from_f64 is buggy. Issue: https://github.com/paupino/rust-decimal/issues/228
+        match Decimal::from_str(&float.to_string()) {
+            Ok(decimal) if float.is_finite() => Ok(Some(decimal)),
+            _ => self.not_a_decimal(column, float),
+        }
+    }
+
+    fn not_a_decimal(&self, column: &str, float: f64) -> crate::Result<Option<Decimal>> {
+        if !float.is_finite() && self.non_finite_floats == NonFiniteFloats::Null {
+            return Ok(None);
+        }
+
+        let msg = format!(
+            "The value {} in column `{}` cannot be read as a decimal.",
+            float, column
+        );
+        Err(Error::builder(ErrorKind::conversion(msg)).build())
+    }
+
+    /// Reads a date that does not exist, written as `value`, from the given
+    /// column.
+    #[cfg(feature = "chrono-0_4")]
+    pub(crate) fn invalid_date(&self, column: &str, value: &str) -> crate::Result<Option<NaiveDateTime>> {
+        match self.invalid_dates {
+            InvalidDates::Null => Ok(None),
+            InvalidDates::MinDate => Ok(Some(NaiveDate::from_ymd(1, 1, 1).and_hms(0, 0, 0))),
+            InvalidDates::Error => {
+                let msg = format!("The value {} in column `{}` is not a valid date.", value, column);
+                Err(Error::builder(ErrorKind::conversion(msg)).build())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_floats_follow_the_policy() {
+        let error = DecodeOptions::default();
+        let null = DecodeOptions {
+            non_finite_floats: NonFiniteFloats::Null,
+            ..Default::default()
+        };
+
+        assert_eq!(Some(Decimal::new(15, 1)), error.f64("price", 1.5).unwrap());
+        assert_eq!(
+            "Conversion failed: The value NaN in column `price` cannot be read as a decimal.",
+            error.f64("price", f64::NAN).unwrap_err().to_string()
+        );
+        assert!(error.f32("price", f32::INFINITY).is_err());
+
+        assert_eq!(None, null.f64("price", f64::NEG_INFINITY).unwrap());
+        assert_eq!(None, null.f32("price", f32::NAN).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "chrono-0_4")]
+    fn invalid_dates_follow_the_policy() {
+        let options = |invalid_dates| DecodeOptions {
+            invalid_dates,
+            ..Default::default()
+        };
+
+        let zero = "0000-00-00 00:00:00";
+
+        assert_eq!(
+            "Conversion failed: The value 0000-00-00 00:00:00 in column `created_at` is not a valid date.",
+            options(InvalidDates::Error)
+                .invalid_date("created_at", zero)
+                .unwrap_err()
+                .to_string()
+        );
+
+        assert_eq!(
+            None,
+            options(InvalidDates::Null).invalid_date("created_at", zero).unwrap()
+        );
+
+        assert_eq!(
+            Some(NaiveDate::from_ymd(1, 1, 1).and_hms(0, 0, 0)),
+            options(InvalidDates::MinDate).invalid_date("created_at", zero).unwrap()
+        );
+    }
+}
@@ -0,0 +1,43 @@
+/// The outcome of a query run with
+/// [execute_result](trait.Queryable.html#method.execute_result).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExecuteResult {
+    rows_affected: u64,
+    last_insert_id: Option<u64>,
+}
+
+impl ExecuteResult {
+    /// Creates a new result from the number of affected rows and the last
+    /// inserted id.
+    pub fn new(rows_affected: u64, last_insert_id: Option<u64>) -> Self {
+        Self {
+            rows_affected,
+            last_insert_id,
+        }
+    }
+
+    /// The number of rows inserted, updated or deleted by the query.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+
+    /// The id of the last row inserted with an auto-increment id, as reported
+    /// by MySQL and SQLite. On SQLite this is the last row inserted on the
+    /// connection, even if the query itself did not insert anything.
+    pub fn last_insert_id(&self) -> Option<u64> {
+        self.last_insert_id
+    }
+}
+
+/// What an upsert did to a row, returned by
+/// [upsert](trait.Queryable.html#method.upsert).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    /// No row with the same key existed, the row was inserted.
+    Inserted,
+    /// The conflicting row was updated.
+    Updated,
+    /// The conflicting row already held the values, so nothing changed.
+    /// Reported only by MySQL.
+    Unchanged,
+}
@@ -1,3 +1,4 @@
+use super::QueryClass;
 use crate::ast::{Params, Value};
 use std::{future::Future, time::Instant};
 
@@ -11,6 +12,7 @@ where
     F: FnOnce() -> U + 'a,
     U: Future<Output = crate::Result<T>>,
 {
+    let class = QueryClass::current();
     let start = Instant::now();
     let res = f().await;
     let end = Instant::now();
@@ -19,9 +21,10 @@ where
         #[cfg(not(feature = "tracing-log"))]
         {
             info!(
-                "query: \"{}\", params: {} (in {}ms)",
+                "query: \"{}\", params: {}, class: {} (in {}ms)",
                 query,
                 Params(params),
+                class,
                 start.elapsed().as_millis(),
             );
         }
@@ -31,12 +34,13 @@ where
                 query,
                 item_type = "query",
                 params = %Params(params),
+                class = class.as_str(),
                 duration_ms = start.elapsed().as_millis() as u64,
             )
         }
     }
 
-    timing!(format!("{}.query.time", tag), start, end);
+    timing!(format!("{}.query.time", tag), start, end, "class" => class.as_str());
 
     res
 }
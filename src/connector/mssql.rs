@@ -1,15 +1,22 @@
 mod conversion;
 mod error;
 
+#[cfg(feature = "proxy")]
+use crate::connector::proxy::ProxyParams;
+#[cfg(feature = "ssh")]
+use crate::connector::ssh::{SshParams, SshTunnel};
 use crate::{
-    ast::{Query, Value},
-    connector::{metrics, queryable::*, ResultSet, Transaction},
+    ast::{Column, ConditionTree, Delete, Query, Table, Value},
+    connector::{credentials, metrics, query_options, queryable::*, ConnectionInfo, ResultSet, Transaction},
     error::{Error, ErrorKind},
     visitor::{self, Visitor},
 };
 use async_trait::async_trait;
-use futures::lock::Mutex;
-use std::{collections::HashMap, convert::TryFrom, fmt::Write, future::Future, time::Duration};
+use futures::{
+    channel::{mpsc, oneshot},
+    StreamExt,
+};
+use std::{collections::HashMap, convert::TryFrom, fmt::Write, future::Future, sync::Arc, time::Duration};
 use tiberius::*;
 use tokio::{net::TcpStream, time::timeout};
 use tokio_util::compat::{Compat, Tokio02AsyncWriteCompatExt};
@@ -30,9 +37,15 @@ pub(crate) struct MssqlQueryParams {
     password: Option<String>,
     database: String,
     trust_server_certificate: bool,
+    resolve_to: Option<String>,
+    #[cfg(feature = "ssh")]
+    ssh: Option<SshParams>,
+    #[cfg(feature = "proxy")]
+    proxy: Option<ProxyParams>,
     connection_limit: Option<usize>,
     socket_timeout: Option<Duration>,
     connect_timeout: Option<Duration>,
+    max_interactive_rows: Option<usize>,
 }
 
 #[async_trait]
@@ -70,6 +83,44 @@ impl MssqlUrl {
     pub fn port(&self) -> u16 {
         self.query_params.port()
     }
+
+    /// The number of rows a `SELECT` without a `LIMIT` is limited to, for
+    /// interactive tools. Set with `maxInteractiveRows=n`, defaults to no
+    /// limit. See [Select::unlimited](../ast/struct.Select.html#method.unlimited)
+    /// for opting out.
+    pub fn max_interactive_rows(&self) -> Option<usize> {
+        self.query_params.max_interactive_rows
+    }
+
+    pub(crate) fn has_password(&self) -> bool {
+        self.query_params.password().is_some()
+    }
+
+    /// The address to connect to instead of resolving the host, if set.
+    pub fn resolve_to(&self) -> Option<&str> {
+        self.query_params.resolve_to.as_deref()
+    }
+
+    /// The SSH server to tunnel the connection through, if set.
+    #[cfg(feature = "ssh")]
+    pub fn ssh_params(&self) -> Option<&SshParams> {
+        self.query_params.ssh.as_ref()
+    }
+
+    /// The proxy to connect through, if set.
+    #[cfg(feature = "proxy")]
+    pub fn proxy(&self) -> Option<&ProxyParams> {
+        self.query_params.proxy.as_ref()
+    }
+
+    /// Overrides the password from the connection string.
+    #[cfg(feature = "pooled")]
+    pub(crate) fn set_password(&mut self, password: String) -> crate::Result<()> {
+        self.query_params.password = Some(password);
+        self.connection_string = Self::create_ado_net_string(&self.query_params)?;
+
+        Ok(())
+    }
 }
 
 impl MssqlQueryParams {
@@ -114,50 +165,233 @@ impl MssqlQueryParams {
     }
 }
 
-/// A connector interface for the PostgreSQL database.
+/// A connector interface for the SQL Server database.
+///
+/// The connection is owned by a task running the commands one at a time.
+/// A command always runs to the end, even if the caller stops waiting for
+/// it, so a cancelled query never leaves the connection in the middle of a
+/// response. A command running past the `socketTimeout` closes the
+/// connection instead.
 #[derive(Debug)]
 pub struct Mssql {
-    client: Mutex<Client<Compat<TcpStream>>>,
+    commands: mpsc::UnboundedSender<(Option<Duration>, Command)>,
     url: MssqlUrl,
+    connection_info: ConnectionInfo,
     socket_timeout: Option<Duration>,
+    #[cfg(feature = "ssh")]
+    _tunnel: Option<SshTunnel>,
 }
 
 impl Mssql {
     pub async fn new(url: MssqlUrl) -> crate::Result<Self> {
         let config = Config::from_ado_string(&url.connection_string)?;
-        let tcp = TcpStream::connect_named(&config).await?;
+        let address = url.resolve_to().map(|host| (host.to_string(), url.port()));
+
+        #[cfg(feature = "ssh")]
+        let (address, tunnel) = match url.ssh_params() {
+            Some(params) => {
+                let target = url.resolve_to().unwrap_or_else(|| url.host());
+                let tunnel = SshTunnel::new(params.clone(), target, url.port())?;
+                let local_addr = tunnel.local_addr();
+
+                (Some((local_addr.ip().to_string(), local_addr.port())), Some(tunnel))
+            }
+            None => (address, None),
+        };
+
+        let tcp = Self::connect_tcp(&url, &config, address).await?;
         let client = Client::connect(config, tcp.compat_write()).await?;
         let socket_timeout = url.socket_timeout();
 
+        let (commands, receiver) = mpsc::unbounded();
+        tokio::spawn(drive(client, receiver));
+
         Ok(Self {
-            client: Mutex::new(client),
+            commands,
+            connection_info: ConnectionInfo::Mssql(url.clone()),
             url,
             socket_timeout,
+            #[cfg(feature = "ssh")]
+            _tunnel: tunnel,
         })
     }
 
-    async fn timeout<T, F, E>(&self, f: F) -> crate::Result<T>
+    /// Opens the TCP connection to `address`, or to the server of the
+    /// connection string if not given. A proxy connects to the host and port
+    /// of the connection string, without looking up named instances.
+    #[cfg_attr(not(feature = "proxy"), allow(unused_variables))]
+    async fn connect_tcp(url: &MssqlUrl, config: &Config, address: Option<(String, u16)>) -> crate::Result<TcpStream> {
+        #[cfg(feature = "proxy")]
+        {
+            if let Some(proxy) = url.proxy() {
+                let (host, port) = address.unwrap_or_else(|| (url.host().to_string(), url.port()));
+                return proxy.connect(&host, port).await;
+            }
+        }
+
+        let tcp = match address {
+            Some((host, port)) => TcpStream::connect((host.as_str(), port)).await?,
+            None => TcpStream::connect_named(config).await?,
+        };
+
+        Ok(tcp)
+    }
+
+    /// Sends the command to the connection task and waits for the reply.
+    /// The time limit of the query is taken here, where the query options
+    /// and the deadline of the caller are visible, and enforced by the task.
+    async fn send<T, F>(&self, command: F) -> crate::Result<T>
     where
-        F: Future<Output = std::result::Result<T, E>>,
-        E: Into<Error>,
+        F: FnOnce(oneshot::Sender<crate::Result<T>>) -> Command,
     {
-        match self.socket_timeout {
-            Some(duration) => match timeout(duration, f).await {
-                Ok(Ok(result)) => Ok(result),
-                Ok(Err(err)) => Err(err.into()),
-                Err(to) => Err(to.into()),
-            },
-            None => match f.await {
-                Ok(result) => Ok(result),
-                Err(err) => Err(err.into()),
-            },
+        let (reply, response) = oneshot::channel();
+        let limit = query_options::socket_timeout(self.socket_timeout);
+
+        if self.commands.unbounded_send((limit, command(reply))).is_err() {
+            return Err(connection_closed());
+        }
+
+        match response.await {
+            Ok(result) => result,
+            Err(_) => Err(connection_closed()),
+        }
+    }
+}
+
+/// A request for the task owning the connection. The task outlives the
+/// caller's borrows, so the query and its parameters are copied into it.
+enum Command {
+    Query {
+        sql: String,
+        params: Vec<Value<'static>>,
+        reply: oneshot::Sender<crate::Result<ResultSet>>,
+    },
+    Execute {
+        sql: String,
+        params: Vec<Value<'static>>,
+        reply: oneshot::Sender<crate::Result<u64>>,
+    },
+    RawCmd {
+        cmd: String,
+        reply: oneshot::Sender<crate::Result<()>>,
+    },
+}
+
+/// Runs the commands in the order they were sent, until the `Mssql` value
+/// is dropped. The reply is discarded if the caller has gone away.
+///
+/// A command running past its time limit leaves the connection in the
+/// middle of a response, so the connection is closed: the commands waiting
+/// behind it fail, and so do the ones sent later, making the pool replace
+/// the connection on the next check out.
+async fn drive(
+    mut client: Client<Compat<TcpStream>>,
+    mut commands: mpsc::UnboundedReceiver<(Option<Duration>, Command)>,
+) {
+    while let Some((limit, command)) = commands.next().await {
+        let completed = match command {
+            Command::Query { sql, params, reply } => {
+                run_with_limit(limit, run_query(&mut client, &sql, &params), reply).await
+            }
+            Command::Execute { sql, params, reply } => {
+                run_with_limit(limit, run_execute(&mut client, &sql, &params), reply).await
+            }
+            Command::RawCmd { cmd, reply } => run_with_limit(limit, run_raw_cmd(&mut client, &cmd), reply).await,
+        };
+
+        if !completed {
+            commands.close();
+
+            // Dropping the pending commands drops their reply channels,
+            // failing them with a closed connection error.
+            while commands.next().await.is_some() {}
+
+            return;
         }
     }
 }
 
+/// Runs the statement and sends its result, returning `false` if it didn't
+/// finish in time.
+async fn run_with_limit<T, F>(limit: Option<Duration>, statement: F, reply: oneshot::Sender<crate::Result<T>>) -> bool
+where
+    F: Future<Output = crate::Result<T>>,
+{
+    let result = match limit {
+        Some(duration) => timeout(duration, statement).await,
+        None => Ok(statement.await),
+    };
+
+    match result {
+        Ok(result) => {
+            let _ = reply.send(result);
+            true
+        }
+        Err(elapsed) => {
+            let _ = reply.send(Err(elapsed.into()));
+            false
+        }
+    }
+}
+
+async fn run_query(
+    client: &mut Client<Compat<TcpStream>>,
+    sql: &str,
+    params: &[Value<'_>],
+) -> crate::Result<ResultSet> {
+    let params = conversion::conv_params(params)?;
+    let results = client.query(sql, params.as_slice()).await?;
+
+    let columns = results
+        .columns()
+        .unwrap_or(&[])
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+
+    let rows = results.into_first_result().await?;
+
+    let mut result = ResultSet::new(columns, Vec::new());
+
+    for row in rows {
+        let mut values: Vec<Value<'_>> = Vec::with_capacity(row.len());
+
+        for val in row.into_iter() {
+            values.push(Value::try_from(val)?);
+        }
+
+        result.rows.push(values);
+    }
+
+    Ok(result)
+}
+
+async fn run_execute(client: &mut Client<Compat<TcpStream>>, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+    let params = conversion::conv_params(params)?;
+    let changes = client.execute(sql, params.as_slice()).await?.total();
+
+    Ok(changes)
+}
+
+async fn run_raw_cmd(client: &mut Client<Compat<TcpStream>>, cmd: &str) -> crate::Result<()> {
+    client.simple_query(cmd).await?.into_results().await?;
+
+    Ok(())
+}
+
+fn connection_closed() -> Error {
+    let kind = ErrorKind::ConnectionError("The connection to the database has been closed.".into());
+    Error::builder(kind).build()
+}
+
 #[async_trait]
 impl Queryable for Mssql {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let q = match self.url.max_interactive_rows() {
+            Some(max) => q.limit_rows(max),
+            None => q,
+        };
+
         let (sql, params) = visitor::Mssql::build(q)?;
         self.query_raw(&sql, &params[..]).await
     }
@@ -169,57 +403,50 @@ impl Queryable for Mssql {
 
     async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
         metrics::query("mssql.query_raw", sql, params, move || async move {
-            let mut client = self.client.lock().await;
-            let params = conversion::conv_params(params)?;
-            let query = client.query(sql, params.as_slice());
-
-            let results = self.timeout(query).await?;
-
-            let columns = results
-                .columns()
-                .unwrap_or(&[])
-                .iter()
-                .map(|c| c.name().to_string())
-                .collect();
-
-            let rows = results.into_first_result().await?;
-
-            let mut result = ResultSet::new(columns, Vec::new());
-
-            for row in rows {
-                let mut values: Vec<Value<'_>> = Vec::with_capacity(row.len());
+            let sql = sql.to_string();
+            let params = params.iter().cloned().map(Value::into_owned).collect();
 
-                for val in row.into_iter() {
-                    values.push(Value::try_from(val)?);
-                }
-
-                result.rows.push(values);
-            }
-
-            Ok(result)
+            self.send(|reply| Command::Query { sql, params, reply }).await
         })
         .await
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
         metrics::query("mssql.execute_raw", sql, params, move || async move {
-            let mut client = self.client.lock().await;
-            let params = conversion::conv_params(params)?;
-            let query = client.execute(sql, params.as_slice());
-
-            let changes = self.timeout(query).await?.total();
+            let sql = sql.to_string();
+            let params = params.iter().cloned().map(Value::into_owned).collect();
 
-            Ok(changes)
+            self.send(|reply| Command::Execute { sql, params, reply }).await
         })
         .await
     }
 
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        let tx = self.start_transaction().await?;
+        let rows = tx.delete_returning(q).await?;
+        tx.commit().await?;
+
+        Ok(rows)
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        let tx = self.start_transaction().await?;
+        let value = tx.increment(table, conditions, column, amount).await?;
+        tx.commit().await?;
+
+        Ok(value)
+    }
+
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
         metrics::query("mssql.raw_cmd", cmd, &[], move || async move {
-            let mut client = self.client.lock().await;
-            self.timeout(client.simple_query(cmd)).await?.into_results().await?;
-
-            Ok(())
+            let cmd = cmd.to_string();
+            self.send(|reply| Command::RawCmd { cmd, reply }).await
         })
         .await
     }
@@ -235,9 +462,42 @@ impl Queryable for Mssql {
         Ok(version_string)
     }
 
+    fn connection_info(&self) -> &ConnectionInfo {
+        &self.connection_info
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        let commands = self.commands.clone();
+        let limit = self.socket_timeout;
+
+        Some(Arc::new(move |cmd| {
+            let (reply, response) = oneshot::channel();
+            let sent = commands.unbounded_send((limit, Command::RawCmd { cmd, reply }));
+
+            Box::pin(async move {
+                match (sent, response.await) {
+                    (Ok(()), Ok(result)) => result,
+                    _ => Err(connection_closed()),
+                }
+            })
+        }))
+    }
+
     fn begin_statement(&self) -> &'static str {
         "BEGIN TRAN"
     }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        format!("SAVE TRANSACTION {}", name)
+    }
+
+    fn release_savepoint_statement(&self, _: &str) -> Option<String> {
+        None
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        format!("ROLLBACK TRANSACTION {}", name)
+    }
 }
 
 impl MssqlUrl {
@@ -284,10 +544,29 @@ impl MssqlUrl {
                 let host = url.host().map(|s| s.to_string());
                 let port = url.port();
                 let user = params.remove("user");
-                let password = params.remove("password");
+                // `password_file` as with the other databases, or camelCase
+                // as the rest of the JDBC parameters.
+                let password_file = params.remove("password_file").or_else(|| params.remove("passwordfile"));
+
+                let password = match password_file {
+                    Some(path) => Some(credentials::password_from_file(&path)?),
+                    None => match params.remove("password") {
+                        Some(password) => credentials::password_from_env(&password)?.or(Some(password)),
+                        None => None,
+                    },
+                };
                 let database = params.remove("database").unwrap_or_else(|| String::from("master"));
                 let connection_limit = params.remove("connectionlimit").and_then(|param| param.parse().ok());
 
+                let max_interactive_rows = params
+                    .remove("maxinteractiverows")
+                    .map(|param| {
+                        param
+                            .parse()
+                            .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())
+                    })
+                    .transpose()?;
+
                 let connect_timeout = params
                     .remove("logintimeout")
                     .or_else(|| params.remove("connecttimeout"))
@@ -310,6 +589,38 @@ impl MssqlUrl {
                     .and_then(|param| param.parse().ok())
                     .unwrap_or(false);
 
+                let resolve_to = params.remove("resolveto");
+
+                #[cfg(feature = "ssh")]
+                let ssh = {
+                    let mut ssh = SshParams::default();
+
+                    for (param, key) in &[
+                        ("sshhost", "ssh_host"),
+                        ("sshport", "ssh_port"),
+                        ("sshuser", "ssh_user"),
+                        ("sshkey", "ssh_key"),
+                        ("sshkeypassword", "ssh_key_password"),
+                    ] {
+                        if let Some(value) = params.remove(*param) {
+                            ssh.set(key, &value)?;
+                        }
+                    }
+
+                    ssh.build()?
+                };
+
+                #[cfg(feature = "proxy")]
+                let proxy = params.remove("proxy").map(|s| ProxyParams::parse(&s)).transpose()?;
+
+                #[cfg(all(feature = "ssh", feature = "proxy"))]
+                {
+                    if ssh.is_some() && proxy.is_some() {
+                        let kind = ErrorKind::DatabaseUrlIsInvalid("`proxy` cannot be used with an SSH tunnel.".into());
+                        return Err(Error::builder(kind).build());
+                    }
+                }
+
                 Ok(MssqlQueryParams {
                     encrypt,
                     port,
@@ -318,9 +629,15 @@ impl MssqlUrl {
                     password,
                     database,
                     trust_server_certificate,
+                    resolve_to,
+                    #[cfg(feature = "ssh")]
+                    ssh,
+                    #[cfg(feature = "proxy")]
+                    proxy,
                     connection_limit,
                     socket_timeout,
                     connect_timeout,
+                    max_interactive_rows,
                 })
             }
             _ => {
@@ -357,7 +674,7 @@ impl MssqlUrl {
 
 #[cfg(test)]
 mod tests {
-    use crate::{ast::*, error::ErrorKind, pooled, prelude::*, single, val};
+    use crate::{ast::*, connector::MssqlUrl, error::ErrorKind, pooled, prelude::*, single, val};
     use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
     use names::Generator;
     use once_cell::sync::Lazy;
@@ -399,6 +716,33 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn a_cancelled_query_leaves_the_connection_usable() -> crate::Result<()> {
+        let connection = single::Quaint::new(&CONN_STR).await?;
+
+        let slow = connection.raw_cmd("WAITFOR DELAY '00:00:01'");
+        let cancelled = tokio::time::timeout(std::time::Duration::from_millis(100), slow).await;
+        assert!(cancelled.is_err());
+
+        let res = connection.query_raw("SELECT 1", &[]).await?;
+        assert_eq!(res.get(0).unwrap()[0].as_i64(), Some(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn a_timed_out_query_closes_the_connection() -> crate::Result<()> {
+        let connection = single::Quaint::new(&format!("{};socketTimeout=1", *CONN_STR)).await?;
+
+        let err = connection.raw_cmd("WAITFOR DELAY '00:00:02'").await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Timeout(_)));
+
+        let err = connection.query_raw("SELECT 1", &[]).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::ConnectionError(_)));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn transactions() -> crate::Result<()> {
         let pool = pooled::Quaint::builder(&CONN_STR)?.build();
@@ -523,6 +867,29 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn increment_returns_the_new_value() -> crate::Result<()> {
+        let connection = single::Quaint::new(&CONN_STR).await?;
+        let table = random_table();
+
+        connection
+            .raw_cmd(&format!("CREATE TABLE {} (id int, counter int)", table))
+            .await?;
+
+        connection
+            .raw_cmd(&format!("INSERT INTO {} (id, counter) VALUES (1, 41)", table))
+            .await?;
+
+        let conditions = ConditionTree::single("id".equals(1));
+        let value = connection
+            .increment(Table::from(table.clone()), conditions, Column::from("counter"), 1)
+            .await?;
+
+        assert_eq!(Some(Value::integer(42)), value);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn in_values_singular() -> crate::Result<()> {
         let connection = single::Quaint::new(&CONN_STR).await?;
@@ -1892,4 +2259,41 @@ mod tests {
             e => panic!("Expected error TableDoesNotExist, got {:?}", e),
         }
     }
+
+    #[test]
+    #[cfg(feature = "proxy")]
+    fn should_parse_proxy() {
+        let url = "sqlserver://db.internal:1433;database=master;proxy=socks5://proxy.corp:1080";
+        let url = MssqlUrl::new(url).unwrap();
+        let proxy = url.proxy().unwrap();
+
+        assert_eq!(crate::connector::ProxyKind::Socks5, proxy.kind());
+        assert_eq!("proxy.corp", proxy.host());
+        assert_eq!(1080, proxy.port());
+    }
+
+    #[test]
+    fn should_read_the_password_from_a_file() {
+        let path = env::temp_dir().join("quaint_test_mssql_password");
+        std::fs::write(&path, "s3cr3t\n").unwrap();
+
+        for param in &["password_file", "passwordFile"] {
+            let url = format!(
+                "sqlserver://localhost:1433;database=master;{}={}",
+                param,
+                path.display()
+            );
+            let url = MssqlUrl::new(&url).unwrap();
+
+            assert_eq!(Some("s3cr3t"), url.query_params.password());
+        }
+    }
+
+    #[test]
+    fn should_not_accept_an_invalid_max_interactive_rows() {
+        let url = "sqlserver://localhost:1433;database=master;maxInteractiveRows=lots";
+        let err = MssqlUrl::new(url).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::InvalidConnectionArguments));
+    }
 }
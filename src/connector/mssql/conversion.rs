@@ -1,7 +1,4 @@
-use crate::{
-    ast::Value,
-    error::{Error, ErrorKind},
-};
+use crate::{ast::Value, error::Error};
 use rust_decimal::{prelude::FromPrimitive, Decimal};
 use std::convert::TryFrom;
 use tiberius::{ColumnData, FromSql, IntoSql, ToSql};
@@ -11,13 +8,7 @@ pub fn conv_params<'a>(params: &'a [Value<'a>]) -> crate::Result<Vec<&'a dyn ToS
 
     for param in params.iter() {
         if param.is_array() {
-            let msg = "Arrays are not supported in T-SQL.";
-            let kind = ErrorKind::conversion(msg);
-
-            let mut builder = Error::builder(kind);
-            builder.set_original_message(msg);
-
-            Err(builder.build())?
+            Err(Error::unsupported("Arrays are not supported in T-SQL."))?
         } else {
             converted.push(param as &dyn ToSql)
         }
@@ -38,6 +29,8 @@ impl<'a> ToSql for Value<'a> {
             Value::Char(val) => val.as_ref().map(|val| format!("{}", val)).into_sql(),
             #[cfg(feature = "json-1")]
             Value::Json(val) => val.as_ref().map(|val| serde_json::to_string(&val).unwrap()).into_sql(),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(val) => val.as_ref().map(crate::ast::bits_to_string).into_sql(),
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(val) => val.to_sql(),
             #[cfg(feature = "chrono-0_4")]
@@ -46,6 +39,8 @@ impl<'a> ToSql for Value<'a> {
             Value::Date(val) => val.to_sql(),
             #[cfg(feature = "chrono-0_4")]
             Value::Time(val) => val.to_sql(),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(val) => val.map(|val| crate::ast::interval_seconds(val).to_string()).into_sql(),
             p => todo!("Type {:?} is not supported", p),
         }
     }
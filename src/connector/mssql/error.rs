@@ -9,6 +9,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -18,6 +19,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -30,6 +32,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -40,6 +43,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -62,6 +66,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -74,6 +79,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -86,6 +92,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -95,6 +102,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -107,6 +115,7 @@ impl From<tiberius::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
@@ -118,6 +127,7 @@ impl From<tiberius::error::Error> for Error {
                 let mut builder = Error::builder(ErrorKind::TableDoesNotExist { table });
                 builder.set_original_code(format!("{}", e.code()));
                 builder.set_original_message(e.message().to_string());
+                builder.set_source(tiberius::error::Error::Server(e));
 
                 builder.build()
             }
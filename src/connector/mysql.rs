@@ -4,13 +4,32 @@ mod error;
 use async_trait::async_trait;
 use mysql_async::{self as my, prelude::Queryable as _, Conn};
 use percent_encoding::percent_decode;
-use std::{borrow::Cow, future::Future, path::Path, time::Duration};
+use std::{
+    borrow::Cow,
+    future::Future,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::time::timeout;
 use url::Url;
 
+#[cfg(feature = "proxy")]
+use crate::connector::proxy::{ProxyParams, ProxyTunnel};
+#[cfg(feature = "ssh")]
+use crate::connector::ssh::{SshParams, SshTunnel};
+#[cfg(any(feature = "ssh", feature = "proxy"))]
+use std::net::SocketAddr;
+
 use crate::{
-    ast::{Query, Value},
-    connector::{metrics, queryable::*, ResultSet},
+    ast::{Column, ConditionTree, Delete, Insert, Query, Table, Value},
+    connector::{
+        credentials, default_application_name, metrics, query_options, queryable::*, ConnectionInfo, DecodeOptions,
+        ExecuteResult, InvalidDates, NonFiniteFloats, ResultSet, UpsertOutcome,
+    },
     error::{Error, ErrorKind},
     visitor::{self, Visitor},
 };
@@ -20,8 +39,65 @@ use crate::{
 pub struct Mysql {
     pub(crate) pool: my::Pool,
     pub(crate) url: MysqlUrl,
+    connection_info: ConnectionInfo,
     socket_timeout: Option<Duration>,
     connect_timeout: Option<Duration>,
+    charset_verified: AtomicBool,
+    #[cfg(feature = "ssh")]
+    _tunnel: Option<SshTunnel>,
+    #[cfg(feature = "proxy")]
+    _proxy_tunnel: Option<ProxyTunnel>,
+}
+
+/// TLS settings for MySQL connections, parsed from the `sslcert`,
+/// `sslidentity`, `sslpassword` and `sslaccept` connection parameters.
+///
+/// The client certificate and its private key are given as a PKCS#12
+/// archive, optionally protected with a password.
+#[derive(Debug, Clone, Default)]
+pub struct MysqlSslParams {
+    root_cert_path: Option<PathBuf>,
+    identity_path: Option<PathBuf>,
+    identity_password: Option<String>,
+    accept_invalid_certs: bool,
+}
+
+impl MysqlSslParams {
+    /// A CA certificate in PEM format for verifying the server.
+    pub fn root_cert_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.root_cert_path = Some(path.into());
+        self
+    }
+
+    /// A PKCS#12 archive with the client certificate and key, used for
+    /// client certificate authentication.
+    pub fn identity_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.identity_path = Some(path.into());
+        self
+    }
+
+    /// The password to open the PKCS#12 archive.
+    pub fn identity_password(&mut self, password: impl Into<String>) -> &mut Self {
+        self.identity_password = Some(password.into());
+        self
+    }
+
+    /// Accept any certificate from the server. Defaults to `false`.
+    pub fn accept_invalid_certs(&mut self, accept: bool) -> &mut Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    fn to_ssl_opts(&self) -> my::SslOpts {
+        let mut ssl_opts = my::SslOpts::default();
+
+        ssl_opts.set_root_cert_path(self.root_cert_path.clone());
+        ssl_opts.set_pkcs12_path(self.identity_path.clone());
+        ssl_opts.set_password(self.identity_password.clone());
+        ssl_opts.set_danger_accept_invalid_certs(self.accept_invalid_certs);
+
+        ssl_opts
+    }
 }
 
 /// Wraps a connection url and exposes the parsing logic used by quaint, including default values.
@@ -62,6 +138,10 @@ impl MysqlUrl {
 
     /// The percent-decoded database password.
     pub fn password(&self) -> Option<Cow<str>> {
+        if let Some(ref password) = self.query_params.password {
+            return Some(Cow::from(password.as_str()));
+        }
+
         match self
             .url
             .password()
@@ -95,17 +175,164 @@ impl MysqlUrl {
         self.url.port().unwrap_or(3306)
     }
 
+    /// The address to connect to instead of resolving the host, if set.
+    ///
+    /// The driver verifies the TLS certificate against the address it
+    /// connects to, so unlike PostgreSQL, the certificate is checked against
+    /// this address, or the local end of an SSH or a proxy tunnel, instead of
+    /// the host.
+    pub fn resolve_to(&self) -> Option<&str> {
+        self.query_params.resolve_to.as_deref()
+    }
+
+    /// The SSH server to tunnel the connection through, if set.
+    #[cfg(feature = "ssh")]
+    pub fn ssh_params(&self) -> Option<&SshParams> {
+        self.query_params.ssh.as_ref()
+    }
+
+    /// The proxy to connect through, if set.
+    #[cfg(feature = "proxy")]
+    pub fn proxy(&self) -> Option<&ProxyParams> {
+        self.query_params.proxy.as_ref()
+    }
+
     pub(crate) fn connect_timeout(&self) -> Option<Duration> {
         self.query_params.connect_timeout
     }
 
+    /// Whether `DECIMAL` values and unsigned integers too large for an `i64`
+    /// are read as text, keeping all of their digits. Set with
+    /// `numeric_as_text=true`.
+    pub fn numeric_as_text(&self) -> bool {
+        self.query_params.numeric_as_text
+    }
+
+    /// What to do with `NaN` and infinite floats, which cannot be read as a
+    /// `Decimal`. Set with `non_finite_floats=error|null`, defaults to `error`.
+    pub fn non_finite_floats(&self) -> NonFiniteFloats {
+        self.query_params.non_finite_floats
+    }
+
+    /// The name of the application using the connection. Defaults to the name
+    /// of the running binary.
+    ///
+    /// The MySQL driver does not send connection attributes, so the name is
+    /// not visible to the server as the `program_name` attribute.
+    pub fn application_name(&self) -> &str {
+        &self.query_params.application_name
+    }
+
+    /// The character set of the connection, e.g. `utf8mb4`. Set with
+    /// `charset=...`, defaults to the server default.
+    pub fn charset(&self) -> Option<&str> {
+        self.query_params.charset.as_deref()
+    }
+
+    /// The collation of the connection, e.g. `utf8mb4_unicode_ci`. Set with
+    /// `collation=...`, defaults to the default collation of the character
+    /// set.
+    pub fn collation(&self) -> Option<&str> {
+        self.query_params.collation.as_deref()
+    }
+
+    /// The statement setting the character set and collation of a new
+    /// connection, if either of them is set.
+    fn set_names(&self) -> Option<String> {
+        match (self.charset(), self.collation()) {
+            (Some(charset), Some(collation)) => Some(format!("SET NAMES {} COLLATE {}", charset, collation)),
+            (Some(charset), None) => Some(format!("SET NAMES {}", charset)),
+            (None, Some(collation)) => Some(format!("SET collation_connection = {}", collation)),
+            (None, None) => None,
+        }
+    }
+
+    /// What to do with the zero dates and other dates that do not exist. Set
+    /// with `invalid_dates=error|null|min_date`, defaults to `error`.
+    pub fn invalid_dates(&self) -> InvalidDates {
+        self.query_params.invalid_dates
+    }
+
+    /// The number of threads decoding the rows of a large result. Set with
+    /// `decode_threads=n`, defaults to 1, decoding on the querying task.
+    pub fn decode_threads(&self) -> usize {
+        self.query_params.decode_threads
+    }
+
+    /// Whether the tables without a database are prefixed with the database
+    /// of the URL in the generated SQL. Set with `qualify_tables=true`.
+    pub fn qualify_tables(&self) -> bool {
+        self.query_params.qualify_tables
+    }
+
+    /// The number of rows a `SELECT` without a `LIMIT` is limited to, for
+    /// interactive tools. Set with `max_interactive_rows=n`, defaults to no
+    /// limit. See [Select::unlimited](../ast/struct.Select.html#method.unlimited)
+    /// for opting out.
+    pub fn max_interactive_rows(&self) -> Option<usize> {
+        self.query_params.max_interactive_rows
+    }
+
+    pub(crate) fn decode_options(&self) -> DecodeOptions {
+        DecodeOptions {
+            numeric_as_text: self.numeric_as_text(),
+            non_finite_floats: self.non_finite_floats(),
+            invalid_dates: self.invalid_dates(),
+            decode_threads: self.decode_threads(),
+        }
+    }
+
+    /// Overrides the password from the URL.
+    #[cfg(feature = "pooled")]
+    pub(crate) fn set_password(&mut self, password: String) {
+        self.query_params.password = Some(password);
+    }
+
+    /// The TLS settings, if any of the SSL parameters were given or set with
+    /// [`set_ssl_params`](#method.set_ssl_params).
+    pub fn ssl_params(&self) -> Option<&MysqlSslParams> {
+        self.query_params.ssl_params.as_ref()
+    }
+
+    /// Replaces the TLS settings of the connections opened from this url. To
+    /// rotate the certificates of a running pool, use
+    /// [`pooled::Quaint::set_ssl_params`](../pooled/struct.Quaint.html#method.set_ssl_params).
+    pub fn set_ssl_params(&mut self, ssl_params: MysqlSslParams) {
+        self.query_params.ssl_params = Some(ssl_params);
+    }
+
+    /// Character set and collation names are written to the `SET NAMES`
+    /// statement as-is, so only letters, digits and underscores are allowed.
+    fn charset_name(name: &str) -> crate::Result<String> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(Error::builder(ErrorKind::InvalidConnectionArguments).build());
+        }
+
+        Ok(name.to_string())
+    }
+
     fn parse_query_params(url: &Url) -> Result<MysqlUrlQueryParams, Error> {
         let mut connection_limit = None;
-        let mut ssl_opts = my::SslOpts::default();
+        let mut ssl_params = MysqlSslParams::default();
         let mut use_ssl = false;
         let mut socket = None;
         let mut socket_timeout = None;
         let mut connect_timeout = None;
+        let mut password = None;
+        let mut resolve_to = None;
+        let mut numeric_as_text = false;
+        let mut decode_threads = 1;
+        let mut qualify_tables = false;
+        let mut max_interactive_rows = None;
+        let mut non_finite_floats = NonFiniteFloats::default();
+        let mut invalid_dates = InvalidDates::default();
+        let mut charset = None;
+        let mut application_name = None;
+        let mut collation = None;
+        #[cfg(feature = "ssh")]
+        let mut ssh = SshParams::default();
+        #[cfg(feature = "proxy")]
+        let mut proxy = None;
 
         for (k, v) in url.query_pairs() {
             match k.as_ref() {
@@ -116,17 +343,68 @@ impl MysqlUrl {
 
                     connection_limit = Some(as_int);
                 }
+                "decode_threads" => {
+                    decode_threads = v
+                        .parse()
+                        .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+                }
+                "qualify_tables" => {
+                    qualify_tables = v
+                        .parse()
+                        .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+                }
+                "max_interactive_rows" => {
+                    let as_int = v
+                        .parse()
+                        .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+
+                    max_interactive_rows = Some(as_int);
+                }
+                "numeric_as_text" => {
+                    numeric_as_text = v
+                        .parse()
+                        .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+                }
+                "non_finite_floats" => {
+                    non_finite_floats = v.parse()?;
+                }
+                "invalid_dates" => {
+                    invalid_dates = v.parse()?;
+                }
+                "application_name" => {
+                    application_name = Some(v.to_string());
+                }
+                "charset" => {
+                    charset = Some(Self::charset_name(&v)?);
+                }
+                "collation" => {
+                    collation = Some(Self::charset_name(&v)?);
+                }
                 "sslcert" => {
                     use_ssl = true;
-                    ssl_opts.set_root_cert_path(Some(Path::new(&*v).to_path_buf()));
+                    ssl_params.root_cert_path(&*v);
                 }
                 "sslidentity" => {
                     use_ssl = true;
-                    ssl_opts.set_pkcs12_path(Some(Path::new(&*v).to_path_buf()));
+                    ssl_params.identity_path(&*v);
                 }
                 "sslpassword" => {
                     use_ssl = true;
-                    ssl_opts.set_password(Some(v.to_string()));
+                    ssl_params.identity_password(v);
+                }
+                "resolve_to" => {
+                    resolve_to = Some(v.to_string());
+                }
+                #[cfg(feature = "ssh")]
+                "ssh_host" | "ssh_port" | "ssh_user" | "ssh_key" | "ssh_key_password" => {
+                    ssh.set(&k, &v)?;
+                }
+                #[cfg(feature = "proxy")]
+                "proxy" => {
+                    proxy = Some(ProxyParams::parse(&v)?);
+                }
+                "password_file" => {
+                    password = Some(credentials::password_from_file(&v)?);
                 }
                 "socket" => {
                     socket = Some(v.replace("(", "").replace(")", ""));
@@ -147,7 +425,7 @@ impl MysqlUrl {
                     match v.as_ref() {
                         "strict" => {}
                         "accept_invalid_certs" => {
-                            ssl_opts.set_danger_accept_invalid_certs(true);
+                            ssl_params.accept_invalid_certs(true);
                         }
                         _ => {
                             #[cfg(not(feature = "tracing-log"))]
@@ -169,13 +447,49 @@ impl MysqlUrl {
             };
         }
 
+        if password.is_none() {
+            if let Some(url_password) = url.password() {
+                let url_password = percent_decode(url_password.as_bytes()).decode_utf8_lossy();
+                password = credentials::password_from_env(&url_password)?;
+            }
+        }
+
+        #[cfg(feature = "ssh")]
+        let ssh = ssh.build()?;
+
+        #[cfg(all(feature = "ssh", feature = "proxy"))]
+        {
+            if ssh.is_some() && proxy.is_some() {
+                let kind = ErrorKind::DatabaseUrlIsInvalid("`proxy` cannot be used with an SSH tunnel.".into());
+                return Err(Error::builder(kind).build());
+            }
+        }
+
+        let ssl_params = if use_ssl { Some(ssl_params) } else { None };
+
         Ok(MysqlUrlQueryParams {
-            ssl_opts,
+            ssl_params,
             connection_limit,
-            use_ssl,
             socket,
             connect_timeout,
             socket_timeout,
+            password,
+            resolve_to,
+            numeric_as_text,
+            decode_threads,
+            qualify_tables,
+            max_interactive_rows,
+            non_finite_floats,
+            invalid_dates,
+            charset,
+            collation,
+            application_name: application_name.unwrap_or_else(default_application_name),
+            #[cfg(feature = "ssh")]
+            ssh,
+            #[cfg(feature = "proxy")]
+            proxy,
+            #[cfg(any(feature = "ssh", feature = "proxy"))]
+            tunnel_addr: None,
         })
     }
 
@@ -184,6 +498,22 @@ impl MysqlUrl {
         self.query_params.connection_limit
     }
 
+    /// Where the TCP connection is opened: the local end of an SSH or a proxy
+    /// tunnel, the `resolve_to` address or the host from the URL. Also the
+    /// name the TLS certificate is verified against, mysql_async has no
+    /// separate setting for it.
+    fn connect_address(&self) -> (String, u16) {
+        #[cfg(any(feature = "ssh", feature = "proxy"))]
+        {
+            if let Some(addr) = self.query_params.tunnel_addr {
+                return (addr.ip().to_string(), addr.port());
+            }
+        }
+
+        let host = self.resolve_to().unwrap_or_else(|| self.host());
+        (host.to_string(), self.port())
+    }
+
     pub(crate) fn to_opts_builder(&self) -> my::OptsBuilder {
         let mut config = my::OptsBuilder::new();
 
@@ -196,16 +526,22 @@ impl MysqlUrl {
                 config.socket(Some(socket));
             }
             None => {
-                config.ip_or_hostname(self.host());
-                config.tcp_port(self.port());
+                let (host, port) = self.connect_address();
+
+                config.ip_or_hostname(host);
+                config.tcp_port(port);
             }
         }
 
+        if let Some(set_names) = self.set_names() {
+            config.init(vec![set_names]);
+        }
+
         config.stmt_cache_size(Some(1000));
         config.conn_ttl(Some(Duration::from_secs(5)));
 
-        if self.query_params.use_ssl {
-            config.ssl_opts(Some(self.query_params.ssl_opts.clone()));
+        if let Some(ssl_params) = self.ssl_params() {
+            config.ssl_opts(Some(ssl_params.to_ssl_opts()));
         }
 
         config
@@ -214,17 +550,39 @@ impl MysqlUrl {
 
 #[derive(Debug, Clone)]
 pub(crate) struct MysqlUrlQueryParams {
-    ssl_opts: my::SslOpts,
+    ssl_params: Option<MysqlSslParams>,
     connection_limit: Option<usize>,
-    use_ssl: bool,
     socket: Option<String>,
     socket_timeout: Option<Duration>,
     connect_timeout: Option<Duration>,
+    password: Option<String>,
+    resolve_to: Option<String>,
+    numeric_as_text: bool,
+    decode_threads: usize,
+    qualify_tables: bool,
+    max_interactive_rows: Option<usize>,
+    non_finite_floats: NonFiniteFloats,
+    invalid_dates: InvalidDates,
+    charset: Option<String>,
+    collation: Option<String>,
+    application_name: String,
+    #[cfg(feature = "ssh")]
+    ssh: Option<SshParams>,
+    #[cfg(feature = "proxy")]
+    proxy: Option<ProxyParams>,
+    #[cfg(any(feature = "ssh", feature = "proxy"))]
+    tunnel_addr: Option<SocketAddr>,
 }
 
 impl Mysql {
     /// Create a new MySQL connection using `OptsBuilder` from the `mysql` crate.
     pub fn new(url: MysqlUrl) -> crate::Result<Self> {
+        #[cfg(feature = "ssh")]
+        let (url, tunnel) = Self::open_tunnel(url)?;
+
+        #[cfg(feature = "proxy")]
+        let (url, proxy_tunnel) = Self::open_proxy_tunnel(url)?;
+
         let mut opts = url.to_opts_builder();
         let pool_opts = my::PoolOptions::with_constraints(my::PoolConstraints::new(1, 1).unwrap());
         opts.pool_options(pool_opts);
@@ -232,17 +590,62 @@ impl Mysql {
         Ok(Self {
             socket_timeout: url.query_params.socket_timeout,
             connect_timeout: url.query_params.connect_timeout,
+            charset_verified: AtomicBool::new(false),
             pool: my::Pool::new(opts),
+            connection_info: ConnectionInfo::Mysql(url.clone()),
             url,
+            #[cfg(feature = "ssh")]
+            _tunnel: tunnel,
+            #[cfg(feature = "proxy")]
+            _proxy_tunnel: proxy_tunnel,
         })
     }
 
+    #[cfg(feature = "ssh")]
+    fn open_tunnel(mut url: MysqlUrl) -> crate::Result<(MysqlUrl, Option<SshTunnel>)> {
+        match url.ssh_params().cloned() {
+            Some(params) => {
+                let target = url.resolve_to().unwrap_or_else(|| url.host()).to_string();
+                let tunnel = SshTunnel::new(params, &target, url.port())?;
+
+                url.query_params.tunnel_addr = Some(tunnel.local_addr());
+
+                Ok((url, Some(tunnel)))
+            }
+            None => Ok((url, None)),
+        }
+    }
+
+    #[cfg(feature = "proxy")]
+    fn open_proxy_tunnel(mut url: MysqlUrl) -> crate::Result<(MysqlUrl, Option<ProxyTunnel>)> {
+        match url.proxy().cloned() {
+            Some(params) => {
+                let target = url.resolve_to().unwrap_or_else(|| url.host()).to_string();
+                let tunnel = ProxyTunnel::new(params, &target, url.port())?;
+
+                url.query_params.tunnel_addr = Some(tunnel.local_addr());
+
+                Ok((url, Some(tunnel)))
+            }
+            None => Ok((url, None)),
+        }
+    }
+
+    /// Prefixes the unqualified tables with the database, if enabled with
+    /// `qualify_tables=true`.
+    fn qualified<'a>(&self, q: Query<'a>) -> Query<'a> {
+        match self.url.dbname() {
+            dbname if self.url.qualify_tables() && !dbname.is_empty() => q.qualify_tables(dbname.to_string()),
+            _ => q,
+        }
+    }
+
     async fn timeout<T, F, E>(&self, f: F) -> crate::Result<T>
     where
         F: Future<Output = std::result::Result<T, E>>,
         E: Into<Error>,
     {
-        match self.socket_timeout {
+        match query_options::socket_timeout(self.socket_timeout) {
             Some(duration) => match timeout(duration, f).await {
                 Ok(Ok(result)) => Ok(result),
                 Ok(Err(err)) => Err(err.into()),
@@ -256,10 +659,48 @@ impl Mysql {
     }
 
     async fn get_conn(&self) -> crate::Result<Conn> {
-        match self.connect_timeout {
-            Some(duration) => Ok(timeout(duration, self.pool.get_conn()).await??),
-            None => Ok(self.pool.get_conn().await?),
+        let conn = match self.connect_timeout {
+            Some(duration) => timeout(duration, self.pool.get_conn()).await??,
+            None => self.pool.get_conn().await?,
+        };
+
+        if self.charset_verified.load(Ordering::Relaxed) {
+            return Ok(conn);
         }
+
+        let conn = self.verify_charset(conn).await?;
+        self.charset_verified.store(true, Ordering::Relaxed);
+
+        Ok(conn)
+    }
+
+    /// Checks the first connection uses the character set and collation from
+    /// the URL. Every connection runs the same `SET NAMES`, so checking one of
+    /// them is enough.
+    async fn verify_charset(&self, conn: Conn) -> crate::Result<Conn> {
+        if self.url.charset().is_none() && self.url.collation().is_none() {
+            return Ok(conn);
+        }
+
+        let query = "SELECT @@character_set_connection, @@collation_connection";
+        let (conn, row): (Conn, Option<(String, String)>) = self.timeout(conn.first(query)).await?;
+        let (charset, collation) = row.unwrap_or_default();
+
+        let matches = |expected: Option<&str>, actual: &str| expected.map_or(true, |e| e.eq_ignore_ascii_case(actual));
+
+        if matches(self.url.charset(), &charset) && matches(self.url.collation(), &collation) {
+            return Ok(conn);
+        }
+
+        let msg = format!(
+            "The connection uses the character set `{}` and the collation `{}`, which do not match the connection string.",
+            charset, collation
+        );
+
+        let mut builder = Error::builder(ErrorKind::ConnectionError(msg.clone().into()));
+        builder.set_original_message(msg);
+
+        Err(builder.build())
     }
 }
 
@@ -268,12 +709,17 @@ impl TransactionCapable for Mysql {}
 #[async_trait]
 impl Queryable for Mysql {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
-        let (sql, params) = visitor::Mysql::build(q)?;
+        let q = match self.url.max_interactive_rows() {
+            Some(max) => q.limit_rows(max),
+            None => q,
+        };
+
+        let (sql, params) = visitor::Mysql::build(self.qualified(q))?;
         self.query_raw(&sql, &params).await
     }
 
     async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
-        let (sql, params) = visitor::Mysql::build(q)?;
+        let (sql, params) = visitor::Mysql::build(self.qualified(q))?;
         self.execute_raw(&sql, &params).await
     }
 
@@ -291,13 +737,15 @@ impl Queryable for Mysql {
                 .collect();
 
             let last_id = results.last_insert_id();
-            let mut result_set = ResultSet::new(columns, Vec::new());
+            let (_, rows) = self.timeout(results.collect::<my::Row>()).await?;
 
-            let (_, rows) = self.timeout(results.map(|mut row| row.take_result_row())).await?;
+            let rows = self
+                .url
+                .decode_options()
+                .decode_rows(rows, |mut row, options| conversion::take_result_row(&mut row, options))
+                .await?;
 
-            for row in rows.into_iter() {
-                result_set.rows.push(row?);
-            }
+            let mut result_set = ResultSet::new(columns, rows);
 
             if let Some(id) = last_id {
                 result_set.set_last_insert_id(id);
@@ -309,16 +757,68 @@ impl Queryable for Mysql {
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        Ok(self.execute_raw_result(sql, params).await?.rows_affected())
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        let (sql, params) = visitor::Mysql::build(self.qualified(q))?;
+        self.execute_raw_result(&sql, &params).await
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
         metrics::query("mysql.execute_raw", sql, params, move || async move {
             let conn = self.get_conn().await?;
             let results = self
                 .timeout(conn.prep_exec(sql, conversion::conv_params(params)?))
                 .await?;
-            Ok(results.affected_rows())
+
+            Ok(ExecuteResult::new(results.affected_rows(), results.last_insert_id()))
         })
         .await
     }
 
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        let tx = self.start_transaction().await?;
+        let rows = tx.delete_returning(q).await?;
+        tx.commit().await?;
+
+        Ok(rows)
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        let tx = self.start_transaction().await?;
+        let value = tx.increment(table, conditions, column, amount).await?;
+        tx.commit().await?;
+
+        Ok(value)
+    }
+
+    async fn upsert(&self, q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        q.ensure_upsert()?;
+
+        let mut outcomes = Vec::new();
+
+        // MySQL counts an inserted row once and an updated row twice, so the
+        // rows are written one at a time to tell them apart.
+        for insert in q.into_rows() {
+            let outcome = match self.execute_result(insert.into()).await?.rows_affected() {
+                0 => UpsertOutcome::Unchanged,
+                1 => UpsertOutcome::Inserted,
+                _ => UpsertOutcome::Updated,
+            };
+
+            outcomes.push(outcome);
+        }
+
+        Ok(outcomes)
+    }
+
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
         metrics::query("mysql.raw_cmd", cmd, &[], move || async move {
             let conn = self.get_conn().await?;
@@ -339,13 +839,40 @@ impl Queryable for Mysql {
 
         Ok(version_string)
     }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        &self.connection_info
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        let pool = self.pool.clone();
+
+        Some(Arc::new(move |cmd| {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                let conn = pool.get_conn().await?;
+                conn.query(cmd).await?;
+
+                Ok(())
+            })
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MysqlUrl;
-    use crate::{ast::*, col, connector::Queryable, error::*, single::Quaint, val, values};
+    use super::{MysqlSslParams, MysqlUrl};
+    use crate::{
+        ast::*,
+        col,
+        connector::{Queryable, TransactionCapable},
+        error::*,
+        single::Quaint,
+        val, values,
+    };
     use chrono::Utc;
+    use mysql_async as my;
     use once_cell::sync::Lazy;
     use std::env;
     use url::Url;
@@ -359,6 +886,51 @@ mod tests {
         assert_eq!(&Some(String::from("/tmp/mysql.sock")), url.socket());
     }
 
+    #[test]
+    fn should_parse_ssl_params() {
+        let url = Url::parse("mysql://root@localhost/dbname?sslidentity=client.p12&sslpassword=secret").unwrap();
+        let url = MysqlUrl::new(url).unwrap();
+        let ssl_params = url.ssl_params().unwrap();
+
+        assert_eq!(
+            Some(std::path::Path::new("client.p12")),
+            ssl_params.identity_path.as_deref()
+        );
+        assert_eq!(Some("secret"), ssl_params.identity_password.as_deref());
+        assert!(!ssl_params.accept_invalid_certs);
+    }
+
+    #[test]
+    fn should_connect_with_the_new_ssl_params() {
+        let mut url = MysqlUrl::new(Url::parse("mysql://root@localhost/dbname").unwrap()).unwrap();
+
+        let mut ssl_params = MysqlSslParams::default();
+        ssl_params.identity_path("rotated.p12");
+        url.set_ssl_params(ssl_params);
+
+        let opts = my::Opts::from(url.to_opts_builder());
+        let ssl_opts = opts.get_ssl_opts().unwrap();
+
+        assert_eq!(Some(std::path::Path::new("rotated.p12")), ssl_opts.pkcs12_path());
+    }
+
+    #[test]
+    fn should_read_the_password_from_a_file() {
+        let path = env::temp_dir().join("quaint_test_mysql_password");
+        std::fs::write(&path, "s3cr3t\n").unwrap();
+
+        let url = format!("mysql://root@localhost/dbname?password_file={}", path.display());
+        let url = MysqlUrl::new(Url::parse(&url).unwrap()).unwrap();
+
+        assert_eq!(Some("s3cr3t"), url.password().as_deref());
+    }
+
+    #[test]
+    fn should_not_use_ssl_without_ssl_params() {
+        let url = MysqlUrl::new(Url::parse("mysql://root@localhost/dbname").unwrap()).unwrap();
+        assert!(url.ssl_params().is_none());
+    }
+
     #[tokio::test]
     async fn should_provide_a_database_connection() {
         let connection = Quaint::new(&CONN_STR).await.unwrap();
@@ -489,6 +1061,41 @@ VALUES (1, 'Joe', 27, 20000.00 );
         );
     }
 
+    #[tokio::test]
+    async fn increment_returns_the_new_value() {
+        let connection = Quaint::new(&CONN_STR).await.unwrap();
+
+        connection.raw_cmd("DROP TABLE IF EXISTS test_increment").await.unwrap();
+        connection
+            .raw_cmd("CREATE TABLE test_increment (id int, counter int)")
+            .await
+            .unwrap();
+        connection
+            .raw_cmd("INSERT INTO test_increment (id, counter) VALUES (1, 41)")
+            .await
+            .unwrap();
+
+        let increment = || {
+            let conditions = ConditionTree::single("id".equals(1));
+            (Table::from("test_increment"), conditions, Column::from("counter"))
+        };
+
+        let (table, conditions, column) = increment();
+        let value = connection.increment(table, conditions, column, 1).await.unwrap();
+        assert_eq!(Some(Value::integer(42)), value);
+
+        // Run in the transaction, not in one of its own.
+        let tx = connection.start_transaction().await.unwrap();
+        let (table, conditions, column) = increment();
+        let value = tx.increment(table, conditions, column, 1).await.unwrap();
+        tx.rollback().await.unwrap();
+
+        assert_eq!(Some(Value::integer(43)), value);
+
+        let rows = connection.select(Select::from_table("test_increment")).await.unwrap();
+        assert_eq!(Some(42), rows.get(0).unwrap()["counter"].as_i64());
+    }
+
     #[tokio::test]
     async fn test_mysql_time() {
         let connection = Quaint::new(&CONN_STR).await.unwrap();
@@ -919,6 +1526,66 @@ VALUES (1, 'Joe', 27, 20000.00 );
         );
     }
 
+    #[tokio::test]
+    async fn numeric_as_text_keeps_all_digits() {
+        let mut url = Url::parse(&CONN_STR).unwrap();
+        url.query_pairs_mut().append_pair("numeric_as_text", "true");
+
+        let conn = Quaint::new(url.as_str()).await.unwrap();
+
+        let query = "SELECT CAST(18446744073709551615 AS UNSIGNED) AS a, CAST('12345678901234567890123456789.12345' AS DECIMAL(65, 5)) AS b";
+        let result = conn.query_raw(query, &[]).await.unwrap();
+        let row = result.first().unwrap();
+
+        assert_eq!(Some("18446744073709551615"), row["a"].as_str());
+        assert_eq!(Some("12345678901234567890123456789.12345"), row["b"].as_str());
+    }
+
+    #[test]
+    fn should_not_allow_sql_in_the_charset() {
+        let url = Url::parse("mysql://root@localhost/dbname?charset=utf8mb4;DROP").unwrap();
+        assert!(MysqlUrl::new(url).is_err());
+    }
+
+    #[tokio::test]
+    async fn charset_and_collation_are_set_on_connect() {
+        let mut url = Url::parse(&CONN_STR).unwrap();
+        url.query_pairs_mut()
+            .append_pair("charset", "utf8mb4")
+            .append_pair("collation", "utf8mb4_unicode_ci");
+
+        let conn = Quaint::new(url.as_str()).await.unwrap();
+
+        let query = "SELECT @@character_set_connection AS charset, @@collation_connection AS collation, ? AS emoji";
+        let result = conn.query_raw(query, &[Value::from("🐈")]).await.unwrap();
+        let row = result.first().unwrap();
+
+        assert_eq!(Some("utf8mb4"), row["charset"].as_str());
+        assert_eq!(Some("utf8mb4_unicode_ci"), row["collation"].as_str());
+        assert_eq!(Some("🐈"), row["emoji"].as_str());
+    }
+
+    #[tokio::test]
+    async fn zero_dates_follow_the_invalid_dates_policy() {
+        let mut url = Url::parse(&CONN_STR).unwrap();
+        url.query_pairs_mut().append_pair("invalid_dates", "null");
+
+        let conn = Quaint::new(url.as_str()).await.unwrap();
+
+        conn.raw_cmd("SET SESSION sql_mode = ''").await.unwrap();
+        conn.raw_cmd("CREATE TEMPORARY TABLE zero_dates (created_at DATETIME)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO zero_dates (created_at) VALUES ('0000-00-00 00:00:00')")
+            .await
+            .unwrap();
+
+        let result = conn.query_raw("SELECT created_at FROM zero_dates", &[]).await.unwrap();
+        let row = result.first().unwrap();
+
+        assert_eq!(Value::DateTime(None), row["created_at"]);
+    }
+
     #[tokio::test]
     async fn json_filtering_works() {
         let conn = Quaint::new(&CONN_STR).await.unwrap();
@@ -1,6 +1,6 @@
 use crate::{
     ast::Value,
-    connector::{queryable::TakeRow, TypeIdentifier},
+    connector::{queryable::TakeRow, DecodeOptions, TypeIdentifier},
     error::{Error, ErrorKind},
 };
 #[cfg(feature = "chrono-0_4")]
@@ -12,6 +12,9 @@ use mysql_async::{
 use rust_decimal::prelude::ToPrimitive;
 use std::convert::TryFrom;
 
+/// Converts the parameters for the driver, which takes them as owned values.
+/// Text and bytes are copied once into the driver buffers, straight from the
+/// borrowed data.
 pub fn conv_params<'a>(params: &[Value<'a>]) -> crate::Result<my::Params> {
     if params.is_empty() {
         // If we don't use explicit 'Empty',
@@ -36,9 +39,9 @@ pub fn conv_params<'a>(params: &[Value<'a>]) -> crate::Result<my::Params> {
                     }
                     None => None,
                 },
-                Value::Text(s) => s.clone().map(|s| my::Value::Bytes((&*s).as_bytes().to_vec())),
-                Value::Bytes(bytes) => bytes.clone().map(|bytes| my::Value::Bytes(bytes.into_owned())),
-                Value::Enum(s) => s.clone().map(|s| my::Value::Bytes((&*s).as_bytes().to_vec())),
+                Value::Text(s) => s.as_ref().map(|s| my::Value::Bytes(s.as_bytes().to_vec())),
+                Value::Bytes(bytes) => bytes.as_ref().map(|bytes| my::Value::Bytes(bytes.to_vec())),
+                Value::Enum(s) => s.as_ref().map(|s| my::Value::Bytes(s.as_bytes().to_vec())),
                 Value::Boolean(b) => b.map(|b| my::Value::Int(b as i64)),
                 Value::Char(c) => c.map(|c| my::Value::Bytes(vec![c as u8])),
                 #[cfg(feature = "json-1")]
@@ -52,15 +55,19 @@ pub fn conv_params<'a>(params: &[Value<'a>]) -> crate::Result<my::Params> {
                     None => None,
                 },
                 #[cfg(feature = "array")]
-                Value::Array(_) => {
-                    let msg = "Arrays are not supported in MySQL.";
-                    let kind = ErrorKind::conversion(msg);
-
-                    let mut builder = Error::builder(kind);
-                    builder.set_original_message(msg);
-
-                    Err(builder.build())?
-                }
+                Value::Array(_) => Err(Error::unsupported("Arrays are not supported in MySQL."))?,
+                #[cfg(feature = "bit-vec")]
+                Value::Bits(bits) => match bits {
+                    // A `BIT(n)` column takes the bits as a number.
+                    Some(bits) if bits.len() <= 64 => {
+                        let number = bits.iter().fold(0u64, |acc, bit| (acc << 1) | bit as u64);
+                        Some(my::Value::UInt(number))
+                    }
+                    Some(_) => Err(Error::unsupported(
+                        "Bit strings longer than 64 bits are not supported in MySQL.",
+                    ))?,
+                    None => None,
+                },
                 #[cfg(feature = "uuid-0_8")]
                 Value::Uuid(u) => u.map(|u| my::Value::Bytes(u.to_hyphenated().to_string().into_bytes())),
                 #[cfg(feature = "chrono-0_4")]
@@ -83,6 +90,10 @@ pub fn conv_params<'a>(params: &[Value<'a>]) -> crate::Result<my::Params> {
                         dt.timestamp_subsec_micros(),
                     )
                 }),
+                #[cfg(feature = "chrono-0_4")]
+                Value::Interval(i) => {
+                    i.map(|i| my::Value::Bytes(crate::ast::interval_seconds(i).to_string().into_bytes()))
+                }
             };
 
             match res {
@@ -183,117 +194,124 @@ impl TypeIdentifier for my::Column {
 
 impl TakeRow for my::Row {
     fn take_result_row(&mut self) -> crate::Result<Vec<Value<'static>>> {
-        fn convert(row: &mut my::Row, i: usize) -> crate::Result<Value<'static>> {
-            let value = row.take(i).ok_or_else(|| {
-                let msg = "Index out of bounds";
-                let kind = ErrorKind::conversion(msg);
+        take_result_row(self, DecodeOptions::default())
+    }
+}
 
-                Error::builder(kind).build()
-            })?;
+/// Reads the values of the row. If `numeric_as_text` is set, `DECIMAL`
+/// values and unsigned integers too large for an `i64` are read as text.
+/// Non-finite floats are read following the `non_finite_floats` policy.
+pub(crate) fn take_result_row(row: &mut my::Row, options: DecodeOptions) -> crate::Result<Vec<Value<'static>>> {
+    fn convert(row: &mut my::Row, i: usize, options: DecodeOptions) -> crate::Result<Value<'static>> {
+        let value = row.take(i).ok_or_else(|| {
+            let msg = "Index out of bounds";
+            let kind = ErrorKind::conversion(msg);
+
+            Error::builder(kind).build()
+        })?;
+
+        let column = row.columns_ref().get(i).ok_or_else(|| {
+            let msg = "Index out of bounds";
+            let kind = ErrorKind::conversion(msg);
+
+            Error::builder(kind).build()
+        })?;
+
+        let res = match value {
+            // JSON is returned as bytes.
+            #[cfg(feature = "json-1")]
+            my::Value::Bytes(b) if column.is_json() => {
+                serde_json::from_slice(&b).map(|val| Value::json(val)).map_err(|_| {
+                    let msg = "Unable to convert bytes to JSON";
+                    let kind = ErrorKind::conversion(msg);
 
-            let column = row.columns_ref().get(i).ok_or_else(|| {
-                let msg = "Index out of bounds";
-                let kind = ErrorKind::conversion(msg);
+                    Error::builder(kind).build()
+                })?
+            }
+            my::Value::Bytes(b) if column.is_enum() => {
+                let s = String::from_utf8(b)?;
+                Value::enum_variant(s)
+            }
+            // NEWDECIMAL returned as bytes. See https://mariadb.com/kb/en/resultset-row/#decimal-binary-encoding
+            my::Value::Bytes(b) if column.is_real() && options.numeric_as_text => Value::text(String::from_utf8(b)?),
+            my::Value::Bytes(b) if column.is_real() => {
+                let s = String::from_utf8(b).map_err(|_| {
+                    let msg = "Could not convert NEWDECIMAL from bytes to String.";
+                    let kind = ErrorKind::conversion(msg);
 
-                Error::builder(kind).build()
-            })?;
+                    Error::builder(kind).build()
+                })?;
 
-            let res = match value {
-                // JSON is returned as bytes.
-                #[cfg(feature = "json-1")]
-                my::Value::Bytes(b) if column.is_json() => {
-                    serde_json::from_slice(&b).map(|val| Value::json(val)).map_err(|_| {
-                        let msg = "Unable to convert bytes to JSON";
-                        let kind = ErrorKind::conversion(msg);
+                let dec = s.parse().map_err(|_| {
+                    let msg = "Could not convert NEWDECIMAL string to a Decimal.";
+                    let kind = ErrorKind::conversion(msg);
 
-                        Error::builder(kind).build()
-                    })?
-                }
-                my::Value::Bytes(b) if column.is_enum() => {
-                    let s = String::from_utf8(b)?;
-                    Value::enum_variant(s)
-                }
-                // NEWDECIMAL returned as bytes. See https://mariadb.com/kb/en/resultset-row/#decimal-binary-encoding
-                my::Value::Bytes(b) if column.is_real() => {
-                    let s = String::from_utf8(b).map_err(|_| {
-                        let msg = "Could not convert NEWDECIMAL from bytes to String.";
-                        let kind = ErrorKind::conversion(msg);
+                    Error::builder(kind).build()
+                })?;
 
-                        Error::builder(kind).build()
-                    })?;
+                Value::real(dec)
+            }
+            // https://dev.mysql.com/doc/internals/en/character-set.html
+            my::Value::Bytes(b) if column.character_set() == 63 => Value::bytes(b),
+            my::Value::Bytes(s) => Value::text(String::from_utf8(s)?),
+            my::Value::Int(i) => Value::integer(i),
+            my::Value::UInt(i) if options.numeric_as_text && i64::try_from(i).is_err() => Value::text(i.to_string()),
+            my::Value::UInt(i) => Value::integer(i64::try_from(i).map_err(|_| {
+                let msg = "Unsigned integers larger than 9_223_372_036_854_775_807 are currently not handled.";
+                let kind = ErrorKind::value_out_of_range(msg);
 
-                    let dec = s.parse().map_err(|_| {
-                        let msg = "Could not convert NEWDECIMAL string to a Decimal.";
-                        let kind = ErrorKind::conversion(msg);
+                Error::builder(kind).build()
+            })?),
+            my::Value::Float(f) => Value::Real(options.f32(&column.name_str(), f)?),
+            my::Value::Double(f) => Value::Real(options.f64(&column.name_str(), f)?),
+            #[cfg(feature = "chrono-0_4")]
+            my::Value::Date(year, month, day, hour, min, sec, micro) => {
+                let time = NaiveTime::from_hms_micro_opt(hour.into(), min.into(), sec.into(), micro);
+                let date = NaiveDate::from_ymd_opt(year.into(), month.into(), day.into());
+
+                let dt = match (date, time) {
+                    (Some(date), Some(time)) => Some(NaiveDateTime::new(date, time)),
+                    _ => {
+                        // Zero dates, such as `0000-00-00 00:00:00`, are stored by MySQL but do not exist.
+                        let value = format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, hour, min, sec);
+
+                        options.invalid_date(&column.name_str(), &value)?
+                    }
+                };
 
-                        Error::builder(kind).build()
-                    })?;
+                Value::DateTime(dt.map(|dt| DateTime::<Utc>::from_utc(dt, Utc)))
+            }
+            #[cfg(feature = "chrono-0_4")]
+            my::Value::Time(is_neg, days, hours, minutes, seconds, micros) => {
+                if is_neg {
+                    let kind = ErrorKind::conversion("Failed to convert a negative time");
+                    Err(Error::builder(kind).build())?
+                }
 
-                    Value::real(dec)
+                if days != 0 {
+                    let kind = ErrorKind::conversion("Failed to read a MySQL `time` as duration");
+                    Err(Error::builder(kind).build())?
                 }
-                // https://dev.mysql.com/doc/internals/en/character-set.html
-                my::Value::Bytes(b) if column.character_set() == 63 => Value::bytes(b),
-                my::Value::Bytes(s) => Value::text(String::from_utf8(s)?),
-                my::Value::Int(i) => Value::integer(i),
-                my::Value::UInt(i) => Value::integer(i64::try_from(i).map_err(|_| {
-                    let msg = "Unsigned integers larger than 9_223_372_036_854_775_807 are currently not handled.";
-                    let kind = ErrorKind::value_out_of_range(msg);
 
-                    Error::builder(kind).build()
-                })?),
-                my::Value::Float(f) => Value::from(f),
-                my::Value::Double(f) => Value::from(f),
+                let time = NaiveTime::from_hms_micro(hours.into(), minutes.into(), seconds.into(), micros);
+                Value::time(time)
+            }
+            my::Value::NULL => match column {
+                t if t.is_enum() => Value::Enum(None),
+                t if t.is_real() => Value::Real(None),
+                t if t.is_null() => Value::Integer(None),
+                t if t.is_integer() => Value::Integer(None),
                 #[cfg(feature = "chrono-0_4")]
-                my::Value::Date(year, month, day, hour, min, sec, micro) => {
-                    let time = NaiveTime::from_hms_micro(hour.into(), min.into(), sec.into(), micro);
-
-                    let date = NaiveDate::from_ymd(year.into(), month.into(), day.into());
-                    let dt = NaiveDateTime::new(date, time);
-
-                    Value::datetime(DateTime::<Utc>::from_utc(dt, Utc))
-                }
+                t if t.is_datetime() => Value::DateTime(None),
                 #[cfg(feature = "chrono-0_4")]
-                my::Value::Time(is_neg, days, hours, minutes, seconds, micros) => {
-                    if is_neg {
-                        let kind = ErrorKind::conversion("Failed to convert a negative time");
-                        Err(Error::builder(kind).build())?
-                    }
-
-                    if days != 0 {
-                        let kind = ErrorKind::conversion("Failed to read a MySQL `time` as duration");
-                        Err(Error::builder(kind).build())?
-                    }
-
-                    let time = NaiveTime::from_hms_micro(hours.into(), minutes.into(), seconds.into(), micros);
-                    Value::time(time)
-                }
-                my::Value::NULL => match column {
-                    t if t.is_enum() => Value::Enum(None),
-                    t if t.is_real() => Value::Real(None),
-                    t if t.is_null() => Value::Integer(None),
-                    t if t.is_integer() => Value::Integer(None),
-                    #[cfg(feature = "chrono-0_4")]
-                    t if t.is_datetime() => Value::DateTime(None),
-                    #[cfg(feature = "chrono-0_4")]
-                    t if t.is_time() => Value::Time(None),
-                    #[cfg(feature = "chrono-0_4")]
-                    t if t.is_date() => Value::Date(None),
-                    t if t.is_text() => Value::Text(None),
-                    t if t.is_bytes() => Value::Bytes(None),
-                    t if t.is_bool() => Value::Boolean(None),
-                    #[cfg(feature = "json-1")]
-                    t if t.is_json() => Value::Json(None),
-                    typ => {
-                        let msg = format!(
-                            "Value of type {:?} is not supported with the current configuration",
-                            typ
-                        );
-
-                        let kind = ErrorKind::conversion(msg);
-                        Err(Error::builder(kind).build())?
-                    }
-                },
-                #[cfg(not(feature = "chrono-0_4"))]
+                t if t.is_time() => Value::Time(None),
+                #[cfg(feature = "chrono-0_4")]
+                t if t.is_date() => Value::Date(None),
+                t if t.is_text() => Value::Text(None),
+                t if t.is_bytes() => Value::Bytes(None),
+                t if t.is_bool() => Value::Boolean(None),
+                #[cfg(feature = "json-1")]
+                t if t.is_json() => Value::Json(None),
                 typ => {
                     let msg = format!(
                         "Value of type {:?} is not supported with the current configuration",
@@ -303,17 +321,27 @@ impl TakeRow for my::Row {
                     let kind = ErrorKind::conversion(msg);
                     Err(Error::builder(kind).build())?
                 }
-            };
+            },
+            #[cfg(not(feature = "chrono-0_4"))]
+            typ => {
+                let msg = format!(
+                    "Value of type {:?} is not supported with the current configuration",
+                    typ
+                );
 
-            Ok(res)
-        }
+                let kind = ErrorKind::conversion(msg);
+                Err(Error::builder(kind).build())?
+            }
+        };
 
-        let mut row = Vec::with_capacity(self.len());
+        Ok(res)
+    }
 
-        for i in 0..self.len() {
-            row.push(convert(self, i)?);
-        }
+    let mut values = Vec::with_capacity(row.len());
 
-        Ok(row)
+    for i in 0..row.len() {
+        values.push(convert(row, i, options)?);
     }
+
+    Ok(values)
 }
@@ -20,6 +20,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -35,6 +36,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -48,6 +50,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -58,6 +61,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(code.to_string());
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -71,6 +75,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -83,6 +88,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -95,6 +101,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -107,6 +114,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -120,6 +128,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -132,6 +141,15 @@ impl From<my::error::Error> for Error {
                 let mut builder = Error::builder(ErrorKind::TableDoesNotExist { table });
                 builder.set_original_code(format!("{}", code));
                 builder.set_original_message(message);
+                builder.set_source(e);
+
+                builder.build()
+            }
+            my::error::Error::Server(ServerError { ref message, code, .. }) if code == 1205 || code == 3572 => {
+                let mut builder = Error::builder(ErrorKind::LockNotAcquired);
+                builder.set_original_code(format!("{}", code));
+                builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -150,6 +168,7 @@ impl From<my::error::Error> for Error {
 
                 builder.set_original_code(code.to_string());
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
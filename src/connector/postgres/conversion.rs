@@ -1,6 +1,9 @@
 use crate::{
-    ast::Value,
-    connector::queryable::{GetRow, ToColumnNames},
+    ast::{bits_from_str, Value},
+    connector::{
+        queryable::{GetRow, ToColumnNames},
+        DecodeOptions,
+    },
     error::{Error, ErrorKind},
 };
 use bit_vec::BitVec;
@@ -8,11 +11,8 @@ use bytes::BytesMut;
 #[cfg(feature = "chrono-0_4")]
 use chrono::{DateTime, NaiveDateTime, Utc};
 use postgres_types::{FromSql, ToSql};
-use rust_decimal::{
-    prelude::{FromPrimitive, ToPrimitive},
-    Decimal,
-};
-use std::{error::Error as StdError, str::FromStr};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use std::{convert::TryFrom, error::Error as StdError};
 use tokio_postgres::{
     types::{self, IsNull, Kind, Type as PostgresType},
     Row as PostgresRow, Statement as PostgresStatement,
@@ -25,6 +25,8 @@ pub fn conv_params<'a>(params: &'a [Value<'a>]) -> Vec<&'a (dyn types::ToSql + S
     params.iter().map(|x| x as &(dyn ToSql + Sync)).collect::<Vec<_>>()
 }
 
+/// The text of an enum variant, or of any other value sent as plain text
+/// such as `citext`.
 struct EnumString {
     value: String,
 }
@@ -41,6 +43,77 @@ impl<'a> FromSql<'a> for EnumString {
     }
 }
 
+/// A `NUMERIC` in its decimal text form, without the precision limits of a
+/// `Decimal`.
+struct NumericText(String);
+
+impl<'a> FromSql<'a> for NumericText {
+    fn from_sql(_ty: &PostgresType, raw: &'a [u8]) -> Result<NumericText, Box<dyn std::error::Error + Sync + Send>> {
+        let read = |pos: usize| -> Result<u16, Box<dyn std::error::Error + Sync + Send>> {
+            match raw.get(pos..pos + 2) {
+                Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]])),
+                None => Err("Invalid NUMERIC value.".into()),
+            }
+        };
+
+        // The header is the number of digits, the weight of the first digit,
+        // the sign and the number of decimals. The digits are in base 10000.
+        let ndigits = read(0)? as usize;
+        let weight = read(2)? as i16 as i64;
+        let sign = read(4)?;
+        let dscale = read(6)? as usize;
+
+        let digits = (0..ndigits).map(|i| read(8 + i * 2)).collect::<Result<Vec<_>, _>>()?;
+        let digit = |i: i64| {
+            if i >= 0 && (i as usize) < ndigits {
+                digits[i as usize]
+            } else {
+                0
+            }
+        };
+
+        let mut text = match sign {
+            0x0000 => String::new(),
+            0x4000 => String::from("-"),
+            0xC000 => return Ok(NumericText(String::from("NaN"))),
+            0xD000 => return Ok(NumericText(String::from("Infinity"))),
+            0xF000 => return Ok(NumericText(String::from("-Infinity"))),
+            _ => return Err("Invalid NUMERIC sign.".into()),
+        };
+
+        if weight < 0 {
+            text.push('0');
+        } else {
+            text.push_str(&digit(0).to_string());
+
+            for i in 1..=weight {
+                text.push_str(&format!("{:04}", digit(i)));
+            }
+        }
+
+        if dscale > 0 {
+            let mut decimals = String::with_capacity(dscale + 4);
+            let mut i = weight + 1;
+
+            while decimals.len() < dscale {
+                decimals.push_str(&format!("{:04}", digit(i)));
+                i += 1;
+            }
+
+            decimals.truncate(dscale);
+
+            text.push('.');
+            text.push_str(&decimals);
+        }
+
+        Ok(NumericText(text))
+    }
+
+    fn accepts(ty: &PostgresType) -> bool {
+        ty == &PostgresType::NUMERIC
+    }
+}
+
 struct TimeTz(chrono::NaiveTime);
 
 impl<'a> FromSql<'a> for TimeTz {
@@ -73,370 +146,574 @@ impl<'a> FromSql<'a> for NaiveMoney {
     }
 }
 
+/// An `INTERVAL` with the months counted as 30 days and the days as 24 hours,
+/// as in `EXTRACT(EPOCH FROM interval)`.
+#[cfg(feature = "chrono-0_4")]
+struct Interval(chrono::Duration);
+
+#[cfg(feature = "chrono-0_4")]
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_ty: &PostgresType, raw: &'a [u8]) -> Result<Interval, Box<dyn std::error::Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err("Invalid INTERVAL value.".into());
+        }
+
+        let mut micros = [0; 8];
+        let mut days = [0; 4];
+        let mut months = [0; 4];
+
+        micros.copy_from_slice(&raw[0..8]);
+        days.copy_from_slice(&raw[8..12]);
+        months.copy_from_slice(&raw[12..16]);
+
+        let duration = chrono::Duration::microseconds(i64::from_be_bytes(micros))
+            + chrono::Duration::days(i64::from(i32::from_be_bytes(days)))
+            + chrono::Duration::days(i64::from(i32::from_be_bytes(months)) * 30);
+
+        Ok(Interval(duration))
+    }
+
+    fn accepts(ty: &PostgresType) -> bool {
+        ty == &PostgresType::INTERVAL
+    }
+}
+
+/// A geometric value (`POINT`, `LINE`, `LSEG`, `BOX`, `PATH`, `POLYGON` or
+/// `CIRCLE`) in the text form PostgreSQL uses for it, e.g. `(1.5,2)` for a
+/// point.
+struct Geometric(String);
+
+impl Geometric {
+    const TYPES: &'static [PostgresType] = &[
+        PostgresType::POINT,
+        PostgresType::LINE,
+        PostgresType::LSEG,
+        PostgresType::BOX,
+        PostgresType::PATH,
+        PostgresType::POLYGON,
+        PostgresType::CIRCLE,
+    ];
+
+    /// Writes the binary form of a geometric value given in its text form.
+    fn to_sql(text: &str, ty: &PostgresType, out: &mut BytesMut) -> crate::Result<IsNull> {
+        let invalid = || {
+            let msg = format!("`{}` is not a valid {} value.", text, ty.name());
+            Error::builder(ErrorKind::conversion(msg)).build()
+        };
+
+        let coordinates = text
+            .split(|c: char| "()[]{}<>,".contains(c) || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<f64>().map_err(|_| invalid()))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let valid = match *ty {
+            PostgresType::POINT => coordinates.len() == 2,
+            PostgresType::LINE | PostgresType::CIRCLE => coordinates.len() == 3,
+            PostgresType::LSEG | PostgresType::BOX => coordinates.len() == 4,
+            _ => !coordinates.is_empty() && coordinates.len() % 2 == 0,
+        };
+
+        if !valid {
+            return Err(invalid());
+        }
+
+        let npts = (coordinates.len() / 2) as i32;
+
+        match *ty {
+            PostgresType::PATH => {
+                // A path in parentheses is closed, in brackets open.
+                let closed = text.trim_start().starts_with('(');
+
+                out.extend_from_slice(&[closed as u8]);
+                out.extend_from_slice(&npts.to_be_bytes());
+            }
+            PostgresType::POLYGON => out.extend_from_slice(&npts.to_be_bytes()),
+            _ => (),
+        }
+
+        for coordinate in coordinates {
+            out.extend_from_slice(&coordinate.to_be_bytes());
+        }
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'a> FromSql<'a> for Geometric {
+    fn from_sql(ty: &PostgresType, raw: &'a [u8]) -> Result<Geometric, Box<dyn std::error::Error + Sync + Send>> {
+        let coordinates = |raw: &[u8], count: usize| -> Result<Vec<f64>, Box<dyn std::error::Error + Sync + Send>> {
+            if raw.len() != count * 8 {
+                return Err(format!("Invalid {} value.", ty.name()).into());
+            }
+
+            let floats = raw.chunks(8).map(|chunk| {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(chunk);
+                f64::from_be_bytes(bytes)
+            });
+
+            Ok(floats.collect())
+        };
+
+        let points = |coordinates: Vec<f64>| {
+            let points: Vec<String> = coordinates
+                .chunks(2)
+                .map(|point| format!("({},{})", point[0], point[1]))
+                .collect();
+
+            points.join(",")
+        };
+
+        let npts = |raw: &[u8]| -> Result<usize, Box<dyn std::error::Error + Sync + Send>> {
+            match raw.get(0..4) {
+                Some(bytes) => Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize),
+                None => Err(format!("Invalid {} value.", ty.name()).into()),
+            }
+        };
+
+        let text = match *ty {
+            PostgresType::POINT => points(coordinates(raw, 2)?),
+            PostgresType::LINE => {
+                let abc = coordinates(raw, 3)?;
+                format!("{{{},{},{}}}", abc[0], abc[1], abc[2])
+            }
+            PostgresType::LSEG => format!("[{}]", points(coordinates(raw, 4)?)),
+            PostgresType::BOX => points(coordinates(raw, 4)?),
+            PostgresType::CIRCLE => {
+                let circle = coordinates(raw, 3)?;
+                format!("<({},{}),{}>", circle[0], circle[1], circle[2])
+            }
+            PostgresType::PATH => {
+                let closed = raw.get(0).map(|b| *b != 0).unwrap_or(false);
+                let npts = npts(raw.get(1..).unwrap_or(&[]))?;
+                let points = points(coordinates(&raw[5..], npts * 2)?);
+
+                if closed {
+                    format!("({})", points)
+                } else {
+                    format!("[{}]", points)
+                }
+            }
+            _ => {
+                let npts = npts(raw)?;
+                format!("({})", points(coordinates(&raw[4..], npts * 2)?))
+            }
+        };
+
+        Ok(Geometric(text))
+    }
+
+    fn accepts(ty: &PostgresType) -> bool {
+        Self::TYPES.contains(ty)
+    }
+}
+
 impl GetRow for PostgresRow {
     fn get_result_row<'b>(&'b self) -> crate::Result<Vec<Value<'static>>> {
-        fn convert(row: &PostgresRow, i: usize) -> crate::Result<Value<'static>> {
-            let result = match *row.columns()[i].type_() {
-                PostgresType::BOOL => Value::Boolean(row.try_get(i)?),
-                PostgresType::INT2 => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: i16 = val;
-                        Value::integer(val)
-                    }
-                    None => Value::Integer(None),
-                },
-                PostgresType::INT4 => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: i32 = val;
-                        Value::integer(val)
-                    }
-                    None => Value::Integer(None),
-                },
-                PostgresType::INT8 => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: i64 = val;
-                        Value::integer(val)
-                    }
-                    None => Value::Integer(None),
-                },
-                PostgresType::NUMERIC => Value::Real(row.try_get(i)?),
-                PostgresType::FLOAT4 => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Decimal = Decimal::from_f32(val).expect("f32 is not a Decimal");
-                        Value::real(val)
-                    }
-                    None => Value::Real(None),
-                },
-                PostgresType::FLOAT8 => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: f64 = val;
-                        // Decimal::from_f64 is buggy. Issue: https://github.com/paupino/rust-decimal/issues/228
-                        let val: Decimal = Decimal::from_str(&val.to_string()).expect("f64 is not a Decimal");
-                        Value::real(val)
-                    }
-                    None => Value::Real(None),
-                },
-                PostgresType::MONEY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: NaiveMoney = val;
-                        Value::real(val.0)
-                    }
-                    None => Value::Real(None),
-                },
-                #[cfg(feature = "chrono-0_4")]
-                PostgresType::TIMESTAMP => match row.try_get(i)? {
-                    Some(val) => {
-                        let ts: NaiveDateTime = val;
-                        let dt = DateTime::<Utc>::from_utc(ts, Utc);
-                        Value::datetime(dt)
-                    }
-                    None => Value::DateTime(None),
-                },
-                #[cfg(feature = "chrono-0_4")]
-                PostgresType::TIMESTAMPTZ => match row.try_get(i)? {
-                    Some(val) => {
-                        let ts: DateTime<Utc> = val;
-                        Value::datetime(ts)
-                    }
-                    None => Value::DateTime(None),
-                },
-                #[cfg(feature = "chrono-0_4")]
-                PostgresType::DATE => match row.try_get(i)? {
-                    Some(val) => Value::date(val),
-                    None => Value::Date(None),
-                },
-                #[cfg(feature = "chrono-0_4")]
-                PostgresType::TIME => match row.try_get(i)? {
-                    Some(val) => Value::time(val),
-                    None => Value::Time(None),
-                },
-                #[cfg(feature = "chrono-0_4")]
-                PostgresType::TIMETZ => match row.try_get(i)? {
-                    Some(val) => {
-                        let time: TimeTz = val;
-                        Value::time(time.0)
-                    }
-                    None => Value::Time(None),
-                },
-                #[cfg(feature = "uuid-0_8")]
-                PostgresType::UUID => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Uuid = val;
-                        Value::uuid(val)
-                    }
-                    None => Value::Uuid(None),
-                },
-                #[cfg(feature = "uuid-0_8")]
-                PostgresType::UUID_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<Uuid> = val;
-                        let val = val.into_iter().map(Value::uuid);
-                        Value::array(val)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "json-1")]
-                PostgresType::JSON | PostgresType::JSONB => Value::Json(row.try_get(i)?),
-                #[cfg(feature = "array")]
-                PostgresType::INT2_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<i16> = val;
-                        let ints = val.into_iter().map(Value::integer);
-                        Value::array(ints)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::INT4_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<i32> = val;
-                        let ints = val.into_iter().map(Value::integer);
-                        Value::array(ints)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::INT8_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<i64> = val;
-                        let ints = val.into_iter().map(Value::integer);
-                        Value::array(ints)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::FLOAT4_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<f32> = val;
-                        let floats = val.into_iter().map(Value::from);
-                        Value::array(floats)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::FLOAT8_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<f64> = val;
-                        let floats = val.into_iter().map(Value::from);
-                        Value::array(floats)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::BOOL_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<bool> = val;
-                        let bools = val.into_iter().map(Value::from);
-                        Value::array(bools)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(all(feature = "array", feature = "chrono-0_4"))]
-                PostgresType::TIMESTAMP_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<NaiveDateTime> = val;
+        get_result_row(self, DecodeOptions::default())
+    }
+}
+
+/// Reads the values of the row. If `numeric_as_text` is set, `NUMERIC`
+/// values are read as text to not lose any precision.
+/// Non-finite floats are read following the `non_finite_floats` policy.
+pub(crate) fn get_result_row(row: &PostgresRow, options: DecodeOptions) -> crate::Result<Vec<Value<'static>>> {
+    fn convert(row: &PostgresRow, i: usize, options: DecodeOptions) -> crate::Result<Value<'static>> {
+        let column = row.columns()[i].name();
+
+        let result = match *row.columns()[i].type_() {
+            PostgresType::BOOL => Value::Boolean(row.try_get(i)?),
+            PostgresType::INT2 => match row.try_get(i)? {
+                Some(val) => {
+                    let val: i16 = val;
+                    Value::integer(val)
+                }
+                None => Value::Integer(None),
+            },
+            PostgresType::INT4 => match row.try_get(i)? {
+                Some(val) => {
+                    let val: i32 = val;
+                    Value::integer(val)
+                }
+                None => Value::Integer(None),
+            },
+            PostgresType::INT8 => match row.try_get(i)? {
+                Some(val) => {
+                    let val: i64 = val;
+                    Value::integer(val)
+                }
+                None => Value::Integer(None),
+            },
+            PostgresType::NUMERIC if options.numeric_as_text => match row.try_get(i)? {
+                Some(val) => {
+                    let val: NumericText = val;
+                    Value::text(val.0)
+                }
+                None => Value::Text(None),
+            },
+            PostgresType::NUMERIC => Value::Real(row.try_get(i)?),
+            PostgresType::FLOAT4 => match row.try_get(i)? {
+                Some(val) => Value::Real(options.f32(column, val)?),
+                None => Value::Real(None),
+            },
+            PostgresType::FLOAT8 => match row.try_get(i)? {
+                Some(val) => Value::Real(options.f64(column, val)?),
+                None => Value::Real(None),
+            },
+            PostgresType::MONEY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: NaiveMoney = val;
+                    Value::real(val.0)
+                }
+                None => Value::Real(None),
+            },
+            #[cfg(feature = "chrono-0_4")]
+            PostgresType::TIMESTAMP => match row.try_get(i)? {
+                Some(val) => {
+                    let ts: NaiveDateTime = val;
+                    let dt = DateTime::<Utc>::from_utc(ts, Utc);
+                    Value::datetime(dt)
+                }
+                None => Value::DateTime(None),
+            },
+            #[cfg(feature = "chrono-0_4")]
+            PostgresType::TIMESTAMPTZ => match row.try_get(i)? {
+                Some(val) => {
+                    let ts: DateTime<Utc> = val;
+                    Value::datetime(ts)
+                }
+                None => Value::DateTime(None),
+            },
+            #[cfg(feature = "chrono-0_4")]
+            PostgresType::DATE => match row.try_get(i)? {
+                Some(val) => Value::date(val),
+                None => Value::Date(None),
+            },
+            #[cfg(feature = "chrono-0_4")]
+            PostgresType::TIME => match row.try_get(i)? {
+                Some(val) => Value::time(val),
+                None => Value::Time(None),
+            },
+            #[cfg(feature = "chrono-0_4")]
+            PostgresType::TIMETZ => match row.try_get(i)? {
+                Some(val) => {
+                    let time: TimeTz = val;
+                    Value::time(time.0)
+                }
+                None => Value::Time(None),
+            },
+            #[cfg(feature = "chrono-0_4")]
+            PostgresType::INTERVAL => match row.try_get(i)? {
+                Some(val) => {
+                    let interval: Interval = val;
+                    Value::interval(interval.0)
+                }
+                None => Value::Interval(None),
+            },
+            #[cfg(feature = "uuid-0_8")]
+            PostgresType::UUID => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Uuid = val;
+                    Value::uuid(val)
+                }
+                None => Value::Uuid(None),
+            },
+            #[cfg(feature = "uuid-0_8")]
+            PostgresType::UUID_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<Uuid> = val;
+                    let val = val.into_iter().map(Value::uuid);
+                    Value::array(val)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "json-1")]
+            PostgresType::JSON | PostgresType::JSONB => Value::Json(row.try_get(i)?),
+            #[cfg(feature = "array")]
+            PostgresType::INT2_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<i16> = val;
+                    let ints = val.into_iter().map(Value::integer);
+                    Value::array(ints)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::INT4_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<i32> = val;
+                    let ints = val.into_iter().map(Value::integer);
+                    Value::array(ints)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::INT8_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<i64> = val;
+                    let ints = val.into_iter().map(Value::integer);
+                    Value::array(ints)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::FLOAT4_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<f32> = val;
+                    let floats = val
+                        .into_iter()
+                        .map(|f| options.f32(column, f).map(Value::Real))
+                        .collect::<crate::Result<Vec<_>>>()?;
 
-                        let dates = val
-                            .into_iter()
-                            .map(|x| Value::datetime(DateTime::<Utc>::from_utc(x, Utc)));
+                    Value::array(floats)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::FLOAT8_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<f64> = val;
+                    let floats = val
+                        .into_iter()
+                        .map(|f| options.f64(column, f).map(Value::Real))
+                        .collect::<crate::Result<Vec<_>>>()?;
 
-                        Value::array(dates)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::NUMERIC_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<Decimal> = val;
+                    Value::array(floats)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::BOOL_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<bool> = val;
+                    let bools = val.into_iter().map(Value::from);
+                    Value::array(bools)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(all(feature = "array", feature = "chrono-0_4"))]
+            PostgresType::TIMESTAMP_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<NaiveDateTime> = val;
+
+                    let dates = val
+                        .into_iter()
+                        .map(|x| Value::datetime(DateTime::<Utc>::from_utc(x, Utc)));
 
-                        let decimals = val.into_iter().map(|x| Value::real(x.to_string().parse().unwrap()));
+                    Value::array(dates)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::NUMERIC_ARRAY if options.numeric_as_text => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<NumericText> = val;
+                    Value::array(val.into_iter().map(|x| x.0))
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::NUMERIC_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<Decimal> = val;
 
-                        Value::array(decimals)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::TEXT_ARRAY | PostgresType::NAME_ARRAY | PostgresType::VARCHAR_ARRAY => {
-                    match row.try_get(i)? {
-                        Some(val) => {
-                            let strings: Vec<&str> = val;
-                            Value::array(strings.into_iter().map(|s| s.to_string()))
-                        }
-                        None => Value::Array(None),
-                    }
+                    let decimals = val.into_iter().map(|x| Value::real(x.to_string().parse().unwrap()));
+
+                    Value::array(decimals)
                 }
-                #[cfg(feature = "array")]
-                PostgresType::MONEY_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<NaiveMoney> = val;
-                        let nums = val.into_iter().map(|x| Value::real(x.0));
-                        Value::array(nums)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::OID_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<u32> = val;
-                        let nums = val.into_iter().map(|x| Value::integer(x as i64));
-                        Value::array(nums)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::TIMESTAMPTZ_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<DateTime<Utc>> = val;
-                        let dates = val.into_iter().map(Value::datetime);
-                        Value::array(dates)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::DATE_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<chrono::NaiveDate> = val;
-                        Value::array(val.into_iter().map(Value::date))
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::TIME_ARRAY => match row.try_get(i)? {
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::TEXT_ARRAY | PostgresType::NAME_ARRAY | PostgresType::VARCHAR_ARRAY => {
+                match row.try_get(i)? {
                     Some(val) => {
-                        let val: Vec<chrono::NaiveTime> = val;
-                        Value::array(val.into_iter().map(Value::time))
+                        let strings: Vec<&str> = val;
+                        Value::array(strings.into_iter().map(|s| s.to_string()))
                     }
                     None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::TIMETZ_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<TimeTz> = val;
+                }
+            }
+            #[cfg(feature = "array")]
+            PostgresType::MONEY_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<NaiveMoney> = val;
+                    let nums = val.into_iter().map(|x| Value::real(x.0));
+                    Value::array(nums)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::OID_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<u32> = val;
+                    let nums = val.into_iter().map(|x| Value::integer(x as i64));
+                    Value::array(nums)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::TIMESTAMPTZ_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<DateTime<Utc>> = val;
+                    let dates = val.into_iter().map(Value::datetime);
+                    Value::array(dates)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::DATE_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<chrono::NaiveDate> = val;
+                    Value::array(val.into_iter().map(Value::date))
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::TIME_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<chrono::NaiveTime> = val;
+                    Value::array(val.into_iter().map(Value::time))
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::TIMETZ_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<TimeTz> = val;
 
-                        let dates = val.into_iter().map(|time| Value::time(time.0));
+                    let dates = val.into_iter().map(|time| Value::time(time.0));
 
-                        Value::array(dates)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::JSON_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<serde_json::Value> = val;
-                        let jsons = val.into_iter().map(Value::json);
-                        Value::array(jsons)
-                    }
-                    None => Value::Array(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::JSONB_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<serde_json::Value> = val;
-                        let jsons = val.into_iter().map(Value::json);
-                        Value::array(jsons)
-                    }
-                    None => Value::Array(None),
-                },
-                PostgresType::OID => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: u32 = val;
-                        Value::integer(val)
-                    }
-                    None => Value::Integer(None),
-                },
-                PostgresType::CHAR => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: i8 = val;
-                        Value::character((val as u8) as char)
-                    }
-                    None => Value::Char(None),
-                },
-                PostgresType::INET | PostgresType::CIDR => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: std::net::IpAddr = val;
-                        Value::text(val.to_string())
-                    }
-                    None => Value::Text(None),
-                },
-                #[cfg(feature = "array")]
-                PostgresType::INET_ARRAY | PostgresType::CIDR_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<std::net::IpAddr> = val;
-                        let addrs = val.into_iter().map(|v| Value::text(v.to_string()));
-                        Value::array(addrs)
-                    }
-                    None => Value::Array(None),
-                },
-                PostgresType::BIT | PostgresType::VARBIT => match row.try_get(i)? {
+                    Value::array(dates)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::JSON_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<serde_json::Value> = val;
+                    let jsons = val.into_iter().map(Value::json);
+                    Value::array(jsons)
+                }
+                None => Value::Array(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::JSONB_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<serde_json::Value> = val;
+                    let jsons = val.into_iter().map(Value::json);
+                    Value::array(jsons)
+                }
+                None => Value::Array(None),
+            },
+            PostgresType::OID => match row.try_get(i)? {
+                Some(val) => {
+                    let val: u32 = val;
+                    Value::integer(val)
+                }
+                None => Value::Integer(None),
+            },
+            PostgresType::CHAR => match row.try_get(i)? {
+                Some(val) => {
+                    let val: i8 = val;
+                    Value::character((val as u8) as char)
+                }
+                None => Value::Char(None),
+            },
+            PostgresType::INET | PostgresType::CIDR => match row.try_get(i)? {
+                Some(val) => {
+                    let val: std::net::IpAddr = val;
+                    Value::text(val.to_string())
+                }
+                None => Value::Text(None),
+            },
+            #[cfg(feature = "array")]
+            PostgresType::INET_ARRAY | PostgresType::CIDR_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<std::net::IpAddr> = val;
+                    let addrs = val.into_iter().map(|v| Value::text(v.to_string()));
+                    Value::array(addrs)
+                }
+                None => Value::Array(None),
+            },
+            PostgresType::BIT | PostgresType::VARBIT => Value::Bits(row.try_get(i)?),
+            #[cfg(feature = "array")]
+            PostgresType::BIT_ARRAY | PostgresType::VARBIT_ARRAY => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Vec<BitVec> = val;
+                    Value::array(val.into_iter().map(Value::bits))
+                }
+                None => Value::Array(None),
+            },
+            ref x if Geometric::TYPES.contains(x) => match row.try_get(i)? {
+                Some(val) => {
+                    let val: Geometric = val;
+                    Value::text(val.0)
+                }
+                None => Value::Text(None),
+            },
+            ref x if x.name() == "citext" => match row.try_get(i)? {
+                Some(val) => {
+                    let val: EnumString = val;
+                    Value::text(val.value)
+                }
+                None => Value::Text(None),
+            },
+            ref x => match x.kind() {
+                Kind::Enum(_) => match row.try_get(i)? {
                     Some(val) => {
-                        let val: BitVec = val;
-                        Value::text(bits_to_string(&val)?)
+                        let val: EnumString = val;
+                        Value::enum_variant(val.value)
                     }
-                    None => Value::Text(None),
+                    None => Value::Enum(None),
                 },
                 #[cfg(feature = "array")]
-                PostgresType::BIT_ARRAY | PostgresType::VARBIT_ARRAY => match row.try_get(i)? {
-                    Some(val) => {
-                        let val: Vec<BitVec> = val;
-
-                        let stringified = val
-                            .into_iter()
-                            .map(|bits| bits_to_string(&bits).map(Value::text))
-                            .collect::<crate::Result<Vec<_>>>()?;
-
-                        Value::array(stringified)
-                    }
-                    None => Value::Array(None),
-                },
-                ref x => match x.kind() {
+                Kind::Array(inner) => match inner.kind() {
                     Kind::Enum(_) => match row.try_get(i)? {
                         Some(val) => {
-                            let val: EnumString = val;
-                            Value::enum_variant(val.value)
+                            let val: Vec<EnumString> = val;
+                            let variants = val.into_iter().map(|x| Value::enum_variant(x.value));
+                            Value::array(variants)
                         }
-                        None => Value::Enum(None),
+                        None => Value::Array(None),
                     },
-                    #[cfg(feature = "array")]
-                    Kind::Array(inner) => match inner.kind() {
-                        Kind::Enum(_) => match row.try_get(i)? {
-                            Some(val) => {
-                                let val: Vec<EnumString> = val;
-                                let variants = val.into_iter().map(|x| Value::enum_variant(x.value));
-                                Value::array(variants)
-                            }
-                            None => Value::Array(None),
-                        },
-                        _ => match row.try_get(i)? {
-                            Some(val) => {
-                                let val: Vec<String> = val;
-                                let strings = val.into_iter().map(Value::text);
-                                Value::array(strings)
-                            }
-                            None => Value::Array(None),
-                        },
+                    _ if inner.name() == "citext" => match row.try_get(i)? {
+                        Some(val) => {
+                            let val: Vec<EnumString> = val;
+                            Value::array(val.into_iter().map(|x| Value::text(x.value)))
+                        }
+                        None => Value::Array(None),
                     },
                     _ => match row.try_get(i)? {
                         Some(val) => {
-                            let val: String = val;
-                            Value::text(val)
+                            let val: Vec<String> = val;
+                            let strings = val.into_iter().map(Value::text);
+                            Value::array(strings)
                         }
-                        None => Value::Text(None),
+                        None => Value::Array(None),
                     },
                 },
-            };
-
-            Ok(result)
-        }
+                _ => match row.try_get(i)? {
+                    Some(val) => {
+                        let val: String = val;
+                        Value::text(val)
+                    }
+                    None => Value::Text(None),
+                },
+            },
+        };
 
-        let num_columns = self.columns().len();
-        let mut row = Vec::with_capacity(num_columns);
+        Ok(result)
+    }
 
-        for i in 0..num_columns {
-            row.push(convert(self, i)?);
-        }
+    let num_columns = row.columns().len();
+    let mut values = Vec::with_capacity(num_columns);
 
-        Ok(row)
+    for i in 0..num_columns {
+        values.push(convert(row, i, options)?);
     }
+
+    Ok(values)
 }
 
 impl ToColumnNames for PostgresStatement {
@@ -523,22 +800,53 @@ impl<'a> ToSql for Value<'a> {
                 .map(|string| serde_json::from_str::<serde_json::Value>(&string)?.to_sql(ty, out)),
             (Value::Text(string), &PostgresType::BIT) | (Value::Text(string), &PostgresType::VARBIT) => {
                 string.as_ref().map(|string| {
-                    let bits: BitVec = string_to_bits(string)?;
+                    let bits: BitVec = bits_from_str(string)?;
 
                     bits.to_sql(ty, out)
                 })
             }
+            (Value::Text(string), ty) if Geometric::TYPES.contains(ty) => {
+                string.as_ref().map(|string| Ok(Geometric::to_sql(string, ty, out)?))
+            }
             (Value::Text(string), _) => string.as_ref().map(|ref string| string.to_sql(ty, out)),
             (Value::Array(values), &PostgresType::BIT_ARRAY) | (Value::Array(values), &PostgresType::VARBIT_ARRAY) => {
                 values.as_ref().map(|values| {
                     let bitvecs: Vec<BitVec> = values
-                        .into_iter()
-                        .filter_map(|val| val.as_str().map(|s| string_to_bits(s)))
+                        .iter()
+                        .filter(|val| !val.is_null())
+                        .map(|val| BitVec::try_from(val.clone()))
                         .collect::<crate::Result<Vec<_>>>()?;
 
                     bitvecs.to_sql(ty, out)
                 })
             }
+            #[cfg(feature = "uuid-0_8")]
+            (Value::Bytes(bytes), &PostgresType::UUID) => bytes.as_ref().map(|bytes| {
+                let uuid = Uuid::from_slice(bytes).map_err(|_| {
+                    let msg = format!("A UUID must be 16 bytes, got {}.", bytes.len());
+                    Error::builder(ErrorKind::conversion(msg)).build()
+                })?;
+
+                uuid.to_sql(ty, out)
+            }),
+            (Value::Bytes(bytes), &PostgresType::INET) | (Value::Bytes(bytes), &PostgresType::CIDR) => {
+                bytes.as_ref().map(|bytes| {
+                    let addr: std::net::IpAddr = match bytes.len() {
+                        4 => <[u8; 4]>::try_from(bytes.as_ref()).map(std::net::IpAddr::from)?,
+                        16 => <[u8; 16]>::try_from(bytes.as_ref()).map(std::net::IpAddr::from)?,
+                        len => {
+                            let msg = format!("An IP address must be 4 or 16 bytes, got {}.", len);
+                            return Err(Error::builder(ErrorKind::conversion(msg)).build().into());
+                        }
+                    };
+
+                    addr.to_sql(ty, out)
+                })
+            }
+            (Value::Bytes(bytes), &PostgresType::BIT) | (Value::Bytes(bytes), &PostgresType::VARBIT) => {
+                bytes.as_ref().map(|bytes| BitVec::from_bytes(bytes).to_sql(ty, out))
+            }
+            (Value::Bits(bits), _) => bits.as_ref().map(|bits| bits.to_sql(ty, out)),
             (Value::Bytes(bytes), _) => bytes.as_ref().map(|bytes| bytes.as_ref().to_sql(ty, out)),
             (Value::Enum(string), _) => string.as_ref().map(|string| {
                 out.extend_from_slice(string.as_bytes());
@@ -571,6 +879,19 @@ impl<'a> ToSql for Value<'a> {
             }),
             #[cfg(feature = "chrono-0_4")]
             (Value::DateTime(value), _) => value.map(|value| value.naive_utc().to_sql(ty, out)),
+            #[cfg(feature = "chrono-0_4")]
+            (Value::Interval(value), _) => value.map(|value| match value.num_microseconds() {
+                Some(micros) => {
+                    // Microseconds, days and months.
+                    out.extend_from_slice(&micros.to_be_bytes());
+                    out.extend_from_slice(&[0; 8]);
+                    Ok(IsNull::No)
+                }
+                None => {
+                    let kind = ErrorKind::conversion("Interval is too long for PostgreSQL.");
+                    Err(Error::builder(kind).build().into())
+                }
+            }),
         };
 
         match res {
@@ -585,38 +906,3 @@ impl<'a> ToSql for Value<'a> {
 
     tokio_postgres::types::to_sql_checked!();
 }
-
-fn string_to_bits(s: &str) -> crate::Result<BitVec> {
-    use bit_vec::*;
-
-    let mut bits = BitVec::with_capacity(s.len());
-
-    for c in s.chars() {
-        match c {
-            '0' => bits.push(false),
-            '1' => bits.push(true),
-            _ => {
-                let msg = "Unexpected character for bits input. Expected only 1 and 0.";
-                let kind = ErrorKind::conversion(msg);
-
-                Err(Error::builder(kind).build())?
-            }
-        }
-    }
-
-    Ok(bits)
-}
-
-fn bits_to_string(bits: &BitVec) -> crate::Result<String> {
-    let mut s = String::with_capacity(bits.len());
-
-    for bit in bits {
-        if bit {
-            s.push('1');
-        } else {
-            s.push('0');
-        }
-    }
-
-    Ok(s)
-}
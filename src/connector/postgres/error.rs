@@ -1,27 +1,30 @@
 use crate::error::{DatabaseConstraint, Error, ErrorKind};
 
+/// The `tokio_postgres::Error` is kept as the source of the converted error,
+/// with the `DbError` of the server as its source in turn for the errors
+/// coming from the database. Both can be found with `Error::downcast_ref`.
 impl From<tokio_postgres::error::Error> for Error {
     fn from(e: tokio_postgres::error::Error) -> Error {
+        use std::error::Error as _;
         use tokio_postgres::error::DbError;
 
-        match e.code().map(|c| c.code()) {
-            Some(code) if code == "22001" => {
+        let db_error = e.source().and_then(|source| source.downcast_ref::<DbError>());
+
+        match (e.code().map(|c| c.code()), db_error) {
+            (Some(code), Some(db_error)) if code == "22001" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
 
                 let mut builder = Error::builder(ErrorKind::LengthMismatch { column: None });
 
                 builder.set_original_code(code);
                 builder.set_original_message(db_error.to_string());
+                builder.set_source(e);
 
                 builder.build()
             }
             // Don't look at me, I'm hideous ;((
-            Some(code) if code == "23505" => {
+            (Some(code), Some(db_error)) if code == "23505" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
                 let detail = db_error.detail().unwrap(); // KA-BOOM
 
                 let splitted: Vec<&str> = detail.split(")=(").collect();
@@ -36,14 +39,13 @@ impl From<tokio_postgres::error::Error> for Error {
 
                 builder.set_original_code(code);
                 builder.set_original_message(detail);
+                builder.set_source(e);
 
                 builder.build()
             }
             // Even lipstick will not save this...
-            Some(code) if code == "23502" => {
+            (Some(code), Some(db_error)) if code == "23502" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
 
                 let column_name = db_error
                     .column()
@@ -56,13 +58,12 @@ impl From<tokio_postgres::error::Error> for Error {
 
                 builder.set_original_code(code);
                 builder.set_original_message(db_error.message());
+                builder.set_source(e);
 
                 builder.build()
             }
-            Some(code) if code == "23503" => {
+            (Some(code), Some(db_error)) if code == "23503" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
 
                 let column_name = db_error
                     .column()
@@ -75,13 +76,12 @@ impl From<tokio_postgres::error::Error> for Error {
 
                 builder.set_original_code(code);
                 builder.set_original_message(db_error.message());
+                builder.set_source(e);
 
                 builder.build()
             }
-            Some(code) if code == "3D000" => {
+            (Some(code), Some(db_error)) if code == "3D000" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
                 let message = db_error.message();
 
                 let splitted: Vec<&str> = message.split_whitespace().collect();
@@ -92,13 +92,12 @@ impl From<tokio_postgres::error::Error> for Error {
 
                 builder.set_original_code(code);
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
-            Some(code) if code == "28P01" => {
+            (Some(code), Some(db_error)) if code == "28P01" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
                 let message = db_error.message();
 
                 let splitted: Vec<&str> = message.split_whitespace().collect();
@@ -109,13 +108,12 @@ impl From<tokio_postgres::error::Error> for Error {
 
                 builder.set_original_code(code);
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
-            Some(code) if code == "42P01" => {
+            (Some(code), Some(db_error)) if code == "42P01" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
                 let message = db_error.message();
 
                 let splitted: Vec<&str> = message.split_whitespace().collect();
@@ -125,13 +123,12 @@ impl From<tokio_postgres::error::Error> for Error {
                 let mut builder = Error::builder(ErrorKind::TableDoesNotExist { table });
                 builder.set_original_code(code);
                 builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
-            Some(code) if code == "42P04" => {
+            (Some(code), Some(db_error)) if code == "42P04" => {
                 let code = code.to_string();
-                let error = e.into_source().unwrap(); // boom
-                let db_error = error.downcast_ref::<DbError>().unwrap(); // BOOM
                 let message = db_error.message();
 
                 let splitted: Vec<&str> = message.split_whitespace().collect();
@@ -142,19 +139,32 @@ impl From<tokio_postgres::error::Error> for Error {
 
                 builder.set_original_code(code);
                 builder.set_original_message(message);
+                builder.set_source(e);
+
+                builder.build()
+            }
+            (Some(code), Some(db_error)) if code == "55P03" => {
+                let code = code.to_string();
+                let message = db_error.message();
+
+                let mut builder = Error::builder(ErrorKind::LockNotAcquired);
+                builder.set_original_code(code);
+                builder.set_original_message(message);
+                builder.set_source(e);
 
                 builder.build()
             }
-            code => {
+            (code, _) => {
                 // This is necessary, on top of the other conversions, for the cases where a
-                // native_tls error comes wrapped in a tokio_postgres error.
-                if let Some(tls_error) = try_extracting_tls_error(&e) {
-                    return tls_error;
-                }
+                // native_tls or an IO error comes wrapped in a tokio_postgres error. The
+                // wrapping error stays as the source.
+                let kind = try_extracting_tls_error(&e).or_else(|| try_extracting_io_error(&e));
 
-                // Same for IO errors.
-                if let Some(io_error) = try_extracting_io_error(&e) {
-                    return io_error;
+                if let Some(kind) = kind {
+                    let mut builder = Error::builder(kind);
+                    builder.set_source(e);
+
+                    return builder.build();
                 }
 
                 let reason = format!("{}", e);
@@ -170,6 +180,7 @@ impl From<tokio_postgres::error::Error> for Error {
                         };
 
                         builder.set_original_message(reason);
+                        builder.set_source(e);
                         builder.build()
                     } // sigh...
                     // https://github.com/sfackler/rust-postgres/blob/0c84ed9f8201f4e5b4803199a24afa2c9f3723b2/tokio-postgres/src/connect_tls.rs#L37
@@ -183,6 +194,7 @@ impl From<tokio_postgres::error::Error> for Error {
                         };
 
                         builder.set_original_message(reason);
+                        builder.set_source(e);
                         builder.build()
                     } // double sigh
                     _ => {
@@ -202,29 +214,63 @@ impl From<tokio_postgres::error::Error> for Error {
     }
 }
 
-fn try_extracting_tls_error(err: &tokio_postgres::error::Error) -> Option<Error> {
+/// The error of the TLS backend the connection was made with, either
+/// native-tls or rustls when both are enabled.
+fn try_extracting_tls_error(err: &tokio_postgres::error::Error) -> Option<ErrorKind> {
     use std::error::Error;
 
-    err.source()
-        .and_then(|err| err.downcast_ref::<native_tls::Error>())
-        .map(|err| err.into())
+    let source = err.source()?;
+
+    #[cfg(feature = "postgresql")]
+    {
+        if let Some(err) = source.downcast_ref::<native_tls::Error>() {
+            return Some(ErrorKind::TlsError {
+                message: format!("{}", err),
+            });
+        }
+    }
+
+    #[cfg(feature = "rustls")]
+    {
+        // tokio-rustls reports handshake failures as IO errors wrapping the rustls error.
+        let rustls_error = source
+            .downcast_ref::<std::io::Error>()
+            .and_then(|err| err.get_ref())
+            .and_then(|err| err.downcast_ref::<tokio_rustls::rustls::TLSError>());
+
+        if let Some(err) = rustls_error {
+            return Some(ErrorKind::TlsError {
+                message: format!("{}", err),
+            });
+        }
+    }
+
+    None
 }
 
-fn try_extracting_io_error(err: &tokio_postgres::error::Error) -> Option<Error> {
+fn try_extracting_io_error(err: &tokio_postgres::error::Error) -> Option<ErrorKind> {
     use std::error::Error as _;
 
     err.source()
         .and_then(|err| err.downcast_ref::<std::io::Error>())
         .map(|err| ErrorKind::ConnectionError(Box::new(std::io::Error::new(err.kind(), format!("{}", err)))))
-        .map(|kind| Error::builder(kind).build())
 }
 
+#[cfg(feature = "postgresql")]
 impl From<native_tls::Error> for Error {
     fn from(e: native_tls::Error) -> Error {
-        Error::from(&e)
+        let kind = ErrorKind::TlsError {
+            message: format!("{}", e),
+        };
+
+        let mut builder = Error::builder(kind);
+        builder.set_source(e);
+
+        builder.build()
     }
 }
 
+#[cfg(feature = "postgresql")]
 impl From<&native_tls::Error> for Error {
     fn from(e: &native_tls::Error) -> Error {
         let kind = ErrorKind::TlsError {
@@ -234,3 +280,28 @@ impl From<&native_tls::Error> for Error {
         Error::builder(kind).build()
     }
 }
+
+#[cfg(feature = "rustls")]
+impl From<tokio_rustls::rustls::TLSError> for Error {
+    fn from(e: tokio_rustls::rustls::TLSError) -> Error {
+        let kind = ErrorKind::TlsError {
+            message: format!("{}", e),
+        };
+
+        let mut builder = Error::builder(kind);
+        builder.set_source(e);
+
+        builder.build()
+    }
+}
+
+#[cfg(feature = "rustls")]
+impl From<&tokio_rustls::rustls::TLSError> for Error {
+    fn from(e: &tokio_rustls::rustls::TLSError) -> Error {
+        let kind = ErrorKind::TlsError {
+            message: format!("{}", e),
+        };
+
+        Error::builder(kind).build()
+    }
+}
@@ -0,0 +1,241 @@
+use super::{SslAcceptMode, SslParams};
+use crate::error::{Error, ErrorKind};
+use futures::future::BoxFuture;
+use std::{
+    fs, io,
+    net::IpAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_postgres::tls::{ChannelBinding, MakeTlsConnect, TlsConnect, TlsStream};
+use tokio_rustls::{
+    client,
+    rustls::{
+        internal::pemfile, Certificate, ClientConfig, RootCertStore, ServerCertVerified, ServerCertVerifier, TLSError,
+    },
+    webpki::DNSNameRef,
+    TlsConnector,
+};
+
+/// The name given to rustls for IP hosts when the certificates are not
+/// verified, as rustls takes only DNS names. It is not sent to the server.
+const UNVERIFIED_HOST: &str = "unverified.invalid";
+
+fn tls_error(message: impl Into<String>) -> Error {
+    Error::builder(ErrorKind::TlsError {
+        message: message.into(),
+    })
+    .build()
+}
+
+/// Skips the server certificate checks, used with `sslaccept=accept_invalid_certs`.
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _roots: &RootCertStore,
+        _presented_certs: &[Certificate],
+        _dns_name: DNSNameRef,
+        _ocsp_response: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+impl SslParams {
+    /// Builds a rustls configuration from the parameters. The server roots
+    /// come from `webpki-roots`, extended with the certificate given in
+    /// `sslcert`.
+    ///
+    /// Rustls cannot read PKCS#12 archives, so with this backend `sslidentity`
+    /// must point to a PEM file holding the client certificate chain and an
+    /// unencrypted PKCS#8 or RSA private key.
+    pub(super) fn into_client_config(self) -> crate::Result<ClientConfig> {
+        let mut config = ClientConfig::new();
+        config
+            .root_store
+            .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+
+        if let Some(ref cert_file) = self.certificate_file {
+            let cert = fs::read(cert_file).map_err(|err| tls_error(format!("cert file not found ({})", err)))?;
+
+            match config.root_store.add_pem_file(&mut cert.as_slice()) {
+                Ok((valid, _)) if valid > 0 => (),
+                _ => return Err(tls_error("cert file does not contain a valid PEM certificate")),
+            }
+        }
+
+        if let Some(ref identity_file) = self.identity_file {
+            if matches!(self.identity_password.0.as_deref(), Some(pw) if !pw.is_empty()) {
+                return Err(tls_error(
+                    "encrypted identities are not supported with rustls, use an unencrypted PEM file",
+                ));
+            }
+
+            let identity =
+                fs::read(identity_file).map_err(|err| tls_error(format!("identity file not found ({})", err)))?;
+
+            let certs = pemfile::certs(&mut identity.as_slice())
+                .map_err(|_| tls_error("identity file contains an invalid certificate"))?;
+
+            let mut keys = pemfile::pkcs8_private_keys(&mut identity.as_slice())
+                .map_err(|_| tls_error("identity file contains an invalid private key"))?;
+
+            if keys.is_empty() {
+                keys = pemfile::rsa_private_keys(&mut identity.as_slice())
+                    .map_err(|_| tls_error("identity file contains an invalid private key"))?;
+            }
+
+            let key = keys
+                .into_iter()
+                .next()
+                .ok_or_else(|| tls_error("identity file does not contain a private key"))?;
+
+            config.set_single_client_cert(certs, key)?;
+        }
+
+        if self.ssl_accept_mode == SslAcceptMode::AcceptInvalidCerts {
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(NoCertificateVerification));
+        }
+
+        Ok(config)
+    }
+}
+
+/// A `MakeTlsConnect` for tokio-postgres, using rustls instead of native-tls.
+///
+/// Rustls verifies certificates only against DNS names, so an IP host can be
+/// connected to only with `sslaccept=accept_invalid_certs`.
+#[derive(Clone)]
+pub(super) struct MakeRustlsConnect {
+    config: Arc<ClientConfig>,
+    /// For IP hosts, without the SNI extension. Set if the certificates are
+    /// not verified.
+    unverified_ip_config: Option<Arc<ClientConfig>>,
+}
+
+impl MakeRustlsConnect {
+    pub(super) fn new(params: SslParams) -> crate::Result<Self> {
+        let verify = params.ssl_accept_mode != SslAcceptMode::AcceptInvalidCerts;
+        let config = params.into_client_config()?;
+
+        let unverified_ip_config = if verify {
+            None
+        } else {
+            let mut config = config.clone();
+            config.enable_sni = false;
+
+            Some(Arc::new(config))
+        };
+
+        Ok(Self {
+            config: Arc::new(config),
+            unverified_ip_config,
+        })
+    }
+}
+
+impl<S> MakeTlsConnect<S> for MakeRustlsConnect
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    type Stream = RustlsStream<S>;
+    type TlsConnect = RustlsConnect;
+    type Error = io::Error;
+
+    /// The host name is checked only when TLS is negotiated, as a host
+    /// connected to without TLS, such as a Unix socket or an IP address with
+    /// `sslmode=disable`, can't be verified by rustls.
+    fn make_tls_connect(&mut self, hostname: &str) -> io::Result<RustlsConnect> {
+        let connect = match (hostname.parse::<IpAddr>(), &self.unverified_ip_config) {
+            (Ok(_), Some(config)) => RustlsConnect {
+                hostname: UNVERIFIED_HOST.to_string(),
+                connector: TlsConnector::from(config.clone()),
+            },
+            _ => RustlsConnect {
+                hostname: hostname.to_string(),
+                connector: TlsConnector::from(self.config.clone()),
+            },
+        };
+
+        Ok(connect)
+    }
+}
+
+pub(super) struct RustlsConnect {
+    hostname: String,
+    connector: TlsConnector,
+}
+
+impl<S> TlsConnect<S> for RustlsConnect
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    type Stream = RustlsStream<S>;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<RustlsStream<S>>>;
+
+    fn connect(self, stream: S) -> Self::Future {
+        let Self { hostname, connector } = self;
+
+        Box::pin(async move {
+            let hostname = DNSNameRef::try_from_ascii_str(&hostname).map_err(|_| {
+                let message = match hostname.parse::<IpAddr>() {
+                    Ok(_) => format!(
+                        "rustls cannot verify the certificate of the IP address `{}`, connect with a host name or \
+                         with `sslaccept=accept_invalid_certs`",
+                        hostname
+                    ),
+                    Err(_) => format!("`{}` is not a valid DNS name for TLS", hostname),
+                };
+
+                io::Error::new(io::ErrorKind::InvalidInput, TLSError::General(message))
+            })?;
+
+            let stream = connector.connect(hostname, stream).await?;
+            Ok(RustlsStream(stream))
+        })
+    }
+}
+
+pub(super) struct RustlsStream<S>(client::TlsStream<S>);
+
+impl<S> AsyncRead for RustlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl<S> AsyncWrite for RustlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+impl<S> TlsStream for RustlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn channel_binding(&self) -> ChannelBinding {
+        ChannelBinding::none()
+    }
+}
@@ -0,0 +1,95 @@
+use std::{fmt, future::Future};
+
+tokio::task_local! {
+    static QUERY_CLASS: QueryClass;
+}
+
+/// The kind of work a query belongs to. The class is added as a `class` label
+/// to the query metrics and logs, and a [pool] can limit how many connections
+/// the batch and migration queries may use at the same time.
+///
+/// Queries are `Interactive` unless run inside [`with_query_class`].
+///
+/// [pool]: ../pooled/struct.Builder.html#method.batch_connection_limit
+/// [`with_query_class`]: fn.with_query_class.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryClass {
+    /// Latency sensitive queries, such as the ones serving a user request.
+    Interactive,
+    /// Background work, such as reports or data exports.
+    Batch,
+    /// Schema changes and data migrations.
+    Migration,
+}
+
+impl QueryClass {
+    /// The class of the queries run in the current task.
+    pub fn current() -> Self {
+        QUERY_CLASS.try_with(|class| *class).unwrap_or_default()
+    }
+
+    /// The name used in the metrics and logs.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Interactive => "interactive",
+            Self::Batch => "batch",
+            Self::Migration => "migration",
+        }
+    }
+
+    /// True for the classes that yield connections to interactive queries.
+    pub fn is_background(self) -> bool {
+        !matches!(self, Self::Interactive)
+    }
+}
+
+impl Default for QueryClass {
+    fn default() -> Self {
+        Self::Interactive
+    }
+}
+
+impl fmt::Display for QueryClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Runs the future with every query in it tagged with the given class,
+/// including the connections it checks out from a pool.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, pooled::Quaint, connector::{with_query_class, QueryClass}};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let pool = Quaint::builder("postgres://localhost/db")?.build();
+///
+/// let report = with_query_class(QueryClass::Batch, async {
+///     let conn = pool.check_out().await?;
+///     conn.select(Select::from_table("orders")).await
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_query_class<F>(class: QueryClass, f: F) -> F::Output
+where
+    F: Future,
+{
+    QUERY_CLASS.scope(class, f).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn class_is_set_for_the_scope() {
+        assert_eq!(QueryClass::Interactive, QueryClass::current());
+
+        let class = with_query_class(QueryClass::Migration, async { QueryClass::current() }).await;
+        assert_eq!(QueryClass::Migration, class);
+
+        assert_eq!(QueryClass::Interactive, QueryClass::current());
+    }
+}
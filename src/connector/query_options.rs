@@ -0,0 +1,39 @@
+use super::deadline;
+use std::{future::Future, time::Duration};
+
+tokio::task_local! {
+    static OPTIONS: QueryOptions;
+}
+
+/// Options for a single query, given to
+/// [query_with](trait.Queryable.html#method.query_with) and
+/// [execute_with](trait.Queryable.html#method.execute_with). The options
+/// left unset use the ones of the connection.
+///
+/// ```
+/// # use quaint::connector::QueryOptions;
+/// # use std::time::Duration;
+/// let options = QueryOptions {
+///     timeout: Some(Duration::from_secs(120)),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryOptions {
+    /// How long to wait for the query, instead of the `socket_timeout` of
+    /// the connection. Can be longer or shorter than it.
+    pub timeout: Option<Duration>,
+}
+
+/// Runs the future with the options set for the queries inside of it.
+pub(crate) async fn scope<F: Future>(options: QueryOptions, f: F) -> F::Output {
+    OPTIONS.scope(options, f).await
+}
+
+/// The timeout of the running query, if overridden, otherwise the socket
+/// timeout of the connection. Shortened to the time left until the deadline
+/// of the task, if any.
+pub(crate) fn socket_timeout(default: Option<Duration>) -> Option<Duration> {
+    let timeout = OPTIONS.try_with(|options| options.timeout).ok().flatten().or(default);
+    deadline::bounded(timeout)
+}
@@ -1,6 +1,15 @@
-use super::{ResultSet, Transaction};
-use crate::ast::*;
+#[cfg(feature = "mysql")]
+use super::SqlFamily;
+use super::{
+    query_options, ConnectionInfo, ExecuteResult, QueryOptions, ResultRow, ResultSet, Transaction, UpsertOutcome,
+};
+use crate::{
+    ast::*,
+    error::{Error, ErrorKind},
+};
 use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::sync::Arc;
 
 pub trait GetRow {
     fn get_result_row(&self) -> crate::Result<Vec<Value<'static>>>;
@@ -14,6 +23,10 @@ pub trait ToColumnNames {
     fn to_column_names(&self) -> Vec<String>;
 }
 
+/// Runs a raw command on a connection from a spawned task, such as the
+/// rollback of a transaction left idle for longer than its timeout.
+pub type CommandHandle = Arc<dyn Fn(String) -> BoxFuture<'static, crate::Result<()>> + Send + Sync>;
+
 /// Represents a connection or a transaction that can be queried.
 #[async_trait]
 pub trait Queryable: Send + Sync {
@@ -30,6 +43,34 @@ pub trait Queryable: Send + Sync {
     /// returning the number of affected rows.
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64>;
 
+    /// Execute the given query with options overriding the ones of the
+    /// connection, such as a longer timeout for a slow report.
+    ///
+    /// The timeout is used by PostgreSQL, MySQL and SQL Server in place of
+    /// the `socket_timeout` of the connection.
+    async fn query_with(&self, q: Query<'_>, options: QueryOptions) -> crate::Result<ResultSet> {
+        query_options::scope(options, self.query(q)).await
+    }
+
+    /// Execute the given query with options overriding the ones of the
+    /// connection, returning the number of affected rows.
+    async fn execute_with(&self, q: Query<'_>, options: QueryOptions) -> crate::Result<u64> {
+        query_options::scope(options, self.execute(q)).await
+    }
+
+    /// Execute the given query, returning the number of affected rows together
+    /// with the id of the last inserted row.
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        Ok(ExecuteResult::new(self.execute(q).await?, None))
+    }
+
+    /// Execute a query given as SQL, interpolating the given parameters and
+    /// returning the number of affected rows together with the id of the last
+    /// inserted row.
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        Ok(ExecuteResult::new(self.execute_raw(sql, params).await?, None))
+    }
+
     /// Run a command in the database, for queries that can't be run using
     /// prepared statements.
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()>;
@@ -40,27 +81,105 @@ pub trait Queryable: Send + Sync {
     /// parsing or normalization.
     async fn version(&self) -> crate::Result<Option<String>>;
 
+    /// Info about the connection and underlying database, such as the
+    /// database name, host and the connection string without the password.
+    fn connection_info(&self) -> &ConnectionInfo;
+
     /// Execute a `SELECT` query.
     async fn select(&self, q: Select<'_>) -> crate::Result<ResultSet> {
         self.query(q.into()).await
     }
 
+    /// Execute a `SELECT` query returning exactly one row. Fails with
+    /// `ErrorKind::NotFound` if no rows match, and with
+    /// `ErrorKind::TooManyRows` if more than one does.
+    async fn select_one(&self, q: Select<'_>) -> crate::Result<ResultRow> {
+        match self.select_optional(q).await? {
+            Some(row) => Ok(row),
+            None => Err(Error::builder(ErrorKind::NotFound).build()),
+        }
+    }
+
+    /// Execute a `SELECT` query returning at most one row. Fails with
+    /// `ErrorKind::TooManyRows` if more than one row matches.
+    async fn select_optional(&self, q: Select<'_>) -> crate::Result<Option<ResultRow>> {
+        let mut rows = self.select(q).await?.into_iter();
+
+        let row = rows.next();
+
+        if rows.next().is_some() {
+            let kind = ErrorKind::TooManyRows(2 + rows.count());
+            return Err(Error::builder(kind).build());
+        }
+
+        Ok(row)
+    }
+
     /// Execute an `INSERT` query.
     async fn insert(&self, q: Insert<'_>) -> crate::Result<ResultSet> {
         self.query(q.into()).await
     }
 
+    /// Execute an upsert, an `INSERT` with `OnConflict::Update`, returning
+    /// whether each row was inserted or updated, in the order of the rows.
+    ///
+    /// PostgreSQL reads the outcome from the `xmax` system column of the
+    /// written rows. MySQL inserts the rows one at a time, reading the
+    /// outcome from the number of affected rows, and should be run in a
+    /// transaction. Other databases return an error.
+    async fn upsert(&self, _q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        Err(Error::unsupported(
+            "Reading the outcome of an upsert is not supported by the database.",
+        ))
+    }
+
     /// Execute an `UPDATE` query, returning the number of affected rows.
     async fn update(&self, q: Update<'_>) -> crate::Result<u64> {
         self.execute(q.into()).await
     }
 
+    /// Increment the `column` by `amount` in the rows matching the
+    /// `conditions`, returning the new value of the first updated row or
+    /// `None` if no rows matched.
+    ///
+    /// The increment itself is atomic. PostgreSQL returns the new value with
+    /// `RETURNING`, MySQL and SQL Server read it with a follow-up `SELECT` in
+    /// a transaction. SQLite reads it without one, so there it should be run
+    /// in a transaction to not see the writes of other connections. The
+    /// `conditions` must not depend on the incremented column.
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        update_and_select(self, table, conditions, column, amount).await
+    }
+
     /// Execute a `DELETE` query, returning the number of affected rows.
     async fn delete(&self, q: Delete<'_>) -> crate::Result<()> {
         self.query(q.into()).await?;
         Ok(())
     }
 
+    /// Execute a `DELETE` query, returning the deleted rows.
+    ///
+    /// PostgreSQL and SQLite 3.35 or newer use `DELETE ... RETURNING *`.
+    /// Other databases select the matching rows and delete them in a
+    /// transaction, locking the rows with `FOR UPDATE` on MySQL. With a
+    /// `limit`, the query should have an ordering for both statements to
+    /// pick the same rows.
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        select_and_delete(self, q).await
+    }
+
+    /// A handle for running raw commands on the connection from another task,
+    /// or `None` if the connection can't be shared.
+    fn command_handle(&self) -> Option<CommandHandle> {
+        None
+    }
+
     /// Execute an arbitrary function in the beginning of each transaction.
     async fn server_reset_query(&self, _: &Transaction<'_>) -> crate::Result<()> {
         Ok(())
@@ -70,6 +189,71 @@ pub trait Queryable: Send + Sync {
     fn begin_statement(&self) -> &'static str {
         "BEGIN"
     }
+
+    /// Statement to create a savepoint for a nested transaction
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        format!("SAVEPOINT {}", name)
+    }
+
+    /// Statement to release a savepoint, if the database needs one
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        Some(format!("RELEASE SAVEPOINT {}", name))
+    }
+
+    /// Statement to roll back to a savepoint
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        format!("ROLLBACK TO SAVEPOINT {}", name)
+    }
+}
+
+/// Increments the column and reads the new value, for databases without
+/// `RETURNING`. Should be run in a transaction.
+pub(crate) async fn update_and_select<Q>(
+    conn: &Q,
+    table: Table<'_>,
+    conditions: ConditionTree<'_>,
+    column: Column<'_>,
+    amount: i64,
+) -> crate::Result<Option<Value<'static>>>
+where
+    Q: Queryable + ?Sized,
+{
+    let update = Update::table(table.clone())
+        .increment(column.clone(), amount)
+        .so_that(conditions.clone());
+
+    if conn.update(update).await? == 0 {
+        return Ok(None);
+    }
+
+    let select = Select::from_table(table).column(column).so_that(conditions).unlimited();
+    let rows = conn.select(select).await?;
+
+    Ok(rows.first().and_then(|row| row.at(0).cloned()))
+}
+
+/// Reads the rows matching the `DELETE` and deletes them, for databases
+/// without `RETURNING`. Should be run in a transaction.
+pub(crate) async fn select_and_delete<Q>(conn: &Q, q: Delete<'_>) -> crate::Result<ResultSet>
+where
+    Q: Queryable + ?Sized,
+{
+    let mut select = Select::from_table(q.table.clone()).unlimited();
+    select.conditions = q.conditions.clone();
+    select.ordering = q.ordering.clone();
+    select.limit = q.limit.clone();
+
+    #[cfg(feature = "mysql")]
+    {
+        if conn.connection_info().sql_family() == SqlFamily::Mysql {
+            select = select.for_update(LockMode::Wait);
+        }
+    }
+
+    let rows = conn.select(select).await?;
+    conn.delete(q).await?;
+
+    Ok(rows)
 }
 
 /// A thing that can start a new transaction.
@@ -1,17 +1,19 @@
+mod compact;
 mod index;
 mod result_row;
 
+pub use compact::*;
 pub use index::*;
 pub use result_row::*;
 
 use crate::{ast::Value, error::*};
-use std::sync::Arc;
+use std::{convert::TryFrom, sync::Arc};
 
 #[cfg(feature = "json-1")]
 use serde_json::Map;
 
 /// Encapsulates a set of results and their respective column names.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ResultSet {
     pub(crate) columns: Arc<Vec<String>>,
     pub(crate) rows: Vec<Vec<Value<'static>>>,
@@ -66,6 +68,99 @@ impl ResultSet {
         })
     }
 
+    /// Extracts all values of the named column in one pass, converting them
+    /// to `T`. A `NULL` is returned as `None`, and a value of another type
+    /// is an error.
+    ///
+    /// ```
+    /// # use quaint::{ast::Value, connector::*};
+    /// let names = vec!["id".to_string(), "name".to_string()];
+    /// let rows = vec![
+    ///     vec![1.into(), "Musti".into()],
+    ///     vec![2.into(), Value::Text(None)],
+    /// ];
+    ///
+    /// let result_set = ResultSet::new(names, rows);
+    ///
+    /// assert_eq!(vec![Some(1), Some(2)], result_set.column::<i64>("id").unwrap());
+    /// assert_eq!(vec![Some("Musti".to_string()), None], result_set.column::<String>("name").unwrap());
+    /// ```
+    pub fn column<T>(&self, name: &str) -> crate::Result<Vec<Option<T>>>
+    where
+        T: TryFrom<Value<'static>, Error = Error>,
+    {
+        let index = self
+            .columns
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| Error::builder(ErrorKind::ColumnNotFound(name.to_string())).build())?;
+
+        let mut values = Vec::with_capacity(self.rows.len());
+
+        for row in self.rows.iter() {
+            let value = &row[index];
+
+            if value.is_null() {
+                values.push(None);
+            } else {
+                values.push(Some(T::try_from(value.clone())?));
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Converts every value into its [canonical form], for comparing the
+    /// results of the same query from different databases.
+    ///
+    /// ```
+    /// # use quaint::{ast::Value, connector::*};
+    /// let names = vec!["active".to_string()];
+    /// let postgres = ResultSet::new(names.clone(), vec![vec![Value::boolean(true)]]);
+    /// let mysql = ResultSet::new(names, vec![vec![Value::integer(1)]]);
+    ///
+    /// let (postgres, mysql) = (postgres.normalized(), mysql.normalized());
+    /// assert_eq!(postgres.get(0).unwrap()[0], mysql.get(0).unwrap()[0]);
+    /// ```
+    ///
+    /// [canonical form]: ../ast/enum.Value.html#method.normalized
+    pub fn normalized(mut self) -> Self {
+        for row in self.rows.iter_mut() {
+            for value in row.iter_mut() {
+                *value = std::mem::replace(value, Value::Integer(None)).normalized();
+            }
+        }
+
+        self
+    }
+
+    /// Moves the rows of `other` to the end of this set, e.g. to merge the
+    /// results of the same query from many databases. An empty set takes
+    /// the columns of `other`, otherwise the columns must be the same.
+    ///
+    /// ```
+    /// # use quaint::connector::*;
+    /// let names = vec!["id".to_string()];
+    ///
+    /// let mut result_set = ResultSet::new(names.clone(), vec![vec![1.into()]]);
+    /// result_set.append(ResultSet::new(names, vec![vec![2.into()]])).unwrap();
+    ///
+    /// assert_eq!(vec![Some(1), Some(2)], result_set.column::<i64>("id").unwrap());
+    /// ```
+    pub fn append(&mut self, other: ResultSet) -> crate::Result<()> {
+        if self.columns.is_empty() && self.rows.is_empty() {
+            self.columns = other.columns;
+        } else if self.columns != other.columns {
+            let msg = "Cannot merge result sets with different columns.";
+            return Err(Error::builder(ErrorKind::conversion(msg)).build());
+        }
+
+        self.rows.extend(other.rows);
+        self.last_insert_id = other.last_insert_id.or(self.last_insert_id);
+
+        Ok(())
+    }
+
     /// Takes the first row if existing, otherwise returns error.
     pub fn into_single(self) -> crate::Result<ResultRow> {
         match self.into_iter().next() {
@@ -0,0 +1,260 @@
+use super::{ResultRow, ResultSet};
+use crate::{
+    ast::Value,
+    error::{Error, ErrorKind},
+};
+use rust_decimal::Decimal;
+use std::{borrow::Cow, collections::HashMap, convert::TryFrom, sync::Arc};
+
+/// A `ResultSet` stored column by column, taking less memory when a large
+/// result needs to be buffered. Integer, decimal and boolean columns are kept
+/// as plain vectors without the `Value` wrapper, and a text column stores
+/// every distinct string once. Columns mixing value types are kept as they
+/// are.
+///
+/// The rows are built on access, so `get`, `first` and the iterator return an
+/// owned [ResultRow](struct.ResultRow.html).
+///
+/// ```
+/// # use quaint::connector::*;
+/// let names = vec!["id".to_string(), "status".to_string()];
+/// let rows = vec![
+///     vec![1.into(), "active".into()],
+///     vec![2.into(), "active".into()],
+/// ];
+///
+/// let compact = ResultSet::new(names, rows).into_compact();
+/// let row = compact.get(1).unwrap();
+///
+/// assert_eq!(Some(2), row["id"].as_i64());
+/// assert_eq!(Some("active"), row["status"].as_str());
+/// ```
+#[derive(Debug, Default)]
+pub struct CompactResultSet {
+    columns: Arc<Vec<String>>,
+    data: Vec<CompactColumn>,
+    len: usize,
+    last_insert_id: Option<u64>,
+}
+
+#[derive(Debug)]
+enum CompactColumn {
+    Integer(Vec<Option<i64>>),
+    Real(Vec<Option<Decimal>>),
+    Boolean(Vec<Option<bool>>),
+    Text {
+        strings: Vec<Arc<str>>,
+        indices: Vec<Option<u32>>,
+        lookup: HashMap<Arc<str>, u32>,
+    },
+    Values(Vec<Value<'static>>),
+}
+
+impl CompactColumn {
+    fn new(first: &Value<'static>, capacity: usize) -> Self {
+        match first {
+            Value::Integer(_) => Self::Integer(Vec::with_capacity(capacity)),
+            Value::Real(_) => Self::Real(Vec::with_capacity(capacity)),
+            Value::Boolean(_) => Self::Boolean(Vec::with_capacity(capacity)),
+            Value::Text(_) => Self::Text {
+                strings: Vec::new(),
+                indices: Vec::with_capacity(capacity),
+                lookup: HashMap::new(),
+            },
+            _ => Self::Values(Vec::with_capacity(capacity)),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Integer(values) => values.len(),
+            Self::Real(values) => values.len(),
+            Self::Boolean(values) => values.len(),
+            Self::Text { indices, .. } => indices.len(),
+            Self::Values(values) => values.len(),
+        }
+    }
+
+    fn push(&mut self, value: Value<'static>) {
+        match (&mut *self, value) {
+            (Self::Integer(values), Value::Integer(i)) => values.push(i),
+            (Self::Real(values), Value::Real(r)) => values.push(r),
+            (Self::Boolean(values), Value::Boolean(b)) => values.push(b),
+            (Self::Text { indices, .. }, Value::Text(None)) => indices.push(None),
+            (
+                Self::Text {
+                    strings,
+                    indices,
+                    lookup,
+                },
+                Value::Text(Some(s)),
+            ) if lookup.contains_key(s.as_ref()) || u32::try_from(strings.len()).is_ok() => {
+                let index = match lookup.get(s.as_ref()) {
+                    Some(index) => *index,
+                    None => {
+                        let string: Arc<str> = Arc::from(s.as_ref());
+                        let index = strings.len() as u32;
+
+                        strings.push(Arc::clone(&string));
+                        lookup.insert(string, index);
+
+                        index
+                    }
+                };
+
+                indices.push(Some(index));
+            }
+            (Self::Values(values), value) => values.push(value),
+            (_, value) => {
+                let mut values: Vec<Value<'static>> = (0..self.len()).map(|i| self.get(i)).collect();
+                values.push(value);
+
+                *self = Self::Values(values);
+            }
+        }
+    }
+
+    /// Drops the lookup table needed only while adding values.
+    fn finish(&mut self) {
+        if let Self::Text { lookup, .. } = self {
+            *lookup = HashMap::new();
+        }
+    }
+
+    fn get(&self, row: usize) -> Value<'static> {
+        match self {
+            Self::Integer(values) => Value::Integer(values[row]),
+            Self::Real(values) => Value::Real(values[row]),
+            Self::Boolean(values) => Value::Boolean(values[row]),
+            Self::Text { strings, indices, .. } => {
+                Value::Text(indices[row].map(|i| Cow::Owned(strings[i as usize].to_string())))
+            }
+            Self::Values(values) => values[row].clone(),
+        }
+    }
+}
+
+impl CompactResultSet {
+    /// The last id inserted, if available.
+    pub fn last_insert_id(&self) -> Option<u64> {
+        self.last_insert_id
+    }
+
+    /// An iterator of column names.
+    pub fn columns(&self) -> &Vec<String> {
+        &self.columns
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the first row, or None if the set is empty.
+    pub fn first(&self) -> Option<ResultRow> {
+        self.get(0)
+    }
+
+    /// Returns the row in a given position.
+    pub fn get(&self, index: usize) -> Option<ResultRow> {
+        if index >= self.len {
+            return None;
+        }
+
+        Some(ResultRow {
+            columns: Arc::clone(&self.columns),
+            values: self.data.iter().map(|column| column.get(index)).collect(),
+        })
+    }
+
+    /// Takes the first row if existing, otherwise returns error.
+    pub fn into_single(self) -> crate::Result<ResultRow> {
+        match self.first() {
+            Some(row) => Ok(row),
+            None => Err(Error::builder(ErrorKind::NotFound).build()),
+        }
+    }
+}
+
+impl ResultSet {
+    /// Moves the rows to a [CompactResultSet](struct.CompactResultSet.html),
+    /// storing them column by column.
+    pub fn into_compact(self) -> CompactResultSet {
+        CompactResultSet::from(self)
+    }
+}
+
+impl From<ResultSet> for CompactResultSet {
+    fn from(result_set: ResultSet) -> Self {
+        let len = result_set.rows.len();
+
+        let mut data: Vec<CompactColumn> = match result_set.rows.first() {
+            Some(row) => row.iter().map(|value| CompactColumn::new(value, len)).collect(),
+            None => Vec::new(),
+        };
+
+        for row in result_set.rows {
+            for (column, value) in data.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+
+        data.iter_mut().for_each(CompactColumn::finish);
+
+        Self {
+            columns: result_set.columns,
+            data,
+            len,
+            last_insert_id: result_set.last_insert_id,
+        }
+    }
+}
+
+impl From<CompactResultSet> for ResultSet {
+    fn from(compact: CompactResultSet) -> Self {
+        let rows = (0..compact.len)
+            .map(|row| compact.data.iter().map(|column| column.get(row)).collect())
+            .collect();
+
+        Self {
+            columns: Arc::clone(&compact.columns),
+            rows,
+            last_insert_id: compact.last_insert_id,
+        }
+    }
+}
+
+impl IntoIterator for CompactResultSet {
+    type Item = ResultRow;
+    type IntoIter = CompactResultSetIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CompactResultSetIterator {
+            result_set: self,
+            position: 0,
+        }
+    }
+}
+
+/// Iterator for the rows of a `CompactResultSet`, building each row when
+/// reached.
+pub struct CompactResultSetIterator {
+    result_set: CompactResultSet,
+    position: usize,
+}
+
+impl Iterator for CompactResultSetIterator {
+    type Item = ResultRow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.result_set.get(self.position)?;
+        self.position += 1;
+
+        Some(row)
+    }
+}
@@ -71,6 +71,13 @@ impl ResultRow {
         }
     }
 
+    /// The row as a JSON object, keyed by the column names. Usage
+    /// documentation in [ResultRowRef](struct.ResultRowRef.html).
+    #[cfg(feature = "json-1")]
+    pub fn to_json_object(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.as_ref().to_json_object()
+    }
+
     pub fn into_single(self) -> crate::Result<Value<'static>> {
         match self.into_iter().next() {
             Some(val) => Ok(val),
@@ -116,4 +123,27 @@ impl<'a> ResultRowRef<'a> {
             None
         }
     }
+
+    /// The row as a JSON object, keyed by the column names. The values are
+    /// converted as in `From<Value> for serde_json::Value`, bytes written as
+    /// base64 strings.
+    ///
+    /// ```
+    /// # use quaint::connector::*;
+    /// # use serde_json::json;
+    /// # let names = vec!["id".to_string(), "name".to_string()];
+    /// # let rows = vec![vec!["1234".into(), "Musti".into()]];
+    /// # let result_set = ResultSet::new(names, rows);
+    /// # let row = result_set.first().unwrap();
+    /// let object = serde_json::Value::Object(row.to_json_object());
+    /// assert_eq!(json!({ "id": "1234", "name": "Musti" }), object);
+    /// ```
+    #[cfg(feature = "json-1")]
+    pub fn to_json_object(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.columns
+            .iter()
+            .zip(self.values.iter())
+            .map(|(column, value)| (column.clone(), serde_json::Value::from(value.clone())))
+            .collect()
+    }
 }
@@ -2,24 +2,26 @@ mod conversion;
 mod error;
 
 use crate::{
-    ast::{Query, Value},
-    connector::{metrics, queryable::*, ResultSet},
+    ast::{Delete, Query, Value},
+    connector::{metrics, queryable::*, ConnectionInfo, ExecuteResult, ResultSet},
     error::{Error, ErrorKind},
     visitor::{self, Visitor},
 };
 use async_trait::async_trait;
 use rusqlite::NO_PARAMS;
-use std::{collections::HashSet, convert::TryFrom, path::Path, time::Duration};
+use std::{collections::HashSet, convert::TryFrom, path::Path, sync::Arc, time::Duration};
 use tokio::sync::Mutex;
 
 const DEFAULT_SCHEMA_NAME: &str = "quaint";
 
 /// A connector interface for the SQLite database
 pub struct Sqlite {
-    pub(crate) client: Mutex<rusqlite::Connection>,
+    pub(crate) client: Arc<Mutex<rusqlite::Connection>>,
     /// This is not a `PathBuf` because we need to `ATTACH` the database to the path, and this can
     /// only be done with UTF-8 paths.
     pub(crate) file_path: String,
+    connection_info: ConnectionInfo,
+    max_interactive_rows: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -30,6 +32,9 @@ pub struct SqliteParams {
     pub file_path: String,
     pub db_name: String,
     pub socket_timeout: Option<Duration>,
+    /// Limits a `SELECT` without a `LIMIT` to this many rows, for
+    /// interactive tools. Set with `max_interactive_rows=n`.
+    pub max_interactive_rows: Option<usize>,
 }
 
 impl TryFrom<&str> for SqliteParams {
@@ -52,6 +57,7 @@ impl TryFrom<&str> for SqliteParams {
             let mut connection_limit = None;
             let mut db_name = None;
             let mut socket_timeout = None;
+            let mut max_interactive_rows = None;
 
             if path_parts.len() > 1 {
                 let params = path_parts.last().unwrap().split('&').map(|kv| {
@@ -78,6 +84,13 @@ impl TryFrom<&str> for SqliteParams {
 
                             socket_timeout = Some(Duration::from_secs(as_int));
                         }
+                        "max_interactive_rows" => {
+                            let as_int = v
+                                .parse()
+                                .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+
+                            max_interactive_rows = Some(as_int);
+                        }
                         _ => {
                             #[cfg(not(feature = "tracing-log"))]
                             trace!("Discarding connection string param: {}", k);
@@ -93,6 +106,7 @@ impl TryFrom<&str> for SqliteParams {
                 file_path: path_str.to_owned(),
                 db_name: db_name.unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_owned()),
                 socket_timeout,
+                max_interactive_rows,
             })
         }
     }
@@ -110,10 +124,20 @@ impl TryFrom<&str> for Sqlite {
             conn.busy_timeout(timeout)?;
         };
 
-        let client = Mutex::new(conn);
+        let client = Arc::new(Mutex::new(conn));
         let file_path = params.file_path;
 
-        Ok(Sqlite { client, file_path })
+        let connection_info = ConnectionInfo::Sqlite {
+            file_path: file_path.clone(),
+            db_name: params.db_name,
+        };
+
+        Ok(Sqlite {
+            client,
+            file_path,
+            connection_info,
+            max_interactive_rows: params.max_interactive_rows,
+        })
     }
 }
 
@@ -141,6 +165,10 @@ impl Sqlite {
 
         rusqlite::Connection::execute(&client, "PRAGMA foreign_keys = ON", NO_PARAMS)?;
 
+        if let ConnectionInfo::Sqlite { db_name: name, .. } = &mut self.connection_info {
+            *name = db_name.to_string();
+        }
+
         Ok(())
     }
 }
@@ -150,6 +178,11 @@ impl TransactionCapable for Sqlite {}
 #[async_trait]
 impl Queryable for Sqlite {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let q = match self.max_interactive_rows {
+            Some(max) => q.limit_rows(max),
+            None => q,
+        };
+
         let (sql, params) = visitor::Sqlite::build(q)?;
         self.query_raw(&sql, &params).await
     }
@@ -180,16 +213,41 @@ impl Queryable for Sqlite {
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        Ok(self.execute_raw_result(sql, params).await?.rows_affected())
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        let (sql, params) = visitor::Sqlite::build(q)?;
+        self.execute_raw_result(&sql, &params).await
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
         metrics::query("sqlite.query_raw", sql, params, move || async move {
             let client = self.client.lock().await;
             let mut stmt = client.prepare_cached(sql)?;
-            let res = u64::try_from(stmt.execute(params)?)?;
+            let changes = u64::try_from(stmt.execute(params)?)?;
+            let last_id = u64::try_from(client.last_insert_rowid()).ok().filter(|id| *id > 0);
 
-            Ok(res)
+            Ok(ExecuteResult::new(changes, last_id))
         })
         .await
     }
 
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        if !self.connection_info.supports_delete_returning() {
+            let tx = self.start_transaction().await?;
+            let rows = tx.delete_returning(q).await?;
+            tx.commit().await?;
+
+            return Ok(rows);
+        }
+
+        let (mut sql, params) = visitor::Sqlite::build(q)?;
+        sql.push_str(" RETURNING *");
+
+        self.query_raw(&sql, &params).await
+    }
+
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
         metrics::query("sqlite.raw_cmd", cmd, &[], move || async move {
             let client = self.client.lock().await;
@@ -202,6 +260,23 @@ impl Queryable for Sqlite {
     async fn version(&self) -> crate::Result<Option<String>> {
         Ok(Some(rusqlite::version().into()))
     }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        let client = self.client.clone();
+
+        Some(Arc::new(move |cmd| {
+            let client = client.clone();
+
+            Box::pin(async move {
+                client.lock().await.execute_batch(&cmd)?;
+                Ok(())
+            })
+        }))
+    }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        &self.connection_info
+    }
 }
 
 #[cfg(test)]
@@ -455,6 +530,545 @@ mod tests {
         assert_eq!(row[0].as_i64(), Some(2));
     }
 
+    #[tokio::test]
+    async fn connection_info_through_a_transaction() {
+        let conn = Quaint::new("file:db/test.db?db_name=test").await.unwrap();
+        let tx = conn.start_transaction().await.unwrap();
+        let queryable: &dyn Queryable = &tx;
+
+        let info = queryable.connection_info();
+
+        assert_eq!(Some("db/test.db"), info.file_path());
+        assert_eq!("test", info.schema_name());
+        assert_eq!("file:db/test.db", info.masked_url());
+
+        tx.rollback().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn commit_hooks_run_only_after_commit() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let tx = conn.start_transaction().await.unwrap();
+        let counter = calls.clone();
+        tx.on_commit(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        tx.rollback().await.unwrap();
+
+        let tx = conn.start_transaction().await.unwrap();
+        let nested = tx.start_transaction().await.unwrap();
+        let counter = calls.clone();
+        nested.on_commit(move || {
+            counter.fetch_add(10, Ordering::SeqCst);
+        });
+        nested.commit().await.unwrap();
+
+        assert_eq!(0, calls.load(Ordering::SeqCst));
+
+        tx.commit().await.unwrap();
+        assert_eq!(10, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn the_journal_is_handed_over_when_the_transaction_ends() {
+        use crate::connector::TransactionOutcome;
+        use std::sync::mpsc;
+
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+        conn.raw_cmd("CREATE TABLE test_journal (id INTEGER PRIMARY KEY, name TEXT, visits INTEGER DEFAULT 0)")
+            .await
+            .unwrap();
+
+        let (sender, journals) = mpsc::channel();
+
+        let tx = conn.start_transaction().await.unwrap();
+        tx.record_journal(move |journal| sender.send(journal.clone()).unwrap());
+
+        let insert = Insert::single_into("test_journal").value("name", "Musti");
+        tx.execute(insert.into()).await.unwrap();
+        tx.select(Select::from_table("test_journal")).await.unwrap();
+
+        let conditions = ConditionTree::single("id".equals(1));
+        tx.increment(Table::from("test_journal"), conditions, Column::from("visits"), 1)
+            .await
+            .unwrap();
+
+        tx.commit().await.unwrap();
+
+        let journal = journals.try_recv().unwrap();
+        assert_eq!(TransactionOutcome::Committed, journal.outcome);
+
+        let entries: Vec<_> = journal
+            .entries
+            .iter()
+            .map(|entry| (entry.fingerprint.as_str(), entry.rows))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("INSERT INTO `test_journal` (`name`) VALUES (?)", Some(1)),
+                ("SELECT `test_journal`.* FROM `test_journal`", Some(1)),
+                (
+                    "UPDATE `test_journal` SET `visits` = (`visits` + ?) WHERE `id` = ?",
+                    Some(1)
+                ),
+            ],
+            entries
+        );
+    }
+
+    #[tokio::test]
+    async fn an_idle_transaction_is_rolled_back_on_next_use() {
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+        conn.raw_cmd("CREATE TABLE test_idle (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+
+        let tx = conn.start_transaction().await.unwrap();
+        tx.set_idle_timeout(std::time::Duration::from_millis(10));
+        tx.execute(Insert::single_into("test_idle").value("id", 1).into())
+            .await
+            .unwrap();
+
+        tokio::time::delay_for(std::time::Duration::from_millis(50)).await;
+
+        let err = tx.select(Select::from_table("test_idle")).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TransactionIdleTimeout { .. }));
+
+        let err = tx.commit().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TransactionIdleTimeout { .. }));
+
+        let rows = conn.select(Select::from_table("test_idle")).await.unwrap();
+        assert!(rows.is_empty());
+    }
+
+    #[tokio::test]
+    async fn an_idle_transaction_is_rolled_back_without_being_used_again() {
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+        conn.raw_cmd("CREATE TABLE test_idle_timer (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+
+        let tx = conn.start_transaction().await.unwrap();
+        tx.set_idle_timeout(std::time::Duration::from_millis(10));
+        tx.execute(Insert::single_into("test_idle_timer").value("id", 1).into())
+            .await
+            .unwrap();
+
+        tokio::time::delay_for(std::time::Duration::from_millis(50)).await;
+
+        // SQLite does not nest transactions, so the next one starts only if
+        // the idle one has been rolled back.
+        let next = conn.start_transaction().await.unwrap();
+        let rows = next.select(Select::from_table("test_idle_timer")).await.unwrap();
+        assert!(rows.is_empty());
+        next.commit().await.unwrap();
+
+        let err = tx.commit().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TransactionIdleTimeout { .. }));
+    }
+
+    #[tokio::test]
+    async fn a_transaction_is_idle_again_after_a_cancelled_statement() {
+        let conn = Sqlite::new("db/test.db").unwrap();
+        conn.raw_cmd("CREATE TABLE test_idle_cancel (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+
+        let tx = conn.start_transaction().await.unwrap();
+        tx.set_idle_timeout(std::time::Duration::from_millis(10));
+
+        // The statement waits for the connection until it is cancelled.
+        let client = conn.client.lock().await;
+        let statement = tx.select(Select::from_table("test_idle_cancel"));
+        let res = tokio::time::timeout(std::time::Duration::from_millis(10), statement).await;
+        assert!(res.is_err());
+        drop(client);
+
+        tokio::time::delay_for(std::time::Duration::from_millis(50)).await;
+
+        let err = tx.select(Select::from_table("test_idle_cancel")).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TransactionIdleTimeout { .. }));
+    }
+
+    #[tokio::test]
+    async fn try_step_rolls_back_only_the_failed_step() {
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+
+        conn.raw_cmd("DROP TABLE IF EXISTS test_try_step").await.unwrap();
+        conn.raw_cmd("CREATE TABLE test_try_step (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+            .await
+            .unwrap();
+
+        let tx = conn.start_transaction().await.unwrap();
+        let mut outcomes = Vec::new();
+
+        for (id, name) in vec![(1, Some("Musti")), (2, None), (3, Some("Naukio"))] {
+            let insert = Insert::single_into("test_try_step").value("id", id).value("name", name);
+            let outcome = tx.try_step(|| tx.insert(insert.into())).await.unwrap();
+
+            outcomes.push(outcome.is_ok());
+        }
+
+        tx.commit().await.unwrap();
+
+        assert_eq!(vec![true, false, true], outcomes);
+
+        let select = Select::from_table("test_try_step").column("id").order_by("id");
+        let ids: Vec<_> = conn
+            .select(select)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row[0].as_i64().unwrap())
+            .collect();
+
+        assert_eq!(vec![1, 3], ids);
+    }
+
+    #[tokio::test]
+    async fn select_one_and_select_optional_check_the_row_count() {
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+
+        conn.raw_cmd("DROP TABLE IF EXISTS test_select_one").await.unwrap();
+        conn.raw_cmd("CREATE TABLE test_select_one (id INTEGER PRIMARY KEY, name TEXT)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_select_one (id, name) VALUES (1, 'Musti'), (2, 'Naukio')")
+            .await
+            .unwrap();
+
+        let select = |id| Select::from_table("test_select_one").so_that("id".greater_than_or_equals(id));
+
+        let row = conn.select_one(select(2)).await.unwrap();
+        assert_eq!(Some("Naukio"), row["name"].as_str());
+
+        let err = conn.select_one(select(3)).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotFound));
+
+        let err = conn.select_one(select(1)).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TooManyRows(2)));
+
+        assert!(conn.select_optional(select(3)).await.unwrap().is_none());
+        assert!(conn.select_optional(select(2)).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn upsert_many_updates_existing_rows() {
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+
+        conn.raw_cmd("DROP TABLE IF EXISTS test_upsert_many").await.unwrap();
+        conn.raw_cmd("CREATE TABLE test_upsert_many (id INTEGER PRIMARY KEY, name TEXT)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_upsert_many (id, name) VALUES (1, 'Musti')")
+            .await
+            .unwrap();
+
+        let insert = Insert::multi_into("test_upsert_many", vec!["id", "name"])
+            .values((1, "Mustikka"))
+            .values((2, "Naukio"));
+
+        crate::helpers::upsert_many(&conn, insert, vec!["id".into()], vec!["name".into()])
+            .await
+            .unwrap();
+
+        let select = Select::from_table("test_upsert_many").column("name").order_by("id");
+        let rows = conn.select(select).await.unwrap();
+        let names: Vec<_> = rows.into_iter().map(|row| row[0].to_string().unwrap()).collect();
+
+        assert_eq!(vec!["Mustikka", "Naukio"], names);
+    }
+
+    #[tokio::test]
+    async fn insert_many_reports_the_duplicate_rows() {
+        let conn = Quaint::new("file:db/test.db").await.unwrap();
+
+        conn.raw_cmd("DROP TABLE IF EXISTS test_insert_many_duplicates")
+            .await
+            .unwrap();
+        conn.raw_cmd("CREATE TABLE test_insert_many_duplicates (id INTEGER PRIMARY KEY, name TEXT)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_insert_many_duplicates (id, name) VALUES (2, 'Musti')")
+            .await
+            .unwrap();
+
+        let insert = Insert::multi_into("test_insert_many_duplicates", vec!["id", "name"])
+            .values((1, "Naukio"))
+            .values((2, "Mustikka"))
+            .values((3, "Pertti"))
+            .values((3, "Pertti"));
+
+        let report = crate::helpers::insert_many_reporting_duplicates(&conn, insert)
+            .await
+            .unwrap();
+
+        assert_eq!(2, report.inserted);
+        assert_eq!(vec![1, 3], report.duplicates);
+
+        let select = Select::from_table("test_insert_many_duplicates")
+            .column("name")
+            .order_by("id");
+        let rows = conn.select(select).await.unwrap();
+        let names: Vec<_> = rows.into_iter().map(|row| row[0].to_string().unwrap()).collect();
+
+        assert_eq!(vec!["Naukio", "Musti", "Pertti"], names);
+    }
+
+    #[tokio::test]
+    async fn nested_transactions_use_savepoints() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+
+        let _ = conn.raw_cmd("DROP TABLE test_savepoints").await;
+        conn.raw_cmd("CREATE TABLE test_savepoints (id int)").await.unwrap();
+
+        let count = crate::test_helpers::with_rollback(&conn, |tx| {
+            Box::pin(async move {
+                tx.insert(Insert::single_into("test_savepoints").value("id", 1).into())
+                    .await?;
+
+                let nested = tx.start_transaction().await?;
+                nested
+                    .insert(Insert::single_into("test_savepoints").value("id", 2).into())
+                    .await?;
+                nested.rollback().await?;
+
+                let rows = tx.select(Select::from_table("test_savepoints")).await?;
+                Ok(rows.len())
+            })
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(1, count);
+
+        let rows = conn.select(Select::from_table("test_savepoints")).await.unwrap();
+        assert!(rows.is_empty());
+    }
+
+    #[tokio::test]
+    async fn increment_returns_the_new_value() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+
+        let _ = conn.raw_cmd("DROP TABLE test_increment").await;
+
+        conn.raw_cmd("CREATE TABLE test_increment (id int, counter int)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_increment (id, counter) VALUES (1, 41)")
+            .await
+            .unwrap();
+
+        let table = Table::from("test_increment");
+        let conditions = ConditionTree::single("id".equals(1));
+
+        let value = conn
+            .increment(table.clone(), conditions, Column::from("counter"), 1)
+            .await
+            .unwrap();
+
+        assert_eq!(Some(Value::integer(42)), value);
+
+        let conditions = ConditionTree::single("id".equals(2));
+        let value = conn
+            .increment(table, conditions, Column::from("counter"), 1)
+            .await
+            .unwrap();
+
+        assert_eq!(None, value);
+    }
+
+    #[tokio::test]
+    async fn max_interactive_rows_limits_selects_without_a_limit() {
+        let conn = Sqlite::try_from("file:db/test.db?max_interactive_rows=2").unwrap();
+
+        conn.raw_cmd("CREATE TABLE test_max_rows (id int)").await.unwrap();
+        conn.raw_cmd("INSERT INTO test_max_rows (id) VALUES (1), (2), (3)")
+            .await
+            .unwrap();
+
+        let select = Select::from_table("test_max_rows");
+
+        assert_eq!(2, conn.select(select.clone()).await.unwrap().len());
+        assert_eq!(3, conn.select(select.clone().limit(3)).await.unwrap().len());
+        assert_eq!(3, conn.select(select.unlimited()).await.unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn estimate_count_reads_the_table_statistics() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+
+        let _ = conn.raw_cmd("DROP TABLE test_estimate_count").await;
+
+        conn.raw_cmd("CREATE TABLE test_estimate_count (id int)").await.unwrap();
+        conn.raw_cmd("INSERT INTO test_estimate_count (id) VALUES (1), (2), (3)")
+            .await
+            .unwrap();
+        conn.raw_cmd("ANALYZE test_estimate_count").await.unwrap();
+        conn.raw_cmd("INSERT INTO test_estimate_count (id) VALUES (4)")
+            .await
+            .unwrap();
+
+        // The statistics are from before the last insert.
+        let estimate = crate::helpers::estimate_count(&conn, "test_estimate_count")
+            .await
+            .unwrap();
+
+        assert_eq!(3, estimate);
+
+        // Queries are counted.
+        let select = Select::from_table("test_estimate_count").so_that("id".greater_than(1));
+        let estimate = crate::helpers::estimate_count(&conn, select).await.unwrap();
+
+        assert_eq!(3, estimate);
+    }
+
+    #[tokio::test]
+    async fn update_with_diff_writes_only_the_changed_columns() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+
+        conn.raw_cmd("CREATE TABLE test_update_with_diff (id int, name text, age int)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_update_with_diff (id, name, age) VALUES (1, 'Musti', 5)")
+            .await
+            .unwrap();
+
+        let values = vec![("name", Value::from("Musti")), ("age", Value::from(6))];
+        let changes = crate::helpers::update_with_diff(&conn, "test_update_with_diff", "id".equals(1), values)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(1, changes.len());
+        assert_eq!("age", changes[0].column);
+        assert_eq!(Value::from(5), changes[0].before);
+        assert_eq!(Value::from(6), changes[0].after);
+
+        let row = conn
+            .select(Select::from_table("test_update_with_diff"))
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(Some(6), row["age"].as_i64());
+
+        let values = vec![("age", 7)];
+        let changes = crate::helpers::update_with_diff(&conn, "test_update_with_diff", "id".equals(2), values)
+            .await
+            .unwrap();
+
+        assert_eq!(None, changes);
+    }
+
+    #[tokio::test]
+    async fn update_with_a_join_is_rejected_before_reaching_the_database() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+
+        conn.raw_cmd("CREATE TABLE test_update_users (id int, name text)")
+            .await
+            .unwrap();
+        conn.raw_cmd("CREATE TABLE test_update_profiles (user_id int, name text)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_update_users (id, name) VALUES (1, 'Musti')")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_update_profiles (user_id, name) VALUES (1, 'Naukio')")
+            .await
+            .unwrap();
+
+        let join = "test_update_profiles"
+            .on(("test_update_profiles", "user_id").equals(Column::from(("test_update_users", "id"))));
+
+        let update = Update::table("test_update_users")
+            .set("name", Column::from(("test_update_profiles", "name")))
+            .inner_join(join);
+
+        let err = conn.update(update).await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::ConversionError(_)));
+
+        let row = conn
+            .select(Select::from_table("test_update_users"))
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+
+        assert_eq!(Some("Musti"), row["name"].as_str());
+    }
+
+    #[tokio::test]
+    async fn delete_returning_returns_the_deleted_rows() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+
+        let _ = conn.raw_cmd("DROP TABLE test_delete_returning").await;
+
+        conn.raw_cmd("CREATE TABLE test_delete_returning (id int, name text)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_delete_returning (id, name) VALUES (1, 'Musti'), (2, 'Naukio')")
+            .await
+            .unwrap();
+
+        let delete = Delete::from_table("test_delete_returning").so_that("id".equals(2));
+        let rows = conn.delete_returning(delete).await.unwrap();
+
+        assert_eq!(1, rows.len());
+        assert_eq!(Some(&Value::text("Naukio")), rows.get(0).unwrap().get("name"));
+
+        let rows = conn.select(Select::from_table("test_delete_returning")).await.unwrap();
+        assert_eq!(1, rows.len());
+    }
+
+    #[tokio::test]
+    async fn delete_returning_is_not_limited_by_max_interactive_rows() {
+        let conn = Sqlite::try_from("file:db/test.db?max_interactive_rows=1").unwrap();
+
+        let _ = conn.raw_cmd("DROP TABLE test_delete_returning_max_rows").await;
+
+        conn.raw_cmd("CREATE TABLE test_delete_returning_max_rows (id int)")
+            .await
+            .unwrap();
+        conn.raw_cmd("INSERT INTO test_delete_returning_max_rows (id) VALUES (1), (2), (3)")
+            .await
+            .unwrap();
+
+        let delete = Delete::from_table("test_delete_returning_max_rows").so_that("id".greater_than(1));
+        let rows = conn.delete_returning(delete).await.unwrap();
+
+        assert_eq!(2, rows.len());
+    }
+
+    #[tokio::test]
+    async fn execute_result_has_the_inserted_id() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+
+        let _ = conn.raw_cmd("DROP TABLE test_execute_result").await;
+
+        conn.raw_cmd("CREATE TABLE test_execute_result (id INTEGER PRIMARY KEY, name TEXT)")
+            .await
+            .unwrap();
+
+        let insert = Insert::multi_into("test_execute_result", vec!["name"])
+            .values(vec!["Musti"])
+            .values(vec!["Naukio"]);
+
+        let res = conn.execute_result(insert.into()).await.unwrap();
+
+        assert_eq!(2, res.rows_affected());
+        assert_eq!(Some(2), res.last_insert_id());
+    }
+
     #[tokio::test]
     async fn test_uniq_constraint_violation() {
         let conn = Sqlite::try_from("file:db/test.db").unwrap();
@@ -498,6 +1112,21 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_driver_error_is_kept_as_source() {
+        let conn = Sqlite::try_from("file:db/test.db").unwrap();
+        let err = conn.raw_cmd("SELECT * FROM test_no_such_table").await.unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::TableDoesNotExist { .. }));
+
+        let description = match err.downcast_ref::<rusqlite::Error>() {
+            Some(rusqlite::Error::SqliteFailure(_, description)) => description.as_deref(),
+            _ => None,
+        };
+
+        assert_eq!(Some("no such table: test_no_such_table"), description);
+    }
+
     #[tokio::test]
     async fn test_null_constraint_violation() {
         let conn = Sqlite::try_from("file:db/test.db").unwrap();
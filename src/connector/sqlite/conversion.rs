@@ -178,15 +178,9 @@ impl<'a> ToSql for Value<'a> {
             Value::Char(c) => c.map(|c| ToSqlOutput::from(c as u8)),
             Value::Bytes(bytes) => bytes.as_ref().map(|bytes| ToSqlOutput::from(bytes.as_ref())),
             #[cfg(feature = "array")]
-            Value::Array(_) => {
-                let msg = "Arrays are not supported in SQLite.";
-                let kind = ErrorKind::conversion(msg);
-
-                let mut builder = Error::builder(kind);
-                builder.set_original_message(msg);
-
-                Err(RusqlError::ToSqlConversionFailure(Box::new(builder.build())))?
-            }
+            Value::Array(_) => Err(RusqlError::ToSqlConversionFailure(Box::new(Error::unsupported(
+                "Arrays are not supported in SQLite.",
+            ))))?,
             #[cfg(feature = "json-1")]
             Value::Json(value) => value.as_ref().map(|value| {
                 let stringified = serde_json::to_string(value)
@@ -195,6 +189,10 @@ impl<'a> ToSql for Value<'a> {
 
                 ToSqlOutput::from(stringified)
             }),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(bits) => bits
+                .as_ref()
+                .map(|bits| ToSqlOutput::from(crate::ast::bits_to_string(bits))),
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(value) => value.map(|value| ToSqlOutput::from(value.to_hyphenated().to_string())),
             #[cfg(feature = "chrono-0_4")]
@@ -212,6 +210,11 @@ impl<'a> ToSql for Value<'a> {
 
                 ToSqlOutput::from(dt.timestamp_millis())
             }),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(interval) => interval.map(|interval| {
+                let seconds = crate::ast::interval_seconds(interval);
+                ToSqlOutput::from(seconds.to_f64().expect("Interval is not a f64."))
+            }),
         };
 
         match value {
@@ -38,7 +38,7 @@ impl From<rusqlite::Error> for Error {
                     code: ffi::ErrorCode::ConstraintViolation,
                     extended_code: 2067,
                 },
-                Some(description),
+                Some(ref description),
             ) => {
                 let splitted: Vec<&str> = description.split(": ").collect();
 
@@ -54,6 +54,7 @@ impl From<rusqlite::Error> for Error {
 
                 builder.set_original_code("2067");
                 builder.set_original_message(description);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -63,7 +64,7 @@ impl From<rusqlite::Error> for Error {
                     code: ffi::ErrorCode::ConstraintViolation,
                     extended_code: 1555,
                 },
-                Some(description),
+                Some(ref description),
             ) => {
                 let splitted: Vec<&str> = description.split(": ").collect();
 
@@ -79,6 +80,7 @@ impl From<rusqlite::Error> for Error {
 
                 builder.set_original_code("1555");
                 builder.set_original_message(description);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -88,7 +90,7 @@ impl From<rusqlite::Error> for Error {
                     code: ffi::ErrorCode::ConstraintViolation,
                     extended_code: 1299,
                 },
-                Some(description),
+                Some(ref description),
             ) => {
                 let splitted: Vec<&str> = description.split(": ").collect();
 
@@ -104,6 +106,7 @@ impl From<rusqlite::Error> for Error {
 
                 builder.set_original_code("1299");
                 builder.set_original_message(description);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -113,7 +116,7 @@ impl From<rusqlite::Error> for Error {
                     code: ffi::ErrorCode::ConstraintViolation,
                     extended_code: 787,
                 },
-                Some(description),
+                Some(ref description),
             ) => {
                 let mut builder = Error::builder(ErrorKind::ForeignKeyConstraintViolation {
                     constraint: DatabaseConstraint::ForeignKey,
@@ -121,6 +124,7 @@ impl From<rusqlite::Error> for Error {
 
                 builder.set_original_code("787");
                 builder.set_original_message(description);
+                builder.set_source(e);
 
                 builder.build()
             }
@@ -130,7 +134,7 @@ impl From<rusqlite::Error> for Error {
                     code: ffi::ErrorCode::DatabaseBusy,
                     extended_code,
                 },
-                description,
+                ref description,
             ) => {
                 let mut builder = Error::builder(ErrorKind::Timeout("SQLite database is busy".into()));
                 builder.set_original_code(format!("{}", extended_code));
@@ -139,6 +143,7 @@ impl From<rusqlite::Error> for Error {
                     builder.set_original_message(description);
                 }
 
+                builder.set_source(e);
                 builder.build()
             }
 
@@ -149,6 +154,7 @@ impl From<rusqlite::Error> for Error {
                     let mut builder = Error::builder(ErrorKind::TableDoesNotExist { table });
                     builder.set_original_code(format!("{}", extended_code));
                     builder.set_original_message(d);
+                    builder.set_source(e);
 
                     builder.build()
                 }
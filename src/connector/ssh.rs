@@ -0,0 +1,270 @@
+//! Tunneling database connections through an SSH server.
+//!
+//! A tunnel listens on a random local port, and every TCP connection accepted
+//! there is forwarded to the database through a `direct-tcpip` channel. The
+//! SSH session is shared by all connections of a tunnel and opened again if it
+//! was lost, so reconnecting to the database works as without a tunnel.
+
+use crate::error::{Error, ErrorKind};
+use futures::{future, lock::Mutex};
+use std::{io, net::SocketAddr, sync::Arc};
+use thrussh::{client, ChannelMsg};
+use thrussh_keys::key;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+};
+
+fn ssh_error(message: impl std::fmt::Display) -> Error {
+    let error = io::Error::new(io::ErrorKind::Other, format!("SSH tunnel: {}", message));
+    Error::builder(ErrorKind::ConnectionError(Box::new(error))).build()
+}
+
+/// SSH server settings, parsed from the `ssh_host`, `ssh_port`, `ssh_user`,
+/// `ssh_key` and `ssh_key_password` connection parameters.
+#[derive(Clone, Default)]
+pub struct SshParams {
+    host: Option<String>,
+    port: Option<u16>,
+    user: Option<String>,
+    key_file: Option<String>,
+    key_password: Option<String>,
+}
+
+impl std::fmt::Debug for SshParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SshParams")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("user", &self.user)
+            .field("key_file", &self.key_file)
+            .field("key_password", &self.key_password.as_ref().map(|_| "****"))
+            .finish()
+    }
+}
+
+impl SshParams {
+    /// Sets one of the `ssh_*` connection parameters.
+    pub(crate) fn set(&mut self, key: &str, value: &str) -> crate::Result<()> {
+        match key {
+            "ssh_host" => self.host = Some(value.to_string()),
+            "ssh_port" => {
+                let port = value
+                    .parse()
+                    .map_err(|_| Error::builder(ErrorKind::InvalidConnectionArguments).build())?;
+
+                self.port = Some(port);
+            }
+            "ssh_user" => self.user = Some(value.to_string()),
+            "ssh_key" => self.key_file = Some(value.to_string()),
+            "ssh_key_password" => self.key_password = Some(value.to_string()),
+            _ => unreachable!("Not an SSH parameter: {}", key),
+        }
+
+        Ok(())
+    }
+
+    /// The parameters if a tunnel was requested with `ssh_host`. Fails if the
+    /// user or the key is missing.
+    pub(crate) fn build(self) -> crate::Result<Option<Self>> {
+        if self.host.is_none() {
+            return Ok(None);
+        }
+
+        if self.user.is_none() || self.key_file.is_none() {
+            let kind = ErrorKind::DatabaseUrlIsInvalid("`ssh_host` requires `ssh_user` and `ssh_key`.".into());
+            return Err(Error::builder(kind).build());
+        }
+
+        Ok(Some(self))
+    }
+
+    /// The SSH server host.
+    pub fn host(&self) -> &str {
+        self.host.as_deref().unwrap_or("localhost")
+    }
+
+    /// The SSH server port, defaults to `22`.
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(22)
+    }
+
+    /// The user to log in to the SSH server.
+    pub fn user(&self) -> &str {
+        self.user.as_deref().unwrap_or("")
+    }
+}
+
+/// Accepts the server key only if found in the user's `known_hosts` file.
+struct KnownHosts {
+    host: String,
+    port: u16,
+}
+
+impl client::Handler for KnownHosts {
+    type Error = thrussh::Error;
+    type FutureUnit = future::Ready<Result<(Self, client::Session), Self::Error>>;
+    type FutureBool = future::Ready<Result<(Self, bool), Self::Error>>;
+
+    fn finished_bool(self, b: bool) -> Self::FutureBool {
+        future::ready(Ok((self, b)))
+    }
+
+    fn finished(self, session: client::Session) -> Self::FutureUnit {
+        future::ready(Ok((self, session)))
+    }
+
+    fn check_server_key(self, server_public_key: &key::PublicKey) -> Self::FutureBool {
+        let known = thrussh_keys::check_known_hosts(&self.host, self.port, server_public_key).unwrap_or(false);
+        self.finished_bool(known)
+    }
+}
+
+struct Session {
+    params: SshParams,
+    handle: Mutex<Option<client::Handle<KnownHosts>>>,
+}
+
+impl Session {
+    async fn connect(&self) -> crate::Result<client::Handle<KnownHosts>> {
+        let key_file = self.params.key_file.as_deref().unwrap_or("");
+        let key = thrussh_keys::load_secret_key(key_file, self.params.key_password.as_deref()).map_err(ssh_error)?;
+
+        let handler = KnownHosts {
+            host: self.params.host().to_string(),
+            port: self.params.port(),
+        };
+
+        let config = Arc::new(client::Config::default());
+        let address = (self.params.host(), self.params.port());
+        let mut handle = client::connect(config, address, handler).await.map_err(ssh_error)?;
+
+        let authenticated = handle
+            .authenticate_publickey(self.params.user(), Arc::new(key))
+            .await
+            .map_err(ssh_error)?;
+
+        if !authenticated {
+            return Err(ssh_error("authentication failed"));
+        }
+
+        Ok(handle)
+    }
+
+    /// Opens a channel to the database, connecting to the SSH server first if
+    /// not connected or if the previous session is not usable anymore.
+    async fn open_channel(&self, host: &str, port: u16) -> crate::Result<client::Channel> {
+        let mut handle = self.handle.lock().await;
+
+        if let Some(ref mut session) = *handle {
+            if let Ok(channel) = session
+                .channel_open_direct_tcpip(host, port as u32, "127.0.0.1", 0)
+                .await
+            {
+                return Ok(channel);
+            }
+        }
+
+        let mut session = self.connect().await?;
+
+        let channel = session
+            .channel_open_direct_tcpip(host, port as u32, "127.0.0.1", 0)
+            .await
+            .map_err(ssh_error)?;
+
+        *handle = Some(session);
+
+        Ok(channel)
+    }
+}
+
+/// A local port forwarded to the database through an SSH server. Dropping
+/// the tunnel stops accepting new connections.
+pub(crate) struct SshTunnel {
+    local_addr: SocketAddr,
+    _shutdown: oneshot::Sender<()>,
+}
+
+impl std::fmt::Debug for SshTunnel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SshTunnel({})", self.local_addr)
+    }
+}
+
+impl SshTunnel {
+    /// Starts forwarding a local port to `host:port`, as seen from the SSH
+    /// server. The SSH connection is opened when the first database
+    /// connection is made.
+    pub(crate) fn new(params: SshParams, host: &str, port: u16) -> crate::Result<Self> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let local_addr = listener.local_addr()?;
+        let mut listener = TcpListener::from_std(listener)?;
+
+        let session = Arc::new(Session {
+            params,
+            handle: Mutex::new(None),
+        });
+
+        let host = host.to_string();
+        let (shutdown, mut stopped) = oneshot::channel();
+
+        tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    _ = &mut stopped => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(_) => break,
+                    },
+                };
+
+                let session = session.clone();
+                let host = host.clone();
+
+                tokio::spawn(async move {
+                    if let Err(e) = forward(&session, stream, &host, port).await {
+                        #[cfg(not(feature = "tracing-log"))]
+                        error!("Error in SSH tunnel: {}", e);
+                        #[cfg(feature = "tracing-log")]
+                        tracing::error!("Error in SSH tunnel: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            local_addr,
+            _shutdown: shutdown,
+        })
+    }
+
+    /// The local address to connect to instead of the database.
+    pub(crate) fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+async fn forward(session: &Session, mut stream: TcpStream, host: &str, port: u16) -> crate::Result<()> {
+    let mut channel = session.open_channel(host, port).await?;
+    let mut buf = vec![0; 8192];
+
+    loop {
+        tokio::select! {
+            read = stream.read(&mut buf) => match read? {
+                0 => {
+                    channel.eof().await.map_err(ssh_error)?;
+                    break;
+                }
+                n => channel.data(&buf[..n]).await.map_err(ssh_error)?,
+            },
+            msg = channel.wait() => match msg {
+                Some(ChannelMsg::Data { ref data }) => stream.write_all(data).await?,
+                Some(ChannelMsg::Eof) | Some(ChannelMsg::Close) | None => break,
+                Some(_) => (),
+            },
+        }
+    }
+
+    Ok(())
+}
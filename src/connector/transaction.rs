@@ -1,19 +1,163 @@
 use super::*;
-use crate::ast::*;
+use crate::{
+    ast::*,
+    error::{Error, ErrorKind},
+};
 use async_trait::async_trait;
+use futures::future::AbortHandle;
+use std::{
+    future::Future,
+    mem,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+type CommitHook = Box<dyn FnOnce() + Send>;
+type JournalObserver = Box<dyn FnOnce(&Journal) + Send>;
+
+/// A statement run in a transaction, as recorded in its [`Journal`].
+///
+/// [`Journal`]: struct.Journal.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// The SQL of the statement, with placeholders in place of the
+    /// parameters.
+    pub fingerprint: String,
+    /// When the statement started, counted from the start of the
+    /// transaction.
+    pub started_at: Duration,
+    /// How long the statement took.
+    pub duration: Duration,
+    /// The number of rows returned or affected. `None` for commands and
+    /// failed statements. An increment is recorded as its `UPDATE`, with one
+    /// row if it found a row to increment.
+    pub rows: Option<u64>,
+}
+
+/// How a journaled transaction ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionOutcome {
+    Committed,
+    RolledBack,
+    /// Dropped without a commit or a rollback.
+    Dropped,
+}
+
+/// The statements run in a transaction, handed to the observer given to
+/// [`record_journal`] when the transaction ends.
+///
+/// [`record_journal`]: struct.Transaction.html#method.record_journal
+#[derive(Debug, Clone, PartialEq)]
+pub struct Journal {
+    /// The statements, in the order they were run.
+    pub entries: Vec<JournalEntry>,
+    /// How long the transaction was open.
+    pub duration: Duration,
+    pub outcome: TransactionOutcome,
+}
+
+impl Journal {
+    /// The time the transaction spent between its statements, waiting for
+    /// the application.
+    pub fn idle_time(&self) -> Duration {
+        let busy: Duration = self.entries.iter().map(|entry| entry.duration).sum();
+        self.duration.checked_sub(busy).unwrap_or_default()
+    }
+}
+
+/// When the transaction was last used, for the idle timeout.
+struct Idle {
+    timeout: Option<Duration>,
+    last_used: Instant,
+    /// A statement is running, so the transaction is not idle.
+    busy: bool,
+    expired: bool,
+}
+
+impl Idle {
+    fn new() -> Self {
+        Self {
+            timeout: None,
+            last_used: Instant::now(),
+            busy: false,
+            expired: false,
+        }
+    }
+
+    /// Marks the transaction expired if it has been idle for its timeout.
+    fn expire(&mut self) -> IdleState {
+        match self.timeout {
+            None => IdleState::Off,
+            Some(_) if self.expired => IdleState::Off,
+            Some(timeout) if self.busy => IdleState::Wait(timeout),
+            Some(timeout) => match timeout.checked_sub(self.last_used.elapsed()) {
+                Some(left) if left > Duration::default() => IdleState::Wait(left),
+                _ => {
+                    self.expired = true;
+                    IdleState::Expired(timeout)
+                }
+            },
+        }
+    }
+}
+
+/// Marks the transaction idle again when the statement finishes, also if
+/// its future is dropped before that.
+struct Busy<'a>(&'a Mutex<Idle>);
+
+impl<'a> Drop for Busy<'a> {
+    fn drop(&mut self) {
+        let mut idle = self.0.lock().unwrap();
+        idle.last_used = Instant::now();
+        idle.busy = false;
+    }
+}
+
+enum IdleState {
+    /// No timeout, or the transaction has expired already.
+    Off,
+    /// The transaction can stay idle for the given time.
+    Wait(Duration),
+    /// The transaction has just expired after the given timeout.
+    Expired(Duration),
+}
 
 /// A representation of an SQL database transaction. If not commited, a
 /// transaction will be rolled back by default when dropped.
 ///
-/// Currently does not support nesting, so starting a new transaction using the
-/// transaction object will panic.
+/// Starting a new transaction using the transaction object creates a
+/// savepoint, which is released on commit and rolled back to on rollback
+/// without affecting the outer transaction.
 pub struct Transaction<'a> {
     pub(crate) inner: &'a dyn Queryable,
+    savepoint: Option<String>,
+    depth: usize,
+    steps: AtomicUsize,
+    commit_hooks: Mutex<Vec<CommitHook>>,
+    parent_hooks: Option<&'a Mutex<Vec<CommitHook>>>,
+    started: Instant,
+    journal: Mutex<Option<(Vec<JournalEntry>, JournalObserver)>>,
+    idle: Arc<Mutex<Idle>>,
+    idle_timer: Mutex<Option<AbortHandle>>,
 }
 
 impl<'a> Transaction<'a> {
     pub(crate) async fn new(inner: &'a dyn Queryable, begin_stmt: &str) -> crate::Result<Transaction<'a>> {
-        let this = Self { inner };
+        let this = Self {
+            inner,
+            savepoint: None,
+            depth: 0,
+            steps: AtomicUsize::new(0),
+            commit_hooks: Mutex::new(Vec::new()),
+            parent_hooks: None,
+            started: Instant::now(),
+            journal: Mutex::new(None),
+            idle: Arc::new(Mutex::new(Idle::new())),
+            idle_timer: Mutex::new(None),
+        };
 
         inner.raw_cmd(begin_stmt).await?;
         inner.server_reset_query(&this).await?;
@@ -21,44 +165,538 @@ impl<'a> Transaction<'a> {
         Ok(this)
     }
 
+    async fn nested(parent: &'a Transaction<'_>) -> crate::Result<Transaction<'a>> {
+        let depth = parent.depth + 1;
+        let name = format!("quaint_savepoint_{}", depth);
+
+        parent.raw_cmd(&parent.create_savepoint_statement(&name)).await?;
+
+        Ok(Self {
+            inner: parent,
+            savepoint: Some(name),
+            depth,
+            steps: AtomicUsize::new(0),
+            commit_hooks: Mutex::new(Vec::new()),
+            parent_hooks: Some(&parent.commit_hooks),
+            started: Instant::now(),
+            journal: Mutex::new(None),
+            idle: Arc::new(Mutex::new(Idle::new())),
+            idle_timer: Mutex::new(None),
+        })
+    }
+
+    /// Registers a callback to run after the transaction is committed. The
+    /// callbacks run in the order they were added, and are dropped without
+    /// running if the transaction is rolled back or dropped.
+    ///
+    /// The callbacks of a nested transaction are handed to the outer one on
+    /// commit, and only run when the outermost transaction commits.
+    pub fn on_commit<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.commit_hooks.lock().unwrap().push(Box::new(f));
+    }
+
+    /// Records the statements run in the transaction from now on, with their
+    /// durations and row counts, and hands the journal to `observer` when
+    /// the transaction is committed, rolled back or dropped. Meant for
+    /// finding out why a transaction was held open for long.
+    ///
+    /// The statements of nested transactions are recorded too. Calling this
+    /// again replaces the observer, keeping the statements recorded so far.
+    ///
+    /// ```no_run
+    /// # use quaint::{prelude::*, single::Quaint};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), quaint::error::Error> {
+    /// let conn = Quaint::new("file:///tmp/example.db").await?;
+    /// let tx = conn.start_transaction().await?;
+    ///
+    /// tx.record_journal(|journal| {
+    ///     for entry in journal.entries.iter() {
+    ///         println!("{:?} {}", entry.duration, entry.fingerprint);
+    ///     }
+    /// });
+    ///
+    /// tx.select(Select::from_table("users")).await?;
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn record_journal<F>(&self, observer: F)
+    where
+        F: FnOnce(&Journal) + Send + 'static,
+    {
+        let mut journal = self.journal.lock().unwrap();
+
+        let entries = match journal.take() {
+            Some((entries, _)) => entries,
+            None => Vec::new(),
+        };
+
+        *journal = Some((entries, Box::new(observer)));
+    }
+
+    /// Rolls the transaction back if no statement is run in it for longer
+    /// than `timeout`, to catch transactions left open by mistake, such as
+    /// by a forgotten `await`. The rollback is run from a spawned task as
+    /// soon as the timeout passes, releasing the locks of the transaction.
+    /// The next statement or commit fails with a `TransactionIdleTimeout`
+    /// error, and so do all the later ones.
+    ///
+    /// Connections without a [command
+    /// handle](trait.Queryable.html#method.command_handle) are rolled back
+    /// only when the transaction is used next, keeping the locks until then.
+    ///
+    /// ```no_run
+    /// # use quaint::{prelude::*, single::Quaint};
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), quaint::error::Error> {
+    /// let conn = Quaint::new("file:///tmp/example.db").await?;
+    /// let tx = conn.start_transaction().await?;
+    /// tx.set_idle_timeout(Duration::from_secs(30));
+    ///
+    /// tx.select(Select::from_table("users")).await?;
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_idle_timeout(&self, timeout: Duration) {
+        {
+            let mut idle = self.idle.lock().unwrap();
+            idle.timeout = Some(timeout);
+            idle.last_used = Instant::now();
+        }
+
+        self.start_idle_timer();
+    }
+
+    fn start_idle_timer(&self) {
+        let handle = match self.inner.command_handle() {
+            Some(handle) => handle,
+            None => return,
+        };
+
+        let rollback = match self.savepoint {
+            Some(ref name) => self.inner.rollback_to_savepoint_statement(name),
+            None => String::from("ROLLBACK"),
+        };
+
+        let (timer, abort) = futures::future::abortable(idle_timer(self.idle.clone(), handle, rollback));
+
+        if let Some(previous) = self.idle_timer.lock().unwrap().replace(abort) {
+            previous.abort();
+        }
+
+        tokio::spawn(timer);
+    }
+
+    /// Turns the idle timeout off when the transaction ends.
+    fn stop_idle_timer(&self) {
+        self.idle.lock().unwrap().timeout = None;
+
+        if let Some(timer) = self.idle_timer.lock().unwrap().take() {
+            timer.abort();
+        }
+    }
+
+    /// Inserts an event row as part of the transaction, and calls `notify`
+    /// once the transaction is committed. Meant for the transactional outbox
+    /// pattern: the event is stored together with the changes it describes,
+    /// and a dispatcher is woken up to publish it only if the changes were
+    /// written.
+    ///
+    /// ```no_run
+    /// # use quaint::{prelude::*, single::Quaint};
+    /// # use std::sync::mpsc;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), quaint::error::Error> {
+    /// let conn = Quaint::new("file:///tmp/example.db").await?;
+    /// let (dispatcher, events) = mpsc::channel();
+    ///
+    /// let tx = conn.start_transaction().await?;
+    /// tx.insert(Insert::single_into("users").value("name", "Musti").into()).await?;
+    ///
+    /// let event = Insert::single_into("outbox").value("kind", "user_created");
+    /// tx.insert_outbox_event(event.into(), move || dispatcher.send(()).unwrap()).await?;
+    ///
+    /// tx.commit().await?;
+    /// assert!(events.try_recv().is_ok());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_outbox_event<F>(&self, event: Insert<'_>, notify: F) -> crate::Result<()>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.execute(event.into()).await?;
+        self.on_commit(notify);
+
+        Ok(())
+    }
+
+    /// Runs one step of a batch inside a savepoint. If the step fails, only
+    /// its changes are rolled back and the transaction stays usable for the
+    /// next steps. The outer result is an error only if the savepoint itself
+    /// could not be created or restored, the inner one is the outcome of the
+    /// step.
+    ///
+    /// Commit callbacks registered by a failed step are dropped.
+    ///
+    /// ```no_run
+    /// # use quaint::{prelude::*, single::Quaint};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), quaint::error::Error> {
+    /// let conn = Quaint::new("file:///tmp/example.db").await?;
+    /// let tx = conn.start_transaction().await?;
+    /// let mut skipped = Vec::new();
+    ///
+    /// for name in vec!["Musti", "Naukio"] {
+    ///     let insert = Insert::single_into("users").value("name", name);
+    ///     let outcome = tx.try_step(|| tx.insert(insert.into())).await?;
+    ///
+    ///     if outcome.is_err() {
+    ///         skipped.push(name);
+    ///     }
+    /// }
+    ///
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn try_step<F, Fut, T>(&self, step: F) -> crate::Result<crate::Result<T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = crate::Result<T>>,
+    {
+        let step_number = self.steps.fetch_add(1, Ordering::SeqCst);
+        let name = format!("quaint_step_{}_{}", self.depth, step_number);
+        let hooks = self.commit_hooks.lock().unwrap().len();
+
+        self.raw_cmd(&self.create_savepoint_statement(&name)).await?;
+
+        let outcome = step().await;
+
+        if outcome.is_err() {
+            self.raw_cmd(&self.rollback_to_savepoint_statement(&name)).await?;
+            self.commit_hooks.lock().unwrap().truncate(hooks);
+        }
+
+        // Released also after a rollback, not to pile up savepoints in
+        // long batches.
+        if let Some(stmt) = self.release_savepoint_statement(&name) {
+            self.raw_cmd(&stmt).await?;
+        }
+
+        Ok(outcome)
+    }
+
     /// Commit the changes to the database and consume the transaction.
     pub async fn commit(&self) -> crate::Result<()> {
-        self.inner.raw_cmd("COMMIT").await?;
+        self.check_idle().await?;
+        self.stop_idle_timer();
+
+        match self.savepoint {
+            Some(ref name) => {
+                if let Some(stmt) = self.inner.release_savepoint_statement(name) {
+                    self.inner.raw_cmd(&stmt).await?;
+                }
+            }
+            None => self.inner.raw_cmd("COMMIT").await?,
+        }
+
+        self.finish_journal(TransactionOutcome::Committed);
+
+        let hooks = mem::take(&mut *self.commit_hooks.lock().unwrap());
+
+        match self.parent_hooks {
+            Some(parent_hooks) => parent_hooks.lock().unwrap().extend(hooks),
+            None => hooks.into_iter().for_each(|hook| hook()),
+        }
 
         Ok(())
     }
 
     /// Rolls back the changes to the database.
     pub async fn rollback(&self) -> crate::Result<()> {
-        self.inner.raw_cmd("ROLLBACK").await?;
+        // Rolled back already when the idle timeout passed.
+        if self.idle.lock().unwrap().expired {
+            return Ok(());
+        }
+
+        self.stop_idle_timer();
+        self.roll_back().await
+    }
+
+    async fn roll_back(&self) -> crate::Result<()> {
+        match self.savepoint {
+            Some(ref name) => {
+                let stmt = self.inner.rollback_to_savepoint_statement(name);
+                self.inner.raw_cmd(&stmt).await?;
+            }
+            None => self.inner.raw_cmd("ROLLBACK").await?,
+        }
+
+        self.commit_hooks.lock().unwrap().clear();
+        self.finish_journal(TransactionOutcome::RolledBack);
 
         Ok(())
     }
+
+    /// Fails if the transaction has been idle for longer than its timeout,
+    /// rolling it back unless the idle timer did it already. Otherwise marks
+    /// the transaction busy until the statement finishes.
+    async fn check_idle(&self) -> crate::Result<()> {
+        let (timeout, rolled_back) = {
+            let mut idle = self.idle.lock().unwrap();
+
+            match (idle.timeout, idle.expired) {
+                (Some(timeout), true) => (timeout, true),
+                _ => match idle.expire() {
+                    IdleState::Expired(timeout) => (timeout, false),
+                    _ => {
+                        idle.last_used = Instant::now();
+                        idle.busy = true;
+                        return Ok(());
+                    }
+                },
+            }
+        };
+
+        if rolled_back {
+            self.commit_hooks.lock().unwrap().clear();
+            self.finish_journal(TransactionOutcome::RolledBack);
+        } else {
+            self.roll_back().await?;
+        }
+
+        Err(idle_timeout(timeout))
+    }
+
+    /// Runs a statement of the transaction, recording it in the journal.
+    async fn run<T, F, R>(&self, fingerprint: Option<String>, statement: F, rows: R) -> crate::Result<T>
+    where
+        F: Future<Output = crate::Result<T>>,
+        R: FnOnce(&T) -> Option<u64>,
+    {
+        self.check_idle().await?;
+        let busy = Busy(&self.idle);
+
+        let start = Instant::now();
+        let result = statement.await;
+
+        drop(busy);
+        self.record(fingerprint, start, result.as_ref().ok().and_then(rows));
+
+        result
+    }
+
+    /// The fingerprint of the query, if the journal is recorded.
+    fn fingerprint<'q, F>(&self, q: F) -> Option<String>
+    where
+        F: FnOnce() -> Query<'q>,
+    {
+        if self.journal.lock().unwrap().is_none() {
+            return None;
+        }
+
+        let family = self.connection_info().sql_family();
+        let fingerprint = match family.build(q()) {
+            Ok((sql, _)) => sql,
+            Err(_) => String::from("<invalid query>"),
+        };
+
+        Some(fingerprint)
+    }
+
+    fn raw_fingerprint(&self, sql: &str) -> Option<String> {
+        self.journal.lock().unwrap().as_ref().map(|_| sql.to_string())
+    }
+
+    fn record(&self, fingerprint: Option<String>, start: Instant, rows: Option<u64>) {
+        if let (Some(fingerprint), Some((entries, _))) = (fingerprint, self.journal.lock().unwrap().as_mut()) {
+            entries.push(JournalEntry {
+                fingerprint,
+                started_at: start.duration_since(self.started),
+                duration: start.elapsed(),
+                rows,
+            });
+        }
+    }
+
+    fn finish_journal(&self, outcome: TransactionOutcome) {
+        if let Some((entries, observer)) = self.journal.lock().unwrap().take() {
+            let journal = Journal {
+                entries,
+                duration: self.started.elapsed(),
+                outcome,
+            };
+
+            observer(&journal);
+        }
+    }
+}
+
+fn idle_timeout(timeout: Duration) -> Error {
+    Error::builder(ErrorKind::TransactionIdleTimeout { timeout }).build()
+}
+
+/// Runs the rollback once the transaction has been idle for longer than its
+/// timeout. A statement run in the meantime postpones it.
+async fn idle_timer(idle: Arc<Mutex<Idle>>, handle: CommandHandle, rollback: String) {
+    loop {
+        let wait = match idle.lock().unwrap().expire() {
+            IdleState::Off => return,
+            IdleState::Wait(wait) => wait,
+            IdleState::Expired(_) => break,
+        };
+
+        tokio::time::delay_for(wait).await;
+    }
+
+    // If the rollback fails, the connection is most likely gone and the
+    // database ends the transaction with it.
+    let _ = handle(rollback).await;
+}
+
+impl<'a> Drop for Transaction<'a> {
+    fn drop(&mut self) {
+        if let Some(timer) = self.idle_timer.lock().unwrap().take() {
+            timer.abort();
+        }
+
+        let outcome = if self.idle.lock().unwrap().expired {
+            TransactionOutcome::RolledBack
+        } else {
+            TransactionOutcome::Dropped
+        };
+
+        self.finish_journal(outcome);
+    }
+}
+
+#[async_trait]
+impl<'a> TransactionCapable for Transaction<'a> {
+    async fn start_transaction(&self) -> crate::Result<Transaction<'_>> {
+        Transaction::nested(self).await
+    }
 }
 
 #[async_trait]
 impl<'a> Queryable for Transaction<'a> {
     async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
-        self.inner.query(q).await
+        let fingerprint = self.fingerprint(|| q.clone());
+        self.run(fingerprint, self.inner.query(q), |rows| Some(rows.len() as u64))
+            .await
     }
 
     async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
-        self.inner.execute(q).await
+        let fingerprint = self.fingerprint(|| q.clone());
+        self.run(fingerprint, self.inner.execute(q), |count| Some(*count)).await
     }
 
     async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
-        self.inner.query_raw(sql, params).await
+        let fingerprint = self.raw_fingerprint(sql);
+        self.run(fingerprint, self.inner.query_raw(sql, params), |rows| {
+            Some(rows.len() as u64)
+        })
+        .await
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
-        self.inner.execute_raw(sql, params).await
+        let fingerprint = self.raw_fingerprint(sql);
+        self.run(fingerprint, self.inner.execute_raw(sql, params), |count| Some(*count))
+            .await
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        let fingerprint = self.fingerprint(|| q.clone());
+        self.run(fingerprint, self.inner.execute_result(q), |res| {
+            Some(res.rows_affected())
+        })
+        .await
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        let fingerprint = self.raw_fingerprint(sql);
+        let statement = self.inner.execute_raw_result(sql, params);
+
+        self.run(fingerprint, statement, |res| Some(res.rows_affected())).await
     }
 
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
-        self.inner.raw_cmd(cmd).await
+        let fingerprint = self.raw_fingerprint(cmd);
+        self.run(fingerprint, self.inner.raw_cmd(cmd), |_| None).await
     }
 
     async fn version(&self) -> crate::Result<Option<String>> {
         self.inner.version().await
     }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        self.inner.connection_info()
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        self.inner.command_handle()
+    }
+
+    async fn upsert(&self, q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        let fingerprint = self.fingerprint(|| q.clone().into());
+        self.run(fingerprint, self.inner.upsert(q), |rows| Some(rows.len() as u64))
+            .await
+    }
+
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        // Without `RETURNING` the connection would start a transaction of
+        // its own, so the rows are selected and deleted in this one.
+        if self.connection_info().supports_delete_returning() {
+            let fingerprint = self.fingerprint(|| q.clone().into());
+            self.run(fingerprint, self.inner.delete_returning(q), |rows| {
+                Some(rows.len() as u64)
+            })
+            .await
+        } else {
+            select_and_delete(self, q).await
+        }
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        // The connection would start a transaction of its own, so the column
+        // is updated and read in this one.
+        if self.connection_info().increment_starts_a_transaction() {
+            return update_and_select(self, table, conditions, column, amount).await;
+        }
+
+        let fingerprint = self.fingerprint(|| {
+            Update::table(table.clone())
+                .increment(column.clone(), amount)
+                .so_that(conditions.clone())
+                .into()
+        });
+
+        let statement = self.inner.increment(table, conditions, column, amount);
+        self.run(fingerprint, statement, |value| Some(value.is_some() as u64))
+            .await
+    }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        self.inner.create_savepoint_statement(name)
+    }
+
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        self.inner.release_savepoint_statement(name)
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        self.inner.rollback_to_savepoint_statement(name)
+    }
 }
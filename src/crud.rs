@@ -0,0 +1,118 @@
+//! Shortcuts for building the queries of simple CRUD operations on one row,
+//! identified by the value of its id column. The queries are the usual AST
+//! builders, so they can be refined further before running them.
+//!
+//! ```rust
+//! # use quaint::{ast::*, crud, visitor::{Visitor, Postgres}};
+//! # fn main() -> Result<(), quaint::error::Error> {
+//! let query = crud::find_by_id("users", "id", 1).column("name");
+//! let (sql, params) = Postgres::build(query)?;
+//!
+//! assert_eq!("SELECT \"name\" FROM \"users\" WHERE \"id\" = $1", sql);
+//! assert_eq!(vec![Value::from(1)], params);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::ast::*;
+
+/// Selects the row with the given id.
+///
+/// ```rust
+/// # use quaint::{ast::*, crud, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let (sql, params) = Sqlite::build(crud::find_by_id("users", "id", 1))?;
+///
+/// assert_eq!("SELECT `users`.* FROM `users` WHERE `id` = ?", sql);
+/// assert_eq!(vec![Value::from(1)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn find_by_id<'a, T, C, V>(table: T, id_column: C, id: V) -> Select<'a>
+where
+    T: Into<Table<'a>>,
+    C: Into<Column<'a>>,
+    V: Into<Value<'a>>,
+{
+    Select::from_table(table).so_that(id_column.into().equals(id.into()))
+}
+
+/// Inserts one row with the given column and value pairs, e.g. from a
+/// `HashMap` or a `Vec` of tuples.
+///
+/// ```rust
+/// # use quaint::{ast::*, crud, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = crud::insert_one("users", vec![("name", Value::from("Musti")), ("age", Value::from(5))]);
+/// let (sql, params) = Sqlite::build(query)?;
+///
+/// assert_eq!("INSERT INTO `users` (`name`, `age`) VALUES (?,?)", sql);
+/// assert_eq!(vec![Value::from("Musti"), Value::from(5)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn insert_one<'a, T, I, K, V>(table: T, values: I) -> Insert<'a>
+where
+    T: Into<Table<'a>>,
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<Column<'a>>,
+    V: Into<Expression<'a>>,
+{
+    values
+        .into_iter()
+        .fold(Insert::single_into(table), |insert, (column, value)| {
+            insert.value(column, value)
+        })
+        .into()
+}
+
+/// Sets the given column and value pairs in the row with the given id.
+///
+/// ```rust
+/// # use quaint::{ast::*, crud, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let query = crud::update_by_id("users", "id", 1, vec![("name", "Naukio")]);
+/// let (sql, params) = Sqlite::build(query)?;
+///
+/// assert_eq!("UPDATE `users` SET `name` = ? WHERE `id` = ?", sql);
+/// assert_eq!(vec![Value::from("Naukio"), Value::from(1)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn update_by_id<'a, T, C, V, I, K, E>(table: T, id_column: C, id: V, values: I) -> Update<'a>
+where
+    T: Into<Table<'a>>,
+    C: Into<Column<'a>>,
+    V: Into<Value<'a>>,
+    I: IntoIterator<Item = (K, E)>,
+    K: Into<Column<'a>>,
+    E: Into<Expression<'a>>,
+{
+    values
+        .into_iter()
+        .fold(Update::table(table), |update, (column, value)| {
+            update.set(column, value)
+        })
+        .so_that(id_column.into().equals(id.into()))
+}
+
+/// Deletes the row with the given id.
+///
+/// ```rust
+/// # use quaint::{ast::*, crud, visitor::{Visitor, Sqlite}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let (sql, params) = Sqlite::build(crud::delete_by_id("users", "id", 1))?;
+///
+/// assert_eq!("DELETE FROM `users` WHERE `id` = ?", sql);
+/// assert_eq!(vec![Value::from(1)], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn delete_by_id<'a, T, C, V>(table: T, id_column: C, id: V) -> Delete<'a>
+where
+    T: Into<Table<'a>>,
+    C: Into<Column<'a>>,
+    V: Into<Value<'a>>,
+{
+    Delete::from_table(table).so_that(id_column.into().equals(id.into()))
+}
@@ -1,5 +1,5 @@
 //! Error module
-use std::{borrow::Cow, fmt, io, num};
+use std::{borrow::Cow, fmt, io, num, time::Duration};
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -19,19 +19,23 @@ impl fmt::Display for DatabaseConstraint {
     }
 }
 
-#[derive(Debug, Error)]
+type Source = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+#[derive(Debug)]
 /// The error types for database I/O, connection and query parameter
 /// construction.
 pub struct Error {
     kind: ErrorKind,
     original_code: Option<String>,
     original_message: Option<String>,
+    source: Option<Source>,
 }
 
 pub(crate) struct ErrorBuilder {
     kind: ErrorKind,
     original_code: Option<String>,
     original_message: Option<String>,
+    source: Option<Source>,
 }
 
 impl ErrorBuilder {
@@ -45,11 +49,18 @@ impl ErrorBuilder {
         self
     }
 
+    /// The driver error behind this one, returned from `source()`.
+    pub(crate) fn set_source(&mut self, source: impl Into<Source>) -> &mut Self {
+        self.source = Some(source.into());
+        self
+    }
+
     pub(crate) fn build(self) -> Error {
         Error {
             kind: self.kind,
             original_code: self.original_code,
             original_message: self.original_message,
+            source: self.source,
         }
     }
 }
@@ -60,9 +71,21 @@ impl Error {
             kind,
             original_code: None,
             original_message: None,
+            source: None,
         }
     }
 
+    /// A conversion error for something the database or the visitor does not
+    /// support, with the message also set as the original message.
+    pub(crate) fn unsupported(msg: impl Into<String>) -> Self {
+        let msg = msg.into();
+
+        let mut builder = Self::builder(ErrorKind::conversion(msg.clone()));
+        builder.set_original_message(msg);
+
+        builder.build()
+    }
+
     /// The error code sent by the database, if available.
     pub fn original_code(&self) -> Option<&str> {
         self.original_code.as_ref().map(|s| s.as_str())
@@ -77,6 +100,35 @@ impl Error {
     pub fn kind(&self) -> &ErrorKind {
         &self.kind
     }
+
+    /// The first error of the given type in the `source()` chain, such as
+    /// the error of the database driver.
+    ///
+    /// ```rust
+    /// # use quaint::error::Error;
+    /// # use std::io;
+    /// let err = Error::from(io::Error::new(io::ErrorKind::BrokenPipe, "gone"));
+    /// let io_err = err.downcast_ref::<io::Error>().unwrap();
+    ///
+    /// assert_eq!(io::ErrorKind::BrokenPipe, io_err.kind());
+    /// assert!(err.downcast_ref::<std::fmt::Error>().is_none());
+    /// ```
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: std::error::Error + 'static,
+    {
+        let mut source = std::error::Error::source(self);
+
+        while let Some(error) = source {
+            if let Some(error) = error.downcast_ref::<T>() {
+                return Some(error);
+            }
+
+            source = error.source();
+        }
+
+        None
+    }
 }
 
 impl fmt::Display for Error {
@@ -85,10 +137,19 @@ impl fmt::Display for Error {
     }
 }
 
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.source {
+            Some(ref source) => Some(source.as_ref()),
+            None => self.kind.source(),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ErrorKind {
     #[error("Error querying the database: {}", _0)]
-    QueryError(Box<dyn std::error::Error + Send + Sync + 'static>),
+    QueryError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 
     #[error("Database '{}' does not exist.", db_name)]
     DatabaseDoesNotExist { db_name: String },
@@ -105,6 +166,9 @@ pub enum ErrorKind {
     #[error("Query returned no data")]
     NotFound,
 
+    #[error("Query returned {} rows instead of one", _0)]
+    TooManyRows(usize),
+
     #[error("No such table: {}", table)]
     TableDoesNotExist { table: String },
 
@@ -118,10 +182,10 @@ pub enum ErrorKind {
     ForeignKeyConstraintViolation { constraint: DatabaseConstraint },
 
     #[error("Error creating a database connection.")]
-    ConnectionError(Box<dyn std::error::Error + Send + Sync + 'static>),
+    ConnectionError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 
     #[error("Error reading the column value: {}", _0)]
-    ColumnReadFailure(Box<dyn std::error::Error + Send + Sync + 'static>),
+    ColumnReadFailure(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 
     #[error("Error accessing result set, index out of bounds: {}", _0)]
     ResultIndexOutOfBounds(usize),
@@ -145,7 +209,7 @@ pub enum ErrorKind {
     InvalidConnectionArguments,
 
     #[error("Error in an I/O operation: {0}")]
-    IoError(io::Error),
+    IoError(#[source] io::Error),
 
     #[error("Connect timed out ({0})")]
     ConnectTimeout(String),
@@ -153,6 +217,18 @@ pub enum ErrorKind {
     #[error("Operation timed out ({0})")]
     Timeout(String),
 
+    #[error("The connection pool is shut down.")]
+    PoolClosed,
+
+    #[error("Could not acquire a lock on the row in time.")]
+    LockNotAcquired,
+
+    #[error("The transaction was idle for longer than {:?} and was rolled back.", timeout)]
+    TransactionIdleTimeout { timeout: Duration },
+
+    #[error("The database is degraded, the circuit breaker is open.")]
+    CircuitOpen,
+
     #[error("Error opening a TLS connection. {}", message)]
     TlsError { message: String },
 
@@ -161,7 +237,7 @@ pub enum ErrorKind {
 
     #[cfg(feature = "serde-support")]
     #[error("Deserializing a ResultRow {:?}", _0)]
-    FromRowError(serde::de::value::Error),
+    FromRowError(#[source] serde::de::value::Error),
 }
 
 impl ErrorKind {
@@ -182,8 +258,10 @@ impl From<Error> for ErrorKind {
 
 #[cfg(feature = "json-1")]
 impl From<serde_json::Error> for Error {
-    fn from(_: serde_json::Error) -> Self {
-        Self::builder(ErrorKind::conversion("Malformed JSON data.")).build()
+    fn from(e: serde_json::Error) -> Self {
+        let mut builder = Self::builder(ErrorKind::conversion("Malformed JSON data."));
+        builder.set_source(e);
+        builder.build()
     }
 }
 
@@ -194,11 +272,13 @@ impl From<std::fmt::Error> for Error {
 }
 
 impl From<num::TryFromIntError> for Error {
-    fn from(_: num::TryFromIntError) -> Self {
-        Self::builder(ErrorKind::conversion(
+    fn from(e: num::TryFromIntError) -> Self {
+        let mut builder = Self::builder(ErrorKind::conversion(
             "Couldn't convert an integer (possible overflow).",
-        ))
-        .build()
+        ));
+
+        builder.set_source(e);
+        builder.build()
     }
 }
 
@@ -220,7 +300,7 @@ impl From<mobc::Error<Error>> for Error {
     }
 }
 
-#[cfg(any(feature = "postgresql", feature = "mysql"))]
+#[cfg(any(feature = "postgresql-core", feature = "mysql"))]
 impl From<tokio::time::Elapsed> for Error {
     fn from(_: tokio::time::Elapsed) -> Self {
         let kind = ErrorKind::Timeout("tokio timeout".into());
@@ -235,7 +315,11 @@ impl From<tokio::time::Elapsed> for Error {
 impl From<url::ParseError> for Error {
     fn from(e: url::ParseError) -> Error {
         let kind = ErrorKind::DatabaseUrlIsInvalid(e.to_string());
-        Error::builder(kind).build()
+
+        let mut builder = Error::builder(kind);
+        builder.set_source(e);
+
+        builder.build()
     }
 }
 
@@ -246,7 +330,10 @@ impl From<io::Error> for Error {
 }
 
 impl From<std::string::FromUtf8Error> for Error {
-    fn from(_: std::string::FromUtf8Error) -> Error {
-        Error::builder(ErrorKind::conversion("Couldn't convert data to UTF-8")).build()
+    fn from(e: std::string::FromUtf8Error) -> Error {
+        let mut builder = Error::builder(ErrorKind::conversion("Couldn't convert data to UTF-8"));
+        builder.set_source(e);
+
+        builder.build()
     }
 }
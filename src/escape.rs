@@ -0,0 +1,153 @@
+//! Escaping of names and values written into raw SQL, following the same
+//! rules the visitors use.
+//!
+//! Parameters are always the safer choice for values. These functions are
+//! for the parts of a raw query that cannot be parameterized, such as table
+//! names, or for values inlined on purpose.
+//!
+//! ```
+//! # use quaint::{escape, visitor::{Mysql, Postgres}};
+//! assert_eq!("\"my\"\"table\"", escape::identifier::<Postgres>(&["my\"table"]));
+//! assert_eq!("`app`.`users`", escape::identifier::<Mysql>(&["app", "users"]));
+//!
+//! assert_eq!("'it''s'", escape::string_literal::<Postgres>("it's"));
+//! assert_eq!("'C:\\\\temp'", escape::string_literal::<Mysql>("C:\\temp"));
+//! ```
+use crate::visitor::Visitor;
+
+/// Quotes an identifier, such as a table or a column name, in the quotes of
+/// the database. The parts are delimited with a `.`, e.g. a database and a
+/// table name.
+pub fn identifier<'a, V: Visitor<'a>>(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|part| quote_identifier(part, V::C_BACKTICK_OPEN, V::C_BACKTICK_CLOSE))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Surrounds a string with single quotes, doubling the quotes inside of it.
+/// On MySQL, which reads backslashes as escapes, the backslashes are doubled
+/// too.
+pub fn string_literal<'a, V: Visitor<'a>>(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('\'');
+
+    for c in s.chars() {
+        match c {
+            '\'' => escaped.push_str("''"),
+            '\\' if V::C_BACKSLASH_ESCAPES => escaped.push_str("\\\\"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('\'');
+    escaped
+}
+
+/// Escapes the wildcards of a `LIKE` pattern with a backslash, matching the
+/// string as it is. PostgreSQL and MySQL use the backslash as the escape
+/// character by default, SQLite and SQL Server need an `ESCAPE '\'` after
+/// the pattern.
+///
+/// ```
+/// # use quaint::escape;
+/// assert_eq!("100\\%", escape::like_pattern("100%"));
+/// assert_eq!("100\\%%", format!("{}%", escape::like_pattern("100%")));
+/// ```
+pub fn like_pattern(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        if let '\\' | '%' | '_' | '[' = c {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// A JSON path for MySQL, SQLite and SQL Server, quoting every key.
+///
+/// ```
+/// # use quaint::escape;
+/// assert_eq!(r#"$."address"."street name""#, escape::json_path(&["address", "street name"]));
+/// ```
+pub fn json_path(keys: &[&str]) -> String {
+    let mut path = String::from("$");
+
+    for key in keys {
+        path.push('.');
+        path.push_str(&json_string(key));
+    }
+
+    path
+}
+
+/// A JSON path for PostgreSQL, as a `text[]` literal of keys.
+///
+/// ```
+/// # use quaint::escape;
+/// assert_eq!(r#"{"address","street name"}"#, escape::json_path_array(&["address", "street name"]));
+/// ```
+pub fn json_path_array(keys: &[&str]) -> String {
+    let keys: Vec<_> = keys.iter().map(|key| json_string(key)).collect();
+    format!("{{{}}}", keys.join(","))
+}
+
+pub(crate) fn quote_identifier(name: &str, open: &str, close: &str) -> String {
+    let name = if close.is_empty() {
+        name.to_string()
+    } else {
+        name.replace(close, &close.repeat(2))
+    };
+
+    format!("{}{}{}", open, name, close)
+}
+
+/// Surrounds the key with double quotes, escaping the quotes and backslashes
+/// inside of it.
+fn json_string(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len() + 2);
+    escaped.push('"');
+
+    for c in key.chars() {
+        if let '"' | '\\' = c {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visitor::{Mssql, Sqlite};
+
+    #[test]
+    fn identifiers_double_the_closing_quote() {
+        assert_eq!("[my]]table]", identifier::<Mssql>(&["my]table"]));
+        assert_eq!("`my``table`", identifier::<Sqlite>(&["my`table"]));
+    }
+
+    #[test]
+    fn backslashes_are_kept_outside_of_mysql() {
+        assert_eq!("'C:\\temp'", string_literal::<Sqlite>("C:\\temp"));
+    }
+
+    #[test]
+    fn like_patterns_escape_the_escape_character() {
+        assert_eq!("a\\\\b\\_c\\[d]", like_pattern("a\\b_c[d]"));
+    }
+
+    #[test]
+    fn json_keys_escape_quotes() {
+        assert_eq!(r#"$."say \"hi\"""#, json_path(&["say \"hi\""]));
+    }
+}
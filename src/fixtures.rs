@@ -0,0 +1,397 @@
+//! Loading test data into the database from a JSON or YAML description.
+//!
+//! The fixtures are a list of tables with their rows. Tables can list the
+//! tables they reference with `depends_on`, and are inserted after them
+//! regardless of the order in the file.
+//!
+//! ```json
+//! [
+//!   {
+//!     "table": "posts",
+//!     "depends_on": ["users"],
+//!     "rows": [{ "id": 1, "user_id": 1, "title": "Hello" }]
+//!   },
+//!   {
+//!     "table": "users",
+//!     "rows": [{ "id": 1, "name": "Musti" }, { "id": 2, "name": "Naukio" }]
+//!   }
+//! ]
+//! ```
+//!
+//! The values are inserted as the JSON types they are written in, so a
+//! string is inserted as text. Columns of other types, such as dates or
+//! UUIDs on PostgreSQL, are given a type in `types`, and their values are
+//! converted to it:
+//!
+//! ```json
+//! [
+//!   {
+//!     "table": "users",
+//!     "types": { "id": "uuid", "born": "date", "balance": "numeric" },
+//!     "rows": [{ "id": "5ac8a3a0-2a9c-4c8e-9a8d-1b2c3d4e5f60", "born": "2015-04-01", "balance": "4.50" }]
+//!   }
+//! ]
+//! ```
+//!
+//! The types are `integer`, `numeric`, `text`, `enum`, `bytes` (base64),
+//! `boolean`, `char`, `json`, `uuid`, `datetime`, `date`, `time` and
+//! `interval`, the last five needing the `uuid-0_8` or `chrono-0_4`
+//! feature.
+//!
+//! ```no_run
+//! # use quaint::{fixtures::Fixtures, single::Quaint};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let conn = Quaint::new("file:///tmp/example.db").await?;
+//! let fixtures = Fixtures::from_json(r#"[{ "table": "users", "rows": [{ "id": 1 }] }]"#)?;
+//!
+//! // Empties the tables before inserting.
+//! fixtures.truncate(true).load(&conn).await?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::{
+    ast::*,
+    connector::Queryable,
+    error::{Error, ErrorKind},
+};
+use std::collections::{HashMap, HashSet};
+
+/// A set of tables and rows to insert into the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixtures {
+    tables: Vec<FixtureTable>,
+    truncate: bool,
+}
+
+/// The rows of one table in the fixtures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureTable {
+    name: String,
+    depends_on: Vec<String>,
+    types: HashMap<String, ValueType>,
+    rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl FixtureTable {
+    /// The name of the table.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The tables that must be inserted before this one.
+    pub fn depends_on(&self) -> &[String] {
+        &self.depends_on
+    }
+
+    /// The number of rows to insert.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// True if the table has no rows to insert.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn from_json(json: &serde_json::Value) -> crate::Result<Self> {
+        let obj = json
+            .as_object()
+            .ok_or_else(|| invalid("Fixture table must be an object."))?;
+
+        let name = obj
+            .get("table")
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| invalid("Fixture table is missing the `table` name."))?
+            .to_string();
+
+        let depends_on = match obj.get("depends_on") {
+            Some(serde_json::Value::Array(tables)) => tables
+                .iter()
+                .map(|t| t.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| invalid(format!("Dependencies of `{}` must be table names.", name)))?,
+            Some(serde_json::Value::Null) | None => Vec::new(),
+            Some(_) => return Err(invalid(format!("Dependencies of `{}` must be a list.", name))),
+        };
+
+        let types = match obj.get("types") {
+            Some(serde_json::Value::Object(types)) => types
+                .iter()
+                .map(|(column, type_name)| {
+                    let value_type = type_name.as_str().and_then(value_type).ok_or_else(|| {
+                        invalid(format!("Unknown type {} for `{}` of `{}`.", type_name, column, name))
+                    })?;
+
+                    Ok((column.clone(), value_type))
+                })
+                .collect::<crate::Result<HashMap<_, _>>>()?,
+            Some(serde_json::Value::Null) | None => HashMap::new(),
+            Some(_) => return Err(invalid(format!("Types of `{}` must be an object.", name))),
+        };
+
+        let rows = match obj.get("rows") {
+            Some(serde_json::Value::Array(rows)) => rows
+                .iter()
+                .map(|row| row.as_object().cloned())
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| invalid(format!("Rows of `{}` must be objects.", name)))?,
+            Some(serde_json::Value::Null) | None => Vec::new(),
+            Some(_) => return Err(invalid(format!("Rows of `{}` must be a list.", name))),
+        };
+
+        Ok(Self {
+            name,
+            depends_on,
+            types,
+            rows,
+        })
+    }
+
+    fn insert(&self) -> crate::Result<Vec<Insert<'_>>> {
+        let mut inserts = Vec::with_capacity(self.rows.len());
+
+        for row in self.rows.iter() {
+            let mut insert = Insert::single_into(self.name.as_str());
+
+            for (column, value) in row.iter() {
+                let value = match self.types.get(column) {
+                    Some(value_type) => Value::from_json(value, *value_type)?,
+                    None => json_to_value(value)?,
+                };
+
+                insert = insert.value(column.as_str(), value);
+            }
+
+            inserts.push(insert.build());
+        }
+
+        Ok(inserts)
+    }
+}
+
+impl Fixtures {
+    /// Parses the fixtures from a JSON string.
+    pub fn from_json(s: &str) -> crate::Result<Self> {
+        let json: serde_json::Value = serde_json::from_str(s)?;
+        Self::from_value(&json)
+    }
+
+    /// Parses the fixtures from a YAML string, in the same form as the JSON
+    /// fixtures.
+    pub fn from_yaml(s: &str) -> crate::Result<Self> {
+        let json: serde_json::Value =
+            serde_yaml::from_str(s).map_err(|e| invalid(format!("Malformed YAML data: {}", e)))?;
+
+        Self::from_value(&json)
+    }
+
+    /// Reads the fixtures from an already parsed JSON value.
+    pub fn from_value(json: &serde_json::Value) -> crate::Result<Self> {
+        let tables = json
+            .as_array()
+            .ok_or_else(|| invalid("Fixtures must be a list of tables."))?
+            .iter()
+            .map(FixtureTable::from_json)
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let fixtures = Self {
+            tables,
+            truncate: false,
+        };
+        fixtures.insertion_order()?;
+
+        Ok(fixtures)
+    }
+
+    /// If set, deletes all rows from the tables before inserting, children
+    /// before their parents. Default: `false`.
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// The tables in the order they are inserted, every table after the ones
+    /// it depends on.
+    pub fn tables(&self) -> Vec<&FixtureTable> {
+        self.insertion_order()
+            .expect("Fixture dependencies are validated when parsing.")
+    }
+
+    /// Inserts the rows to the database. The inserts are not wrapped in a
+    /// transaction, so pass one if the data should be inserted atomically.
+    pub async fn load(&self, conn: &dyn Queryable) -> crate::Result<()> {
+        let tables = self.insertion_order()?;
+
+        if self.truncate {
+            for table in tables.iter().rev() {
+                conn.delete(Delete::from_table(table.name.as_str())).await?;
+            }
+        }
+
+        for table in tables {
+            for insert in table.insert()? {
+                conn.execute(insert.into()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn insertion_order(&self) -> crate::Result<Vec<&FixtureTable>> {
+        let known: HashSet<&str> = self.tables.iter().map(|t| t.name.as_str()).collect();
+
+        for table in self.tables.iter() {
+            if let Some(missing) = table.depends_on.iter().find(|d| !known.contains(d.as_str())) {
+                let msg = format!(
+                    "Table `{}` depends on `{}`, not found in the fixtures.",
+                    table.name, missing
+                );
+                return Err(invalid(msg));
+            }
+        }
+
+        let mut inserted: HashSet<&str> = HashSet::new();
+        let mut ordered = Vec::with_capacity(self.tables.len());
+
+        while ordered.len() < self.tables.len() {
+            let next = self.tables.iter().find(|t| {
+                !inserted.contains(t.name.as_str()) && t.depends_on.iter().all(|d| inserted.contains(d.as_str()))
+            });
+
+            match next {
+                Some(table) => {
+                    inserted.insert(table.name.as_str());
+                    ordered.push(table);
+                }
+                None => return Err(invalid("The fixture tables have circular dependencies.")),
+            }
+        }
+
+        Ok(ordered)
+    }
+}
+
+fn json_to_value(json: &serde_json::Value) -> crate::Result<Value<'static>> {
+    let hint = match json {
+        serde_json::Value::Null | serde_json::Value::String(_) => ValueType::Text,
+        serde_json::Value::Number(n) if n.is_i64() => ValueType::Integer,
+        serde_json::Value::Number(_) => ValueType::Real,
+        serde_json::Value::Bool(_) => ValueType::Boolean,
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => ValueType::Json,
+    };
+
+    Value::from_json(json, hint)
+}
+
+/// The column type of the `types` of a fixture table.
+fn value_type(name: &str) -> Option<ValueType> {
+    let value_type = match name {
+        "integer" => ValueType::Integer,
+        "numeric" => ValueType::Real,
+        "text" => ValueType::Text,
+        "enum" => ValueType::Enum,
+        "bytes" => ValueType::Bytes,
+        "boolean" => ValueType::Boolean,
+        "char" => ValueType::Char,
+        "json" => ValueType::Json,
+        #[cfg(feature = "uuid-0_8")]
+        "uuid" => ValueType::Uuid,
+        #[cfg(feature = "chrono-0_4")]
+        "datetime" => ValueType::DateTime,
+        #[cfg(feature = "chrono-0_4")]
+        "date" => ValueType::Date,
+        #[cfg(feature = "chrono-0_4")]
+        "time" => ValueType::Time,
+        #[cfg(feature = "chrono-0_4")]
+        "interval" => ValueType::Interval,
+        _ => return None,
+    };
+
+    Some(value_type)
+}
+
+fn invalid(msg: impl Into<String>) -> Error {
+    Error::builder(ErrorKind::conversion(msg.into())).build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_are_ordered_by_dependencies() {
+        let json = r#"[
+            { "table": "comments", "depends_on": ["posts", "users"], "rows": [] },
+            { "table": "posts", "depends_on": ["users"], "rows": [{ "id": 1 }] },
+            { "table": "users", "rows": [{ "id": 1, "name": "Musti" }] }
+        ]"#;
+
+        let fixtures = Fixtures::from_json(json).unwrap();
+        let names: Vec<_> = fixtures.tables().into_iter().map(|t| t.name()).collect();
+
+        assert_eq!(vec!["users", "posts", "comments"], names);
+    }
+
+    #[test]
+    fn yaml_fixtures() {
+        let yaml = "
+- table: users
+  rows:
+    - id: 1
+      name: Musti
+      weight: 4.5
+      tags: [cat]
+";
+
+        let fixtures = Fixtures::from_yaml(yaml).unwrap();
+        let inserts = fixtures.tables()[0].insert().unwrap();
+        let insert = inserts.into_iter().next().unwrap();
+
+        assert_eq!(4, insert.columns.len());
+    }
+
+    #[test]
+    #[cfg(all(feature = "uuid-0_8", feature = "chrono-0_4"))]
+    fn typed_columns_are_converted() {
+        let json = r#"[{
+            "table": "users",
+            "types": { "id": "uuid", "born": "date" },
+            "rows": [{ "id": "5ac8a3a0-2a9c-4c8e-9a8d-1b2c3d4e5f60", "born": "2015-04-01", "name": "Musti" }]
+        }]"#;
+
+        let fixtures = Fixtures::from_json(json).unwrap();
+        let inserts = fixtures.tables()[0].insert().unwrap();
+        let insert = inserts.into_iter().next().unwrap();
+
+        let id = uuid::Uuid::parse_str("5ac8a3a0-2a9c-4c8e-9a8d-1b2c3d4e5f60").unwrap();
+        let born = chrono::NaiveDate::from_ymd(2015, 4, 1);
+
+        let expected = Insert::single_into("users")
+            .value("born", Value::date(born))
+            .value("id", Value::uuid(id))
+            .value("name", "Musti")
+            .build();
+
+        assert_eq!(expected, insert);
+    }
+
+    #[test]
+    fn unknown_column_types_are_errors() {
+        let json = r#"[{ "table": "users", "types": { "id": "bignum" } }]"#;
+        assert!(Fixtures::from_json(json).is_err());
+    }
+
+    #[test]
+    fn circular_and_unknown_dependencies_are_errors() {
+        let circular = r#"[
+            { "table": "a", "depends_on": ["b"] },
+            { "table": "b", "depends_on": ["a"] }
+        ]"#;
+
+        assert!(Fixtures::from_json(circular).is_err());
+
+        let unknown = r#"[{ "table": "a", "depends_on": ["b"] }]"#;
+        assert!(Fixtures::from_json(unknown).is_err());
+    }
+}
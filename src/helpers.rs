@@ -0,0 +1,699 @@
+//! Shortcuts for common multi-statement operations.
+#[cfg(feature = "postgresql-core")]
+use crate::visitor::{self, Visitor};
+use crate::{
+    ast::*,
+    connector::{Queryable, ResultRow, ResultSet, SqlFamily, TransactionCapable},
+    error::{Error, ErrorKind},
+};
+use std::time::Duration;
+
+/// Inserts the rows of `insert`, updating the `update_columns` of the rows
+/// that already exist. Returns the number of affected rows as reported by the
+/// database, MySQL counting an updated row twice.
+///
+/// The rows are inserted in chunks that fit in the bind parameter limit of
+/// the database, all in one transaction. A conflict is a row with the same
+/// values in the `conflict_columns`, or in the columns of the first unique
+/// index of the table if empty. With no `update_columns` the existing rows
+/// are left as they are. See [`OnConflict`] for the database specific
+/// details.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, single::Quaint, helpers::upsert_many};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let conn = Quaint::new("postgres://localhost/db").await?;
+///
+/// let insert = Insert::multi_into("users", vec!["id", "name"])
+///     .values((1, "Musti"))
+///     .values((2, "Naukio"));
+///
+/// upsert_many(&conn, insert, vec!["id".into()], vec!["name".into()]).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`OnConflict`]: ../ast/enum.OnConflict.html
+pub async fn upsert_many<'a, C>(
+    conn: &C,
+    insert: MultiRowInsert<'a>,
+    conflict_columns: Vec<Column<'a>>,
+    update_columns: Vec<Column<'a>>,
+) -> crate::Result<u64>
+where
+    C: TransactionCapable,
+{
+    let MultiRowInsert {
+        table,
+        columns,
+        values,
+        overriding_system_value,
+    } = insert;
+
+    let table = table.ok_or_else(|| {
+        let kind = ErrorKind::conversion("An upsert needs to point to a table.");
+        Error::builder(kind).build()
+    })?;
+
+    if values.is_empty() {
+        return Ok(0);
+    }
+
+    let on_conflict = if update_columns.is_empty() {
+        OnConflict::DoNothing
+    } else {
+        OnConflict::Update {
+            target: conflict_columns,
+            columns: update_columns,
+        }
+    };
+
+    let max_values = max_bind_values(conn.connection_info().sql_family());
+    let chunk_size = (max_values / columns.len().max(1)).max(1);
+
+    let tx = conn.start_transaction().await?;
+    let mut rows = values.into_iter();
+    let mut changes = 0;
+
+    loop {
+        let chunk: Vec<_> = rows.by_ref().take(chunk_size).collect();
+
+        if chunk.is_empty() {
+            break;
+        }
+
+        let insert = chunk.into_iter().fold(
+            chunk_insert(&table, &columns, overriding_system_value),
+            |insert, row| insert.values(row),
+        );
+
+        let query = Insert::from(insert).on_conflict(on_conflict.clone());
+
+        match tx.execute(query.into()).await {
+            Ok(affected) => changes += affected,
+            Err(e) => {
+                tx.rollback().await?;
+                return Err(e);
+            }
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(changes)
+}
+
+/// The outcome of [`insert_many_reporting_duplicates`].
+///
+/// [`insert_many_reporting_duplicates`]: fn.insert_many_reporting_duplicates.html
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InsertManyReport {
+    /// The number of inserted rows.
+    pub inserted: u64,
+    /// The positions of the rows not inserted due to a unique constraint
+    /// violation, in the order they were given.
+    pub duplicates: Vec<usize>,
+}
+
+/// Inserts the rows of `insert`, skipping the ones violating a unique
+/// constraint and reporting their positions instead of failing.
+///
+/// The rows are inserted in chunks like in [`upsert_many`], all in one
+/// transaction. Only a chunk failing with a unique constraint violation is
+/// retried row by row, each row in its own savepoint, to find the
+/// duplicates. Any other error rolls back the transaction and is returned.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, single::Quaint, helpers::insert_many_reporting_duplicates};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let conn = Quaint::new("postgres://localhost/db").await?;
+///
+/// let insert = Insert::multi_into("users", vec!["id", "name"])
+///     .values((1, "Musti"))
+///     .values((1, "Naukio"));
+///
+/// let report = insert_many_reporting_duplicates(&conn, insert).await?;
+/// assert_eq!(vec![1], report.duplicates);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`upsert_many`]: fn.upsert_many.html
+pub async fn insert_many_reporting_duplicates<'a, C>(
+    conn: &C,
+    insert: MultiRowInsert<'a>,
+) -> crate::Result<InsertManyReport>
+where
+    C: TransactionCapable,
+{
+    let MultiRowInsert {
+        table,
+        columns,
+        values,
+        overriding_system_value,
+    } = insert;
+
+    let table = table.ok_or_else(|| {
+        let kind = ErrorKind::conversion("An insert needs to point to a table.");
+        Error::builder(kind).build()
+    })?;
+
+    let mut report = InsertManyReport::default();
+
+    if values.is_empty() {
+        return Ok(report);
+    }
+
+    let max_values = max_bind_values(conn.connection_info().sql_family());
+    let chunk_size = (max_values / columns.len().max(1)).max(1);
+
+    let tx = conn.start_transaction().await?;
+    let mut rows = values.into_iter().enumerate();
+
+    loop {
+        let chunk: Vec<_> = rows.by_ref().take(chunk_size).collect();
+
+        if chunk.is_empty() {
+            break;
+        }
+
+        let insert = chunk.iter().fold(
+            chunk_insert(&table, &columns, overriding_system_value),
+            |insert, (_, row)| insert.values(row.clone()),
+        );
+
+        let outcome = match tx.try_step(|| tx.execute(insert.into())).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                tx.rollback().await?;
+                return Err(e);
+            }
+        };
+
+        match outcome {
+            Ok(affected) => report.inserted += affected,
+            Err(e) if is_unique_violation(&e) => {
+                for (position, row) in chunk {
+                    let insert = chunk_insert(&table, &columns, overriding_system_value).values(row);
+
+                    match tx.try_step(|| tx.execute(insert.into())).await {
+                        Ok(Ok(affected)) => report.inserted += affected,
+                        Ok(Err(e)) if is_unique_violation(&e) => report.duplicates.push(position),
+                        Ok(Err(e)) | Err(e) => {
+                            tx.rollback().await?;
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                return Err(e);
+            }
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(report)
+}
+
+/// An empty insert for one chunk of the rows, keeping the
+/// `OVERRIDING SYSTEM VALUE` flag of the original statement.
+fn chunk_insert<'a>(table: &Table<'a>, columns: &[Column<'a>], overriding_system_value: bool) -> MultiRowInsert<'a> {
+    let insert = Insert::multi_into(table.clone(), columns.to_vec());
+
+    if overriding_system_value {
+        insert.overriding_system_value()
+    } else {
+        insert
+    }
+}
+
+fn is_unique_violation(e: &Error) -> bool {
+    matches!(e.kind(), ErrorKind::UniqueConstraintViolation { .. })
+}
+
+/// Selects the row matching `conditions` with `SELECT ... FOR UPDATE`,
+/// locking it until the end of the transaction. Returns `None` if no row
+/// matches, or if the row is locked and the mode is
+/// `LockMode::SkipLocked`.
+///
+/// With `LockMode::NoWait`, or when the lock is not acquired within the
+/// given `timeout`, the error kind is `ErrorKind::LockNotAcquired`. Without
+/// a timeout the wait is limited by the database settings. Only supported
+/// on PostgreSQL and MySQL 8, and meant to be called in a transaction: in
+/// autocommit mode the lock is released as soon as the query returns.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, single::Quaint, helpers::lock_row};
+/// # use std::time::Duration;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let conn = Quaint::new("postgres://localhost/db").await?;
+/// let tx = conn.start_transaction().await?;
+///
+/// let timeout = Some(Duration::from_secs(2));
+/// let job = lock_row(&tx, "jobs", "id".equals(1), LockMode::Wait, timeout).await?;
+///
+/// if job.is_some() {
+///     tx.update(Update::table("jobs").set("state", "running").so_that("id".equals(1))).await?;
+/// }
+///
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(not(any(feature = "postgresql-core", feature = "mysql")), allow(unused_variables))]
+pub async fn lock_row<'a>(
+    conn: &dyn Queryable,
+    table: impl Into<Table<'a>>,
+    conditions: impl Into<ConditionTree<'a>>,
+    mode: LockMode,
+    timeout: Option<Duration>,
+) -> crate::Result<Option<ResultRow>> {
+    let select = Select::from_table(table)
+        .so_that(conditions)
+        .for_update(mode)
+        .unlimited();
+
+    let timeout = match (mode, timeout) {
+        (LockMode::Wait, Some(timeout)) => timeout,
+        _ => return Ok(conn.select(select).await?.into_iter().next()),
+    };
+
+    match conn.connection_info().sql_family() {
+        #[cfg(feature = "postgresql-core")]
+        SqlFamily::Postgres => {
+            // Reset when the transaction ends.
+            let cmd = format!("SET LOCAL lock_timeout = '{}ms'", timeout.as_millis().max(1));
+            conn.raw_cmd(&cmd).await?;
+
+            // A failed query aborts the transaction, and with it the setting.
+            let rows = conn.select(select).await?;
+            conn.raw_cmd("SET LOCAL lock_timeout = DEFAULT").await?;
+
+            Ok(rows.into_iter().next())
+        }
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => {
+            // The session setting outlives the transaction, so it is set
+            // back after the query.
+            let previous = conn
+                .query_raw("SELECT @@SESSION.innodb_lock_wait_timeout", &[])
+                .await?
+                .into_single()?[0]
+                .as_i64()
+                .unwrap_or(50);
+
+            // Whole seconds, rounded up.
+            let secs = (timeout.as_millis() + 999) / 1000;
+            let cmd = format!("SET SESSION innodb_lock_wait_timeout = {}", secs.max(1));
+            conn.raw_cmd(&cmd).await?;
+
+            let result = conn.select(select).await;
+
+            let cmd = format!("SET SESSION innodb_lock_wait_timeout = {}", previous);
+            let reset = conn.raw_cmd(&cmd).await;
+
+            let rows = result?;
+            reset?;
+
+            Ok(rows.into_iter().next())
+        }
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => Err(not_supported("Locking rows with SELECT ... FOR UPDATE", "SQLite")),
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => Err(not_supported("Locking rows with SELECT ... FOR UPDATE", "SQL Server")),
+    }
+}
+
+/// A database session left idle in an open transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleSession {
+    /// The backend process id.
+    pub id: i64,
+    /// How long the session has been idle, in whole seconds.
+    pub idle_for: Duration,
+}
+
+/// Lists the sessions of the application that have been idle in an open
+/// transaction for at least `idle_for`, such as the ones leaked by a
+/// transaction that was never committed or rolled back. The current session
+/// is never listed.
+///
+/// The sessions are matched by the `application_name`, which quaint
+/// connections set from [`ConnectionInfo::application_name`]. Only supported
+/// on PostgreSQL: the MySQL driver does not send connection attributes, so
+/// the sessions of one application cannot be told apart on MySQL.
+///
+/// [`ConnectionInfo::application_name`]: ../connector/enum.ConnectionInfo.html#method.application_name
+#[cfg_attr(not(feature = "postgresql-core"), allow(unused_variables))]
+pub async fn idle_in_transaction_sessions(
+    conn: &dyn Queryable,
+    application_name: &str,
+    idle_for: Duration,
+) -> crate::Result<Vec<IdleSession>> {
+    let idle_secs = Value::integer(idle_for.as_secs() as i64);
+
+    let rows: ResultSet = match conn.connection_info().sql_family() {
+        #[cfg(feature = "postgresql-core")]
+        SqlFamily::Postgres => {
+            let sql = r#"
+                SELECT pid AS id, EXTRACT(EPOCH FROM now() - state_change)::int8 AS idle_secs
+                FROM pg_stat_activity
+                WHERE state IN ('idle in transaction', 'idle in transaction (aborted)')
+                AND datname = current_database()
+                AND application_name = $1
+                AND pid <> pg_backend_pid()
+                AND state_change <= now() - $2::int8 * INTERVAL '1 second'
+            "#;
+
+            conn.query_raw(sql, &[Value::from(application_name), idle_secs]).await
+        }
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => Err(not_supported("Listing idle sessions", "MySQL")),
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => Err(not_supported("Listing idle sessions", "SQLite")),
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => Err(not_supported("Listing idle sessions", "SQL Server")),
+    }?;
+
+    let sessions = rows
+        .into_iter()
+        .filter_map(|row| {
+            let id = row["id"].as_i64()?;
+            let idle_secs = row["idle_secs"].as_i64().unwrap_or(0).max(0) as u64;
+
+            Some(IdleSession {
+                id,
+                idle_for: Duration::from_secs(idle_secs),
+            })
+        })
+        .collect();
+
+    Ok(sessions)
+}
+
+/// Terminates the sessions [`idle_in_transaction_sessions`] lists, rolling
+/// back their transactions. Returns the terminated sessions. Needs the
+/// `pg_signal_backend` role for the sessions of other users. Only supported
+/// on PostgreSQL.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, single::Quaint, helpers::kill_idle_in_transaction_sessions};
+/// # use std::time::Duration;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let conn = Quaint::new("postgres://localhost/db").await?;
+/// let killed = kill_idle_in_transaction_sessions(&conn, "billing", Duration::from_secs(600)).await?;
+///
+/// for session in killed {
+///     println!("Terminated session {}, idle for {:?}", session.id, session.idle_for);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`idle_in_transaction_sessions`]: fn.idle_in_transaction_sessions.html
+pub async fn kill_idle_in_transaction_sessions(
+    conn: &dyn Queryable,
+    application_name: &str,
+    idle_for: Duration,
+) -> crate::Result<Vec<IdleSession>> {
+    let sessions = idle_in_transaction_sessions(conn, application_name, idle_for).await?;
+    let mut killed = Vec::with_capacity(sessions.len());
+
+    for session in sessions {
+        if kill_session(conn, session.id).await? {
+            killed.push(session);
+        }
+    }
+
+    Ok(killed)
+}
+
+/// Terminates the session, returning false if it had already ended.
+#[cfg_attr(not(feature = "postgresql-core"), allow(unused_variables))]
+async fn kill_session(conn: &dyn Queryable, id: i64) -> crate::Result<bool> {
+    match conn.connection_info().sql_family() {
+        #[cfg(feature = "postgresql-core")]
+        SqlFamily::Postgres => {
+            let row = conn
+                .query_raw("SELECT pg_terminate_backend($1) AS terminated", &[Value::integer(id)])
+                .await?
+                .into_single()?;
+
+            Ok(row["terminated"].as_bool().unwrap_or(false))
+        }
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => Err(not_supported("Terminating sessions", "MySQL")),
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => Err(not_supported("Terminating sessions", "SQLite")),
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => Err(not_supported("Terminating sessions", "SQL Server")),
+    }
+}
+
+/// An approximate number of rows in the table, or returned by the query
+/// given as a `Select`, read from the statistics of the database instead of
+/// counting the rows. Meant for pagination and other places where a cheap
+/// estimate is good enough.
+///
+/// PostgreSQL reads the `rows` estimate of the query plan from `EXPLAIN`.
+/// MySQL reads `table_rows` from `information_schema.tables`, and SQLite the
+/// first number of the `stat` in `sqlite_stat1`, filled by `ANALYZE`, of the
+/// database of the table or `main`. The
+/// rows are counted with `COUNT(*)` when the database has no estimate, which
+/// is always the case for a query on MySQL and SQLite, and on SQL Server.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, single::Quaint, helpers::estimate_count};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let conn = Quaint::new("postgres://localhost/db").await?;
+///
+/// let users = estimate_count(&conn, "users").await?;
+/// let banned = estimate_count(&conn, Select::from_table("users").so_that("banned".is_true())).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn estimate_count<'a>(conn: &dyn Queryable, table: impl Into<Table<'a>>) -> crate::Result<u64> {
+    let table = table.into();
+
+    let estimate: Option<u64> = match conn.connection_info().sql_family() {
+        #[cfg(feature = "postgresql-core")]
+        SqlFamily::Postgres => {
+            let select = match table.typ {
+                TableType::Query(ref select) => select.clone(),
+                _ => Select::from_table(table.clone()),
+            };
+
+            let (sql, params) = visitor::Postgres::build(select)?;
+            let plan = conn.query_raw(&format!("EXPLAIN {}", sql), &params).await?;
+
+            // The first line is the top node of the plan, e.g.
+            // `Seq Scan on users  (cost=0.00..22.70 rows=1270 width=36)`.
+            plan.first()
+                .and_then(|row| row.at(0).and_then(|line| line.to_string()))
+                .and_then(|line| {
+                    let rows = line.split("rows=").nth(1)?;
+                    rows.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()
+                })
+        }
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => match table.typ {
+            TableType::Table(ref name) => {
+                let sql = r#"
+                    SELECT table_rows
+                    FROM information_schema.tables
+                    WHERE table_schema = COALESCE(?, DATABASE())
+                    AND table_name = ?
+                "#;
+
+                let schema = Value::Text(table.database.clone());
+                let rows = conn.query_raw(sql, &[schema, Value::text(name.clone())]).await?;
+
+                // Views have no row count.
+                rows.first()
+                    .and_then(|row| row.at(0).and_then(|rows| rows.as_i64()))
+                    .map(|rows| rows.max(0) as u64)
+            }
+            _ => None,
+        },
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => match table.typ {
+            TableType::Table(ref name) => {
+                // Every attached database has statistics of its own.
+                let schema = table
+                    .database
+                    .as_ref()
+                    .map(|db| db.replace('"', "\"\""))
+                    .unwrap_or_else(|| "main".into());
+
+                // Created by the first `ANALYZE`.
+                let sql = format!(
+                    "SELECT name FROM \"{}\".sqlite_master WHERE type = 'table' AND name = 'sqlite_stat1'",
+                    schema
+                );
+
+                if conn.query_raw(&sql, &[]).await?.is_empty() {
+                    None
+                } else {
+                    let sql = format!("SELECT stat FROM \"{}\".sqlite_stat1 WHERE tbl = ?", schema);
+                    let stats = conn.query_raw(&sql, &[Value::text(name.clone())]).await?;
+
+                    // The number of rows, followed by the selectivity of the
+                    // index columns.
+                    stats
+                        .first()
+                        .and_then(|row| row.at(0).and_then(|stat| stat.to_string()))
+                        .and_then(|stat| stat.split_whitespace().next()?.parse().ok())
+                }
+            }
+            _ => None,
+        },
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => None,
+    };
+
+    if let Some(estimate) = estimate {
+        return Ok(estimate);
+    }
+
+    let table = match (table.typ, table.alias) {
+        // A derived table needs an alias.
+        (TableType::Query(select), None) => Table::from(select).alias("estimated"),
+        (typ, alias) => Table { typ, alias, ..table },
+    };
+
+    let row = conn
+        .select(Select::from_table(table).value(count(asterisk())).unlimited())
+        .await?
+        .into_single()?;
+
+    Ok(row.at(0).and_then(|count| count.as_i64()).unwrap_or(0).max(0) as u64)
+}
+
+/// A column changed by [`update_with_diff`], with its value before and after
+/// the update.
+///
+/// [`update_with_diff`]: fn.update_with_diff.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnChange<'a> {
+    pub column: String,
+    pub before: Value<'a>,
+    pub after: Value<'a>,
+}
+
+/// Updates the row matching `conditions` with the given values, writing only
+/// the columns whose value differs from the current one. Returns the changed
+/// columns, empty if the row already had the values, or `None` if no row
+/// matches. Fails with `ErrorKind::TooManyRows` if more than one does.
+///
+/// The current values are compared to the new ones as they are read from the
+/// database, so a value of another type, such as an integer given to a
+/// decimal column, is always written. Meant to be called in a transaction,
+/// where PostgreSQL and MySQL lock the row with `SELECT ... FOR UPDATE` until
+/// the transaction ends.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, single::Quaint, helpers::update_with_diff};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let conn = Quaint::new("postgres://localhost/db").await?;
+/// let tx = conn.start_transaction().await?;
+///
+/// let values = vec![("name", Value::from("Musti")), ("age", Value::from(6))];
+///
+/// if let Some(changes) = update_with_diff(&tx, "cats", "id".equals(1), values).await? {
+///     for change in changes {
+///         println!("{}: {:?} -> {:?}", change.column, change.before, change.after);
+///     }
+/// }
+///
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn update_with_diff<'a, K, V, I>(
+    conn: &dyn Queryable,
+    table: impl Into<Table<'a>>,
+    conditions: impl Into<ConditionTree<'a>>,
+    values: I,
+) -> crate::Result<Option<Vec<ColumnChange<'a>>>>
+where
+    K: Into<Column<'a>>,
+    V: Into<Value<'a>>,
+    I: IntoIterator<Item = (K, V)>,
+{
+    let table = table.into();
+    let conditions = conditions.into();
+    let values: Vec<(Column<'a>, Value<'a>)> = values.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+
+    let columns: Vec<Column<'a>> = values.iter().map(|(column, _)| column.clone()).collect();
+    let select = Select::from_table(table.clone())
+        .columns(columns)
+        .so_that(conditions.clone())
+        .unlimited();
+
+    let select = match conn.connection_info().sql_family() {
+        #[cfg(feature = "postgresql-core")]
+        SqlFamily::Postgres => select.for_update(LockMode::Wait),
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => select.for_update(LockMode::Wait),
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => select,
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => select,
+    };
+
+    let row = match conn.select_optional(select).await? {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    let changes: Vec<ColumnChange<'a>> = values
+        .into_iter()
+        .zip(row.into_iter())
+        .filter(|((_, after), before)| after != before)
+        .map(|((column, after), before)| ColumnChange {
+            column: column.name.into_owned(),
+            before,
+            after,
+        })
+        .collect();
+
+    if changes.is_empty() {
+        return Ok(Some(changes));
+    }
+
+    let update = changes.iter().fold(Update::table(table), |update, change| {
+        update.set(change.column.clone(), change.after.clone())
+    });
+
+    conn.update(update.so_that(conditions)).await?;
+
+    Ok(Some(changes))
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite", feature = "mssql"))]
+fn not_supported(feature: &str, db: &str) -> Error {
+    Error::unsupported(format!("{} is not supported in {}.", feature, db))
+}
+
+/// The maximum number of parameters in one statement.
+fn max_bind_values(family: SqlFamily) -> usize {
+    match family {
+        #[cfg(feature = "postgresql-core")]
+        SqlFamily::Postgres => 32767,
+        #[cfg(feature = "mysql")]
+        SqlFamily::Mysql => 65535,
+        #[cfg(feature = "sqlite")]
+        SqlFamily::Sqlite => 999,
+        #[cfg(feature = "mssql")]
+        SqlFamily::Mssql => 2099,
+    }
+}
@@ -108,36 +108,90 @@ mod macros;
 
 #[cfg(all(
     not(feature = "tracing-log"),
-    any(feature = "sqlite", feature = "mysql", feature = "postgresql")
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
 ))]
 #[macro_use]
 extern crate log;
 
 #[macro_use]
-#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql"))]
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql-core"))]
 extern crate metrics;
 
 pub mod ast;
-#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql"))]
+#[cfg(all(
+    feature = "audit",
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod audit;
+#[cfg(all(
+    feature = "cache",
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod cache;
+#[cfg(all(
+    feature = "circuit-breaker",
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod circuit_breaker;
+#[cfg(all(
+    any(feature = "conformance", test),
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod conformance;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql-core"))]
 pub mod connector;
+pub mod crud;
 pub mod error;
+pub mod escape;
+#[cfg(all(
+    feature = "fixtures",
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod fixtures;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql-core"))]
+pub mod helpers;
 #[cfg(all(
     feature = "pooled",
-    any(feature = "sqlite", feature = "mysql", feature = "postgresql")
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
 ))]
 pub mod pooled;
 pub mod prelude;
 #[cfg(feature = "serde-support")]
 pub mod serde;
-#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql"))]
+#[cfg(all(
+    feature = "shard",
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod shard;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql-core"))]
 pub mod single;
-pub mod visitor;
+#[cfg(all(
+    feature = "table-stats",
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod table_stats;
+#[cfg(all(
+    any(feature = "audit", feature = "cache", feature = "table-stats"),
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+mod tables;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql-core"))]
+pub mod test_helpers;
 #[cfg(test)]
 mod tests;
+#[cfg(all(
+    feature = "validation",
+    any(feature = "sqlite", feature = "mysql", feature = "postgresql-core")
+))]
+pub mod validation;
+pub mod visitor;
 
 use once_cell::sync::Lazy;
 
 pub use ast::Value;
+#[cfg(feature = "sql-macro")]
+#[doc(hidden)]
+pub use macros::placeholder_count;
 
 pub(crate) static LOG_QUERIES: Lazy<bool> = Lazy::new(|| std::env::var("LOG_QUERIES").map(|_| true).unwrap_or(false));
 
@@ -86,6 +86,152 @@ macro_rules! val {
     };
 }
 
+/// Defines an enum with the names of its variants in the database,
+/// implementing [DbEnum](ast/trait.DbEnum.html) for it. The variants convert
+/// into `Value::Enum` when used as parameters, and can be read back from
+/// enum and text values with `TryFrom`.
+///
+/// ```rust
+/// # use quaint::{db_enum, ast::*, visitor::{Visitor, Postgres}};
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// db_enum! {
+///     #[derive(Debug, Clone, Copy, PartialEq)]
+///     pub enum Status {
+///         Active => "ACTIVE",
+///         Banned => "BANNED",
+///     }
+/// }
+///
+/// let query = Select::from_table("users").so_that("status".equals(Status::Banned));
+/// let (sql, params) = Postgres::build(query)?;
+///
+/// assert_eq!("SELECT \"users\".* FROM \"users\" WHERE \"status\" = $1", sql);
+/// assert_eq!(vec![Value::enum_variant("BANNED")], params);
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! db_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident => $db_name:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant),+
+        }
+
+        impl $crate::ast::DbEnum for $name {
+            fn db_name(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $db_name),+
+                }
+            }
+
+            fn from_db_name(name: &str) -> Option<Self> {
+                match name {
+                    $($db_name => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl<'a> From<$name> for $crate::ast::Value<'a> {
+            fn from(that: $name) -> Self {
+                $crate::ast::DbEnum::to_value(&that)
+            }
+        }
+
+        impl<'a> ::std::convert::TryFrom<$crate::ast::Value<'a>> for $name {
+            type Error = $crate::error::Error;
+
+            fn try_from(value: $crate::ast::Value<'a>) -> Result<Self, Self::Error> {
+                <$name as $crate::ast::DbEnum>::from_value(&value)
+            }
+        }
+    };
+}
+
+/// Raw SQL with its parameters, as a `(String, Vec<Value>)` pair for
+/// [query_raw](connector/trait.Queryable.html#method.query_raw). The number
+/// of placeholders in the SQL is checked against the number of parameters
+/// when compiling, counting `?`, or the highest `$n` or `@Pn`, outside of
+/// quotes.
+///
+/// ```rust
+/// # use quaint::{sql, ast::Value};
+/// let (sql, params) = sql!("SELECT * FROM cats WHERE name = ? AND age > ?", "Musti", 3);
+///
+/// assert_eq!("SELECT * FROM cats WHERE name = ? AND age > ?", sql);
+/// assert_eq!(vec![Value::from("Musti"), Value::from(3)], params);
+/// ```
+///
+/// A missing parameter fails the build:
+///
+/// ```compile_fail
+/// # use quaint::sql;
+/// let (sql, params) = sql!("SELECT * FROM cats WHERE name = $1 AND age > $2", "Musti");
+/// ```
+#[cfg(feature = "sql-macro")]
+#[macro_export]
+macro_rules! sql {
+    (@unit $param:expr) => {
+        ()
+    };
+
+    ($sql:literal $(, $param:expr)* $(,)?) => {{
+        const PLACEHOLDERS: usize = $crate::placeholder_count($sql);
+        let _: [(); PLACEHOLDERS] = [$($crate::sql!(@unit $param)),*];
+
+        let params: Vec<$crate::ast::Value> = vec![$($crate::ast::Value::from($param)),*];
+        (String::from($sql), params)
+    }};
+}
+
+/// The number of parameters the SQL expects, for [sql](macro.sql.html).
+#[cfg(feature = "sql-macro")]
+#[doc(hidden)]
+pub const fn placeholder_count(sql: &str) -> usize {
+    let bytes = sql.as_bytes();
+
+    let mut i = 0;
+    let mut questions = 0;
+    let mut numbered = 0;
+    let mut quote = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        if quote != 0 {
+            if b == quote {
+                quote = 0;
+            }
+        } else if b == b'\'' || b == b'"' || b == b'`' {
+            quote = b;
+        } else if b == b'?' {
+            questions += 1;
+        } else if b == b'$' || (b == b'@' && i + 1 < bytes.len() && bytes[i + 1] == b'P') {
+            let mut j = if b == b'$' { i + 1 } else { i + 2 };
+            let mut n = 0;
+
+            while j < bytes.len() && bytes[j].is_ascii_digit() {
+                n = n * 10 + (bytes[j] - b'0') as usize;
+                j += 1;
+            }
+
+            if n > numbered {
+                numbered = n;
+            }
+        }
+
+        i += 1;
+    }
+
+    questions + numbered
+}
+
 macro_rules! value {
     ($target:ident: $kind:ty,$paramkind:ident,$that:expr) => {
         impl<'a> From<$kind> for crate::ast::Value<'a> {
@@ -2,21 +2,26 @@
 use crate::connector::MssqlUrl;
 #[cfg(feature = "mysql")]
 use crate::connector::MysqlUrl;
-#[cfg(feature = "postgresql")]
+#[cfg(feature = "postgresql-core")]
 use crate::connector::PostgresUrl;
 use async_trait::async_trait;
 
 use crate::{
     ast,
-    connector::{self, Queryable, Transaction, TransactionCapable},
+    connector::{self, CredentialsProvider, Queryable, Transaction, TransactionCapable},
     error::Error,
 };
 use mobc::{Connection as MobcPooled, Manager};
+use std::sync::Arc;
+#[cfg(feature = "mysql")]
+use std::sync::RwLock;
+use tokio::sync::OwnedSemaphorePermit;
 
 /// A connection from the pool. Implements
 /// [Queryable](connector/trait.Queryable.html).
 pub struct PooledConnection {
     pub(crate) inner: MobcPooled<QuaintManager>,
+    pub(crate) _permit: Option<OwnedSemaphorePermit>,
 }
 
 impl TransactionCapable for PooledConnection {}
@@ -39,6 +44,18 @@ impl Queryable for PooledConnection {
         self.inner.execute_raw(sql, params).await
     }
 
+    async fn execute_result(&self, q: ast::Query<'_>) -> crate::Result<connector::ExecuteResult> {
+        self.inner.execute_result(q).await
+    }
+
+    async fn execute_raw_result(
+        &self,
+        sql: &str,
+        params: &[ast::Value<'_>],
+    ) -> crate::Result<connector::ExecuteResult> {
+        self.inner.execute_raw_result(sql, params).await
+    }
+
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
         self.inner.raw_cmd(cmd).await
     }
@@ -47,6 +64,32 @@ impl Queryable for PooledConnection {
         self.inner.version().await
     }
 
+    fn connection_info(&self) -> &connector::ConnectionInfo {
+        self.inner.connection_info()
+    }
+
+    fn command_handle(&self) -> Option<connector::CommandHandle> {
+        self.inner.command_handle()
+    }
+
+    async fn upsert(&self, q: ast::Insert<'_>) -> crate::Result<Vec<connector::UpsertOutcome>> {
+        self.inner.upsert(q).await
+    }
+
+    async fn delete_returning(&self, q: ast::Delete<'_>) -> crate::Result<connector::ResultSet> {
+        self.inner.delete_returning(q).await
+    }
+
+    async fn increment(
+        &self,
+        table: ast::Table<'_>,
+        conditions: ast::ConditionTree<'_>,
+        column: ast::Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<ast::Value<'static>>> {
+        self.inner.increment(table, conditions, column, amount).await
+    }
+
     async fn server_reset_query(&self, tx: &Transaction<'_>) -> crate::Result<()> {
         self.inner.server_reset_query(tx).await
     }
@@ -54,14 +97,32 @@ impl Queryable for PooledConnection {
     fn begin_statement(&self) -> &'static str {
         self.inner.begin_statement()
     }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        self.inner.create_savepoint_statement(name)
+    }
+
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        self.inner.release_savepoint_statement(name)
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        self.inner.rollback_to_savepoint_statement(name)
+    }
 }
 
 #[doc(hidden)]
-pub enum QuaintManager {
+pub struct QuaintManager {
+    database: ManagedDatabase,
+    credentials: Option<Arc<dyn CredentialsProvider>>,
+}
+
+pub(crate) enum ManagedDatabase {
+    /// Shared with the pool, for rotating the TLS settings.
     #[cfg(feature = "mysql")]
-    Mysql(MysqlUrl),
+    Mysql(Arc<RwLock<MysqlUrl>>),
 
-    #[cfg(feature = "postgresql")]
+    #[cfg(feature = "postgresql-core")]
     Postgres(PostgresUrl),
 
     #[cfg(feature = "sqlite")]
@@ -71,15 +132,37 @@ pub enum QuaintManager {
     Mssql(MssqlUrl),
 }
 
+impl QuaintManager {
+    pub(crate) fn new(database: ManagedDatabase) -> Self {
+        Self {
+            database,
+            credentials: None,
+        }
+    }
+
+    pub(crate) fn set_credentials_provider(&mut self, credentials: Arc<dyn CredentialsProvider>) {
+        self.credentials = Some(credentials);
+    }
+
+    /// The url new MySQL connections are opened from.
+    #[cfg(feature = "mysql")]
+    pub(crate) fn mysql_url(&self) -> Option<Arc<RwLock<MysqlUrl>>> {
+        match self.database {
+            ManagedDatabase::Mysql(ref url) => Some(url.clone()),
+            _ => None,
+        }
+    }
+}
+
 #[async_trait]
 impl Manager for QuaintManager {
     type Connection = Box<dyn Queryable>;
     type Error = Error;
 
     async fn connect(&self) -> crate::Result<Self::Connection> {
-        match self {
+        match &self.database {
             #[cfg(feature = "sqlite")]
-            QuaintManager::Sqlite { url, db_name } => {
+            ManagedDatabase::Sqlite { url, db_name } => {
                 use crate::connector::Sqlite;
 
                 let mut conn = Sqlite::new(&url)?;
@@ -89,21 +172,42 @@ impl Manager for QuaintManager {
             }
 
             #[cfg(feature = "mysql")]
-            QuaintManager::Mysql(url) => {
+            ManagedDatabase::Mysql(url) => {
                 use crate::connector::Mysql;
-                Ok(Box::new(Mysql::new(url.clone())?) as Self::Connection)
+                let mut url = url.read().unwrap().clone();
+
+                if let Some(ref credentials) = self.credentials {
+                    let info = connector::ConnectionInfo::Mysql(url.clone());
+                    url.set_password(credentials.password(&info).await?);
+                }
+
+                Ok(Box::new(Mysql::new(url)?) as Self::Connection)
             }
 
-            #[cfg(feature = "postgresql")]
-            QuaintManager::Postgres(url) => {
+            #[cfg(feature = "postgresql-core")]
+            ManagedDatabase::Postgres(url) => {
                 use crate::connector::PostgreSql;
-                Ok(Box::new(PostgreSql::new(url.clone()).await?) as Self::Connection)
+                let mut url = url.clone();
+
+                if let Some(ref credentials) = self.credentials {
+                    let info = connector::ConnectionInfo::Postgres(url.clone());
+                    url.set_password(credentials.password(&info).await?);
+                }
+
+                Ok(Box::new(PostgreSql::new(url).await?) as Self::Connection)
             }
 
             #[cfg(feature = "mssql")]
-            QuaintManager::Mssql(url) => {
+            ManagedDatabase::Mssql(url) => {
                 use crate::connector::Mssql;
-                Ok(Box::new(Mssql::new(url.clone()).await?) as Self::Connection)
+                let mut url = url.clone();
+
+                if let Some(ref credentials) = self.credentials {
+                    let info = connector::ConnectionInfo::Mssql(url.clone());
+                    url.set_password(credentials.password(&info).await?)?;
+                }
+
+                Ok(Box::new(Mssql::new(url).await?) as Self::Connection)
             }
         }
     }
@@ -142,7 +246,30 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg(feature = "postgresql")]
+    #[cfg(feature = "mysql")]
+    async fn mysql_query_all_keeps_the_order_and_failures() {
+        use crate::ast::{Query, Select};
+
+        let conn_string = std::env::var("TEST_MYSQL").expect("TEST_MYSQL connection string not set.");
+        let pool = Quaint::builder(&conn_string).unwrap().build();
+
+        let queries = vec![
+            Select::default().value(1).into(),
+            Query::from("SELECT * FROM this_table_does_not_exist"),
+            Select::default().value(3).into(),
+        ];
+
+        let results = pool.query_all(queries).await;
+        let first_value = |i: usize| results[i].as_ref().unwrap().first().and_then(|row| row[0].as_i64());
+
+        assert_eq!(3, results.len());
+        assert_eq!(Some(1), first_value(0));
+        assert!(results[1].is_err());
+        assert_eq!(Some(3), first_value(2));
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "postgresql-core")]
     async fn psql_default_connection_limit() {
         let conn_string = std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.");
 
@@ -152,7 +279,7 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg(feature = "postgresql")]
+    #[cfg(feature = "postgresql-core")]
     async fn psql_custom_connection_limit() {
         let conn_string = format!(
             "{}?connection_limit=10",
@@ -164,6 +291,39 @@ mod tests {
         assert_eq!(10, pool.capacity().await as usize);
     }
 
+    #[tokio::test]
+    #[cfg(feature = "postgresql-core")]
+    async fn psql_credentials_provider() {
+        use crate::connector::{ConnectionInfo, CredentialsProvider, Queryable};
+        use async_trait::async_trait;
+        use percent_encoding::percent_decode;
+        use url::Url;
+
+        struct StaticPassword(String);
+
+        #[async_trait]
+        impl CredentialsProvider for StaticPassword {
+            async fn password(&self, _: &ConnectionInfo) -> crate::Result<String> {
+                Ok(self.0.clone())
+            }
+        }
+
+        let conn_string = std::env::var("TEST_PSQL").expect("TEST_PSQL connection string not set.");
+        let mut url = Url::parse(&conn_string).unwrap();
+
+        let password = url.password().unwrap_or("");
+        let password = percent_decode(password.as_bytes()).decode_utf8().unwrap().into_owned();
+        url.set_password(Some("wrong")).unwrap();
+
+        let mut builder = Quaint::builder(url.as_str()).unwrap();
+        builder.credentials_provider(StaticPassword(password));
+
+        let pool = builder.build();
+        let conn = pool.check_out().await.unwrap();
+
+        conn.raw_cmd("SELECT 1").await.unwrap();
+    }
+
     #[tokio::test]
     #[cfg(feature = "mssql")]
     async fn mssql_default_connection_limit() {
@@ -204,4 +364,64 @@ mod tests {
 
         assert_eq!(10, pool.capacity().await as usize);
     }
+
+    #[tokio::test]
+    #[cfg(feature = "sqlite")]
+    async fn test_batch_connection_limit() {
+        use crate::connector::{with_query_class, QueryClass};
+        use futures::FutureExt;
+
+        let mut builder = Quaint::builder("file:db/test.db?connection_limit=3").unwrap();
+        builder.batch_connection_limit(1);
+        let pool = builder.build();
+
+        let batch = with_query_class(QueryClass::Batch, pool.check_out()).await.unwrap();
+        assert!(with_query_class(QueryClass::Batch, pool.check_out())
+            .now_or_never()
+            .is_none());
+
+        let _interactive = pool.check_out().await.unwrap();
+
+        drop(batch);
+        assert!(with_query_class(QueryClass::Batch, pool.check_out()).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "mysql")]
+    async fn mysql_new_connections_use_the_rotated_ssl_params() {
+        use crate::connector::MysqlSslParams;
+        use mysql_async as my;
+
+        let pool = Quaint::builder("mysql://root@localhost/dbname").unwrap().build();
+
+        let mut ssl_params = MysqlSslParams::default();
+        ssl_params.identity_path("rotated.p12");
+        pool.set_ssl_params(ssl_params).unwrap();
+
+        let url = pool.mysql_url.as_ref().unwrap();
+        let opts = my::Opts::from(url.read().unwrap().to_opts_builder());
+        let ssl_opts = opts.get_ssl_opts().unwrap();
+
+        assert_eq!(Some(std::path::Path::new("rotated.p12")), ssl_opts.pkcs12_path());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sqlite")]
+    async fn test_shutdown() {
+        use crate::error::ErrorKind;
+        use std::time::Duration;
+
+        let pool = Quaint::builder("file:db/test.db").unwrap().build();
+        let conn = pool.check_out().await.unwrap();
+
+        let res = pool.shutdown(Duration::from_millis(50)).await;
+        assert!(matches!(res.unwrap_err().kind(), ErrorKind::Timeout(_)));
+
+        let err = pool.check_out().await.err().unwrap();
+        assert!(matches!(err.kind(), ErrorKind::PoolClosed));
+
+        drop(conn);
+        assert!(pool.shutdown(Duration::from_millis(50)).await.is_ok());
+        assert!(pool.is_closed());
+    }
 }
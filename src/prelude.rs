@@ -1,6 +1,6 @@
 //! A "prelude" for users of the `quaint` crate.
 pub use crate::ast::*;
-#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql"))]
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgresql-core"))]
 pub use crate::connector::{
     ConnectionInfo, Queryable, ResultRow, ResultSet, SqlFamily, Transaction, TransactionCapable,
 };
@@ -122,6 +122,11 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             Value::Real(Some(real)) => visitor.visit_f64(real.to_f64().unwrap()),
             Value::Real(None) => visitor.visit_none(),
 
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(Some(bits)) => visitor.visit_string(crate::ast::bits_to_string(&bits)),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(None) => visitor.visit_none(),
+
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(Some(uuid)) => visitor.visit_string(uuid.to_string()),
             #[cfg(feature = "uuid-0_8")]
@@ -152,12 +157,17 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             #[cfg(feature = "chrono-0_4")]
             Value::Time(None) => visitor.visit_none(),
 
-            #[cfg(all(feature = "array", feature = "postgresql"))]
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(Some(i)) => visitor.visit_string(format!("{}", i)),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(None) => visitor.visit_none(),
+
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
             Value::Array(Some(values)) => {
                 let deserializer = serde::de::value::SeqDeserializer::new(values.into_iter());
                 visitor.visit_seq(deserializer)
             }
-            #[cfg(all(feature = "array", feature = "postgresql"))]
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
             Value::Array(None) => visitor.visit_none(),
         }
     }
@@ -0,0 +1,335 @@
+//! Routing of queries over sharded databases.
+//!
+//! A [`Router`] holds a connection to every shard, and runs each query built
+//! with the AST on the shard picked by a user-given function. The function
+//! usually reads the shard key from the query with [`key_value`], and maps it
+//! to the index of a shard.
+//!
+//! Queries the function can't route are handled by their type:
+//!
+//! - `SELECT` and `UNION` queries run on every shard concurrently, and the
+//!   results are merged in the order of the shards. Ordering, limits and
+//!   aggregates apply to the rows of one shard, not to the merged result.
+//! - Writes return an error, as do raw queries and commands. They can be run
+//!   on an explicit shard from [`Router::shard`].
+//!
+//! Transactions span one database, so they should be started on a shard.
+//!
+//! ```no_run
+//! # use quaint::{prelude::*, single::Quaint, shard::{self, Router}};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let shards = vec![
+//!     Quaint::new("postgres://shard0/db").await?,
+//!     Quaint::new("postgres://shard1/db").await?,
+//! ];
+//!
+//! let router = Router::new(shards, |q: &Query<'_>| {
+//!     shard::key_value(q, "tenant_id")
+//!         .and_then(|v| v.as_i64())
+//!         .map(|id| (id % 2) as usize)
+//! });
+//!
+//! // Runs on the second shard.
+//! router.select(Select::from_table("users").so_that("tenant_id".equals(3))).await?;
+//!
+//! // Runs on both shards.
+//! router.select(Select::from_table("users")).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Router`]: struct.Router.html
+//! [`Router::shard`]: struct.Router.html#method.shard
+//! [`key_value`]: fn.key_value.html
+
+use crate::{
+    ast::*,
+    connector::{ConnectionInfo, ExecuteResult, Queryable, ResultSet, UpsertOutcome},
+    error::{Error, ErrorKind},
+};
+use async_trait::async_trait;
+use futures::future;
+
+/// A connection running every query on the shard it belongs to. See the
+/// [module documentation](index.html) for how the queries are routed.
+pub struct Router<Q> {
+    shards: Vec<Q>,
+    pick: Box<dyn Fn(&Query<'_>) -> Option<usize> + Send + Sync>,
+}
+
+impl<Q> Router<Q>
+where
+    Q: Queryable,
+{
+    /// Creates a router over the shards, picking the index of the shard for
+    /// a query with `route`, or `None` if the query has no shard key.
+    ///
+    /// Panics if `shards` is empty.
+    pub fn new<F>(shards: Vec<Q>, route: F) -> Self
+    where
+        F: Fn(&Query<'_>) -> Option<usize> + Send + Sync + 'static,
+    {
+        assert!(!shards.is_empty(), "A router needs at least one shard.");
+
+        Self {
+            shards,
+            pick: Box::new(route),
+        }
+    }
+
+    /// The shard in the given index, for running queries on it explicitly.
+    pub fn shard(&self, index: usize) -> Option<&Q> {
+        self.shards.get(index)
+    }
+
+    /// All the shards, in the order of their indices.
+    pub fn shards(&self) -> &[Q] {
+        &self.shards
+    }
+
+    /// The shard picked for the query, if it has a shard key.
+    fn route(&self, q: &Query<'_>) -> crate::Result<Option<&Q>> {
+        match (self.pick)(q) {
+            Some(index) => match self.shards.get(index) {
+                Some(shard) => Ok(Some(shard)),
+                None => {
+                    let msg = format!("Shard {} does not exist, the router has {}.", index, self.shards.len());
+                    Err(Error::builder(ErrorKind::conversion(msg)).build())
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// The shard picked for a write, which can't run on every shard.
+    fn route_write(&self, q: &Query<'_>) -> crate::Result<&Q> {
+        self.route(q)?.ok_or_else(|| unroutable("a write query"))
+    }
+
+    async fn scatter(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let queries: Vec<_> = self.shards.iter().map(|shard| shard.query(q.clone())).collect();
+        let results = future::try_join_all(queries).await?;
+
+        let mut merged = ResultSet::default();
+
+        for result in results {
+            merged.append(result)?;
+        }
+
+        Ok(merged)
+    }
+}
+
+#[async_trait]
+impl<Q> Queryable for Router<Q>
+where
+    Q: Queryable,
+{
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        if let Some(shard) = self.route(&q)? {
+            return shard.query(q).await;
+        }
+
+        match q {
+            Query::Select(_) | Query::Union(_) => self.scatter(q).await,
+            _ => Err(unroutable("a write query")),
+        }
+    }
+
+    async fn query_raw(&self, _: &str, _: &[Value<'_>]) -> crate::Result<ResultSet> {
+        Err(unroutable("a raw query"))
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        self.route_write(&q)?.execute(q).await
+    }
+
+    async fn execute_raw(&self, _: &str, _: &[Value<'_>]) -> crate::Result<u64> {
+        Err(unroutable("a raw query"))
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        self.route_write(&q)?.execute_result(q).await
+    }
+
+    async fn upsert(&self, q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        let shard = self.route_write(&Query::from(q.clone()))?;
+        shard.upsert(q).await
+    }
+
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        let shard = self.route_write(&Query::from(q.clone()))?;
+        shard.delete_returning(q).await
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        let update = Update::table(table.clone()).so_that(conditions.clone());
+        let shard = self.route_write(&Query::from(update))?;
+
+        shard.increment(table, conditions, column, amount).await
+    }
+
+    async fn execute_raw_result(&self, _: &str, _: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        Err(unroutable("a raw query"))
+    }
+
+    async fn raw_cmd(&self, _: &str) -> crate::Result<()> {
+        Err(unroutable("a raw command"))
+    }
+
+    /// The version of the first shard.
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.shards[0].version().await
+    }
+
+    /// The connection info of the first shard.
+    fn connection_info(&self) -> &ConnectionInfo {
+        self.shards[0].connection_info()
+    }
+}
+
+/// The value the query compares the column to, or writes to it, to be used
+/// as the shard key. Found from the `column = value` comparisons joined with
+/// `AND` in the conditions of a select, update or delete, and from the
+/// values of a single row insert.
+///
+/// ```rust
+/// # use quaint::{ast::*, shard};
+/// let query = Query::from(Select::from_table("users").so_that("tenant_id".equals(3).and("name".equals("Musti"))));
+/// assert_eq!(Some(&Value::from(3)), shard::key_value(&query, "tenant_id"));
+///
+/// let query = Query::from(Select::from_table("users").so_that("tenant_id".equals(3).or("tenant_id".equals(4))));
+/// assert_eq!(None, shard::key_value(&query, "tenant_id"));
+/// ```
+pub fn key_value<'a, 'b>(q: &'b Query<'a>, column: &str) -> Option<&'b Value<'a>> {
+    match q {
+        Query::Select(select) => select.conditions.as_ref().and_then(|c| condition_value(c, column)),
+        Query::Update(update) => update.conditions.as_ref().and_then(|c| condition_value(c, column)),
+        Query::Delete(delete) => delete.conditions.as_ref().and_then(|c| condition_value(c, column)),
+        Query::Insert(insert) => {
+            let index = insert.columns.iter().position(|c| c.name == column)?;
+
+            match &insert.values.kind {
+                ExpressionKind::Row(row) => parameter(row.values.get(index)?),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn condition_value<'a, 'b>(tree: &'b ConditionTree<'a>, column: &str) -> Option<&'b Value<'a>> {
+    match tree {
+        ConditionTree::And(exprs) => exprs.iter().find_map(|expr| expression_value(expr, column)),
+        ConditionTree::Single(expr) => expression_value(expr, column),
+        _ => None,
+    }
+}
+
+fn expression_value<'a, 'b>(expr: &'b Expression<'a>, column: &str) -> Option<&'b Value<'a>> {
+    match &expr.kind {
+        ExpressionKind::ConditionTree(tree) => condition_value(tree, column),
+        ExpressionKind::Compare(Compare::Equals(left, right)) => match (&left.kind, &right.kind) {
+            (ExpressionKind::Column(c), _) if c.name == column => parameter(right),
+            (_, ExpressionKind::Column(c)) if c.name == column => parameter(left),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parameter<'a, 'b>(expr: &'b Expression<'a>) -> Option<&'b Value<'a>> {
+    match &expr.kind {
+        ExpressionKind::Parameterized(value) => Some(value),
+        ExpressionKind::Value(expr) => parameter(expr),
+        _ => None,
+    }
+}
+
+fn unroutable(what: &str) -> Error {
+    let msg = format!(
+        "Cannot route {} without a shard key, it should be run on an explicit shard.",
+        what
+    );
+
+    Error::builder(ErrorKind::conversion(msg)).build()
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::connector::Sqlite;
+
+    async fn router() -> Router<Sqlite> {
+        let shards = vec![Sqlite::new("db/test.db").unwrap(), Sqlite::new("db/test.db").unwrap()];
+
+        for (i, shard) in shards.iter().enumerate() {
+            shard
+                .raw_cmd("CREATE TABLE users (tenant_id INTEGER, name TEXT)")
+                .await
+                .unwrap();
+
+            let insert = Insert::single_into("users")
+                .value("tenant_id", i as i64)
+                .value("name", "Musti");
+            shard.insert(insert.into()).await.unwrap();
+        }
+
+        Router::new(shards, |q: &Query<'_>| {
+            key_value(q, "tenant_id")
+                .and_then(|v| v.as_i64())
+                .map(|id| (id % 2) as usize)
+        })
+    }
+
+    #[tokio::test]
+    async fn queries_with_a_key_run_on_their_shard() {
+        let router = router().await;
+
+        let insert = Insert::single_into("users")
+            .value("tenant_id", 3)
+            .value("name", "Naukio");
+        router.insert(insert.into()).await.unwrap();
+
+        let rows = router
+            .shard(1)
+            .unwrap()
+            .select(Select::from_table("users"))
+            .await
+            .unwrap();
+        assert_eq!(2, rows.len());
+
+        let select = Select::from_table("users").so_that("tenant_id".equals(3));
+        let rows = router.select(select).await.unwrap();
+
+        assert_eq!(
+            vec![Some(String::from("Naukio"))],
+            rows.column::<String>("name").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn selects_without_a_key_are_merged_from_every_shard() {
+        let router = router().await;
+
+        let select = Select::from_table("users").column("tenant_id");
+        let rows = router.select(select).await.unwrap();
+
+        assert_eq!(vec![Some(0), Some(1)], rows.column::<i64>("tenant_id").unwrap());
+    }
+
+    #[tokio::test]
+    async fn writes_without_a_key_are_refused() {
+        let router = router().await;
+
+        let update = Update::table("users").set("name", "Naukio");
+        assert!(router.update(update).await.is_err());
+    }
+}
@@ -41,6 +41,10 @@ impl Quaint {
     /// All parameters should be given in the query string format:
     /// `?key1=val1&key2=val2`. All parameters are optional.
     ///
+    /// A password written as `${VAR}` is read from the environment variable
+    /// `VAR` instead, so secrets don't need to be a part of the connection
+    /// string.
+    ///
     /// SQLite:
     ///
     /// - `user`/`password` do not do anything and can be emitted.
@@ -55,12 +59,17 @@ impl Quaint {
     ///
     /// - `sslmode` either `disable`, `prefer` or `require`. [Read more](https://docs.rs/tokio-postgres/0.5.0-alpha.1/tokio_postgres/config/enum.SslMode.html)
     /// - `sslcert` should point to a PEM certificate file.
-    /// - `sslidentity` should point to a PKCS12 certificate database.
-    /// - `sslpassword` the password to open the PKCS12 database.
+    /// - `sslidentity` should point to a PKCS12 certificate database. With the
+    ///   `postgresql-rustls` feature, a PEM file with the client certificate and
+    ///   an unencrypted private key.
+    /// - `sslpassword` the password to open the PKCS12 database. Not supported
+    ///   with `postgresql-rustls`.
     /// - `sslaccept` either `strict` or `accept_invalid_certs`. If strict, the
     ///   certificate needs to be valid and in the CA certificates.
     ///   `accept_invalid_certs` accepts any certificate from the server and can
     ///   lead to weakened security. Defaults to `strict`.
+    /// - `sslbackend` either `native-tls` or `rustls`, when both the `postgresql`
+    ///   and `postgresql-rustls` features are enabled. Defaults to `native-tls`.
     /// - `schema` the default search path.
     /// - `host` additionally the host can be given as a parameter, typically in
     ///   cases when connectiong to the database through a unix socket to
@@ -80,6 +89,25 @@ impl Quaint {
     /// - `statement_cache_size`, number of prepared statements kept cached.
     ///   Defaults to 500, which means caching is off. If `pgbouncer` mode is enabled,
     ///   caching is always off.
+    /// - `numeric_as_text` either `true` or `false`. If set, `NUMERIC` values are
+    ///   read as `Value::Text` with all of their digits, instead of a `Decimal`.
+    /// - `non_finite_floats` either `error` or `null` (default: `error`). What
+    ///   to do with `NaN` and infinite floats, which cannot be read as a `Decimal`.
+    /// - `decode_threads` the number of threads decoding the rows of a large
+    ///   result in parallel (default: 1).
+    /// - `qualify_tables` either `true` or `false`. If set, tables without a
+    ///   schema are prefixed with `schema` in the generated SQL.
+    /// - `application_name` the name reported to the server, shown in
+    ///   `pg_stat_activity`. Defaults to the name of the running binary.
+    /// - `password_file` reads the password from the given file instead of the
+    ///   URL, e.g. from `/run/secrets/db`.
+    /// - `resolve_to` connects to the given IP address or host instead of the
+    ///   one in the URL. TLS still verifies the certificate against the host
+    ///   in the URL.
+    /// - `ssh_host`, `ssh_port`, `ssh_user`, `ssh_key` and `ssh_key_password`
+    ///   connect through an SSH tunnel. Requires the `ssh` feature.
+    /// - `proxy` connects through a SOCKS5 (`socks5://host:port`) or an HTTP
+    ///   `CONNECT` (`http://host:port`) proxy. Requires the `proxy` feature.
     ///
     /// MySQL:
     ///
@@ -92,11 +120,36 @@ impl Quaint {
     ///   lead to weakened security. Defaults to `strict`.
     /// - `socket` needed when connecting to MySQL database through a unix
     ///   socket. When set, the host parameter is dismissed.
+    /// - `numeric_as_text` either `true` or `false`. If set, `DECIMAL` values and
+    ///   unsigned integers too large for an `i64` are read as `Value::Text`.
+    /// - `non_finite_floats` either `error` or `null` (default: `error`). What
+    ///   to do with `NaN` and infinite floats, which cannot be read as a `Decimal`.
+    /// - `decode_threads` the number of threads decoding the rows of a large
+    ///   result in parallel (default: 1).
+    /// - `qualify_tables` either `true` or `false`. If set, tables without a
+    ///   database are prefixed with the database of the URL in the generated SQL.
+    /// - `invalid_dates` either `error`, `null` or `min_date` (default: `error`).
+    ///   What to do with zero dates, such as `0000-00-00 00:00:00`. The
+    ///   `min_date` option reads them as `0001-01-01 00:00:00 UTC`.
+    /// - `charset` and `collation` set the character set and the collation of
+    ///   the connection, e.g. `utf8mb4` and `utf8mb4_unicode_ci`. The first
+    ///   connection fails if the server does not use them.
+    /// - `application_name` the name of the application, available from the
+    ///   `ConnectionInfo`. Defaults to the name of the running binary.
     /// - `socket_timeout` defined in seconds. If set, a query will return a
     ///   `Timeout` error if it fails to resolve before given time.
     /// - `connect_timeout` defined in seconds (default: 5). Connecting to a
     ///   database will return a `ConnectTimeout` error if taking more than the
     ///   defined value.
+    /// - `password_file` reads the password from the given file instead of the
+    ///   URL, e.g. from `/run/secrets/db`.
+    /// - `resolve_to` connects to the given IP address or host instead of the
+    ///   one in the URL. The server certificate is validated against the new
+    ///   address.
+    /// - `ssh_host`, `ssh_port`, `ssh_user`, `ssh_key` and `ssh_key_password`
+    ///   connect through an SSH tunnel. Requires the `ssh` feature.
+    /// - `proxy` connects through a SOCKS5 (`socks5://host:port`) or an HTTP
+    ///   `CONNECT` (`http://host:port`) proxy. Requires the `proxy` feature.
     pub async fn new(url_str: &str) -> crate::Result<Self> {
         let inner = match url_str {
             #[cfg(feature = "sqlite")]
@@ -115,7 +168,7 @@ impl Quaint {
 
                 Arc::new(mysql) as Arc<dyn Queryable>
             }
-            #[cfg(feature = "postgresql")]
+            #[cfg(feature = "postgresql-core")]
             s if s.starts_with("postgres") || s.starts_with("postgresql") => {
                 let url = connector::PostgresUrl::new(Url::parse(s)?)?;
                 let psql = connector::PostgreSql::new(url).await?;
@@ -175,15 +228,65 @@ impl Queryable for Quaint {
         self.inner.execute_raw(sql, params).await
     }
 
+    async fn execute_result(&self, q: ast::Query<'_>) -> crate::Result<connector::ExecuteResult> {
+        self.inner.execute_result(q).await
+    }
+
+    async fn execute_raw_result(
+        &self,
+        sql: &str,
+        params: &[ast::Value<'_>],
+    ) -> crate::Result<connector::ExecuteResult> {
+        self.inner.execute_raw_result(sql, params).await
+    }
+
     async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
         self.inner.raw_cmd(cmd).await
     }
 
+    async fn upsert(&self, q: ast::Insert<'_>) -> crate::Result<Vec<connector::UpsertOutcome>> {
+        self.inner.upsert(q).await
+    }
+
+    async fn delete_returning(&self, q: ast::Delete<'_>) -> crate::Result<connector::ResultSet> {
+        self.inner.delete_returning(q).await
+    }
+
+    async fn increment(
+        &self,
+        table: ast::Table<'_>,
+        conditions: ast::ConditionTree<'_>,
+        column: ast::Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<ast::Value<'static>>> {
+        self.inner.increment(table, conditions, column, amount).await
+    }
+
     async fn version(&self) -> crate::Result<Option<String>> {
         self.inner.version().await
     }
 
+    fn connection_info(&self) -> &ConnectionInfo {
+        &self.connection_info
+    }
+
+    fn command_handle(&self) -> Option<connector::CommandHandle> {
+        self.inner.command_handle()
+    }
+
     fn begin_statement(&self) -> &'static str {
         self.inner.begin_statement()
     }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        self.inner.create_savepoint_statement(name)
+    }
+
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        self.inner.release_savepoint_statement(name)
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        self.inner.rollback_to_savepoint_statement(name)
+    }
 }
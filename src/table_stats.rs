@@ -0,0 +1,301 @@
+//! Usage statistics of the tables.
+//!
+//! A [`TrackedQueryable`] wraps a connection, counting the reads and writes
+//! of every table referenced in the queries built with the AST, together
+//! with the time of the last read and write. The counts are kept in a shared
+//! [`TableStats`], that can be read while the application runs.
+//!
+//! A select reads the tables in its joins and subqueries too, and an insert,
+//! update, delete or merge writes to its table. Only successful queries are
+//! counted, and raw queries are never. Transactions started on the wrapper
+//! are counted, the ones started on the wrapped connection are not.
+//!
+//! ```no_run
+//! # use quaint::{prelude::*, single::Quaint, table_stats::{TableStats, TrackedQueryable}};
+//! # use std::sync::Arc;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let stats = Arc::new(TableStats::default());
+//! let conn = TrackedQueryable::new(Quaint::new("postgres://localhost/db").await?, stats.clone());
+//!
+//! conn.select(Select::from_table("users")).await?;
+//!
+//! assert_eq!(1, stats.get("users").unwrap().reads);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TrackedQueryable`]: struct.TrackedQueryable.html
+//! [`TableStats`]: struct.TableStats.html
+
+use crate::{
+    ast::*,
+    connector::{
+        CommandHandle, ConnectionInfo, ExecuteResult, Queryable, ResultSet, Transaction, TransactionCapable,
+        UpsertOutcome,
+    },
+    tables::{query_tables, table_names, written_tables},
+};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// The reads and writes of one table.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TableUsage {
+    /// The number of queries reading the table.
+    pub reads: u64,
+    /// The number of queries writing to the table.
+    pub writes: u64,
+    /// When the table was last read, if ever.
+    pub last_read: Option<SystemTime>,
+    /// When the table was last written to, if ever.
+    pub last_write: Option<SystemTime>,
+}
+
+impl TableUsage {
+    /// When the table was last read or written to, if ever.
+    pub fn last_access(&self) -> Option<SystemTime> {
+        self.last_read.max(self.last_write)
+    }
+}
+
+/// The usage of the tables, keyed by their names. A table is named
+/// `database.schema.table` with the parts given in the query.
+#[derive(Debug, Default)]
+pub struct TableStats {
+    tables: Mutex<HashMap<String, TableUsage>>,
+}
+
+impl TableStats {
+    /// The usage of the table, if it has been read or written to.
+    pub fn get(&self, table: &str) -> Option<TableUsage> {
+        self.tables.lock().unwrap().get(table).copied()
+    }
+
+    /// The usage of every table read or written to, sorted by name.
+    pub fn snapshot(&self) -> Vec<(String, TableUsage)> {
+        let mut tables: Vec<_> = self
+            .tables
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, usage)| (name.clone(), *usage))
+            .collect();
+
+        tables.sort_by(|a, b| a.0.cmp(&b.0));
+        tables
+    }
+
+    /// Forgets the usage of all tables.
+    pub fn reset(&self) {
+        self.tables.lock().unwrap().clear();
+    }
+
+    fn record_reads(&self, names: Vec<String>) {
+        let now = SystemTime::now();
+        let mut tables = self.tables.lock().unwrap();
+
+        for name in names {
+            let usage = tables.entry(name).or_default();
+            usage.reads += 1;
+            usage.last_read = Some(now);
+        }
+    }
+
+    fn record_writes(&self, names: Vec<String>) {
+        let now = SystemTime::now();
+        let mut tables = self.tables.lock().unwrap();
+
+        for name in names {
+            let usage = tables.entry(name).or_default();
+            usage.writes += 1;
+            usage.last_write = Some(now);
+        }
+    }
+}
+
+/// A connection counting the reads and writes of the tables in its queries.
+/// See the [module documentation](index.html) for what is counted.
+pub struct TrackedQueryable<Q> {
+    inner: Q,
+    stats: Arc<TableStats>,
+}
+
+impl<Q> TrackedQueryable<Q>
+where
+    Q: Queryable,
+{
+    pub fn new(inner: Q, stats: Arc<TableStats>) -> Self {
+        Self { inner, stats }
+    }
+
+    /// The wrapped connection.
+    pub fn inner(&self) -> &Q {
+        &self.inner
+    }
+
+    /// The usage of the tables.
+    pub fn stats(&self) -> &TableStats {
+        &self.stats
+    }
+}
+
+impl<Q> TransactionCapable for TrackedQueryable<Q> where Q: TransactionCapable {}
+
+#[async_trait]
+impl<Q> Queryable for TrackedQueryable<Q>
+where
+    Q: Queryable,
+{
+    async fn query(&self, q: Query<'_>) -> crate::Result<ResultSet> {
+        let mut reads = Vec::new();
+        query_tables(&q, &mut reads);
+        let writes = written_tables(&q);
+
+        let result = self.inner.query(q).await?;
+        self.stats.record_reads(reads);
+        self.stats.record_writes(writes);
+
+        Ok(result)
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.inner.query_raw(sql, params).await
+    }
+
+    async fn execute(&self, q: Query<'_>) -> crate::Result<u64> {
+        let names = written_tables(&q);
+        let result = self.inner.execute(q).await?;
+        self.stats.record_writes(names);
+
+        Ok(result)
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.inner.execute_raw(sql, params).await
+    }
+
+    async fn execute_result(&self, q: Query<'_>) -> crate::Result<ExecuteResult> {
+        let names = written_tables(&q);
+        let result = self.inner.execute_result(q).await?;
+        self.stats.record_writes(names);
+
+        Ok(result)
+    }
+
+    async fn upsert(&self, q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        let mut names = Vec::new();
+
+        if let Some(ref table) = q.table {
+            table_names(table, &mut names);
+        }
+
+        let result = self.inner.upsert(q).await?;
+        self.stats.record_writes(names);
+
+        Ok(result)
+    }
+
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        let mut names = Vec::new();
+        table_names(&q.table, &mut names);
+
+        let result = self.inner.delete_returning(q).await?;
+        self.stats.record_writes(names);
+
+        Ok(result)
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        let mut names = Vec::new();
+        table_names(&table, &mut names);
+
+        let result = self.inner.increment(table, conditions, column, amount).await?;
+        self.stats.record_writes(names);
+
+        Ok(result)
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        self.inner.execute_raw_result(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.inner.raw_cmd(cmd).await
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.inner.version().await
+    }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        self.inner.connection_info()
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        self.inner.command_handle()
+    }
+
+    async fn server_reset_query(&self, tx: &Transaction<'_>) -> crate::Result<()> {
+        self.inner.server_reset_query(tx).await
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        self.inner.create_savepoint_statement(name)
+    }
+
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        self.inner.release_savepoint_statement(name)
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        self.inner.rollback_to_savepoint_statement(name)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::connector::Sqlite;
+
+    #[tokio::test]
+    async fn reads_and_writes_are_counted_per_table() {
+        let stats = Arc::new(TableStats::default());
+        let conn = TrackedQueryable::new(Sqlite::new("db/test.db").unwrap(), stats.clone());
+
+        conn.raw_cmd("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+            .await
+            .unwrap();
+        conn.raw_cmd("CREATE TABLE banned (user_id INTEGER)").await.unwrap();
+
+        let insert = Insert::single_into("users").value("name", "Musti");
+        conn.insert(insert.into()).await.unwrap();
+
+        let banned = Select::from_table("banned").column("user_id");
+        let select = Select::from_table("users").so_that("id".not_in_selection(banned));
+        conn.select(select).await.unwrap();
+        conn.select(Select::from_table("users")).await.unwrap();
+
+        let users = stats.get("users").unwrap();
+        assert_eq!((2, 1), (users.reads, users.writes));
+        assert!(users.last_access().is_some());
+
+        let banned = stats.get("banned").unwrap();
+        assert_eq!((1, 0), (banned.reads, banned.writes));
+        assert_eq!(None, banned.last_write);
+    }
+}
@@ -0,0 +1,140 @@
+//! The tables referenced by a query, named `database.schema.table` with the
+//! parts given in the AST.
+
+use crate::ast::*;
+
+/// The tables an insert, update, delete or merge writes to.
+pub(crate) fn written_tables(q: &Query<'_>) -> Vec<String> {
+    let mut names = Vec::new();
+
+    match q {
+        Query::Insert(insert) => {
+            if let Some(ref table) = insert.table {
+                table_names(table, &mut names);
+            }
+        }
+        Query::Update(update) => table_names(&update.table, &mut names),
+        Query::Delete(delete) => table_names(&delete.table, &mut names),
+        Query::Merge(merge) => table_names(&merge.table, &mut names),
+        _ => (),
+    }
+
+    names
+}
+
+/// The tables a query reads from, including the ones in joins, unions and
+/// nested selects.
+pub(crate) fn query_tables(q: &Query<'_>, names: &mut Vec<String>) {
+    match q {
+        Query::Select(select) => select_tables(select, names),
+        Query::Union(union) => union.selects.iter().for_each(|select| select_tables(select, names)),
+        _ => (),
+    }
+}
+
+fn select_tables(select: &Select<'_>, names: &mut Vec<String>) {
+    for table in select.tables.iter() {
+        table_names(table, names);
+    }
+
+    for join in select.joins.iter() {
+        let data = match join {
+            Join::Inner(data) | Join::Left(data) | Join::Right(data) | Join::Full(data) => data,
+        };
+
+        table_names(&data.table, names);
+        condition_tables(&data.conditions, names);
+    }
+
+    for column in select.columns.iter() {
+        expression_tables(column, names);
+    }
+
+    if let Some(ref conditions) = select.conditions {
+        condition_tables(conditions, names);
+    }
+}
+
+pub(crate) fn table_names(table: &Table<'_>, names: &mut Vec<String>) {
+    match &table.typ {
+        TableType::Table(name) => {
+            let name = vec![table.database.as_deref(), table.schema.as_deref(), Some(&**name)]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(".");
+
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        TableType::Query(select) => select_tables(select, names),
+        TableType::Values(_) | TableType::Unnest(_) => (),
+    }
+}
+
+fn condition_tables(tree: &ConditionTree<'_>, names: &mut Vec<String>) {
+    match tree {
+        ConditionTree::And(exprs) | ConditionTree::Or(exprs) => {
+            exprs.iter().for_each(|expr| expression_tables(expr, names))
+        }
+        ConditionTree::Not(expr) | ConditionTree::Single(expr) => expression_tables(expr, names),
+        ConditionTree::NoCondition | ConditionTree::NegativeCondition => (),
+    }
+}
+
+fn expression_tables(expr: &Expression<'_>, names: &mut Vec<String>) {
+    match &expr.kind {
+        ExpressionKind::Select(select) => select_tables(select, names),
+        ExpressionKind::ConditionTree(tree) => condition_tables(tree, names),
+        ExpressionKind::Value(expr) => expression_tables(expr, names),
+        ExpressionKind::Row(row) => row.values.iter().for_each(|expr| expression_tables(expr, names)),
+        ExpressionKind::Compare(compare) => match compare {
+            Compare::Equals(left, right)
+            | Compare::NotEquals(left, right)
+            | Compare::LessThan(left, right)
+            | Compare::LessThanOrEquals(left, right)
+            | Compare::GreaterThan(left, right)
+            | Compare::GreaterThanOrEquals(left, right)
+            | Compare::In(left, right)
+            | Compare::NotIn(left, right)
+            | Compare::ArrayContains(left, right)
+            | Compare::ArrayContainedBy(left, right)
+            | Compare::ArrayOverlaps(left, right)
+            | Compare::EqualsAny(left, right)
+            | Compare::NotDistinctFrom(left, right)
+            | Compare::DistinctFrom(left, right)
+            | Compare::EqualsIgnoreCase(left, right) => {
+                expression_tables(left, names);
+                expression_tables(right, names);
+            }
+            _ => (),
+        },
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_tables_include_joins_and_subqueries() {
+        let subquery = Select::from_table("banned").column("user_id");
+
+        let query = Select::from_table(Table::from("users").database("app"))
+            .inner_join("posts".on(("posts", "user_id").equals(Column::from(("users", "id")))))
+            .so_that("id".not_in_selection(subquery));
+
+        let mut names = Vec::new();
+        query_tables(&Query::from(query), &mut names);
+
+        assert_eq!(vec!["app.users", "posts", "banned"], names);
+    }
+
+    #[test]
+    fn writes_are_named_by_their_table() {
+        let update = Update::table("users").set("name", "Musti");
+        assert_eq!(vec!["users"], written_tables(&Query::from(update)));
+    }
+}
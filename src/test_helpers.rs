@@ -0,0 +1,46 @@
+//! Helpers for testing code against a real database.
+use crate::connector::{Transaction, TransactionCapable};
+use futures::future::BoxFuture;
+
+/// Runs the closure in a transaction that is always rolled back, leaving the
+/// database as it was before the test. The code under test can start its own
+/// transactions from the given one, which are then run in savepoints.
+///
+/// The result of the closure is returned after the rollback.
+///
+/// ```no_run
+/// # use quaint::{prelude::*, single::Quaint, test_helpers::with_rollback};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), quaint::error::Error> {
+/// let conn = Quaint::new("file:///tmp/example.db").await?;
+///
+/// let count = with_rollback(&conn, |tx| {
+///     Box::pin(async move {
+///         tx.insert(Insert::single_into("users").value("name", "Musti").into()).await?;
+///
+///         let nested = tx.start_transaction().await?;
+///         nested.delete(Delete::from_table("users")).await?;
+///         nested.rollback().await?;
+///
+///         let rows = tx.select(Select::from_table("users")).await?;
+///         Ok(rows.len())
+///     })
+/// })
+/// .await?;
+///
+/// assert_eq!(1, count);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_rollback<C, F, T>(conn: &C, f: F) -> crate::Result<T>
+where
+    C: TransactionCapable,
+    F: for<'t> FnOnce(&'t Transaction<'t>) -> BoxFuture<'t, crate::Result<T>>,
+{
+    let tx = conn.start_transaction().await?;
+    let result = f(&tx).await;
+
+    tx.rollback().await?;
+
+    result
+}
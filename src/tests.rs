@@ -2,7 +2,7 @@
 mod mssql;
 #[cfg(feature = "mysql")]
 mod mysql;
-#[cfg(feature = "postgresql")]
+#[cfg(feature = "postgresql-core")]
 mod postgres;
 #[cfg(feature = "sqlite")]
 mod sqlite;
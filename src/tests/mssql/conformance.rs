@@ -0,0 +1,25 @@
+use super::CONN_STR;
+use crate::{
+    conformance::{self, ColumnType, QuoteStyle, TableHarness},
+    single::Quaint,
+};
+
+fn column_type(r#type: ColumnType) -> &'static str {
+    match r#type {
+        ColumnType::Id => "INT PRIMARY KEY",
+        ColumnType::Integer => "BIGINT",
+        ColumnType::Text => "NVARCHAR(MAX)",
+        ColumnType::Decimal => "DECIMAL(12, 4)",
+        ColumnType::Boolean => "BIT",
+        ColumnType::Bytes => "VARBINARY(MAX)",
+        ColumnType::DateTime => "DATETIMEOFFSET(3)",
+    }
+}
+
+#[tokio::test]
+async fn mssql_passes_the_conformance_suite() -> crate::Result<()> {
+    let conn = Quaint::new(&CONN_STR).await?;
+    let harness = TableHarness::new(conn, column_type, QuoteStyle::Brackets);
+
+    conformance::run_all(&harness).await
+}
@@ -0,0 +1,25 @@
+use super::CONN_STR;
+use crate::{
+    conformance::{self, ColumnType, QuoteStyle, TableHarness},
+    single::Quaint,
+};
+
+fn column_type(r#type: ColumnType) -> &'static str {
+    match r#type {
+        ColumnType::Id => "INT PRIMARY KEY",
+        ColumnType::Integer => "BIGINT",
+        ColumnType::Text => "TEXT",
+        ColumnType::Decimal => "DECIMAL(12, 4)",
+        ColumnType::Boolean => "BOOLEAN",
+        ColumnType::Bytes => "MEDIUMBLOB",
+        ColumnType::DateTime => "DATETIME(3)",
+    }
+}
+
+#[tokio::test]
+async fn mysql_passes_the_conformance_suite() -> crate::Result<()> {
+    let conn = Quaint::new(&CONN_STR).await?;
+    let harness = TableHarness::new(conn, column_type, QuoteStyle::Backticks);
+
+    conformance::run_all(&harness).await
+}
@@ -1,3 +1,4 @@
+mod conformance;
 mod types;
 
 use super::type_test::TypeTest;
@@ -0,0 +1,25 @@
+use super::CONN_STR;
+use crate::{
+    conformance::{self, ColumnType, QuoteStyle, TableHarness},
+    single::Quaint,
+};
+
+fn column_type(r#type: ColumnType) -> &'static str {
+    match r#type {
+        ColumnType::Id => "INTEGER PRIMARY KEY",
+        ColumnType::Integer => "BIGINT",
+        ColumnType::Text => "TEXT",
+        ColumnType::Decimal => "DECIMAL(12, 4)",
+        ColumnType::Boolean => "BOOLEAN",
+        ColumnType::Bytes => "BYTEA",
+        ColumnType::DateTime => "TIMESTAMPTZ",
+    }
+}
+
+#[tokio::test]
+async fn postgres_passes_the_conformance_suite() -> crate::Result<()> {
+    let conn = Quaint::new(&CONN_STR).await?;
+    let harness = TableHarness::new(conn, column_type, QuoteStyle::DoubleQuotes);
+
+    conformance::run_all(&harness).await
+}
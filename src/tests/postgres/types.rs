@@ -204,21 +204,29 @@ test_type!(text_array(
     Value::array(vec![Value::text("foobar"), Value::text("omgwtf")])
 ));
 
-test_type!(bit(PostgreSql, "bit(4)", Value::Text(None), Value::text("1001")));
+test_type!(bit(
+    PostgreSql,
+    "bit(4)",
+    Value::Bits(None),
+    Value::bits_from_str("1001").unwrap()
+));
 
 #[cfg(feature = "array")]
 test_type!(bit_array(
     PostgreSql,
     "bit(4)[]",
     Value::Array(None),
-    Value::array(vec![Value::text("1001"), Value::text("0110")])
+    Value::array(vec![
+        Value::bits_from_str("1001").unwrap(),
+        Value::bits_from_str("0110").unwrap()
+    ])
 ));
 
 test_type!(varbit(
     PostgreSql,
     "varbit(20)",
-    Value::Text(None),
-    Value::text("001010101")
+    Value::Bits(None),
+    Value::bits_from_str("001010101").unwrap()
 ));
 
 #[cfg(feature = "array")]
@@ -226,7 +234,10 @@ test_type!(varbit_array(
     PostgreSql,
     "varbit(20)[]",
     Value::Array(None),
-    Value::array(vec![Value::text("001010101"), Value::text("01101111")])
+    Value::array(vec![
+        Value::bits_from_str("001010101").unwrap(),
+        Value::bits_from_str("01101111").unwrap()
+    ])
 ));
 
 test_type!(inet(PostgreSql, "inet", Value::Text(None), Value::text("127.0.0.1")));
@@ -239,6 +250,41 @@ test_type!(inet_array(
     Value::array(vec![Value::text("127.0.0.1"), Value::text("192.168.1.1")])
 ));
 
+test_type!(point(PostgreSql, "point", Value::Text(None), Value::text("(1.5,-2)")));
+
+test_type!(line(PostgreSql, "line", Value::Text(None), Value::text("{1,-1,0}")));
+
+test_type!(lseg(
+    PostgreSql,
+    "lseg",
+    Value::Text(None),
+    Value::text("[(1,2),(3,4)]")
+));
+
+test_type!(box_(PostgreSql, "box", Value::Text(None), Value::text("(3,4),(1,2)")));
+
+test_type!(path(
+    PostgreSql,
+    "path",
+    Value::Text(None),
+    Value::text("[(0,0),(1,1),(2,0)]"),
+    Value::text("((0,0),(1,1),(2,0))")
+));
+
+test_type!(polygon(
+    PostgreSql,
+    "polygon",
+    Value::Text(None),
+    Value::text("((0,0),(1,1),(1,0))")
+));
+
+test_type!(circle(
+    PostgreSql,
+    "circle",
+    Value::Text(None),
+    Value::text("<(1,2),3>")
+));
+
 #[cfg(feature = "json-1")]
 test_type!(json(
     PostgreSql,
@@ -341,6 +387,14 @@ test_type!(timestamptz_array(PostgreSql, "timestamptz[]", Value::Array(None), {
     Value::array(vec![dt.with_timezone(&chrono::Utc)])
 }));
 
+#[cfg(feature = "chrono-0_4")]
+test_type!(interval(
+    PostgreSql,
+    "interval",
+    Value::Interval(None),
+    Value::interval(chrono::Duration::days(1) + chrono::Duration::milliseconds(1500))
+));
+
 /* Reserved for SQLx. All of these are broken in the current impl!
 #[cfg(feature = "chrono-0_4")]
 test_type!(timetz(PostgreSql, "timetz", {
@@ -0,0 +1,24 @@
+use crate::{
+    conformance::{self, ColumnType, QuoteStyle, TableHarness},
+    single::Quaint,
+};
+
+fn column_type(r#type: ColumnType) -> &'static str {
+    match r#type {
+        ColumnType::Id => "INTEGER PRIMARY KEY",
+        ColumnType::Integer => "INTEGER",
+        ColumnType::Text => "TEXT",
+        ColumnType::Decimal => "DECIMAL",
+        ColumnType::Boolean => "BOOLEAN",
+        ColumnType::Bytes => "BLOB",
+        ColumnType::DateTime => "DATETIME",
+    }
+}
+
+#[tokio::test]
+async fn sqlite_passes_the_conformance_suite() -> crate::Result<()> {
+    let conn = Quaint::new("file:db/test.db").await?;
+    let harness = TableHarness::new(conn, column_type, QuoteStyle::Backticks);
+
+    conformance::run_all(&harness).await
+}
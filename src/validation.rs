@@ -0,0 +1,353 @@
+//! Checking the rows written by inserts and updates before running them.
+//!
+//! A [`RowValidator`] is given the table and the column and value pairs of
+//! every row an `INSERT` or `UPDATE` writes, after the query is built and
+//! before it's turned into SQL. It can change the values, such as trimming
+//! strings, or reject the query with an error, such as for a string too long
+//! for its column.
+//!
+//! [`Validated`] wraps a connection, validating the queries built with the
+//! AST before running them. Only the values given as parameters are
+//! validated: the ones computed by the database, such as function calls and
+//! nested selects, are not, nor are the rows inserted from a select. Raw
+//! queries are run as they are.
+//!
+//! An upsert with `OnConflict::Update` sets the conflicting row to the
+//! inserted values, so its assignments write the values as changed by the
+//! validator.
+//!
+//! ```no_run
+//! # use quaint::{prelude::*, single::Quaint, validation::{RowValidator, Validated}};
+//! # use quaint::error::{Error, ErrorKind};
+//! struct TrimNames;
+//!
+//! impl RowValidator for TrimNames {
+//!     fn validate(&self, _: &Table<'_>, row: &mut [(&str, &mut Value<'_>)]) -> quaint::Result<()> {
+//!         for (column, value) in row.iter_mut() {
+//!             if *column == "name" {
+//!                 let trimmed = value.as_str().map(|s| s.trim().to_string());
+//!
+//!                 match trimmed {
+//!                     Some(name) if name.is_empty() => {
+//!                         let kind = ErrorKind::conversion("The name can't be empty.");
+//!                         return Err(Error::builder(kind).build());
+//!                     }
+//!                     Some(name) => **value = Value::text(name),
+//!                     None => (),
+//!                 }
+//!             }
+//!         }
+//!
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), quaint::error::Error> {
+//! let conn = Validated::new(Quaint::new("postgres://localhost/db").await?, TrimNames);
+//!
+//! // Inserts the name `Musti`.
+//! conn.insert(Insert::single_into("users").value("name", "  Musti ").into()).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`RowValidator`]: trait.RowValidator.html
+//! [`Validated`]: struct.Validated.html
+
+use crate::{
+    ast::*,
+    connector::{
+        CommandHandle, ConnectionInfo, ExecuteResult, Queryable, ResultSet, Transaction, TransactionCapable,
+        UpsertOutcome,
+    },
+};
+use async_trait::async_trait;
+
+/// Checks the rows written to the database.
+pub trait RowValidator: Send + Sync {
+    /// Checks the values written to one row of the table, changing them if
+    /// needed. The row has the columns written with a parameter value, in
+    /// the order of the query. An error rejects the whole query.
+    fn validate(&self, table: &Table<'_>, row: &mut [(&str, &mut Value<'_>)]) -> crate::Result<()>;
+}
+
+/// Validates the rows written by the query, if it's an insert or an update.
+///
+/// ```rust
+/// # use quaint::{ast::*, validation::{self, RowValidator}, visitor::{Visitor, Sqlite}};
+/// struct Lowercase;
+///
+/// impl RowValidator for Lowercase {
+///     fn validate(&self, _: &Table<'_>, row: &mut [(&str, &mut Value<'_>)]) -> quaint::Result<()> {
+///         for (_, value) in row.iter_mut() {
+///             if let Some(s) = value.as_str().map(|s| s.to_lowercase()) {
+///                 **value = Value::text(s);
+///             }
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// # fn main() -> Result<(), quaint::error::Error> {
+/// let mut query = Update::table("users").set("email", "Musti@Example.com").into();
+/// validation::validate(&mut query, &Lowercase)?;
+///
+/// let (_, params) = Sqlite::build(query)?;
+/// assert_eq!(vec![Value::from("musti@example.com")], params);
+/// # Ok(())
+/// # }
+/// ```
+pub fn validate(query: &mut Query<'_>, validator: &dyn RowValidator) -> crate::Result<()> {
+    match query {
+        Query::Insert(insert) => validate_insert(insert, validator),
+        Query::Update(update) => validate_row(&update.table, &update.columns, &mut update.values, validator),
+        _ => Ok(()),
+    }
+}
+
+fn validate_insert(insert: &mut Insert<'_>, validator: &dyn RowValidator) -> crate::Result<()> {
+    let table = match insert.table {
+        Some(ref table) => table,
+        None => return Ok(()),
+    };
+
+    match insert.values.kind {
+        ExpressionKind::Row(ref mut row) => validate_row(table, &insert.columns, &mut row.values, validator),
+        ExpressionKind::Values(ref mut values) => {
+            for row in values.rows.iter_mut() {
+                validate_row(table, &insert.columns, &mut row.values, validator)?;
+            }
+
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_row(
+    table: &Table<'_>,
+    columns: &[Column<'_>],
+    values: &mut [Expression<'_>],
+    validator: &dyn RowValidator,
+) -> crate::Result<()> {
+    let mut row: Vec<_> = columns
+        .iter()
+        .zip(values.iter_mut())
+        .filter_map(|(column, expression)| match expression.kind {
+            ExpressionKind::Parameterized(ref mut value) => Some((column.name.as_ref(), value)),
+            _ => None,
+        })
+        .collect();
+
+    validator.validate(table, &mut row)
+}
+
+/// A connection validating the rows written by its queries. See the
+/// [module documentation](index.html) for what is validated.
+pub struct Validated<Q> {
+    inner: Q,
+    validator: Box<dyn RowValidator>,
+}
+
+impl<Q> Validated<Q>
+where
+    Q: Queryable,
+{
+    pub fn new<V>(inner: Q, validator: V) -> Self
+    where
+        V: RowValidator + 'static,
+    {
+        Self {
+            inner,
+            validator: Box::new(validator),
+        }
+    }
+
+    /// The wrapped connection.
+    pub fn inner(&self) -> &Q {
+        &self.inner
+    }
+}
+
+impl<Q> TransactionCapable for Validated<Q> where Q: TransactionCapable {}
+
+#[async_trait]
+impl<Q> Queryable for Validated<Q>
+where
+    Q: Queryable,
+{
+    async fn query(&self, mut q: Query<'_>) -> crate::Result<ResultSet> {
+        validate(&mut q, &*self.validator)?;
+        self.inner.query(q).await
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ResultSet> {
+        self.inner.query_raw(sql, params).await
+    }
+
+    async fn execute(&self, mut q: Query<'_>) -> crate::Result<u64> {
+        validate(&mut q, &*self.validator)?;
+        self.inner.execute(q).await
+    }
+
+    async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<u64> {
+        self.inner.execute_raw(sql, params).await
+    }
+
+    async fn execute_result(&self, mut q: Query<'_>) -> crate::Result<ExecuteResult> {
+        validate(&mut q, &*self.validator)?;
+        self.inner.execute_result(q).await
+    }
+
+    async fn upsert(&self, mut q: Insert<'_>) -> crate::Result<Vec<UpsertOutcome>> {
+        validate_insert(&mut q, &*self.validator)?;
+        self.inner.upsert(q).await
+    }
+
+    async fn delete_returning(&self, q: Delete<'_>) -> crate::Result<ResultSet> {
+        self.inner.delete_returning(q).await
+    }
+
+    async fn increment(
+        &self,
+        table: Table<'_>,
+        conditions: ConditionTree<'_>,
+        column: Column<'_>,
+        amount: i64,
+    ) -> crate::Result<Option<Value<'static>>> {
+        self.inner.increment(table, conditions, column, amount).await
+    }
+
+    async fn execute_raw_result(&self, sql: &str, params: &[Value<'_>]) -> crate::Result<ExecuteResult> {
+        self.inner.execute_raw_result(sql, params).await
+    }
+
+    async fn raw_cmd(&self, cmd: &str) -> crate::Result<()> {
+        self.inner.raw_cmd(cmd).await
+    }
+
+    async fn version(&self) -> crate::Result<Option<String>> {
+        self.inner.version().await
+    }
+
+    fn connection_info(&self) -> &ConnectionInfo {
+        self.inner.connection_info()
+    }
+
+    fn command_handle(&self) -> Option<CommandHandle> {
+        self.inner.command_handle()
+    }
+
+    async fn server_reset_query(&self, tx: &Transaction<'_>) -> crate::Result<()> {
+        self.inner.server_reset_query(tx).await
+    }
+
+    fn begin_statement(&self) -> &'static str {
+        self.inner.begin_statement()
+    }
+
+    fn create_savepoint_statement(&self, name: &str) -> String {
+        self.inner.create_savepoint_statement(name)
+    }
+
+    fn release_savepoint_statement(&self, name: &str) -> Option<String> {
+        self.inner.release_savepoint_statement(name)
+    }
+
+    fn rollback_to_savepoint_statement(&self, name: &str) -> String {
+        self.inner.rollback_to_savepoint_statement(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::{Error, ErrorKind},
+        visitor::{Sqlite, Visitor},
+    };
+
+    struct MaxLength(usize);
+
+    impl RowValidator for MaxLength {
+        fn validate(&self, table: &Table<'_>, row: &mut [(&str, &mut Value<'_>)]) -> crate::Result<()> {
+            assert!(matches!(table.typ, TableType::Table(ref name) if name == "users"));
+
+            for (column, value) in row.iter() {
+                if value.as_str().map(|s| s.len() > self.0).unwrap_or(false) {
+                    let kind = ErrorKind::LengthMismatch {
+                        column: Some(column.to_string()),
+                    };
+
+                    return Err(Error::builder(kind).build());
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn every_inserted_row_is_validated() {
+        let insert = Insert::multi_into("users", vec!["name"])
+            .values(vec!["Musti"])
+            .values(vec!["Naukio the cat"]);
+
+        let mut query = Query::from(insert);
+        let err = validate(&mut query, &MaxLength(10)).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::LengthMismatch { column: Some(c) } if c == "name"));
+    }
+
+    #[test]
+    fn upsert_assignments_write_the_validated_values() {
+        struct Trim;
+
+        impl RowValidator for Trim {
+            fn validate(&self, _: &Table<'_>, row: &mut [(&str, &mut Value<'_>)]) -> crate::Result<()> {
+                for (_, value) in row.iter_mut() {
+                    if let Some(s) = value.as_str().map(|s| s.trim().to_string()) {
+                        **value = Value::text(s);
+                    }
+                }
+
+                Ok(())
+            }
+        }
+
+        let insert = Insert::single_into("users")
+            .value("id", 1)
+            .value("name", " Musti ")
+            .build()
+            .on_conflict(OnConflict::Update {
+                target: vec!["id".into()],
+                columns: vec!["name".into()],
+            });
+
+        let mut query = Query::from(insert);
+        validate(&mut query, &Trim).unwrap();
+
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(
+            "INSERT INTO `users` (`id`, `name`) VALUES (?,?) ON CONFLICT (`id`) DO UPDATE SET `name` = `excluded`.`name`",
+            sql
+        );
+        assert_eq!(vec![Value::from(1), Value::from("Musti")], params);
+    }
+
+    #[test]
+    fn only_parameterized_values_are_validated() {
+        let update = Update::table("users")
+            .set("name", "Musti")
+            .set("email", lower(Column::from("email")));
+
+        let mut query = Query::from(update);
+        validate(&mut query, &MaxLength(10)).unwrap();
+
+        let (_, params) = Sqlite::build(query).unwrap();
+        assert_eq!(vec![Value::from("Musti")], params);
+    }
+}
@@ -6,21 +6,46 @@
 //! [ast](../ast/index.html) module.
 //!
 //! For prelude, all important imports are in `quaint::visitor::*`;
+mod ansi;
 mod mssql;
 mod mysql;
 mod postgres;
 mod sqlite;
 
+pub use self::ansi::Ansi;
 pub use self::mssql::Mssql;
 pub use self::mysql::Mysql;
 pub use self::postgres::Postgres;
 pub use self::sqlite::Sqlite;
 
-use crate::ast::*;
+use crate::{
+    ast::*,
+    error::{Error, ErrorKind},
+    escape,
+};
 use std::fmt;
 
 pub type Result = crate::Result<()>;
 
+/// How a visitor surrounds identifiers, such as table and column names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quoting {
+    /// The quotes of the database: backticks for MySQL and SQLite, double
+    /// quotes for PostgreSQL and brackets for SQL Server.
+    Default,
+    /// ANSI double quotes, such as for MySQL in the `ANSI_QUOTES` mode.
+    Ansi,
+    /// Identifiers are written as they are, for names that are already
+    /// quoted.
+    Unquoted,
+}
+
+impl Default for Quoting {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
 /// A function travelling through the query AST, building the final query string
 /// and gathering parameters sent to the database together with the query.
 pub trait Visitor<'a> {
@@ -30,6 +55,14 @@ pub trait Visitor<'a> {
     const C_BACKTICK_CLOSE: &'static str;
     /// Wildcard character to be used in `LIKE` queries.
     const C_WILDCARD: &'static str;
+    /// If set, the `AS OF` clause of a `SELECT` follows every table in the
+    /// `FROM` clause and every joined table instead of the whole clause.
+    const C_AS_OF_PER_TABLE: bool = false;
+    /// If set, a backslash escapes the next character in string literals.
+    const C_BACKSLASH_ESCAPES: bool = false;
+    /// A column identifying the rows of any table, used for limiting the rows
+    /// of a `DELETE` or an `UPDATE` with a subquery.
+    const C_ROW_ID: Option<&'static str> = None;
 
     /// Convert the given `Query` to an SQL string and a vector of parameters.
     /// When certain parameters are replaced with the `C_PARAM` character in the
@@ -57,6 +90,77 @@ pub trait Visitor<'a> {
     where
         Q: Into<Query<'a>>;
 
+    /// Convert the given `Query` to an SQL string and a vector of
+    /// parameters, surrounding the identifiers as set in `quoting`.
+    ///
+    /// ```
+    /// # use quaint::{ast::*, visitor::*};
+    /// # fn main() -> Result {
+    /// let query = Select::from_table("cats").column("name");
+    ///
+    /// let (ansi, _) = Mysql::build_with_quoting(query.clone(), Quoting::Ansi)?;
+    /// let (unquoted, _) = Mysql::build_with_quoting(query, Quoting::Unquoted)?;
+    ///
+    /// assert_eq!("SELECT \"name\" FROM \"cats\"", ansi);
+    /// assert_eq!("SELECT name FROM cats", unquoted);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Visitors not overriding this only support `Quoting::Default`.
+    fn build_with_quoting<Q>(query: Q, quoting: Quoting) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        match quoting {
+            Quoting::Default => Self::build(query),
+            _ => Err(Error::unsupported(format!(
+                "{:?} quoting is not supported by the visitor.",
+                quoting
+            ))),
+        }
+    }
+
+    /// Convert the given `Query` to an SQL string with the parameter values
+    /// written into it as escaped literals, to be read or pasted into a
+    /// console, e.g. after an `EXPLAIN`.
+    ///
+    /// Not for executing: the values lose their parameter types, and a
+    /// different value produces a different statement for the database to
+    /// prepare.
+    ///
+    /// ```
+    /// # use quaint::{ast::*, visitor::*};
+    /// # fn main() -> Result {
+    /// let query = Select::from_table("users").so_that("name".equals("O'Brien")).limit(10);
+    /// let sql = Postgres::build_with_inlined_params(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" WHERE \"name\" = 'O''Brien' LIMIT 10", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Visitors not overriding this return an error.
+    fn build_with_inlined_params<Q>(_query: Q) -> crate::Result<String>
+    where
+        Q: Into<Query<'a>>,
+    {
+        Err(Error::unsupported(
+            "Inlining the parameters is not supported by the visitor.",
+        ))
+    }
+
+    /// How the identifiers are surrounded in the query.
+    fn quoting(&self) -> Quoting {
+        Quoting::Default
+    }
+
+    /// If set, the parameter values are written into the query instead of
+    /// the placeholders.
+    fn inlines_params(&self) -> bool {
+        false
+    }
+
     /// Write to the query.
     fn write<D: fmt::Display>(&mut self, s: D) -> Result;
 
@@ -90,30 +194,44 @@ pub trait Visitor<'a> {
 
     /// A visit to a value we parameterize
     fn visit_parameterized(&mut self, value: Value<'a>) -> Result {
+        if self.inlines_params() {
+            return self.visit_raw_value(value);
+        }
+
         self.add_parameter(value);
         self.parameter_substitution()
     }
 
+    /// The `DEFAULT` keyword in `INSERT` values or `UPDATE` assignments.
+    fn visit_default(&mut self) -> Result {
+        self.write("DEFAULT")
+    }
+
     /// The join statements in the query
     fn visit_joins(&mut self, joins: Vec<Join<'a>>) -> Result {
+        self.visit_joins_as_of(joins, None)
+    }
+
+    /// The join statements in the query, with the joined tables read as of
+    /// the given point in time if set.
+    fn visit_joins_as_of(&mut self, joins: Vec<Join<'a>>, time: Option<&Expression<'a>>) -> Result {
         for j in joins {
-            match j {
-                Join::Inner(data) => {
-                    self.write(" INNER JOIN ")?;
-                    self.visit_join_data(data)?;
-                }
-                Join::Left(data) => {
-                    self.write(" LEFT JOIN ")?;
-                    self.visit_join_data(data)?;
-                }
-                Join::Right(data) => {
-                    self.write(" RIGHT JOIN ")?;
-                    self.visit_join_data(data)?;
-                }
-                Join::Full(data) => {
-                    self.write(" FULL JOIN ")?;
-                    self.visit_join_data(data)?;
+            let (keyword, data) = match j {
+                Join::Inner(data) => (" INNER JOIN ", data),
+                Join::Left(data) => (" LEFT JOIN ", data),
+                Join::Right(data) => (" RIGHT JOIN ", data),
+                Join::Full(data) => (" FULL JOIN ", data),
+            };
+
+            self.write(keyword)?;
+
+            match time {
+                Some(time) => {
+                    self.visit_table_as_of(data.table, time.clone())?;
+                    self.write(" ON ")?;
+                    self.visit_conditions(data.conditions)?;
                 }
+                None => self.visit_join_data(data)?,
             }
         }
 
@@ -126,6 +244,66 @@ pub trait Visitor<'a> {
         self.visit_conditions(data.conditions)
     }
 
+    /// A table from the values of an array. Not supported by default.
+    fn visit_unnest(&mut self, _unnest: Unnest<'a>) -> Result {
+        Err(Error::unsupported(
+            "Turning an array into a table is not supported by the database.",
+        ))
+    }
+
+    /// Reading a value from a JSON document. Not supported by default.
+    fn visit_json_extract(&mut self, _json_extract: JsonExtract<'a>) -> Result {
+        Err(Error::unsupported("JSON_EXTRACT is not supported by the database."))
+    }
+
+    /// Replacing a value in a JSON document. Not supported by default.
+    fn visit_json_set(&mut self, _json_set: JsonSet<'a>) -> Result {
+        Err(Error::unsupported("JSON_SET is not supported by the database."))
+    }
+
+    /// Converting a JSON value into text. Not supported by default.
+    fn visit_json_unquote(&mut self, _json_unquote: JsonUnquote<'a>) -> Result {
+        Err(Error::unsupported("JSON_UNQUOTE is not supported by the database."))
+    }
+
+    /// The point in time to read the data from. Not supported by default.
+    fn visit_as_of(&mut self, _time: Expression<'a>) -> Result {
+        Err(Error::unsupported(
+            "Reading data as of a point in time is not supported by the database.",
+        ))
+    }
+
+    /// The `FOR UPDATE` clause locking the selected rows.
+    fn visit_lock(&mut self, mode: LockMode) -> Result {
+        self.write(" FOR UPDATE")?;
+
+        match mode {
+            LockMode::Wait => Ok(()),
+            LockMode::NoWait => self.write(" NOWAIT"),
+            LockMode::SkipLocked => self.write(" SKIP LOCKED"),
+        }
+    }
+
+    /// A table in the `FROM` clause, read as of the given point in time.
+    fn visit_table_as_of(&mut self, mut table: Table<'a>, time: Expression<'a>) -> Result {
+        match table.typ {
+            TableType::Table(_) => {
+                let alias = table.alias.take();
+
+                self.visit_table(table, false)?;
+                self.visit_as_of(time)?;
+
+                if let Some(alias) = alias {
+                    self.write(" AS ")?;
+                    self.delimited_identifiers(&[&*alias])?;
+                }
+
+                Ok(())
+            }
+            _ => self.visit_table(table, true),
+        }
+    }
+
     /// A walk through a `SELECT` statement
     fn visit_select(&mut self, select: Select<'a>) -> Result {
         self.write("SELECT ")?;
@@ -142,20 +320,16 @@ pub trait Visitor<'a> {
                     }
 
                     match table.typ {
-                        TableType::Query(_) | TableType::Values(_) => match table.alias {
+                        TableType::Query(_) | TableType::Values(_) | TableType::Unnest(_) => match table.alias {
                             Some(ref alias) => {
-                                self.surround_with(Self::C_BACKTICK_OPEN, Self::C_BACKTICK_CLOSE, |ref mut s| {
-                                    s.write(alias)
-                                })?;
+                                self.delimited_identifiers(&[&*alias])?;
                                 self.write(".*")?;
                             }
                             None => self.write("*")?,
                         },
                         TableType::Table(_) => match table.alias.clone() {
                             Some(ref alias) => {
-                                self.surround_with(Self::C_BACKTICK_OPEN, Self::C_BACKTICK_CLOSE, |ref mut s| {
-                                    s.write(alias)
-                                })?;
+                                self.delimited_identifiers(&[&*alias])?;
                                 self.write(".*")?;
                             }
                             None => {
@@ -176,11 +350,22 @@ pub trait Visitor<'a> {
                     self.write(", ")?;
                 }
 
-                self.visit_table(*table, true)?;
+                match select.as_of {
+                    Some(ref time) if Self::C_AS_OF_PER_TABLE => self.visit_table_as_of(*table, (**time).clone())?,
+                    _ => self.visit_table(*table, true)?,
+                }
             }
 
             if !select.joins.is_empty() {
-                self.visit_joins(select.joins)?;
+                match select.as_of {
+                    Some(ref time) if Self::C_AS_OF_PER_TABLE => self.visit_joins_as_of(select.joins, Some(time))?,
+                    _ => self.visit_joins(select.joins)?,
+                }
+            }
+
+            match select.as_of {
+                Some(time) if !Self::C_AS_OF_PER_TABLE => self.visit_as_of(*time)?,
+                _ => (),
             }
 
             if let Some(conditions) = select.conditions {
@@ -201,6 +386,10 @@ pub trait Visitor<'a> {
             }
 
             self.visit_limit_and_offset(select.limit, select.offset)?;
+
+            if let Some(mode) = select.lock {
+                self.visit_lock(mode)?;
+            }
         } else if select.columns.is_empty() {
             self.write(" *")?;
         } else {
@@ -212,18 +401,56 @@ pub trait Visitor<'a> {
 
     /// A walk through an `UPDATE` statement
     fn visit_update(&mut self, update: Update<'a>) -> Result {
+        if update.limit.is_some() && !update.joins.is_empty() {
+            return Err(Error::unsupported(
+                "Limiting the rows of an UPDATE with joins is not supported.",
+            ));
+        }
+
         self.write("UPDATE ")?;
-        self.visit_table(update.table, true)?;
+        self.visit_table(update.table.clone(), true)?;
+
+        self.write(" SET ")?;
+        self.visit_update_assignments(update.columns, update.values)?;
+
+        if let Some(limit) = update.limit {
+            self.write(" WHERE ")?;
+            return self.visit_limited_rows(update.table, update.conditions, update.ordering, limit);
+        }
+
+        let mut conditions = self.visit_update_from(update.joins)?;
+
+        if let Some(tree) = update.conditions {
+            conditions.push(Expression::from(tree));
+        }
+
+        match conditions.len() {
+            0 => (),
+            1 => {
+                self.write(" WHERE ")?;
+                self.visit_expression(conditions.pop().unwrap())?;
+            }
+            _ => {
+                self.write(" WHERE ")?;
+                self.visit_conditions(ConditionTree::And(conditions))?;
+            }
+        }
 
-        {
-            self.write(" SET ")?;
-            let pairs = update.columns.into_iter().zip(update.values.into_iter());
-            let len = pairs.len();
+        Ok(())
+    }
 
-            for (i, (key, value)) in pairs.enumerate() {
-                self.visit_column(key)?;
-                self.write(" = ")?;
-                self.visit_expression(value)?;
+    /// The tables joined to an `UPDATE` as an `UPDATE ... FROM`, returning
+    /// the join conditions to be added to the `WHERE` clause.
+    fn visit_update_from(&mut self, joins: Vec<JoinData<'a>>) -> crate::Result<Vec<Expression<'a>>> {
+        let mut conditions = Vec::with_capacity(joins.len() + 1);
+
+        if !joins.is_empty() {
+            self.write(" FROM ")?;
+            let len = joins.len();
+
+            for (i, join) in joins.into_iter().enumerate() {
+                self.visit_table(join.table, true)?;
+                conditions.push(Expression::from(join.conditions));
 
                 if i < (len - 1) {
                     self.write(", ")?;
@@ -231,36 +458,134 @@ pub trait Visitor<'a> {
             }
         }
 
-        if let Some(conditions) = update.conditions {
-            self.write(" WHERE ")?;
-            self.visit_conditions(conditions)?;
+        Ok(conditions)
+    }
+
+    /// The `column = value` pairs of an `UPDATE` statement
+    fn visit_update_assignments(&mut self, columns: Vec<Column<'a>>, values: Vec<Expression<'a>>) -> Result {
+        let pairs = columns.into_iter().zip(values.into_iter());
+        let len = pairs.len();
+
+        for (i, (key, value)) in pairs.enumerate() {
+            self.visit_column(key)?;
+            self.write(" = ")?;
+            self.visit_expression(value)?;
+
+            if i < (len - 1) {
+                self.write(", ")?;
+            }
         }
 
         Ok(())
     }
 
+    /// A walk through the `ON CONFLICT` clause of an upsert, setting the
+    /// columns of the existing row to the values of the `excluded` row.
+    fn visit_on_conflict_update(&mut self, target: Vec<Column<'a>>, columns: Vec<Column<'a>>) -> Result {
+        self.write(" ON CONFLICT ")?;
+        self.visit_row(Row::from(target))?;
+        self.write(" DO UPDATE SET ")?;
+
+        let values = columns.iter().map(|c| c.clone().table("excluded").into()).collect();
+        self.visit_update_assignments(columns, values)
+    }
+
+    /// A walk through a `MERGE` statement. Not supported by default.
+    fn visit_merge(&mut self, _merge: Merge<'a>) -> Result {
+        Err(Error::unsupported(
+            "The MERGE statement is not supported by the database.",
+        ))
+    }
+
+    /// A `WHEN MATCHED` branch of a `MERGE` statement, either an `UPDATE` or a
+    /// `DELETE` with optional conditions.
+    fn visit_merge_when_matched(&mut self, query: Query<'a>) -> Result {
+        let (conditions, update) = match query {
+            Query::Update(update) => (update.conditions.clone(), Some(*update)),
+            Query::Delete(delete) => (delete.conditions, None),
+            _ => {
+                let kind = ErrorKind::conversion("Only UPDATE or DELETE is allowed for matched rows in MERGE.");
+                return Err(Error::builder(kind).build());
+            }
+        };
+
+        self.write(" WHEN MATCHED")?;
+
+        if let Some(conditions) = conditions {
+            self.write(" AND ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        match update {
+            Some(update) => {
+                self.write(" THEN UPDATE SET ")?;
+                self.visit_update_assignments(update.columns, update.values)
+            }
+            None => self.write(" THEN DELETE"),
+        }
+    }
+
     /// A walk through an `DELETE` statement
     fn visit_delete(&mut self, delete: Delete<'a>) -> Result {
         self.write("DELETE FROM ")?;
-        self.visit_table(delete.table, true)?;
+        self.visit_table(delete.table.clone(), true)?;
 
-        if let Some(conditions) = delete.conditions {
-            self.write(" WHERE ")?;
-            self.visit_conditions(conditions)?;
+        match (delete.limit, delete.conditions) {
+            (Some(limit), conditions) => {
+                self.write(" WHERE ")?;
+                self.visit_limited_rows(delete.table, conditions, delete.ordering, limit)?;
+            }
+            (None, Some(conditions)) => {
+                self.write(" WHERE ")?;
+                self.visit_conditions(conditions)?;
+            }
+            (None, None) => (),
         }
 
         Ok(())
     }
 
+    /// The condition of a `DELETE` or an `UPDATE` with a `LIMIT`, selecting
+    /// the rows by `C_ROW_ID` in a subquery. Not supported by default.
+    fn visit_limited_rows(
+        &mut self,
+        table: Table<'a>,
+        conditions: Option<ConditionTree<'a>>,
+        ordering: Ordering<'a>,
+        limit: Value<'a>,
+    ) -> Result {
+        let row_id = match Self::C_ROW_ID {
+            Some(row_id) => row_id,
+            None => {
+                return Err(Error::unsupported(
+                    "Limiting the rows of a DELETE or an UPDATE is not supported by the database.",
+                ));
+            }
+        };
+
+        let mut select = Select::from_table(table).column(row_id);
+        select.conditions = conditions;
+        select.ordering = ordering;
+        select.limit = Some(limit);
+
+        self.delimited_identifiers(&[row_id])?;
+        self.write(" IN ")?;
+        self.surround_with("(", ")", |s| s.visit_select(select))
+    }
+
     /// A helper for delimiting an identifier, surrounding every part with `C_BACKTICK`
     /// and delimiting the values with a `.`
     fn delimited_identifiers(&mut self, parts: &[&str]) -> Result {
         let len = parts.len();
 
-        for (i, parts) in parts.iter().enumerate() {
-            self.surround_with(Self::C_BACKTICK_OPEN, Self::C_BACKTICK_CLOSE, |ref mut s| {
-                s.write(parts)
-            })?;
+        let (open, close) = match self.quoting() {
+            Quoting::Default => (Self::C_BACKTICK_OPEN, Self::C_BACKTICK_CLOSE),
+            Quoting::Ansi => ("\"", "\""),
+            Quoting::Unquoted => ("", ""),
+        };
+
+        for (i, part) in parts.iter().enumerate() {
+            self.write(escape::quote_identifier(part, open, close))?;
 
             if i < (len - 1) {
                 self.write(".")?;
@@ -277,26 +602,26 @@ pub trait Visitor<'a> {
             Query::Insert(insert) => self.visit_insert(*insert),
             Query::Update(update) => self.visit_update(*update),
             Query::Delete(delete) => self.visit_delete(*delete),
+            Query::Merge(merge) => self.visit_merge(*merge),
             Query::Union(union) => self.visit_union(union),
             Query::Raw(string) => self.write(string),
         }
     }
 
     /// A walk through a union of `SELECT` statements
-    fn visit_union(&mut self, mut ua: Union<'a>) -> Result {
-        let len = ua.selects.len();
-        let mut types = ua.types.drain(0..);
+    fn visit_union(&mut self, ua: Union<'a>) -> Result {
+        let mut selects = ua.selects.into_iter();
 
-        for (i, sel) in ua.selects.into_iter().enumerate() {
+        if let Some(sel) = selects.next() {
             self.surround_with("(", ")", |ref mut se| se.visit_select(sel))?;
+        }
 
-            if i < (len - 1) {
-                let typ = types.next().unwrap();
+        for (typ, sel) in ua.types.into_iter().zip(selects) {
+            self.write(" ")?;
+            self.write(typ)?;
+            self.write(" ")?;
 
-                self.write(" ")?;
-                self.write(typ)?;
-                self.write(" ")?;
-            }
+            self.surround_with("(", ")", |ref mut se| se.visit_select(sel))?;
         }
 
         Ok(())
@@ -361,6 +686,7 @@ pub trait Visitor<'a> {
             ExpressionKind::Function(function) => self.visit_function(function)?,
             ExpressionKind::Op(op) => self.visit_operation(*op)?,
             ExpressionKind::Values(values) => self.visit_values(*values)?,
+            ExpressionKind::Default => self.visit_default()?,
             ExpressionKind::Asterisk(table) => match table {
                 Some(table) => {
                     self.visit_table(*table, false)?;
@@ -402,12 +728,17 @@ pub trait Visitor<'a> {
     /// A database table identifier
     fn visit_table(&mut self, table: Table<'a>, include_alias: bool) -> Result {
         match table.typ {
-            TableType::Table(table_name) => match table.database {
-                Some(database) => self.delimited_identifiers(&[&*database, &*table_name])?,
-                None => self.delimited_identifiers(&[&*table_name])?,
-            },
+            TableType::Table(table_name) => {
+                let parts: Vec<&str> = vec![table.database.as_deref(), table.schema.as_deref(), Some(&*table_name)]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+
+                self.delimited_identifiers(&parts)?
+            }
             TableType::Values(values) => self.visit_values(values)?,
             TableType::Query(select) => self.surround_with("(", ")", |ref mut s| s.visit_select(select))?,
+            TableType::Unnest(unnest) => self.visit_unnest(unnest)?,
         };
 
         if include_alias {
@@ -532,10 +863,7 @@ pub trait Visitor<'a> {
 
                 // To prevent `x IN ()` from happening.
                 (
-                    Expression {
-                        kind: ExpressionKind::Row(_),
-                        ..
-                    },
+                    _,
                     Expression {
                         kind: ExpressionKind::Values(ref vals),
                         ..
@@ -604,10 +932,7 @@ pub trait Visitor<'a> {
 
                 // To prevent `x NOT IN ()` from happening.
                 (
-                    Expression {
-                        kind: ExpressionKind::Row(_),
-                        ..
-                    },
+                    _,
                     Expression {
                         kind: ExpressionKind::Values(ref vals),
                         ..
@@ -667,60 +992,48 @@ pub trait Visitor<'a> {
             Compare::Like(left, right) => {
                 self.visit_expression(*left)?;
 
-                self.add_parameter(Value::text(format!(
+                self.write(" LIKE ")?;
+                self.visit_parameterized(Value::text(format!(
                     "{}{}{}",
                     Self::C_WILDCARD,
                     right,
                     Self::C_WILDCARD
-                )));
-
-                self.write(" LIKE ")?;
-                self.parameter_substitution()
+                )))
             }
             Compare::NotLike(left, right) => {
                 self.visit_expression(*left)?;
 
-                self.add_parameter(Value::text(format!(
+                self.write(" NOT LIKE ")?;
+                self.visit_parameterized(Value::text(format!(
                     "{}{}{}",
                     Self::C_WILDCARD,
                     right,
                     Self::C_WILDCARD
-                )));
-
-                self.write(" NOT LIKE ")?;
-                self.parameter_substitution()
+                )))
             }
             Compare::BeginsWith(left, right) => {
                 self.visit_expression(*left)?;
 
-                self.add_parameter(Value::text(format!("{}{}", right, Self::C_WILDCARD)));
-
                 self.write(" LIKE ")?;
-                self.parameter_substitution()
+                self.visit_parameterized(Value::text(format!("{}{}", right, Self::C_WILDCARD)))
             }
             Compare::NotBeginsWith(left, right) => {
                 self.visit_expression(*left)?;
 
-                self.add_parameter(Value::text(format!("{}{}", right, Self::C_WILDCARD)));
-
                 self.write(" NOT LIKE ")?;
-                self.parameter_substitution()
+                self.visit_parameterized(Value::text(format!("{}{}", right, Self::C_WILDCARD)))
             }
             Compare::EndsInto(left, right) => {
                 self.visit_expression(*left)?;
 
-                self.add_parameter(Value::text(format!("{}{}", Self::C_WILDCARD, right,)));
-
                 self.write(" LIKE ")?;
-                self.parameter_substitution()
+                self.visit_parameterized(Value::text(format!("{}{}", Self::C_WILDCARD, right,)))
             }
             Compare::NotEndsInto(left, right) => {
                 self.visit_expression(*left)?;
 
-                self.add_parameter(Value::text(format!("{}{}", Self::C_WILDCARD, right,)));
-
                 self.write(" NOT LIKE ")?;
-                self.parameter_substitution()
+                self.visit_parameterized(Value::text(format!("{}{}", Self::C_WILDCARD, right,)))
             }
             Compare::Null(column) => {
                 self.visit_expression(*column)?;
@@ -730,6 +1043,8 @@ pub trait Visitor<'a> {
                 self.visit_expression(*column)?;
                 self.write(" IS NOT NULL")
             }
+            Compare::IsTrue(value) => self.visit_boolean_test(*value, true),
+            Compare::IsFalse(value) => self.visit_boolean_test(*value, false),
             Compare::Between(val, left, right) => {
                 self.visit_expression(*val)?;
                 self.write(" BETWEEN ")?;
@@ -744,9 +1059,56 @@ pub trait Visitor<'a> {
                 self.write(" AND ")?;
                 self.visit_expression(*right)
             }
+            Compare::ArrayContains(left, right) => self.visit_array_operator(*left, "@>", *right),
+            Compare::ArrayContainedBy(left, right) => self.visit_array_operator(*left, "<@", *right),
+            Compare::ArrayOverlaps(left, right) => self.visit_array_operator(*left, "&&", *right),
+            Compare::EqualsAny(left, right) => self.visit_equals_any(*left, *right),
+            Compare::NotDistinctFrom(left, right) => self.visit_distinct_from(*left, *right, false),
+            Compare::DistinctFrom(left, right) => self.visit_distinct_from(*left, *right, true),
+            Compare::EqualsIgnoreCase(left, right) => {
+                self.write("LOWER")?;
+                self.surround_with("(", ")", |ref mut s| s.visit_expression(*left))?;
+                self.write(" = LOWER")?;
+                self.surround_with("(", ")", |ref mut s| s.visit_expression(*right))
+            }
         }
     }
 
+    /// A comparison between two arrays. Not supported by default.
+    fn visit_array_operator(&mut self, _left: Expression<'a>, _operator: &str, _right: Expression<'a>) -> Result {
+        Err(Error::unsupported(
+            "Array comparisons are not supported by the database.",
+        ))
+    }
+
+    /// `left = ANY(right)`, comparing a value to the values in an array. Not
+    /// supported by default.
+    fn visit_equals_any(&mut self, _left: Expression<'a>, _right: Expression<'a>) -> Result {
+        Err(Error::unsupported(
+            "Comparing to ANY value in an array is not supported by the database.",
+        ))
+    }
+
+    /// `value IS TRUE` or `value IS FALSE`.
+    fn visit_boolean_test(&mut self, value: Expression<'a>, expected: bool) -> Result {
+        self.visit_expression(value)?;
+        self.write(if expected { " IS TRUE" } else { " IS FALSE" })
+    }
+
+    /// A `NULL` safe comparison, `left IS NOT DISTINCT FROM right`, or
+    /// `left IS DISTINCT FROM right` if negated.
+    fn visit_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>, negate: bool) -> Result {
+        self.visit_expression(left)?;
+
+        if negate {
+            self.write(" IS DISTINCT FROM ")?;
+        } else {
+            self.write(" IS NOT DISTINCT FROM ")?;
+        }
+
+        self.visit_expression(right)
+    }
+
     fn visit_condition_equals(&mut self, left: Expression<'a>, right: Expression<'a>) -> Result {
         self.visit_expression(left)?;
         self.write(" = ")?;
@@ -844,6 +1206,17 @@ pub trait Visitor<'a> {
                 self.write("MAX")?;
                 self.surround_with("(", ")", |ref mut s| s.visit_column(max.column))?;
             }
+            FunctionType::Cast(cast) => {
+                self.write("CAST")?;
+                self.surround_with("(", ")", |ref mut s| {
+                    s.visit_expression(*cast.expression)?;
+                    s.write(" AS ")?;
+                    s.write(cast.target)
+                })?;
+            }
+            FunctionType::JsonExtract(json_extract) => self.visit_json_extract(json_extract)?,
+            FunctionType::JsonSet(json_set) => self.visit_json_set(json_set)?,
+            FunctionType::JsonUnquote(json_unquote) => self.visit_json_unquote(json_unquote)?,
         };
 
         if let Some(alias) = fun.alias {
@@ -880,3 +1253,25 @@ pub trait Visitor<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::visitor::*;
+
+    #[test]
+    fn test_empty_in_selection() {
+        let empty: Vec<i64> = Vec::new();
+
+        let query = Select::from_table("users").so_that("id".in_selection(empty));
+        let (sql, params) = Ansi::build(query).unwrap();
+
+        assert_eq!("SELECT \"users\".* FROM \"users\" WHERE 1=0", sql);
+        assert!(params.is_empty());
+
+        let query = Select::from_table("users").so_that("id".not_in_selection(Values::empty()));
+        let (sql, params) = Ansi::build(query).unwrap();
+
+        assert_eq!("SELECT \"users\".* FROM \"users\" WHERE 1=1", sql);
+        assert!(params.is_empty());
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{
+    ast::*,
+    error::Error,
+    escape,
+    visitor::{self, Quoting, Visitor},
+};
+use std::fmt::{self, Write};
+
+/// A visitor to generate standard SQL, for databases without a dialect of
+/// their own.
+///
+/// Identifiers are in double quotes, the parameters are marked with `?` and
+/// the rows are limited with `OFFSET ... FETCH FIRST`. Features outside of
+/// the standard, such as upserts or `RETURNING`, are errors.
+pub struct Ansi<'a> {
+    query: String,
+    parameters: Vec<Value<'a>>,
+    quoting: Quoting,
+    inline_params: bool,
+}
+
+impl<'a> Ansi<'a> {
+    fn not_supported(feature: &str) -> visitor::Result {
+        Err(Error::unsupported(format!("{} is not part of standard SQL.", feature)))
+    }
+}
+
+impl<'a> Visitor<'a> for Ansi<'a> {
+    const C_BACKTICK_OPEN: &'static str = "\"";
+    const C_BACKTICK_CLOSE: &'static str = "\"";
+    const C_WILDCARD: &'static str = "%";
+
+    fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        Self::build_with_quoting(query, Quoting::Default)
+    }
+
+    fn build_with_quoting<Q>(query: Q, quoting: Quoting) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        let mut ansi = Ansi {
+            query: String::with_capacity(4096),
+            parameters: Vec::with_capacity(128),
+            quoting,
+            inline_params: false,
+        };
+
+        Ansi::visit_query(&mut ansi, query.into())?;
+
+        Ok((ansi.query, ansi.parameters))
+    }
+
+    fn build_with_inlined_params<Q>(query: Q) -> crate::Result<String>
+    where
+        Q: Into<Query<'a>>,
+    {
+        let mut ansi = Ansi {
+            query: String::with_capacity(4096),
+            parameters: Vec::new(),
+            quoting: Quoting::Default,
+            inline_params: true,
+        };
+
+        Ansi::visit_query(&mut ansi, query.into())?;
+
+        Ok(ansi.query)
+    }
+
+    fn write<D: fmt::Display>(&mut self, s: D) -> visitor::Result {
+        write!(&mut self.query, "{}", s)?;
+        Ok(())
+    }
+
+    fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    fn inlines_params(&self) -> bool {
+        self.inline_params
+    }
+
+    fn add_parameter(&mut self, value: Value<'a>) {
+        self.parameters.push(value);
+    }
+
+    fn parameter_substitution(&mut self) -> visitor::Result {
+        self.write("?")
+    }
+
+    fn visit_limit_and_offset(&mut self, limit: Option<Value<'a>>, offset: Option<Value<'a>>) -> visitor::Result {
+        if let Some(offset) = offset {
+            self.write(" OFFSET ")?;
+            self.visit_parameterized(offset)?;
+            self.write(" ROWS")?;
+        }
+
+        if let Some(limit) = limit {
+            self.write(" FETCH FIRST ")?;
+            self.visit_parameterized(limit)?;
+            self.write(" ROWS ONLY")?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_raw_value(&mut self, value: Value<'a>) -> visitor::Result {
+        let res = match value {
+            Value::Integer(i) => i.map(|i| self.write(i)),
+            Value::Real(r) => r.map(|r| self.write(r)),
+            Value::Text(t) => t.map(|t| self.write(escape::string_literal::<Self>(&t))),
+            Value::Enum(e) => e.map(|e| self.write(escape::string_literal::<Self>(&e))),
+            Value::Bytes(b) => b.map(|b| self.write(format!("X'{}'", hex::encode(b)))),
+            Value::Boolean(b) => b.map(|b| self.write(if b { "TRUE" } else { "FALSE" })),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(b) => b.map(|b| self.write(format!("B'{}'", crate::ast::bits_to_string(&b)))),
+            Value::Char(c) => c.map(|c| self.write(escape::string_literal::<Self>(&c.to_string()))),
+            #[cfg(feature = "json-1")]
+            Value::Json(j) => match j {
+                Some(ref j) => {
+                    let s = serde_json::to_string(j)?;
+                    Some(self.write(escape::string_literal::<Self>(&s)))
+                }
+                None => None,
+            },
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(_) => Some(Self::not_supported("An array value")),
+            #[cfg(feature = "uuid-0_8")]
+            Value::Uuid(uuid) => uuid.map(|uuid| self.write(format!("'{}'", uuid.to_hyphenated().to_string()))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::DateTime(dt) => dt.map(|dt| self.write(format!("TIMESTAMP '{}'", dt.naive_utc()))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Date(date) => date.map(|date| self.write(format!("DATE '{}'", date))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Time(time) => time.map(|time| self.write(format!("TIME '{}'", time))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(interval) => {
+                interval.map(|interval| self.write(format!("INTERVAL '{}' SECOND", interval_seconds(interval))))
+            }
+        };
+
+        match res {
+            Some(res) => res,
+            None => self.write("NULL"),
+        }
+    }
+
+    fn visit_insert(&mut self, insert: Insert<'a>) -> visitor::Result {
+        if insert.on_conflict.is_some() {
+            return Self::not_supported("An INSERT with a conflict resolution");
+        }
+
+        if insert.returning.is_some() {
+            return Self::not_supported("RETURNING");
+        }
+
+        self.write("INSERT")?;
+
+        if let Some(table) = insert.table {
+            self.write(" INTO ")?;
+            self.visit_table(table, true)?;
+        }
+
+        match insert.values {
+            Expression {
+                kind: ExpressionKind::Row(ref row),
+                ..
+            } if row.values.is_empty() => {
+                self.write(" DEFAULT VALUES")?;
+            }
+            Expression {
+                kind: ExpressionKind::Row(row),
+                ..
+            } => {
+                self.write(" ")?;
+                self.visit_row(Row::from(insert.columns))?;
+                self.write(" VALUES ")?;
+                self.visit_row(row)?;
+            }
+            Expression {
+                kind: ExpressionKind::Values(values),
+                ..
+            } => {
+                self.write(" ")?;
+                self.visit_row(Row::from(insert.columns))?;
+                self.write(" VALUES ")?;
+
+                let values_len = values.len();
+
+                for (i, row) in values.into_iter().enumerate() {
+                    self.visit_row(row)?;
+
+                    if i < (values_len - 1) {
+                        self.write(", ")?;
+                    }
+                }
+            }
+            expr => self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?,
+        }
+
+        Ok(())
+    }
+
+    fn visit_aggregate_to_string(&mut self, value: Expression<'a>) -> visitor::Result {
+        self.write("LISTAGG")?;
+        self.surround_with("(", ")", |ref mut s| {
+            s.visit_expression(value)?;
+            s.write(", ','")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::visitor::*;
+
+    #[test]
+    fn test_select_with_limit_and_offset() {
+        let query = Select::from_table("users")
+            .column("name")
+            .so_that("id".greater_than(10))
+            .limit(10)
+            .offset(20);
+
+        let (sql, params) = Ansi::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"name\" FROM \"users\" WHERE \"id\" > ? OFFSET ? ROWS FETCH FIRST ? ROWS ONLY",
+            sql
+        );
+        assert_eq!(vec![Value::from(10), Value::from(20), Value::from(10)], params);
+    }
+
+    #[test]
+    fn test_multi_row_insert() {
+        let query = Insert::multi_into("users", vec!["id", "name"])
+            .values(vec![Value::from(1), Value::from("Musti")])
+            .values(vec![Value::from(2), Value::from("Naukio")]);
+
+        let (sql, params) = Ansi::build(query).unwrap();
+
+        assert_eq!("INSERT INTO \"users\" (\"id\",\"name\") VALUES (?,?), (?,?)", sql);
+        assert_eq!(4, params.len());
+    }
+
+    #[test]
+    fn test_upsert_is_not_supported() {
+        let query: Insert = Insert::single_into("users").value("id", 1).into();
+        assert!(Ansi::build(query.on_conflict(OnConflict::DoNothing)).is_err());
+    }
+
+    #[test]
+    fn test_raw_boolean() {
+        let (sql, params) = Ansi::build(Select::default().value(true.raw())).unwrap();
+
+        assert_eq!("SELECT TRUE", sql);
+        assert!(params.is_empty());
+    }
+}
@@ -1,39 +1,52 @@
-use super::Visitor;
+use super::{Quoting, Visitor};
 use crate::{
     ast::{
-        Column, Expression, ExpressionKind, Insert, IntoRaw, Merge, OnConflict, Order, Ordering, Row, Table, TableType,
-        Using, Values,
+        Column, ConditionTree, Delete, Expression, ExpressionKind, Insert, IntoRaw, Join, LockMode, Merge, Order,
+        Ordering, Row, Table, TableType, Update, Using, Values,
     },
-    error::{Error, ErrorKind},
-    visitor, Value,
+    error::Error,
+    escape, visitor, Value,
 };
 use std::{convert::TryFrom, fmt::Write};
 
+const LIMITED_TABLE: &str = "quaint_limited";
+
 pub struct Mssql<'a> {
     query: String,
     parameters: Vec<Value<'a>>,
     order_by_set: bool,
+    quoting: Quoting,
+    inline_params: bool,
 }
 
 impl<'a> Mssql<'a> {
-    fn visit_merge(&mut self, merge: Merge<'a>) -> visitor::Result {
-        self.write("MERGE INTO ")?;
-        self.visit_table(merge.table, true)?;
-
-        self.visit_using(merge.using)?;
-
-        if let Some(query) = merge.when_not_matched {
-            self.write(" WHEN NOT MATCHED THEN ")?;
-            self.visit_query(query)?;
+    /// A common table expression of the `TOP` rows of a `DELETE` or an
+    /// `UPDATE`, which then targets the expression instead of the table.
+    fn visit_limited_table(
+        &mut self,
+        table: Table<'a>,
+        conditions: Option<ConditionTree<'a>>,
+        ordering: Ordering<'a>,
+        limit: Value<'a>,
+    ) -> visitor::Result {
+        self.write("WITH ")?;
+        self.delimited_identifiers(&[LIMITED_TABLE])?;
+        self.write(" AS (SELECT TOP (")?;
+        self.visit_parameterized(limit)?;
+        self.write(") * FROM ")?;
+        self.visit_table(table, true)?;
+
+        if let Some(conditions) = conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
         }
 
-        if let Some(columns) = merge.returning {
-            self.visit_returning(columns)?;
+        if !ordering.is_empty() {
+            self.write(" ORDER BY ")?;
+            self.visit_ordering(ordering)?;
         }
 
-        self.write(";")?;
-
-        Ok(())
+        self.write(") ")
     }
 
     fn visit_using(&mut self, using: Using<'a>) -> visitor::Result {
@@ -77,8 +90,16 @@ impl<'a> Visitor<'a> for Mssql<'a> {
     const C_BACKTICK_OPEN: &'static str = "[";
     const C_BACKTICK_CLOSE: &'static str = "]";
     const C_WILDCARD: &'static str = "%";
+    const C_AS_OF_PER_TABLE: bool = true;
 
     fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<crate::ast::Query<'a>>,
+    {
+        Self::build_with_quoting(query, Quoting::Default)
+    }
+
+    fn build_with_quoting<Q>(query: Q, quoting: Quoting) -> crate::Result<(String, Vec<Value<'a>>)>
     where
         Q: Into<crate::ast::Query<'a>>,
     {
@@ -86,6 +107,8 @@ impl<'a> Visitor<'a> for Mssql<'a> {
             query: String::with_capacity(4096),
             parameters: Vec::with_capacity(128),
             order_by_set: false,
+            quoting,
+            inline_params: false,
         };
 
         Mssql::visit_query(&mut this, query.into())?;
@@ -93,36 +116,76 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         Ok((this.query, this.parameters))
     }
 
+    fn build_with_inlined_params<Q>(query: Q) -> crate::Result<String>
+    where
+        Q: Into<crate::ast::Query<'a>>,
+    {
+        let mut this = Mssql {
+            query: String::with_capacity(4096),
+            parameters: Vec::new(),
+            order_by_set: false,
+            quoting: Quoting::Default,
+            inline_params: true,
+        };
+
+        Mssql::visit_query(&mut this, query.into())?;
+
+        Ok(this.query)
+    }
+
     fn write<D: std::fmt::Display>(&mut self, s: D) -> visitor::Result {
         write!(&mut self.query, "{}", s)?;
         Ok(())
     }
 
+    fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    fn inlines_params(&self) -> bool {
+        self.inline_params
+    }
+
     fn add_parameter(&mut self, value: Value<'a>) {
         self.parameters.push(value)
     }
 
+    fn visit_parameterized(&mut self, value: Value<'a>) -> visitor::Result {
+        // SQL Server has no array parameters. Inlining an array fails with a
+        // conversion error, so the query is never sent with one.
+        #[cfg(all(feature = "array", feature = "postgresql-core"))]
+        {
+            if value.is_array() {
+                return self.visit_raw_value(value);
+            }
+        }
+
+        if self.inlines_params() {
+            return self.visit_raw_value(value);
+        }
+
+        self.add_parameter(value);
+        self.parameter_substitution()
+    }
+
     fn visit_raw_value(&mut self, value: Value<'a>) -> visitor::Result {
         let res = match value {
             Value::Integer(i) => i.map(|i| self.write(i)),
             Value::Real(r) => r.map(|r| self.write(r)),
-            Value::Text(t) => t.map(|t| self.write(format!("'{}'", t))),
-            Value::Enum(e) => e.map(|e| self.write(e)),
+            Value::Text(t) => t.map(|t| self.write(escape::string_literal::<Self>(&t))),
+            Value::Enum(e) => e.map(|e| self.write(escape::string_literal::<Self>(&e))),
             Value::Bytes(b) => b.map(|b| self.write(format!("0x{}", hex::encode(b)))),
             Value::Boolean(b) => b.map(|b| self.write(if b { 1 } else { 0 })),
-            Value::Char(c) => c.map(|c| self.write(format!("'{}'", c))),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(b) => b.map(|b| self.write(format!("'{}'", crate::ast::bits_to_string(&b)))),
+            Value::Char(c) => c.map(|c| self.write(escape::string_literal::<Self>(&c.to_string()))),
             #[cfg(feature = "json-1")]
-            Value::Json(j) => j.map(|j| self.write(format!("'{}'", serde_json::to_string(&j).unwrap()))),
-            #[cfg(all(feature = "array", feature = "postgresql"))]
-            Value::Array(_) => {
-                let msg = "Arrays are not supported in T-SQL.";
-                let kind = ErrorKind::conversion(msg);
-
-                let mut builder = Error::builder(kind);
-                builder.set_original_message(msg);
-
-                Err(builder.build())?
-            }
+            Value::Json(j) => j.map(|j| {
+                let json = serde_json::to_string(&j)?;
+                self.write(escape::string_literal::<Self>(&json))
+            }),
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(_) => Err(Error::unsupported("Arrays are not supported in T-SQL."))?,
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(uuid) => uuid.map(|uuid| {
                 let s = format!("CONVERT(uniqueidentifier, N'{}')", uuid.to_hyphenated().to_string());
@@ -143,6 +206,8 @@ impl<'a> Visitor<'a> for Mssql<'a> {
                 let s = format!("CONVERT(time, N'{}')", time);
                 self.write(s)
             }),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(interval) => interval.map(|interval| self.write(crate::ast::interval_seconds(interval))),
         };
 
         match res {
@@ -191,10 +256,100 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         }
     }
 
+    fn visit_as_of(&mut self, time: Expression<'a>) -> visitor::Result {
+        self.write(" FOR SYSTEM_TIME AS OF ")?;
+        self.visit_expression(time)
+    }
+
+    fn visit_lock(&mut self, _mode: LockMode) -> visitor::Result {
+        Err(Error::unsupported(
+            "SELECT ... FOR UPDATE is not supported in SQL Server, use the UPDLOCK table hint instead.",
+        ))
+    }
+
+    fn visit_update(&mut self, update: Update<'a>) -> visitor::Result {
+        if let Some(limit) = update.limit {
+            if !update.joins.is_empty() {
+                return Err(Error::unsupported(
+                    "Limiting the rows of an UPDATE with joins is not supported.",
+                ));
+            }
+
+            self.visit_limited_table(update.table, update.conditions, update.ordering, limit)?;
+            self.write("UPDATE ")?;
+            self.delimited_identifiers(&[LIMITED_TABLE])?;
+            self.write(" SET ")?;
+
+            return self.visit_update_assignments(update.columns, update.values);
+        }
+
+        self.write("UPDATE ")?;
+        self.visit_table(update.table.clone(), true)?;
+
+        self.write(" SET ")?;
+        self.visit_update_assignments(update.columns, update.values)?;
+
+        if !update.joins.is_empty() {
+            self.write(" FROM ")?;
+            self.visit_table(update.table, true)?;
+            self.visit_joins(update.joins.into_iter().map(Join::Inner).collect())?;
+        }
+
+        if let Some(conditions) = update.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_delete(&mut self, delete: Delete<'a>) -> visitor::Result {
+        if let Some(limit) = delete.limit {
+            self.visit_limited_table(delete.table, delete.conditions, delete.ordering, limit)?;
+            self.write("DELETE FROM ")?;
+
+            return self.delimited_identifiers(&[LIMITED_TABLE]);
+        }
+
+        self.write("DELETE FROM ")?;
+        self.visit_table(delete.table, true)?;
+
+        if let Some(conditions) = delete.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_merge(&mut self, merge: Merge<'a>) -> visitor::Result {
+        self.write("MERGE INTO ")?;
+        self.visit_table(merge.table, true)?;
+
+        self.visit_using(merge.using)?;
+
+        for query in merge.when_matched {
+            self.visit_merge_when_matched(query)?;
+        }
+
+        if let Some(query) = merge.when_not_matched {
+            self.write(" WHEN NOT MATCHED THEN ")?;
+            self.visit_query(query)?;
+        }
+
+        if let Some(columns) = merge.returning {
+            self.visit_returning(columns)?;
+        }
+
+        self.write(";")?;
+
+        Ok(())
+    }
+
     fn visit_insert(&mut self, insert: Insert<'a>) -> visitor::Result {
         match insert.on_conflict {
-            Some(OnConflict::DoNothing) => {
-                let merge = Merge::try_from(insert).unwrap();
+            Some(_) => {
+                let merge = Merge::try_from(insert)?;
                 self.visit_merge(merge)?;
             }
             _ => {
@@ -303,6 +458,26 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         })
     }
 
+    fn visit_boolean_test(&mut self, value: Expression<'a>, expected: bool) -> visitor::Result {
+        self.visit_expression(value)?;
+        self.write(if expected { " = 1" } else { " = 0" })
+    }
+
+    fn visit_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>, negate: bool) -> visitor::Result {
+        if negate {
+            self.write("NOT ")?;
+        }
+
+        // `IS DISTINCT FROM` is only available since SQL Server 2022.
+        self.write("EXISTS ")?;
+        self.surround_with("(", ")", |s| {
+            s.write("SELECT ")?;
+            s.visit_expression(left)?;
+            s.write(" INTERSECT SELECT ")?;
+            s.visit_expression(right)
+        })
+    }
+
     fn visit_ordering(&mut self, ordering: Ordering<'a>) -> visitor::Result {
         let len = ordering.0.len();
 
@@ -325,12 +500,18 @@ impl<'a> Visitor<'a> for Mssql<'a> {
         Ok(())
     }
 
-    /// A database table identifier
+    /// A database table identifier. The database is written only together
+    /// with a schema, as a three-part `[database].[schema].[table]` name.
     fn visit_table(&mut self, table: Table<'a>, include_alias: bool) -> visitor::Result {
         match table.typ {
-            TableType::Table(table_name) => self.delimited_identifiers(&[&*table_name])?,
+            TableType::Table(table_name) => match (table.database, table.schema) {
+                (Some(database), Some(schema)) => self.delimited_identifiers(&[&*database, &*schema, &*table_name])?,
+                (None, Some(schema)) => self.delimited_identifiers(&[&*schema, &*table_name])?,
+                (_, None) => self.delimited_identifiers(&[&*table_name])?,
+            },
             TableType::Values(values) => self.visit_values(values)?,
             TableType::Query(select) => self.surround_with("(", ")", |ref mut s| s.visit_select(select))?,
+            TableType::Unnest(unnest) => self.visit_unnest(unnest)?,
         };
 
         if include_alias {
@@ -371,6 +552,64 @@ mod tests {
         result
     }
 
+    #[test]
+    fn test_distinct_from() {
+        let query = Select::from_table("users").so_that("parent_id".is_not_distinct_from(Value::Integer(None)));
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE EXISTS (SELECT [parent_id] INTERSECT SELECT @P1)",
+            sql
+        );
+        assert_eq!(vec![Value::Integer(None)], params);
+
+        let query = Select::from_table("users").so_that("parent_id".is_distinct_from(1));
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT [users].* FROM [users] WHERE NOT EXISTS (SELECT [parent_id] INTERSECT SELECT @P1)",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_boolean_conditions() {
+        let query = Select::from_table("users").so_that(Column::from("active"));
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!("SELECT [users].* FROM [users] WHERE [active] = 1", sql);
+
+        let query = Select::from_table("users").so_that("active".is_false());
+        let (sql, _) = Mssql::build(query).unwrap();
+
+        assert_eq!("SELECT [users].* FROM [users] WHERE [active] = 0", sql);
+    }
+
+    #[test]
+    fn test_limited_delete_and_update() {
+        let query = Delete::from_table("jobs")
+            .so_that("state".equals("done"))
+            .order_by("id")
+            .limit(10);
+
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "WITH [quaint_limited] AS (SELECT TOP (@P1) * FROM [jobs] WHERE [state] = @P2 ORDER BY [id]) DELETE FROM [quaint_limited]",
+            sql
+        );
+        assert_eq!(vec![Value::from(10), Value::from("done")], params);
+
+        let query = Update::table("jobs").set("state", "running").limit(10);
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(
+            "WITH [quaint_limited] AS (SELECT TOP (@P1) * FROM [jobs]) UPDATE [quaint_limited] SET [state] = @P2",
+            sql
+        );
+        assert_eq!(vec![Value::from(10), Value::from("running")], params);
+    }
+
     #[test]
     fn test_select_1() {
         let expected = expected_values("SELECT @P1", vec![1]);
@@ -742,6 +981,22 @@ mod tests {
         assert_eq!(expected_sql, sql);
     }
 
+    #[test]
+    fn test_select_as_of() {
+        let expected_sql = "SELECT [foo] FROM [bar] FOR SYSTEM_TIME AS OF @P1";
+        let query = Select::from_table("bar").column("foo").as_of("2020-06-01T00:00:00");
+        let (sql, params) = Mssql::build(query).unwrap();
+
+        assert_eq!(expected_sql, sql);
+        assert_eq!(vec![Value::from("2020-06-01T00:00:00")], params);
+    }
+
+    #[test]
+    fn test_select_for_update_is_not_supported() {
+        let query = Select::from_table("bar").for_update(LockMode::NoWait);
+        assert!(Mssql::build(query).is_err());
+    }
+
     #[test]
     fn test_limit_with_no_offset() {
         let expected_sql = "SELECT [foo] FROM [bar] ORDER BY [id] OFFSET @P1 ROWS FETCH NEXT @P2 ROWS ONLY";
@@ -893,6 +1148,67 @@ mod tests {
         assert!(params.is_empty());
     }
 
+    #[test]
+    fn test_single_insert_with_default() {
+        let insert = Insert::single_into("foo").value_default("bar").value("wtf", "meow");
+        let (sql, params) = Mssql::build(insert).unwrap();
+
+        assert_eq!("INSERT INTO [foo] ([bar],[wtf]) VALUES (DEFAULT,@P1)", sql);
+        assert_eq!(vec![Value::from("meow")], params);
+    }
+
+    #[test]
+    fn test_update_from_joined_table() {
+        let join = "bar".on(("bar", "foo_id").equals(Column::from(("foo", "id"))));
+        let update = Update::table("foo")
+            .set("wtf", col!(("bar", "wtf")) + 1)
+            .inner_join(join)
+            .so_that(("bar", "lol").equals("meow"));
+        let (sql, params) = Mssql::build(update).unwrap();
+
+        assert_eq!(
+            "UPDATE [foo] SET [wtf] = ([bar].[wtf] + @P1) FROM [foo] INNER JOIN [bar] ON [bar].[foo_id] = [foo].[id] WHERE [bar].[lol] = @P2",
+            sql
+        );
+        assert_eq!(vec![Value::from(1), Value::from("meow")], params);
+    }
+
+    #[test]
+    fn test_merge() {
+        let using = Select::from_table("bar")
+            .into_using("src", vec!["id", "wtf"])
+            .on(("src", "id").equals(Column::from(("foo", "id"))));
+
+        let insert =
+            Insert::multi(vec!["id", "wtf"]).values(vec![Column::from(("src", "id")), Column::from(("src", "wtf"))]);
+
+        let merge = Merge::new("foo", using)
+            .when_matched_update(
+                Update::table("foo")
+                    .set("wtf", Column::from(("src", "wtf")))
+                    .so_that(("src", "wtf").not_equals("lol")),
+            )
+            .when_matched_delete()
+            .when_not_matched(insert);
+
+        let (sql, params) = Mssql::build(merge).unwrap();
+
+        assert_eq!(
+            "MERGE INTO [foo] USING (SELECT [bar].* FROM [bar]) AS [src] ([id],[wtf]) ON [src].[id] = [foo].[id] WHEN MATCHED AND [src].[wtf] <> @P1 THEN UPDATE SET [wtf] = [src].[wtf] WHEN MATCHED THEN DELETE WHEN NOT MATCHED THEN INSERT ([id],[wtf]) VALUES ([src].[id],[src].[wtf]);",
+            sql
+        );
+        assert_eq!(vec![Value::from("lol")], params);
+    }
+
+    #[test]
+    fn test_single_insert_conflict_with_default_value_is_not_supported() {
+        let id = Column::from("id").table("foo");
+        let table = Table::from("foo").add_unique_index(id.clone());
+        let insert: Insert<'_> = Insert::single_into(table).value_default(id).into();
+
+        assert!(Mssql::build(insert.on_conflict(OnConflict::DoNothing)).is_err());
+    }
+
     #[test]
     #[cfg(feature = "mssql")]
     fn test_returning_insert() {
@@ -950,6 +1266,34 @@ mod tests {
         assert_eq!(vec![Value::from("lol"), Value::from("meow")], params);
     }
 
+    #[test]
+    fn test_single_insert_conflict_update() {
+        let insert: Insert<'_> = Insert::single_into("foo")
+            .value("bar", "lol")
+            .value("wtf", "meow")
+            .into();
+
+        let (sql, params) = Mssql::build(insert.on_conflict(OnConflict::Update {
+            target: vec!["bar".into()],
+            columns: vec!["wtf".into()],
+        }))
+        .unwrap();
+
+        let expected_sql = indoc!(
+            "
+            MERGE INTO [foo]
+            USING (SELECT @P1 AS [bar], @P2 AS [wtf]) AS [dual] ([bar],[wtf])
+            ON [dual].[bar] = [foo].[bar]
+            WHEN MATCHED THEN UPDATE SET [wtf] = [dual].[wtf]
+            WHEN NOT MATCHED THEN
+            INSERT ([bar],[wtf]) VALUES ([dual].[bar],[dual].[wtf]);
+        "
+        );
+
+        assert_eq!(expected_sql.replace('\n', " ").trim(), sql);
+        assert_eq!(vec![Value::from("lol"), Value::from("meow")], params);
+    }
+
     #[test]
     fn test_single_insert_conflict_do_nothing_single_unique_with_default() {
         let unique_column = Column::from("bar").default("purr");
@@ -1233,4 +1577,13 @@ mod tests {
         let (sql, _) = Mssql::build(query).unwrap();
         assert_eq!(expected_sql, sql);
     }
+
+    #[test]
+    #[cfg(all(feature = "array", feature = "postgresql-core"))]
+    fn test_array_parameters_are_rejected() {
+        let query = Select::from_table("users").so_that("ids".equals(Value::array(vec![1, 2])));
+        let err = Mssql::build(query).unwrap_err();
+
+        assert_eq!("Conversion failed: Arrays are not supported in T-SQL.", err.to_string());
+    }
 }
@@ -1,7 +1,8 @@
 use crate::{
     ast::*,
-    error::{Error, ErrorKind},
-    visitor::{self, Visitor},
+    error::Error,
+    escape,
+    visitor::{self, Quoting, Visitor},
 };
 use std::fmt::{self, Write};
 
@@ -11,6 +12,8 @@ use std::fmt::{self, Write};
 pub struct Mysql<'a> {
     query: String,
     parameters: Vec<Value<'a>>,
+    quoting: Quoting,
+    inline_params: bool,
 }
 
 impl<'a> Mysql<'a> {
@@ -29,20 +32,50 @@ impl<'a> Mysql<'a> {
 
         Ok(())
     }
+
+    /// The `ORDER BY` and `LIMIT` clauses of a `DELETE` or an `UPDATE`.
+    fn visit_ordering_and_limit(&mut self, ordering: Ordering<'a>, limit: Option<Value<'a>>) -> visitor::Result {
+        if !ordering.is_empty() {
+            self.write(" ORDER BY ")?;
+            self.visit_ordering(ordering)?;
+        }
+
+        self.visit_limit_and_offset(limit, None)
+    }
+
+    fn visit_json_path(&mut self, path: JsonPath<'a>) -> visitor::Result {
+        match path {
+            JsonPath::String(path) => self.visit_parameterized(Value::text(path)),
+            JsonPath::Array(_) => Err(Error::unsupported(
+                "MySQL JSON paths must be strings, such as `$.address.city`.",
+            )),
+        }
+    }
 }
 
 impl<'a> Visitor<'a> for Mysql<'a> {
     const C_BACKTICK_OPEN: &'static str = "`";
     const C_BACKTICK_CLOSE: &'static str = "`";
     const C_WILDCARD: &'static str = "%";
+    const C_AS_OF_PER_TABLE: bool = true;
+    const C_BACKSLASH_ESCAPES: bool = true;
 
     fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        Self::build_with_quoting(query, Quoting::Default)
+    }
+
+    fn build_with_quoting<Q>(query: Q, quoting: Quoting) -> crate::Result<(String, Vec<Value<'a>>)>
     where
         Q: Into<Query<'a>>,
     {
         let mut mysql = Mysql {
             query: String::with_capacity(4096),
             parameters: Vec::with_capacity(128),
+            quoting,
+            inline_params: false,
         };
 
         Mysql::visit_query(&mut mysql, query.into())?;
@@ -50,38 +83,56 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         Ok((mysql.query, mysql.parameters))
     }
 
+    fn build_with_inlined_params<Q>(query: Q) -> crate::Result<String>
+    where
+        Q: Into<Query<'a>>,
+    {
+        let mut mysql = Mysql {
+            query: String::with_capacity(4096),
+            parameters: Vec::new(),
+            quoting: Quoting::Default,
+            inline_params: true,
+        };
+
+        Mysql::visit_query(&mut mysql, query.into())?;
+
+        Ok(mysql.query)
+    }
+
     fn write<D: fmt::Display>(&mut self, s: D) -> visitor::Result {
         write!(&mut self.query, "{}", s)?;
         Ok(())
     }
 
+    fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    fn inlines_params(&self) -> bool {
+        self.inline_params
+    }
+
     fn visit_raw_value(&mut self, value: Value<'a>) -> visitor::Result {
         let res = match value {
             Value::Integer(i) => i.map(|i| self.write(i)),
             Value::Real(r) => r.map(|r| self.write(r)),
-            Value::Text(t) => t.map(|t| self.write(format!("'{}'", t))),
-            Value::Enum(e) => e.map(|e| self.write(e)),
+            Value::Text(t) => t.map(|t| self.write(escape::string_literal::<Self>(&t))),
+            Value::Enum(e) => e.map(|e| self.write(escape::string_literal::<Self>(&e))),
             Value::Bytes(b) => b.map(|b| self.write(format!("x'{}'", hex::encode(b)))),
             Value::Boolean(b) => b.map(|b| self.write(b)),
-            Value::Char(c) => c.map(|c| self.write(format!("'{}'", c))),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(b) => b.map(|b| self.write(format!("b'{}'", crate::ast::bits_to_string(&b)))),
+            Value::Char(c) => c.map(|c| self.write(escape::string_literal::<Self>(&c.to_string()))),
             #[cfg(feature = "json-1")]
             Value::Json(j) => match j {
                 Some(ref j) => {
                     let s = serde_json::to_string(&j)?;
-                    Some(self.write(format!("CONVERT('{}', JSON)", s)))
+                    Some(self.write(format!("CONVERT({}, JSON)", escape::string_literal::<Self>(&s))))
                 }
                 None => None,
             },
-            #[cfg(all(feature = "array", feature = "postgresql"))]
-            Value::Array(_) => {
-                let msg = "Arrays are not supported in MySQL.";
-                let kind = ErrorKind::conversion(msg);
-
-                let mut builder = Error::builder(kind);
-                builder.set_original_message(msg);
-
-                Err(builder.build())?
-            }
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(_) => Err(Error::unsupported("Arrays are not supported in MySQL."))?,
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(uuid) => uuid.map(|uuid| self.write(format!("'{}'", uuid.to_hyphenated().to_string()))),
             #[cfg(feature = "chrono-0_4")]
@@ -90,6 +141,8 @@ impl<'a> Visitor<'a> for Mysql<'a> {
             Value::Date(date) => date.map(|date| self.write(format!("'{}'", date))),
             #[cfg(feature = "chrono-0_4")]
             Value::Time(time) => time.map(|time| self.write(format!("'{}'", time))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(interval) => interval.map(|interval| self.write(interval_seconds(interval))),
         };
 
         match res {
@@ -98,10 +151,48 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         }
     }
 
+    fn visit_update(&mut self, update: Update<'a>) -> visitor::Result {
+        let limited = update.limit.is_some() || !update.ordering.is_empty();
+
+        if limited && !update.joins.is_empty() {
+            return Err(Error::unsupported(
+                "Ordering or limiting the rows of an UPDATE with joins is not supported in MySQL.",
+            ));
+        }
+
+        self.write("UPDATE ")?;
+        self.visit_table(update.table, true)?;
+        self.visit_joins(update.joins.into_iter().map(Join::Inner).collect())?;
+
+        self.write(" SET ")?;
+        self.visit_update_assignments(update.columns, update.values)?;
+
+        if let Some(conditions) = update.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        self.visit_ordering_and_limit(update.ordering, update.limit)
+    }
+
+    fn visit_delete(&mut self, delete: Delete<'a>) -> visitor::Result {
+        self.write("DELETE FROM ")?;
+        self.visit_table(delete.table, true)?;
+
+        if let Some(conditions) = delete.conditions {
+            self.write(" WHERE ")?;
+            self.visit_conditions(conditions)?;
+        }
+
+        self.visit_ordering_and_limit(delete.ordering, delete.limit)
+    }
+
     fn visit_insert(&mut self, insert: Insert<'a>) -> visitor::Result {
-        match insert.on_conflict {
+        let on_conflict = insert.checked_on_conflict()?;
+
+        match on_conflict {
             Some(OnConflict::DoNothing) => self.write("INSERT IGNORE ")?,
-            None => self.write("INSERT ")?,
+            _ => self.write("INSERT ")?,
         };
 
         if let Some(table) = insert.table {
@@ -163,6 +254,26 @@ impl<'a> Visitor<'a> for Mysql<'a> {
             expr => self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?,
         }
 
+        // MySQL checks all unique indices for duplicates, so the target is
+        // not needed.
+        if let Some(OnConflict::Update { columns, .. }) = on_conflict {
+            self.write(" ON DUPLICATE KEY UPDATE ")?;
+            let len = columns.len();
+
+            for (i, column) in columns.into_iter().enumerate() {
+                let column = column.into_bare();
+
+                self.visit_column(column.clone())?;
+                self.write(" = VALUES(")?;
+                self.visit_column(column)?;
+                self.write(")")?;
+
+                if i < (len - 1) {
+                    self.write(", ")?;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -199,11 +310,51 @@ impl<'a> Visitor<'a> for Mysql<'a> {
         }
     }
 
+    fn visit_as_of(&mut self, time: Expression<'a>) -> visitor::Result {
+        self.write(" FOR SYSTEM_TIME AS OF TIMESTAMP ")?;
+        self.visit_expression(time)
+    }
+
     fn visit_aggregate_to_string(&mut self, value: Expression<'a>) -> visitor::Result {
         self.write(" GROUP_CONCAT")?;
         self.surround_with("(", ")", |ref mut s| s.visit_expression(value))
     }
 
+    fn visit_unnest(&mut self, unnest: Unnest<'a>) -> visitor::Result {
+        self.write("JSON_TABLE")?;
+        self.surround_with("(", ")", |s| {
+            s.visit_expression(*unnest.expression)?;
+            s.write(", '$[*]' COLUMNS (")?;
+            s.delimited_identifiers(&["value"])?;
+            s.write(format!(" {} PATH '$')", unnest.value_type))
+        })
+    }
+
+    fn visit_json_extract(&mut self, json_extract: JsonExtract<'a>) -> visitor::Result {
+        self.write("JSON_EXTRACT")?;
+        self.surround_with("(", ")", |s| {
+            s.visit_expression(*json_extract.expression)?;
+            s.write(", ")?;
+            s.visit_json_path(json_extract.path)
+        })
+    }
+
+    fn visit_json_set(&mut self, json_set: JsonSet<'a>) -> visitor::Result {
+        self.write("JSON_SET")?;
+        self.surround_with("(", ")", |s| {
+            s.visit_expression(*json_set.expression)?;
+            s.write(", ")?;
+            s.visit_json_path(json_set.path)?;
+            s.write(", ")?;
+            s.visit_expression(*json_set.value)
+        })
+    }
+
+    fn visit_json_unquote(&mut self, json_unquote: JsonUnquote<'a>) -> visitor::Result {
+        self.write("JSON_UNQUOTE")?;
+        self.surround_with("(", ")", |s| s.visit_expression(*json_unquote.expression))
+    }
+
     fn visit_condition_equals(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
         #[cfg(feature = "json-1")]
         {
@@ -263,6 +414,21 @@ impl<'a> Visitor<'a> for Mysql<'a> {
             self.visit_regular_difference_comparison(left, right)
         }
     }
+
+    fn visit_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>, negate: bool) -> visitor::Result {
+        let null_safe_equals = |s: &mut Self| {
+            s.visit_expression(left)?;
+            s.write(" <=> ")?;
+            s.visit_expression(right)
+        };
+
+        if negate {
+            self.write("NOT ")?;
+            self.surround_with("(", ")", null_safe_equals)
+        } else {
+            null_safe_equals(self)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -286,6 +452,45 @@ mod tests {
         result
     }
 
+    #[test]
+    fn test_distinct_from() {
+        let query = Select::from_table("users").so_that("parent_id".is_not_distinct_from(Value::Integer(None)));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE `parent_id` <=> ?", sql);
+        assert_eq!(vec![Value::Integer(None)], params);
+
+        let query = Select::from_table("users").so_that("parent_id".is_distinct_from(1));
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE NOT (`parent_id` <=> ?)", sql);
+    }
+
+    #[test]
+    fn test_equals_ignore_case() {
+        let query = Select::from_table("users").so_that(("users", "email").equals_ignore_case("Musti@Example.com"));
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT `users`.* FROM `users` WHERE LOWER(`users`.`email`) = LOWER(?)",
+            sql
+        );
+        assert_eq!(vec![Value::from("Musti@Example.com")], params);
+    }
+
+    #[test]
+    fn test_boolean_conditions() {
+        let query = Select::from_table("users").so_that(Column::from("active"));
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE `active` IS TRUE", sql);
+
+        let query = Select::from_table("users").so_that("active".is_false());
+        let (sql, _) = Mysql::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE `active` IS FALSE", sql);
+    }
+
     #[test]
     fn test_single_row_insert_default_values() {
         let query = Insert::single_into("users");
@@ -317,6 +522,61 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_single_row_insert_with_default() {
+        let expected = expected_values("INSERT INTO `users` (`id`,`foo`) VALUES (DEFAULT,?)", vec![10]);
+        let query = Insert::single_into("users").value_default("id").value("foo", 10);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_upsert() {
+        let expected = expected_values(
+            "INSERT INTO `users` (`id`,`name`,`age`) VALUES (?,?,?) \
+             ON DUPLICATE KEY UPDATE `name` = VALUES(`name`), `age` = VALUES(`age`)",
+            vec![Value::from(1), Value::from("Musti"), Value::from(4)],
+        );
+
+        let insert: Insert = Insert::single_into("users")
+            .value("id", 1)
+            .value("name", "Musti")
+            .value("age", 4)
+            .into();
+
+        let (sql, params) = Mysql::build(insert.on_conflict(OnConflict::Update {
+            target: Vec::new(),
+            columns: vec!["name".into(), "age".into()],
+        }))
+        .unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_upsert_needs_columns() {
+        let insert: Insert = Insert::single_into("users").value("id", 1).into();
+
+        let no_columns = insert.on_conflict(OnConflict::Update {
+            target: Vec::new(),
+            columns: Vec::new(),
+        });
+        assert!(Mysql::build(no_columns).is_err());
+    }
+
+    #[test]
+    fn test_update_set_default() {
+        let expected = expected_values("UPDATE `users` SET `foo` = ?, `bar` = DEFAULT", vec![10]);
+        let query = Update::table("users").set("foo", 10).set_default("bar");
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
     #[test]
     fn test_limit_and_offset_when_both_are_set() {
         let expected = expected_values("SELECT `users`.* FROM `users` LIMIT ? OFFSET ?", vec![10, 2]);
@@ -351,6 +611,114 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_select_as_of() {
+        let expected = expected_values(
+            "SELECT `u`.* FROM `users` FOR SYSTEM_TIME AS OF TIMESTAMP ? AS `u` INNER JOIN `posts` FOR SYSTEM_TIME AS OF TIMESTAMP ? ON `u`.`id` = `posts`.`user_id`",
+            vec!["2020-06-01 00:00:00", "2020-06-01 00:00:00"],
+        );
+
+        let join = "posts".on(("u", "id").equals(Column::from(("posts", "user_id"))));
+        let query = Select::from_table(Table::from("users").alias("u"))
+            .inner_join(join)
+            .as_of("2020-06-01 00:00:00");
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_json_functions() {
+        let expected = expected_values(
+            "UPDATE `users` SET `data` = JSON_SET(`data`, ?, ?) WHERE CAST(JSON_UNQUOTE(JSON_EXTRACT(`data`, ?)) AS SIGNED) > ?",
+            vec![Value::from("$.seen"), Value::from(true), Value::from("$.age"), Value::from(18)],
+        );
+
+        let age: Expression = cast(json_unquote(json_extract(Column::from("data"), "$.age")), "SIGNED").into();
+        let query = Update::table("users")
+            .set("data", json_set(Column::from("data"), "$.seen", true))
+            .so_that(age.greater_than(18));
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_array_comparisons_are_not_supported() {
+        let query = Select::from_table("users").so_that("id".equals_any(Column::from("friend_ids")));
+        assert!(Mysql::build(query).is_err());
+    }
+
+    #[test]
+    fn test_json_path_must_be_a_string() {
+        let query = Select::from_table("users").value(json_extract(Column::from("data"), vec!["age"]));
+        assert!(Mysql::build(query).is_err());
+    }
+
+    #[test]
+    fn test_select_for_update() {
+        let expected = expected_values("SELECT `jobs`.* FROM `jobs` WHERE `id` = ? FOR UPDATE", vec![1]);
+        let query = Select::from_table("jobs")
+            .so_that("id".equals(1))
+            .for_update(LockMode::Wait);
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_select_for_update_with_limit() {
+        let expected = expected_values(
+            "SELECT `jobs`.* FROM `jobs` WHERE `state` = ? ORDER BY `id` LIMIT ? FOR UPDATE SKIP LOCKED",
+            vec![Value::from("queued"), Value::from(10)],
+        );
+
+        let query = Select::from_table("jobs")
+            .so_that("state".equals("queued"))
+            .order_by("id")
+            .limit(10)
+            .for_update(LockMode::SkipLocked);
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_update_with_order_and_limit() {
+        let expected = expected_values(
+            "UPDATE `jobs` SET `state` = ? WHERE `state` = ? ORDER BY `id` DESC LIMIT ?",
+            vec![Value::from("running"), Value::from("queued"), Value::from(10)],
+        );
+
+        let query = Update::table("jobs")
+            .set("state", "running")
+            .so_that("state".equals("queued"))
+            .order_by("id".descend())
+            .limit(10);
+
+        let (sql, params) = Mysql::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_limited_update_with_joins_is_not_supported() {
+        let query = Update::table("jobs")
+            .set("state", "running")
+            .inner_join("queues".on(("queues", "id").equals(Column::from(("jobs", "queue_id")))))
+            .limit(10);
+
+        assert!(Mysql::build(query).is_err());
+    }
+
     #[test]
     fn test_in_values_2_tuple() {
         use crate::{col, values};
@@ -411,6 +779,34 @@ mod tests {
         assert!(params.is_empty());
     }
 
+    #[test]
+    fn test_raw_text_is_escaped() {
+        let (sql, params) = Mysql::build(Select::default().value("it's C:\\temp".raw())).unwrap();
+        assert_eq!("SELECT 'it''s C:\\\\temp'", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_inlined_params() {
+        let query = Select::from_table("files")
+            .so_that("path".like("C:\\te'mp"))
+            .and_where("size".greater_than(10))
+            .limit(5);
+
+        let sql = Mysql::build_with_inlined_params(query).unwrap();
+
+        assert_eq!(
+            "SELECT `files`.* FROM `files` WHERE (`path` LIKE '%C:\\\\te''mp%' AND `size` > 10) LIMIT 5",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_quotes_in_identifiers_are_escaped() {
+        let (sql, _) = Mysql::build(Select::from_table("my`table")).unwrap();
+        assert_eq!("SELECT `my``table`.* FROM `my``table`", sql);
+    }
+
     #[test]
     fn test_raw_int() {
         let (sql, params) = Mysql::build(Select::default().value(1.raw())).unwrap();
@@ -520,4 +916,36 @@ mod tests {
         assert_eq!(format!("SELECT '{}'", dt.to_rfc3339(),), sql);
         assert!(params.is_empty());
     }
+
+    #[test]
+    fn test_ansi_quoting() {
+        let query = Select::from_table(Table::from("users").alias("u"))
+            .value(asterisk())
+            .so_that(Column::from(("u", "name")).equals("Musti"));
+
+        let (sql, params) = Mysql::build_with_quoting(query, Quoting::Ansi).unwrap();
+
+        assert_eq!("SELECT * FROM \"users\" AS \"u\" WHERE \"u\".\"name\" = ?", sql);
+        assert_eq!(vec![Value::from("Musti")], params);
+    }
+
+    #[test]
+    fn test_unquoted_identifiers() {
+        let query = Insert::single_into("`users`").value("`name`", "Musti");
+        let (sql, _) = Mysql::build_with_quoting(query, Quoting::Unquoted).unwrap();
+
+        assert_eq!("INSERT INTO `users` (`name`) VALUES (?)", sql);
+    }
+
+    #[test]
+    fn test_merge_is_not_supported() {
+        let using = Select::from_table("staging")
+            .into_using("src", vec!["id", "name"])
+            .on(("src", "id").equals(Column::from(("users", "id"))));
+
+        let query = Merge::new("users", using)
+            .when_matched_update(Update::table("users").set("name", Column::from(("src", "name"))));
+
+        assert!(Mysql::build(query).is_err());
+    }
 }
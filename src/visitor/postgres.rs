@@ -1,6 +1,8 @@
 use crate::{
     ast::*,
-    visitor::{self, Visitor},
+    error::Error,
+    escape,
+    visitor::{self, Quoting, Visitor},
 };
 use std::fmt::{self, Write};
 
@@ -11,20 +13,56 @@ use std::fmt::{self, Write};
 pub struct Postgres<'a> {
     query: String,
     parameters: Vec<Value<'a>>,
+    quoting: Quoting,
+    inline_params: bool,
+}
+
+impl<'a> Postgres<'a> {
+    fn visit_json_path(&mut self, path: JsonPath<'a>) -> visitor::Result {
+        match path {
+            JsonPath::Array(keys) => {
+                let len = keys.len();
+                self.write("ARRAY[")?;
+
+                for (i, key) in keys.into_iter().enumerate() {
+                    self.visit_parameterized(Value::text(key))?;
+
+                    if i < (len - 1) {
+                        self.write(", ")?;
+                    }
+                }
+
+                self.write("]::text[]")
+            }
+            JsonPath::String(_) => Err(Error::unsupported(
+                "PostgreSQL JSON paths must be arrays of keys, such as `[\"address\", \"city\"]`.",
+            )),
+        }
+    }
 }
 
 impl<'a> Visitor<'a> for Postgres<'a> {
     const C_BACKTICK_OPEN: &'static str = "\"";
     const C_BACKTICK_CLOSE: &'static str = "\"";
     const C_WILDCARD: &'static str = "%";
+    const C_ROW_ID: Option<&'static str> = Some("ctid");
 
     fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        Self::build_with_quoting(query, Quoting::Default)
+    }
+
+    fn build_with_quoting<Q>(query: Q, quoting: Quoting) -> crate::Result<(String, Vec<Value<'a>>)>
     where
         Q: Into<Query<'a>>,
     {
         let mut postgres = Postgres {
             query: String::with_capacity(4096),
             parameters: Vec::with_capacity(128),
+            quoting,
+            inline_params: false,
         };
 
         Postgres::visit_query(&mut postgres, query.into())?;
@@ -32,11 +70,35 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok((postgres.query, postgres.parameters))
     }
 
+    fn build_with_inlined_params<Q>(query: Q) -> crate::Result<String>
+    where
+        Q: Into<Query<'a>>,
+    {
+        let mut postgres = Postgres {
+            query: String::with_capacity(4096),
+            parameters: Vec::new(),
+            quoting: Quoting::Default,
+            inline_params: true,
+        };
+
+        Postgres::visit_query(&mut postgres, query.into())?;
+
+        Ok(postgres.query)
+    }
+
     fn write<D: fmt::Display>(&mut self, s: D) -> visitor::Result {
         write!(&mut self.query, "{}", s)?;
         Ok(())
     }
 
+    fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    fn inlines_params(&self) -> bool {
+        self.inline_params
+    }
+
     fn add_parameter(&mut self, value: Value<'a>) {
         self.parameters.push(value);
     }
@@ -67,32 +129,33 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         }
     }
 
+    /// CockroachDB only, PostgreSQL has no `AS OF SYSTEM TIME` and rejects
+    /// the query.
+    fn visit_as_of(&mut self, time: Expression<'a>) -> visitor::Result {
+        self.write(" AS OF SYSTEM TIME ")?;
+        self.visit_expression(time)
+    }
+
     fn visit_raw_value(&mut self, value: Value<'a>) -> visitor::Result {
         let res = match value {
             Value::Integer(i) => i.map(|i| self.write(i)),
             Value::Real(r) => r.map(|r| self.write(r)),
-            Value::Text(t) => t.map(|t| self.write(format!("'{}'", t))),
-            Value::Enum(e) => e.map(|e| self.write(e)),
+            Value::Text(t) => t.map(|t| self.write(escape::string_literal::<Self>(&t))),
+            Value::Enum(e) => e.map(|e| self.write(escape::string_literal::<Self>(&e))),
             Value::Bytes(b) => b.map(|b| self.write(format!("E'{}'", hex::encode(b)))),
             Value::Boolean(b) => b.map(|b| self.write(b)),
-            Value::Char(c) => c.map(|c| self.write(format!("'{}'", c))),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(b) => b.map(|b| self.write(format!("B'{}'", crate::ast::bits_to_string(&b)))),
+            Value::Char(c) => c.map(|c| self.write(escape::string_literal::<Self>(&c.to_string()))),
             #[cfg(feature = "json-1")]
-            Value::Json(j) => j.map(|j| self.write(format!("'{}'", serde_json::to_string(&j).unwrap()))),
-            #[cfg(all(feature = "array", feature = "postgresql"))]
+            Value::Json(j) => j.map(|j| {
+                let json = serde_json::to_string(&j)?;
+                self.write(escape::string_literal::<Self>(&json))
+            }),
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
             Value::Array(ary) => ary.map(|ary| {
-                self.surround_with("'{", "}'", |ref mut s| {
-                    let len = ary.len();
-
-                    for (i, item) in ary.into_iter().enumerate() {
-                        s.write(item)?;
-
-                        if i < len - 1 {
-                            s.write(",")?;
-                        }
-                    }
-
-                    Ok(())
-                })
+                let items: Vec<String> = ary.iter().map(|item| item.to_string()).collect();
+                self.write(escape::string_literal::<Self>(&format!("{{{}}}", items.join(","))))
             }),
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(uuid) => uuid.map(|uuid| self.write(format!("'{}'", uuid.to_hyphenated().to_string()))),
@@ -102,6 +165,10 @@ impl<'a> Visitor<'a> for Postgres<'a> {
             Value::Date(date) => date.map(|date| self.write(format!("'{}'", date))),
             #[cfg(feature = "chrono-0_4")]
             Value::Time(time) => time.map(|time| self.write(format!("'{}'", time))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(interval) => {
+                interval.map(|interval| self.write(format!("INTERVAL '{} seconds'", interval_seconds(interval))))
+            }
         };
 
         match res {
@@ -111,10 +178,11 @@ impl<'a> Visitor<'a> for Postgres<'a> {
     }
 
     fn visit_insert(&mut self, insert: Insert<'a>) -> visitor::Result {
-        self.write("INSERT ")?;
+        let on_conflict = insert.resolved_on_conflict()?;
+        self.write("INSERT")?;
 
         if let Some(table) = insert.table {
-            self.write("INTO ")?;
+            self.write(" INTO ")?;
             self.visit_table(table, true)?;
         }
 
@@ -138,6 +206,11 @@ impl<'a> Visitor<'a> for Postgres<'a> {
                     }
 
                     self.write(")")?;
+
+                    if insert.overriding_system_value {
+                        self.write(" OVERRIDING SYSTEM VALUE")?;
+                    }
+
                     self.write(" VALUES ")?;
                     self.visit_row(row)?;
                 }
@@ -158,6 +231,11 @@ impl<'a> Visitor<'a> for Postgres<'a> {
                 }
 
                 self.write(")")?;
+
+                if insert.overriding_system_value {
+                    self.write(" OVERRIDING SYSTEM VALUE")?;
+                }
+
                 self.write(" VALUES ")?;
                 let values_len = values.len();
 
@@ -169,11 +247,19 @@ impl<'a> Visitor<'a> for Postgres<'a> {
                     }
                 }
             }
-            expr => self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?,
+            expr => {
+                if insert.overriding_system_value {
+                    self.write(" OVERRIDING SYSTEM VALUE ")?;
+                }
+
+                self.surround_with("(", ")", |ref mut s| s.visit_expression(expr))?
+            }
         }
 
-        if let Some(OnConflict::DoNothing) = insert.on_conflict {
-            self.write(" ON CONFLICT DO NOTHING")?;
+        match on_conflict {
+            Some(OnConflict::DoNothing) => self.write(" ON CONFLICT DO NOTHING")?,
+            Some(OnConflict::Update { target, columns }) => self.visit_on_conflict_update(target, columns)?,
+            None => (),
         };
 
         if let Some(returning) = insert.returning {
@@ -187,6 +273,42 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         Ok(())
     }
 
+    fn visit_merge(&mut self, merge: Merge<'a>) -> visitor::Result {
+        self.write("MERGE INTO ")?;
+        self.visit_table(merge.table, true)?;
+
+        let using = merge.using;
+        let base_query = using.base_query;
+
+        self.write(" USING ")?;
+        self.surround_with("(", ")", |ref mut s| s.visit_query(base_query))?;
+        self.write(" AS ")?;
+        self.visit_table(using.as_table, false)?;
+        self.write(" ")?;
+        self.visit_row(Row::from(using.columns))?;
+        self.write(" ON ")?;
+        self.visit_conditions(using.on_conditions)?;
+
+        for query in merge.when_matched {
+            self.visit_merge_when_matched(query)?;
+        }
+
+        if let Some(query) = merge.when_not_matched {
+            self.write(" WHEN NOT MATCHED THEN ")?;
+            self.visit_query(query)?;
+        }
+
+        if let Some(returning) = merge.returning {
+            if !returning.is_empty() {
+                let values = returning.into_iter().map(|r| r.into()).collect();
+                self.write(" RETURNING ")?;
+                self.visit_columns(values)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn visit_aggregate_to_string(&mut self, value: Expression<'a>) -> visitor::Result {
         self.write("ARRAY_TO_STRING")?;
         self.write("(")?;
@@ -198,6 +320,53 @@ impl<'a> Visitor<'a> for Postgres<'a> {
         self.write(")")
     }
 
+    fn visit_array_operator(&mut self, left: Expression<'a>, operator: &str, right: Expression<'a>) -> visitor::Result {
+        self.visit_expression(left)?;
+        self.write(format!(" {} ", operator))?;
+        self.visit_expression(right)
+    }
+
+    fn visit_equals_any(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
+        self.visit_expression(left)?;
+        self.write(" = ANY")?;
+        self.surround_with("(", ")", |s| s.visit_expression(right))
+    }
+
+    fn visit_unnest(&mut self, unnest: Unnest<'a>) -> visitor::Result {
+        self.surround_with("(", ")", |s| {
+            s.write("SELECT unnest")?;
+            s.surround_with("(", ")", |s| s.visit_expression(*unnest.expression))?;
+            s.write(" AS ")?;
+            s.delimited_identifiers(&["value"])
+        })
+    }
+
+    fn visit_json_extract(&mut self, json_extract: JsonExtract<'a>) -> visitor::Result {
+        self.surround_with("(", ")", |s| {
+            s.visit_expression(*json_extract.expression)?;
+            s.write(" #> ")?;
+            s.visit_json_path(json_extract.path)
+        })
+    }
+
+    fn visit_json_set(&mut self, json_set: JsonSet<'a>) -> visitor::Result {
+        self.write("jsonb_set")?;
+        self.surround_with("(", ")", |s| {
+            s.visit_expression(*json_set.expression)?;
+            s.write(", ")?;
+            s.visit_json_path(json_set.path)?;
+            s.write(", ")?;
+            s.visit_expression(*json_set.value)
+        })
+    }
+
+    fn visit_json_unquote(&mut self, json_unquote: JsonUnquote<'a>) -> visitor::Result {
+        self.surround_with("(", ")", |s| {
+            s.visit_expression(*json_unquote.expression)?;
+            s.write(" #>> '{}'")
+        })
+    }
+
     #[cfg(feature = "json-1")]
     fn visit_condition_equals(&mut self, left: Expression<'a>, right: Expression<'a>) -> visitor::Result {
         let (left_is_json, right_is_json) = (left.is_json_value(), right.is_json_value());
@@ -272,6 +441,61 @@ mod tests {
         result
     }
 
+    #[test]
+    fn test_qualified_tables() {
+        let sub = Select::from_table("banned").column("user_id");
+        let select = Select::from_table("users").so_that("id".not_in_selection(sub));
+        let (sql, _) = Postgres::build(Query::from(select).qualify_tables("app")).unwrap();
+
+        assert_eq!(
+            "SELECT \"app\".\"users\".* FROM \"app\".\"users\" WHERE \"id\" NOT IN (SELECT \"user_id\" FROM \"app\".\"banned\")",
+            sql
+        );
+
+        let delete = Delete::from_table("users").so_that("id".equals(1));
+        let (sql, _) = Postgres::build(Query::from(delete).qualify_tables("app")).unwrap();
+
+        assert_eq!("DELETE FROM \"app\".\"users\" WHERE \"id\" = $1", sql);
+
+        let insert = Insert::single_into(Table::from("users").database("other")).value("id", 1);
+        let (sql, _) = Postgres::build(Query::from(insert).qualify_tables("app")).unwrap();
+
+        assert_eq!("INSERT INTO \"other\".\"users\" (\"id\") VALUES ($1)", sql);
+    }
+
+    #[test]
+    fn test_schema_qualified_tables() {
+        let query = Select::from_table(Table::from("users").schema("auth"))
+            .column(Column::from((Table::from("users").schema("auth"), "id")))
+            .so_that(Column::from((("app", "public", "users"), "id")).is_null());
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"auth\".\"users\".\"id\" FROM \"auth\".\"users\" WHERE \"app\".\"public\".\"users\".\"id\" IS NULL",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_distinct_from() {
+        let query = Select::from_table("users").so_that("parent_id".is_not_distinct_from(Value::Integer(None)));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE \"parent_id\" IS NOT DISTINCT FROM $1",
+            sql
+        );
+        assert_eq!(vec![Value::Integer(None)], params);
+
+        let query = Select::from_table("users").so_that("parent_id".is_distinct_from(1));
+        let (sql, _) = Postgres::build(query).unwrap();
+
+        assert_eq!(
+            "SELECT \"users\".* FROM \"users\" WHERE \"parent_id\" IS DISTINCT FROM $1",
+            sql
+        );
+    }
+
     #[test]
     fn test_single_row_insert_default_values() {
         let query = Insert::single_into("users");
@@ -305,6 +529,26 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    #[cfg(feature = "postgresql-core")]
+    fn test_returning_generated_columns() {
+        let expected = expected_values(
+            "INSERT INTO \"users\" (\"foo\") VALUES ($1) RETURNING \"foo\", \"id\"",
+            vec![10],
+        );
+
+        let table = Table::from("users")
+            .add_generated_column(Column::from("id").generated(Generated::Identity))
+            .add_generated_column("full_name");
+
+        let query = Insert::single_into(table).value("foo", 10).value("full_name", "Musti");
+        let insert = Insert::from(query).returning(vec!["foo"]).returning_generated();
+        let (sql, params) = Postgres::build(insert).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
     #[test]
     fn test_multi_row_insert() {
         let expected = expected_values("INSERT INTO \"users\" (\"foo\") VALUES ($1), ($2)", vec![10, 11]);
@@ -317,6 +561,252 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_multi_row_insert_with_default() {
+        let expected = expected_values(
+            "INSERT INTO \"users\" (\"id\",\"foo\") VALUES (DEFAULT,$1), ($2,DEFAULT)",
+            vec![10, 11],
+        );
+        let query = Insert::multi_into("users", vec!["id", "foo"])
+            .values(vec![default_value(), 10.into()])
+            .values(vec![11.into(), default_value()]);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_multi_row_insert_overriding_system_value() {
+        let expected = expected_values(
+            "INSERT INTO \"users\" (\"id\",\"foo\") OVERRIDING SYSTEM VALUE VALUES ($1,$2), ($3,$4)",
+            vec![1, 10, 2, 11],
+        );
+
+        let table = Table::from("users")
+            .add_generated_column(Column::from("id").generated(Generated::Identity))
+            .add_generated_column("full_foo");
+
+        let query = Insert::multi_into(table, vec!["id", "foo", "full_foo"])
+            .values(vec![1, 10, 100])
+            .values(vec![2, 11, 110])
+            .overriding_system_value();
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_multi_row_upsert_with_inferred_target() {
+        let expected = expected_values(
+            "INSERT INTO \"users\" (\"id\",\"name\") VALUES ($1,$2), ($3,$4) \
+             ON CONFLICT (\"id\") DO UPDATE SET \"name\" = \"excluded\".\"name\"",
+            vec![
+                Value::from(1),
+                Value::from("Musti"),
+                Value::from(2),
+                Value::from("Naukio"),
+            ],
+        );
+
+        let table = Table::from("users").add_unique_index("id");
+        let insert: Insert = Insert::multi_into(table, vec!["id", "name"])
+            .values(vec![Value::from(1), Value::from("Musti")])
+            .values(vec![Value::from(2), Value::from("Naukio")])
+            .into();
+
+        let (sql, params) = Postgres::build(insert.on_conflict(OnConflict::Update {
+            target: Vec::new(),
+            columns: vec!["name".into()],
+        }))
+        .unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_upsert_needs_a_target_and_columns() {
+        let insert: Insert = Insert::single_into("users").value("id", 1).into();
+
+        let no_target = insert.clone().on_conflict(OnConflict::Update {
+            target: Vec::new(),
+            columns: vec!["id".into()],
+        });
+        assert!(Postgres::build(no_target).is_err());
+
+        let no_columns = insert.on_conflict(OnConflict::Update {
+            target: vec!["id".into()],
+            columns: Vec::new(),
+        });
+        assert!(Postgres::build(no_columns).is_err());
+    }
+
+    #[test]
+    fn test_update_set_default() {
+        let expected = expected_values("UPDATE \"users\" SET \"foo\" = DEFAULT WHERE \"id\" = $1", vec![1]);
+        let query = Update::table("users").set_default("foo").so_that("id".equals(1));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_merge() {
+        let using = Select::from_table("staging")
+            .into_using("src", vec!["id", "name"])
+            .on(("src", "id").equals(Column::from(("users", "id"))));
+
+        let insert =
+            Insert::multi(vec!["id", "name"]).values(vec![Column::from(("src", "id")), Column::from(("src", "name"))]);
+
+        let query = Merge::new("users", using)
+            .when_matched_delete_if(("src", "name").is_null())
+            .when_matched_update(Update::table("users").set("name", Column::from(("src", "name"))))
+            .when_not_matched(insert);
+
+        let expected = expected_values(
+            "MERGE INTO \"users\" USING (SELECT \"staging\".* FROM \"staging\") AS \"src\" (\"id\",\"name\") ON \"src\".\"id\" = \"users\".\"id\" WHEN MATCHED AND \"src\".\"name\" IS NULL THEN DELETE WHEN MATCHED THEN UPDATE SET \"name\" = \"src\".\"name\" WHEN NOT MATCHED THEN INSERT (\"id\",\"name\") VALUES (\"src\".\"id\",\"src\".\"name\")",
+            Vec::<i64>::new(),
+        );
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_select_as_of() {
+        let expected = expected_values(
+            "SELECT \"users\".* FROM \"users\" INNER JOIN \"posts\" ON \"users\".\"id\" = \"posts\".\"user_id\" AS OF SYSTEM TIME $1 WHERE \"users\".\"id\" = $2",
+            vec![Value::from("-10s"), Value::from(1)],
+        );
+
+        let join = "posts".on(("users", "id").equals(Column::from(("posts", "user_id"))));
+        let query = Select::from_table("users")
+            .inner_join(join)
+            .so_that(("users", "id").equals(1))
+            .as_of("-10s");
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    #[cfg(all(feature = "array", feature = "postgresql-core"))]
+    fn test_array_comparisons() {
+        let expected = expected_values(
+            "SELECT \"posts\".* FROM \"posts\" WHERE (\"tags\" @> $1 AND \"tags\" && $2 AND $3 = ANY(\"editor_ids\"))",
+            vec![
+                Value::array(vec!["rust"]),
+                Value::array(vec!["sql", "databases"]),
+                Value::from(1),
+            ],
+        );
+
+        let conditions = "tags"
+            .array_contains(Value::array(vec!["rust"]))
+            .and("tags".array_overlaps(Value::array(vec!["sql", "databases"])))
+            .and(Expression::from(Value::from(1)).equals_any(Column::from("editor_ids")));
+
+        let query = Select::from_table("posts").so_that(conditions);
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    #[cfg(all(feature = "array", feature = "postgresql-core"))]
+    fn test_join_unnest() {
+        let expected = expected_values(
+            "SELECT \"users\".* FROM \"users\" INNER JOIN (SELECT unnest($1) AS \"value\") AS \"ids\" ON \"users\".\"id\" = \"ids\".\"value\"",
+            vec![Value::array(vec![1, 2, 3])],
+        );
+
+        let ids = Table::from(unnest(Value::array(vec![1, 2, 3]))).alias("ids");
+        let query =
+            Select::from_table("users").inner_join(ids.on(("users", "id").equals(Column::from(("ids", "value")))));
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    #[cfg(feature = "json-1")]
+    fn test_json_functions() {
+        let expected = expected_values(
+            "UPDATE \"users\" SET \"data\" = jsonb_set(\"data\", ARRAY[$1, $2]::text[], $3) WHERE ((\"data\" #> ARRAY[$4]::text[]) #>> '{}') = $5",
+            vec![
+                Value::from("address"),
+                Value::from("city"),
+                Value::json(serde_json::json!("Helsinki")),
+                Value::from("name"),
+                Value::from("Musti"),
+            ],
+        );
+
+        let name: Expression = json_unquote(json_extract(Column::from("data"), vec!["name"])).into();
+        let query = Update::table("users")
+            .set(
+                "data",
+                json_set(
+                    Column::from("data"),
+                    vec!["address", "city"],
+                    Value::json(serde_json::json!("Helsinki")),
+                ),
+            )
+            .so_that(name.equals("Musti"));
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    #[cfg(feature = "json-1")]
+    fn test_update_json_path() {
+        let expected = expected_values(
+            "UPDATE \"users\" SET \"data\" = jsonb_set(\"data\", ARRAY[$1]::text[], $2) WHERE \"id\" = $3",
+            vec![Value::from("visits"), Value::json(serde_json::json!(3)), Value::from(1)],
+        );
+
+        let query = Update::table("users")
+            .set_json_path("data", vec!["visits"], Value::json(serde_json::json!(3)))
+            .so_that("id".equals(1));
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_select_for_update_skip_locked() {
+        let expected = expected_values(
+            "SELECT \"jobs\".* FROM \"jobs\" WHERE \"state\" = $1 LIMIT $2 FOR UPDATE SKIP LOCKED",
+            vec![Value::from("queued"), Value::from(10)],
+        );
+
+        let query = Select::from_table("jobs")
+            .so_that("state".equals("queued"))
+            .limit(10)
+            .for_update(LockMode::SkipLocked);
+
+        let (sql, params) = Postgres::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
     #[test]
     fn test_limit_and_offset_when_both_are_set() {
         let expected = expected_values("SELECT \"users\".* FROM \"users\" LIMIT $1 OFFSET $2", vec![10, 2]);
@@ -1,7 +1,8 @@
 use crate::{
     ast::*,
-    error::{Error, ErrorKind},
-    visitor::{self, Visitor},
+    error::Error,
+    escape,
+    visitor::{self, Quoting, Visitor},
 };
 
 use std::fmt::{self, Write};
@@ -13,20 +14,32 @@ use std::fmt::{self, Write};
 pub struct Sqlite<'a> {
     query: String,
     parameters: Vec<Value<'a>>,
+    quoting: Quoting,
+    inline_params: bool,
 }
 
 impl<'a> Visitor<'a> for Sqlite<'a> {
     const C_BACKTICK_OPEN: &'static str = "`";
     const C_BACKTICK_CLOSE: &'static str = "`";
     const C_WILDCARD: &'static str = "%";
+    const C_ROW_ID: Option<&'static str> = Some("rowid");
 
     fn build<Q>(query: Q) -> crate::Result<(String, Vec<Value<'a>>)>
+    where
+        Q: Into<Query<'a>>,
+    {
+        Self::build_with_quoting(query, Quoting::Default)
+    }
+
+    fn build_with_quoting<Q>(query: Q, quoting: Quoting) -> crate::Result<(String, Vec<Value<'a>>)>
     where
         Q: Into<Query<'a>>,
     {
         let mut sqlite = Sqlite {
             query: String::with_capacity(4096),
             parameters: Vec::with_capacity(128),
+            quoting,
+            inline_params: false,
         };
 
         Sqlite::visit_query(&mut sqlite, query.into())?;
@@ -34,38 +47,56 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
         Ok((sqlite.query, sqlite.parameters))
     }
 
+    fn build_with_inlined_params<Q>(query: Q) -> crate::Result<String>
+    where
+        Q: Into<Query<'a>>,
+    {
+        let mut sqlite = Sqlite {
+            query: String::with_capacity(4096),
+            parameters: Vec::new(),
+            quoting: Quoting::Default,
+            inline_params: true,
+        };
+
+        Sqlite::visit_query(&mut sqlite, query.into())?;
+
+        Ok(sqlite.query)
+    }
+
     fn write<D: fmt::Display>(&mut self, s: D) -> visitor::Result {
         write!(&mut self.query, "{}", s)?;
         Ok(())
     }
 
+    fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    fn inlines_params(&self) -> bool {
+        self.inline_params
+    }
+
     fn visit_raw_value(&mut self, value: Value<'a>) -> visitor::Result {
         let res = match value {
             Value::Integer(i) => i.map(|i| self.write(i)),
             Value::Real(r) => r.map(|r| self.write(r)),
-            Value::Text(t) => t.map(|t| self.write(format!("'{}'", t))),
-            Value::Enum(e) => e.map(|e| self.write(e)),
+            Value::Text(t) => t.map(|t| self.write(escape::string_literal::<Self>(&t))),
+            Value::Enum(e) => e.map(|e| self.write(escape::string_literal::<Self>(&e))),
             Value::Bytes(b) => b.map(|b| self.write(format!("x'{}'", hex::encode(b)))),
             Value::Boolean(b) => b.map(|b| self.write(b)),
-            Value::Char(c) => c.map(|c| self.write(format!("'{}'", c))),
+            #[cfg(feature = "bit-vec")]
+            Value::Bits(b) => b.map(|b| self.write(format!("'{}'", crate::ast::bits_to_string(&b)))),
+            Value::Char(c) => c.map(|c| self.write(escape::string_literal::<Self>(&c.to_string()))),
             #[cfg(feature = "json-1")]
             Value::Json(j) => match j {
                 Some(ref j) => {
                     let s = serde_json::to_string(j)?;
-                    Some(self.write(format!("'{}'", s)))
+                    Some(self.write(escape::string_literal::<Self>(&s)))
                 }
                 None => None,
             },
-            #[cfg(all(feature = "array", feature = "postgresql"))]
-            Value::Array(_) => {
-                let msg = "Arrays are not supported in SQLite.";
-                let kind = ErrorKind::conversion(msg);
-
-                let mut builder = Error::builder(kind);
-                builder.set_original_message(msg);
-
-                Err(builder.build())?
-            }
+            #[cfg(all(feature = "array", feature = "postgresql-core"))]
+            Value::Array(_) => Err(Error::unsupported("Arrays are not supported in SQLite."))?,
             #[cfg(feature = "uuid-0_8")]
             Value::Uuid(uuid) => uuid.map(|uuid| self.write(format!("'{}'", uuid.to_hyphenated().to_string()))),
             #[cfg(feature = "chrono-0_4")]
@@ -74,6 +105,8 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
             Value::Date(date) => date.map(|date| self.write(format!("'{}'", date))),
             #[cfg(feature = "chrono-0_4")]
             Value::Time(time) => time.map(|time| self.write(format!("'{}'", time))),
+            #[cfg(feature = "chrono-0_4")]
+            Value::Interval(interval) => interval.map(|interval| self.write(interval_seconds(interval))),
         };
 
         match res {
@@ -83,9 +116,11 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
     }
 
     fn visit_insert(&mut self, insert: Insert<'a>) -> visitor::Result {
-        match insert.on_conflict {
+        let on_conflict = insert.resolved_on_conflict()?;
+
+        match on_conflict {
             Some(OnConflict::DoNothing) => self.write("INSERT OR IGNORE")?,
-            None => self.write("INSERT")?,
+            _ => self.write("INSERT")?,
         };
 
         if let Some(table) = insert.table {
@@ -98,16 +133,26 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
                 kind: ExpressionKind::Row(row),
                 ..
             } => {
-                if row.values.is_empty() {
+                // SQLite has no `DEFAULT` keyword in `VALUES`, so we leave the
+                // columns out and let the database fill in the defaults.
+                let (columns, values): (Vec<_>, Vec<_>) = insert
+                    .columns
+                    .into_iter()
+                    .zip(row.values.into_iter())
+                    .filter(|(_, value)| value.kind != ExpressionKind::Default)
+                    .unzip();
+
+                if values.is_empty() {
                     self.write(" DEFAULT VALUES")?;
                 } else {
-                    let columns = insert.columns.len();
+                    let row = Row::from(values);
+                    let columns_len = columns.len();
 
                     self.write(" (")?;
-                    for (i, c) in insert.columns.into_iter().enumerate() {
+                    for (i, c) in columns.into_iter().enumerate() {
                         self.visit_column(c)?;
 
-                        if i < (columns - 1) {
+                        if i < (columns_len - 1) {
                             self.write(", ")?;
                         }
                     }
@@ -147,9 +192,55 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
             expr => self.visit_expression(expr)?,
         }
 
+        if let Some(OnConflict::Update { target, columns }) = on_conflict {
+            self.visit_on_conflict_update(target, columns)?;
+        }
+
         Ok(())
     }
 
+    fn visit_default(&mut self) -> visitor::Result {
+        Err(Error::unsupported(
+            "The DEFAULT keyword is only supported in a single-row INSERT in SQLite.",
+        ))
+    }
+
+    fn visit_json_set(&mut self, json_set: JsonSet<'a>) -> visitor::Result {
+        self.write("json_set")?;
+        self.surround_with("(", ")", |s| {
+            s.visit_expression(*json_set.expression)?;
+            s.write(", ")?;
+
+            match json_set.path {
+                JsonPath::String(path) => s.visit_parameterized(Value::text(path))?,
+                JsonPath::Array(_) => {
+                    return Err(Error::unsupported(
+                        "SQLite JSON paths must be strings, such as `$.address.city`.",
+                    ));
+                }
+            }
+
+            s.write(", ")?;
+            s.visit_expression(*json_set.value)
+        })
+    }
+
+    fn visit_lock(&mut self, _mode: LockMode) -> visitor::Result {
+        Err(Error::unsupported(
+            "SELECT ... FOR UPDATE is not supported in SQLite, the whole database is locked by a write transaction.",
+        ))
+    }
+
+    fn visit_update_from(&mut self, joins: Vec<JoinData<'a>>) -> crate::Result<Vec<Expression<'a>>> {
+        if joins.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Err(Error::unsupported(
+            "UPDATE with joins is not supported in SQLite, the bundled version has no UPDATE ... FROM.",
+        ))
+    }
+
     fn parameter_substitution(&mut self) -> visitor::Result {
         self.write("?")
     }
@@ -200,6 +291,12 @@ impl<'a> Visitor<'a> for Sqlite<'a> {
             Ok(())
         })
     }
+
+    fn visit_distinct_from(&mut self, left: Expression<'a>, right: Expression<'a>, negate: bool) -> visitor::Result {
+        self.visit_expression(left)?;
+        self.write(if negate { " IS NOT " } else { " IS " })?;
+        self.visit_expression(right)
+    }
 }
 
 #[cfg(test)]
@@ -223,6 +320,20 @@ mod tests {
         result
     }
 
+    #[test]
+    fn test_distinct_from() {
+        let query = Select::from_table("users").so_that("parent_id".is_not_distinct_from(Value::Integer(None)));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE `parent_id` IS ?", sql);
+        assert_eq!(vec![Value::Integer(None)], params);
+
+        let query = Select::from_table("users").so_that("parent_id".is_distinct_from(1));
+        let (sql, _) = Sqlite::build(query).unwrap();
+
+        assert_eq!("SELECT `users`.* FROM `users` WHERE `parent_id` IS NOT ?", sql);
+    }
+
     #[test]
     fn test_select_1() {
         let expected = expected_values("SELECT ?", vec![1]);
@@ -234,6 +345,125 @@ mod tests {
         assert_eq!(expected.1, params);
     }
 
+    #[test]
+    fn test_select_as_of_is_not_supported() {
+        let query = Select::from_table("users").as_of("2020-06-01 00:00:00");
+        assert!(Sqlite::build(query).is_err());
+    }
+
+    #[test]
+    fn test_json_functions_are_not_supported() {
+        let query = Select::from_table("users").value(json_extract(Column::from("data"), "$.age"));
+        assert!(Sqlite::build(query).is_err());
+    }
+
+    #[test]
+    fn test_select_for_update_is_not_supported() {
+        let query = Select::from_table("users").for_update(LockMode::Wait);
+        assert!(Sqlite::build(query).is_err());
+    }
+
+    #[test]
+    fn test_single_row_insert_skips_default_columns() {
+        let expected = expected_values("INSERT INTO `users` (`foo`) VALUES (?)", vec![10]);
+        let query = Insert::single_into("users").value_default("id").value("foo", 10);
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_single_row_insert_only_default_columns() {
+        let query = Insert::single_into("users").value_default("id");
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!("INSERT INTO `users` DEFAULT VALUES", sql);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_multi_row_insert_omits_generated_columns() {
+        let expected = expected_values("INSERT INTO `users` (`name`) VALUES (?), (?)", vec!["Musti", "Naukio"]);
+
+        let table = Table::from("users").add_generated_column(Column::from("id").generated(Generated::Identity));
+        let query = Insert::multi_into(table, vec!["id", "name"])
+            .values((1, "Musti"))
+            .values((2, "Naukio"));
+
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_upsert() {
+        let expected = expected_values(
+            "INSERT INTO `users` (`id`, `name`) VALUES (?,?) \
+             ON CONFLICT (`id`) DO UPDATE SET `name` = `excluded`.`name`",
+            vec![Value::from(1), Value::from("Musti")],
+        );
+
+        let insert: Insert = Insert::single_into("users")
+            .value("id", 1)
+            .value("name", "Musti")
+            .into();
+
+        let (sql, params) = Sqlite::build(insert.on_conflict(OnConflict::Update {
+            target: vec!["id".into()],
+            columns: vec!["name".into()],
+        }))
+        .unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_default_is_not_supported_in_multi_row_insert_or_update() {
+        let insert = Insert::multi_into("users", vec!["id"]).values(vec![default_value()]);
+        assert!(Sqlite::build(insert).is_err());
+
+        let update = Update::table("users").set_default("id");
+        assert!(Sqlite::build(update).is_err());
+    }
+
+    #[test]
+    fn test_merge_is_not_supported() {
+        let using = Select::from_table("staging")
+            .into_using("src", vec!["id"])
+            .on(("src", "id").equals(Column::from(("users", "id"))));
+
+        let query = Merge::new("users", using).when_matched_delete();
+        assert!(Sqlite::build(query).is_err());
+    }
+
+    #[test]
+    fn test_update_from_expression() {
+        let expected = expected_values(
+            "UPDATE `users` SET `counter` = (`counter` + ?) WHERE `id` = ?",
+            vec![1, 2],
+        );
+        let query = Update::table("users")
+            .set("counter", col!("counter") + 1)
+            .so_that("id".equals(2));
+        let (sql, params) = Sqlite::build(query).unwrap();
+
+        assert_eq!(expected.0, sql);
+        assert_eq!(expected.1, params);
+    }
+
+    #[test]
+    fn test_update_from_joined_table_is_not_supported() {
+        let join = "profiles".on(("profiles", "user_id").equals(Column::from(("users", "id"))));
+        let query = Update::table("users")
+            .set("name", Column::from(("profiles", "name")))
+            .inner_join(join);
+
+        assert!(Sqlite::build(query).is_err());
+    }
+
     #[test]
     fn test_aliased_value() {
         let expected = expected_values("SELECT ? AS `test`", vec![1]);